@@ -0,0 +1,118 @@
+//! `cos-num` checks that run on the ATmega328P itself
+//!
+//! Host tests run where `i64` is native. Here every multiply, divide and
+//! remainder goes through the compiler-builtins intrinsics, and `match` tables
+//! like the one behind [`Num::factorial`] are placed by the AVR backend, so
+//! codegen bugs in the math hot paths only show up on the target.
+//!
+//! Inputs are passed through [`black_box`] so the checks are computed at run
+//! time instead of being folded by the compiler. Each check prints a line on
+//! the serial port, and the run ends with `done, N failed`. Run with
+//! `cargo xtask avr-test` in the repository root.
+
+#![no_std]
+#![no_main]
+
+use core::{convert::Infallible, hint::black_box, panic::PanicInfo};
+
+use arduino_hal::prelude::*;
+use cos_num::Num;
+use ufmt::{uWrite, uwriteln};
+
+type N0 = Num<0, 8>;
+type N2 = Num<2, 8>;
+type N4 = Num<4, 8>;
+type N8 = Num<8, 8>;
+
+/// Name, value computed on the target and value computed on the host
+type Check = (&'static str, i64, i64);
+
+fn checks() -> [Check; 18] {
+    let n2 = |raw| black_box(N2::from_raw(raw));
+    let n8 = |raw| black_box(N8::from_raw(raw));
+
+    [
+        ("mul", (n2(12345) * n2(-678)).raw(), -83699),
+        (
+            "mul wraps",
+            (n2(i64::MAX / 100 * 100) * n2(100_000)).raw(),
+            -8000,
+        ),
+        ("div", (n2(100) / n2(300)).raw(), 33),
+        ("div negative", (n2(-200) / n2(300)).raw(), -67),
+        (
+            "div big",
+            (n8(N8::PI.raw()) / n8(N8::E.raw())).raw(),
+            115572735,
+        ),
+        ("rem", (n8(N8::TAU.raw()) % n8(N8::E.raw())).raw(), 84662165),
+        ("add wraps", (n2(i64::MAX) + n2(1)).raw(), i64::MIN),
+        (
+            "factorial",
+            black_box(N0::from_int(20)).factorial().raw(),
+            2432902008176640000,
+        ),
+        ("factorial saturates", n2(2000).factorial().raw(), i64::MAX),
+        ("gamma", n2(50).gamma().raw(), 177),
+        ("sqrt", black_box(N4::from_int(2)).sqrt().raw(), 14142),
+        ("sin", n8(N8::ONE.raw()).sin().raw(), 84147098),
+        ("cos", n8(N8::ONE.raw()).cos().raw(), 54030230),
+        ("sin steps", n2(100).sin_steps().finish().raw(), 84),
+        ("ln", n8(2 * N8::SCALE).ln().raw(), 69314718),
+        ("sinh", n8(N8::ONE.raw()).sinh().raw(), 117520120),
+        (
+            "push digit overflows",
+            i64::from(n2(i64::MAX / 10).checked_push_digit(9).is_none()),
+            1,
+        ),
+        (
+            "decrease frac",
+            n8(-123_456_789).decrease_frac::<2>().raw(),
+            -123,
+        ),
+    ]
+}
+
+/// Print one line per check, returns the number of failed checks
+fn report<W: uWrite<Error = Infallible>>(w: &mut W, checks: &[Check]) -> u8 {
+    let mut failed = 0;
+
+    for &(name, got, expected) in checks {
+        if got == expected {
+            uwriteln!(w, "ok {}", name).unwrap_infallible();
+        } else {
+            uwriteln!(w, "FAIL {}: got {}, expected {}", name, got, expected).unwrap_infallible();
+            failed += 1;
+        }
+    }
+
+    failed
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let failed = report(&mut serial, &checks());
+    uwriteln!(&mut serial, "done, {} failed", failed).unwrap_infallible();
+    serial.flush();
+
+    halt()
+}
+
+/// Stop the simulator, simavr exits when the CPU sleeps with interrupts off
+fn halt() -> ! {
+    avr_device::interrupt::disable();
+
+    loop {
+        avr_device::asm::sleep();
+    }
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo<'_>) -> ! {
+    // No `done` line is printed, so xtask reports the run as failed
+    halt()
+}
@@ -0,0 +1,68 @@
+//! Link-time check that the math and the calculator have no panic branch left
+//!
+//! Built with the `panic-free` feature, where `cos-num` returns a fallback instead
+//! of panicking. The panic handler calls a function that is defined nowhere, so
+//! linking fails with that symbol unless the optimizer removed every path to it.
+//! Inputs come from [`black_box`], so every branch is kept instead of being folded
+//! for the values passed here.
+//!
+//! The binary is never run, building it is the check. Run with
+//! `cargo xtask panic-free` in the repository root.
+
+#![no_std]
+#![no_main]
+
+use core::{hint::black_box, panic::PanicInfo};
+
+use cos::{Calculator, Key, config::FRACTION_COUNT, num::Num};
+
+type N = Num<FRACTION_COUNT>;
+
+/// Every `cos-num` function that panics without the feature, on any input
+fn math() {
+    let (a, b) = (black_box(N::from_raw(0)), black_box(N::from_raw(0)));
+
+    black_box((a / b, a % b, a.saturating_div(b), a.recip()));
+    black_box((
+        a.sqrt(),
+        a.factorial(),
+        a.checked_factorial(),
+        a.gcd(b),
+        a.lcm(b),
+    ));
+    black_box((a.ln(), a.log2(), a.log10(), a.log(b), a.powf(b)));
+    black_box((a.gamma(), a.lgamma(), a.checked_gamma()));
+    black_box((a.arcsin(), a.arccos(), a.clamp(a, b)));
+    black_box((a.sin(), a.cos(), a.tan()));
+    black_box(N::from_dms(black_box(0), black_box(0), b));
+    black_box(N::from_ascii(black_box(b"0")));
+}
+
+/// Any key in any order, the calculator is what the firmware runs on every press
+fn calculator() -> ! {
+    let mut calc = Calculator::<FRACTION_COUNT>::new();
+
+    loop {
+        black_box(calc.handle_input(black_box(Key::Clear))).ok();
+        while calc.is_busy() {
+            black_box(calc.poll()).ok();
+        }
+    }
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    math();
+    calculator()
+}
+
+unsafe extern "C" {
+    /// Not defined anywhere, a call that survives optimization fails the link
+    fn panic_branch_left_in_the_binary() -> !;
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo<'_>) -> ! {
+    // SAFETY: the function doesn't exist, the binary only links if this is unreachable
+    unsafe { panic_branch_left_in_the_binary() }
+}
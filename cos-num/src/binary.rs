@@ -0,0 +1,466 @@
+//! Binary sibling of [`Num`] for control loops, where nobody reads the value in between
+//!
+//! [`BinNum`] stores a value as an `i64` scaled by `2^FRAC_BITS` instead of `10^F`, so
+//! scaling a product or dividend back is a shift instead of a division by a power of ten,
+//! the bulk of the cost of `*` and `/` on 8-bit targets. A PID step or a filter runs in
+//! it, the values read out convert to a [`Num`]:
+//!
+//! ```
+//! use cos_num::{Num, binary::BinNum};
+//!
+//! type Q = BinNum<16>;
+//!
+//! let gain = Q::from_num(Num::<2, 8>::from_raw(125)).unwrap(); // 1.25
+//! let error = Q::from_int(3) - Q::from_raw(Q::ONE.raw() / 4); // 2.75
+//! let output = gain * error;
+//! assert_eq!(output.to_string(), "3.4375");
+//! assert_eq!(output.to_num(), Some(Num::<2, 8>::from_raw(344)));
+//! ```
+//!
+//! Decimal fractions like 0.1 have no exact binary value, [`BinNum::from_num`] rounds them
+//! to the nearest step of `2^-FRAC_BITS`. Every binary fraction has an exact decimal one,
+//! which `Display` prints in full. `FRAC_BITS` can be up to 62, larger ones fail to
+//! compile. Operators wrap like [`Num`]'s, with the `wide` feature only once the result
+//! doesn't fit.
+
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+use crate::Num;
+
+/// [`Num`] scaled by `2^FRAC_BITS`, up to about ±9.2 × 10¹⁸ / `2^FRAC_BITS`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[repr(transparent)]
+pub struct BinNum<const FRAC_BITS: u8>(pub i64);
+
+impl<const FRAC_BITS: u8> BinNum<FRAC_BITS> {
+    /// `2^FRAC_BITS`, the raw value of 1
+    pub const SCALE: i64 = {
+        assert!(FRAC_BITS <= 62, "FRAC_BITS is too large for i64");
+        1 << FRAC_BITS
+    };
+
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(Self::SCALE);
+    /// Smallest value
+    pub const MIN: Self = Self(i64::MIN);
+    /// Largest value
+    pub const MAX: Self = Self(i64::MAX);
+
+    /// Half a step of the result, added before shifting to round
+    const HALF: i64 = Self::SCALE >> 1;
+
+    /// Create from raw inner representation (no scaling)
+    #[inline]
+    #[must_use]
+    pub const fn from_raw(raw: i64) -> Self {
+        Self(raw)
+    }
+
+    /// Get raw inner
+    #[inline]
+    #[must_use]
+    pub const fn raw(self) -> i64 {
+        self.0
+    }
+
+    /// Create from integer, clamped to [`Self::MIN`] and [`Self::MAX`]
+    #[inline]
+    #[must_use]
+    pub const fn from_int(n: i64) -> Self {
+        Self(n.saturating_mul(Self::SCALE))
+    }
+
+    /// Create from integer, `None` if it doesn't fit instead of saturating
+    #[inline]
+    #[must_use]
+    pub const fn checked_from_int(n: i64) -> Option<Self> {
+        match n.checked_mul(Self::SCALE) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
+    /// Nearest value to a [`Num`], rounded half away from zero, `None` if it doesn't fit
+    #[must_use]
+    pub const fn from_num<const F: u8, const TF: u8>(n: Num<F, TF>) -> Option<Self> {
+        // Below 2^126 for every FRAC_BITS
+        let r = (n.0 as i128) << FRAC_BITS;
+        let scale = Num::<F, TF>::SCALE as i128;
+        let r = if r >= 0 {
+            (r + scale / 2) / scale
+        } else {
+            (r - scale / 2) / scale
+        };
+        Self::narrow(r)
+    }
+
+    /// Nearest [`Num`], rounded half away from zero, `None` if it doesn't fit
+    #[must_use]
+    pub const fn to_num<const F: u8, const TF: u8>(self) -> Option<Num<F, TF>> {
+        // Below 2^63 · 10^18 < 2^127
+        Num::narrow(Self::shr_round(
+            self.0 as i128 * Num::<F, TF>::SCALE as i128,
+        ))
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn abs(self) -> Self {
+        Self(self.0.wrapping_abs())
+    }
+
+    /// Whether self is greater than zero
+    #[inline]
+    #[must_use]
+    pub const fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    /// Whether self is less than zero
+    #[inline]
+    #[must_use]
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// `self + rhs`, or `None` on overflow instead of wrapping
+    #[inline]
+    #[must_use]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
+    /// `self - rhs`, or `None` on overflow instead of wrapping
+    #[inline]
+    #[must_use]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
+    /// `-self`, or `None` for [`Self::MIN`]
+    #[inline]
+    #[must_use]
+    pub const fn checked_neg(self) -> Option<Self> {
+        match self.0.checked_neg() {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
+    /// `self * rhs`, or `None` if the product doesn't fit
+    #[inline]
+    #[must_use]
+    pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Self::narrow(self.mul_wide(rhs))
+    }
+
+    /// `self / rhs`, or `None` if `rhs` is zero or the quotient doesn't fit
+    #[inline]
+    #[must_use]
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.0 == 0 {
+            return None;
+        }
+        Self::narrow(self.div_wide(rhs))
+    }
+
+    /// `self + rhs`, clamped to the representable range instead of wrapping
+    #[inline]
+    #[must_use]
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// `self - rhs`, clamped to the representable range instead of wrapping
+    #[inline]
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// `self * rhs`, clamped to the representable range instead of wrapping
+    #[inline]
+    #[must_use]
+    pub const fn saturating_mul(self, rhs: Self) -> Self {
+        Self::saturate(self.mul_wide(rhs))
+    }
+
+    /// `self / rhs`, clamped to the representable range instead of wrapping
+    ///
+    /// # Panics
+    /// Will panic if `rhs` is zero
+    #[inline]
+    #[must_use]
+    pub const fn saturating_div(self, rhs: Self) -> Self {
+        require!(rhs.0 != 0, "division by zero", Self::ZERO);
+
+        Self::saturate(self.div_wide(rhs))
+    }
+
+    /// `r / 2^FRAC_BITS` rounded half away from zero
+    const fn shr_round(r: i128) -> i128 {
+        let half = Self::HALF as i128;
+        if r >= 0 {
+            (r + half) >> FRAC_BITS
+        } else {
+            -((half - r) >> FRAC_BITS)
+        }
+    }
+
+    /// Raw product rounded half away from zero, in `i128` so it can't overflow
+    const fn mul_wide(self, rhs: Self) -> i128 {
+        Self::shr_round(self.0 as i128 * rhs.0 as i128)
+    }
+
+    /// Raw quotient rounded half away from zero, in `i128` so it can't overflow, `rhs` must
+    /// not be zero
+    const fn div_wide(self, rhs: Self) -> i128 {
+        let r = (self.0 as i128) << FRAC_BITS;
+        let rhs = rhs.0 as i128;
+        if r >= 0 {
+            (r + rhs / 2) / rhs
+        } else {
+            (r - rhs / 2) / rhs
+        }
+    }
+
+    const fn narrow(raw: i128) -> Option<Self> {
+        if raw < i64::MIN as i128 || raw > i64::MAX as i128 {
+            None
+        } else {
+            Some(Self(raw as i64))
+        }
+    }
+
+    const fn saturate(raw: i128) -> Self {
+        if raw < i64::MIN as i128 {
+            Self::MIN
+        } else if raw > i64::MAX as i128 {
+            Self::MAX
+        } else {
+            Self(raw as i64)
+        }
+    }
+
+    /// Decimal digits of the fraction, all of them: a binary fraction has at most
+    /// `FRAC_BITS` and none after the last non-zero one
+    fn fraction_digits(self) -> impl Iterator<Item = u8> {
+        let mask = (1u128 << FRAC_BITS) - 1;
+        let mut frac = u128::from(self.0.unsigned_abs()) & mask;
+        core::iter::from_fn(move || {
+            if frac == 0 {
+                return None;
+            }
+            frac *= 10;
+            let digit = (frac >> FRAC_BITS) as u8;
+            frac &= mask;
+            Some(digit)
+        })
+    }
+}
+
+impl<const FRAC_BITS: u8> Add for BinNum<FRAC_BITS> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_add(rhs.0))
+    }
+}
+
+impl<const FRAC_BITS: u8> Sub for BinNum<FRAC_BITS> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+impl<const FRAC_BITS: u8> Neg for BinNum<FRAC_BITS> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(self.0.wrapping_neg())
+    }
+}
+
+impl<const FRAC_BITS: u8> Mul for BinNum<FRAC_BITS> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        // Wraps only if the result doesn't fit, at the cost of i128 code on AVR
+        if cfg!(feature = "wide") {
+            return Self(self.mul_wide(rhs) as i64);
+        }
+
+        let r = self.0.wrapping_mul(rhs.0);
+        if r >= 0 {
+            Self(r.wrapping_add(Self::HALF) >> FRAC_BITS)
+        } else {
+            Self(-(Self::HALF.wrapping_sub(r) >> FRAC_BITS))
+        }
+    }
+}
+
+impl<const FRAC_BITS: u8> Div for BinNum<FRAC_BITS> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        require!(rhs.0 != 0, "division by zero", Self::ZERO);
+
+        if cfg!(feature = "wide") {
+            return Self(self.div_wide(rhs) as i64);
+        }
+
+        let r = self.0 << FRAC_BITS;
+        if r >= 0 {
+            Self(r.wrapping_add(rhs.0 / 2).wrapping_div(rhs.0))
+        } else {
+            Self(r.wrapping_sub(rhs.0 / 2).wrapping_div(rhs.0))
+        }
+    }
+}
+
+impl<const FRAC_BITS: u8> Rem for BinNum<FRAC_BITS> {
+    type Output = Self;
+
+    #[inline]
+    fn rem(self, rhs: Self) -> Self {
+        require!(rhs.0 != 0, "division by zero", Self::ZERO);
+
+        Self(self.0.wrapping_rem(rhs.0))
+    }
+}
+
+macro_rules! assign {
+    ($assign:ident $assign_fn:ident $fn:ident) => {
+        impl<const FRAC_BITS: u8> $assign for BinNum<FRAC_BITS> {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = (*self).$fn(rhs);
+            }
+        }
+    };
+}
+
+assign!(AddAssign add_assign add);
+assign!(SubAssign sub_assign sub);
+assign!(MulAssign mul_assign mul);
+assign!(DivAssign div_assign div);
+assign!(RemAssign rem_assign rem);
+
+impl<const FRAC_BITS: u8> core::fmt::Display for BinNum<FRAC_BITS> {
+    /// Exact value like `3.4375` or `-0.5`, without trailing zeros
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.0 < 0 {
+            f.write_str("-")?;
+        }
+        write!(f, "{}", self.0.unsigned_abs() >> FRAC_BITS)?;
+        for (i, digit) in self.fraction_digits().enumerate() {
+            if i == 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{digit}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<const FRAC_BITS: u8> ufmt::uDisplay for BinNum<FRAC_BITS> {
+    /// Exact value like `3.4375` or `-0.5`, without trailing zeros
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        if self.0 < 0 {
+            f.write_str("-")?;
+        }
+        ufmt::uwrite!(f, "{}", self.0.unsigned_abs() >> FRAC_BITS)?;
+        for (i, digit) in self.fraction_digits().enumerate() {
+            if i == 0 {
+                f.write_str(".")?;
+            }
+            ufmt::uwrite!(f, "{}", digit)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinNum;
+    use crate::Num;
+
+    type Q = BinNum<16>;
+    type N = Num<4, 8>;
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(Q::SCALE, 65_536);
+        assert_eq!(BinNum::<0>::ONE.raw(), 1);
+        assert_eq!(Q::from_int(3) + Q::from_raw(32_768), Q::from_raw(229_376));
+        assert_eq!(Q::from_int(2) - Q::from_int(5), Q::from_int(-3));
+        assert_eq!(Q::from_int(7) % Q::from_int(4), Q::from_int(3));
+        assert_eq!(Q::from_int(-3) * Q::from_raw(16_384), Q::from_raw(-49_152));
+        assert_eq!(Q::from_int(3) / Q::from_int(4), Q::from_raw(49_152));
+    }
+
+    #[test]
+    fn test_rounding() {
+        // Rounded half away from zero like Num
+        assert_eq!(Q::from_raw(1) * Q::from_raw(32_768), Q::from_raw(1));
+        assert_eq!(Q::from_raw(-1) * Q::from_raw(32_768), Q::from_raw(-1));
+        assert_eq!(Q::ONE / Q::from_int(3), Q::from_raw(21_845));
+        assert_eq!(Q::from_int(-2) / Q::from_int(3), Q::from_raw(-43_691));
+    }
+
+    #[test]
+    fn test_num_conversions() {
+        // Decimal fractions to the nearest step and back
+        assert_eq!(Q::from_num(N::from_raw(1000)), Some(Q::from_raw(6554)));
+        assert_eq!(Q::from_num(N::from_raw(-1000)), Some(Q::from_raw(-6554)));
+        assert_eq!(Q::from_raw(6554).to_num(), Some(N::from_raw(1000)));
+        assert_eq!(
+            Q::from_num(N::from_raw(-25_000)),
+            Some(Q::from_raw(-163_840))
+        );
+        assert_eq!(Q::MAX.to_num::<18, 18>(), None);
+        assert_eq!(BinNum::<62>::from_num(N::from_int(2)), None);
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(Q::MAX.checked_add(Q::ONE), None);
+        assert_eq!(Q::MIN.checked_neg(), None);
+        assert_eq!(Q::from_int(1 << 30).checked_mul(Q::from_int(1 << 20)), None);
+        assert_eq!(
+            Q::from_int(1 << 30).saturating_mul(Q::from_int(-(1 << 20))),
+            Q::MIN
+        );
+        assert_eq!(Q::ONE.checked_div(Q::ZERO), None);
+        assert_eq!(Q::checked_from_int(1 << 50), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Q::from_raw(-32_768).to_string(), "-0.5");
+        assert_eq!(Q::from_raw(225_280).to_string(), "3.4375");
+        assert_eq!(Q::from_int(-12).to_string(), "-12");
+        assert_eq!(Q::from_raw(1).to_string(), "0.0000152587890625");
+        assert_eq!(BinNum::<62>::MIN.to_string(), "-2");
+    }
+}
@@ -0,0 +1,123 @@
+//! CORDIC sine, cosine and arctangent, enabled by the `cordic` feature
+//!
+//! Every iteration is a shift and an addition instead of the division of a series
+//! term, which is slow and large on AVR. The iterations run at 18 digits, so their
+//! rounding stays far below the last of the `TF` digits.
+
+use crate::Num;
+
+/// Digits of the iterations
+const DIGITS: u32 = 18;
+
+/// atan(2^-i) scaled by `10^18`
+const ATAN: [i64; 48] = [
+    785_398_163_397_448_310,
+    463_647_609_000_806_116,
+    244_978_663_126_864_154,
+    124_354_994_546_761_435,
+    62_418_809_995_957_348,
+    31_239_833_430_268_276,
+    15_623_728_620_476_831,
+    7_812_341_060_101_111,
+    3_906_230_131_966_972,
+    1_953_122_516_478_819,
+    976_562_189_559_319,
+    488_281_211_194_898,
+    244_140_620_149_362,
+    122_070_311_893_670,
+    61_035_156_174_209,
+    30_517_578_115_526,
+    15_258_789_061_316,
+    7_629_394_531_102,
+    3_814_697_265_606,
+    1_907_348_632_810,
+    953_674_316_406,
+    476_837_158_203,
+    238_418_579_102,
+    119_209_289_551,
+    59_604_644_775,
+    29_802_322_388,
+    14_901_161_194,
+    7_450_580_597,
+    3_725_290_298,
+    1_862_645_149,
+    931_322_575,
+    465_661_287,
+    232_830_644,
+    116_415_322,
+    58_207_661,
+    29_103_830,
+    14_551_915,
+    7_275_958,
+    3_637_979,
+    1_818_989,
+    909_495,
+    454_747,
+    227_374,
+    113_687,
+    56_843,
+    28_422,
+    14_211,
+    7_105,
+];
+
+/// Inverse gain of the rotations, ∏ 1 / √(1 + 2^-2i), scaled by `10^18`
+const K: i64 = 607_252_935_008_881_256;
+
+/// Factor between `TF` digits and the iterations
+const fn unit<const TF: u8>() -> i64 {
+    10i64.pow(DIGITS - TF as u32)
+}
+
+/// Angle steps down to a hundredth of the last `TF` digit, with their shifts
+fn steps<const TF: u8>() -> impl Iterator<Item = (usize, i64)> {
+    ATAN.into_iter()
+        .enumerate()
+        .take_while(|&(_, atan)| atan >= unit::<TF>() / 100)
+}
+
+/// Iteration value rounded to `TF` digits
+const fn narrow<const TF: u8>(v: i64) -> Num<TF, TF> {
+    let unit = unit::<TF>();
+    Num::from_raw(if v >= 0 {
+        (v + unit / 2) / unit
+    } else {
+        (v - unit / 2) / unit
+    })
+}
+
+/// Sine and cosine of `angle` in [-π/2, π/2]
+#[cfg_attr(
+    feature = "lut",
+    expect(dead_code, reason = "the table computes sine and cosine")
+)]
+pub fn sin_cos<const TF: u8>(angle: Num<TF, TF>) -> (Num<TF, TF>, Num<TF, TF>) {
+    // Rotate (K, 0) by `angle`, the gain brings it to the unit circle
+    let (mut x, mut y, mut z) = (K, 0, angle.0 * unit::<TF>());
+    for (i, atan) in steps::<TF>() {
+        let (dx, dy) = (y >> i, x >> i);
+        if z >= 0 {
+            (x, y, z) = (x - dx, y + dy, z - atan);
+        } else {
+            (x, y, z) = (x + dx, y - dy, z + atan);
+        }
+    }
+
+    (narrow(y), narrow(x))
+}
+
+/// Arctangent of `r` in [-1, 1]
+pub fn atan<const TF: u8>(r: Num<TF, TF>) -> Num<TF, TF> {
+    // Rotate (1, r) onto the x axis, summing the angles it took
+    let (mut x, mut y, mut z) = (10i64.pow(DIGITS), r.0 * unit::<TF>(), 0);
+    for (i, atan) in steps::<TF>() {
+        let (dx, dy) = (y >> i, x >> i);
+        if y > 0 {
+            (x, y, z) = (x + dx, y - dy, z + atan);
+        } else {
+            (x, y, z) = (x - dx, y + dy, z - atan);
+        }
+    }
+
+    narrow(z)
+}
@@ -0,0 +1,516 @@
+//! Ranges of [`Num`] sure to hold the exact result, however often it was rounded on the way
+//!
+//! Every [`Num`] operation rounds to `F` digits, and after a few of them it is no longer
+//! clear how many of the digits shown are right, least of all at a small `F`. An
+//! [`Interval`] carries a lower and an upper end instead of one value and rounds the lower
+//! end down and the upper end up, so the result of the same computation on exact real
+//! numbers is always inside and the width is a certified bound on the error:
+//!
+//! ```
+//! use cos_num::{Num, interval::Interval};
+//!
+//! type N = Num<2, 8>;
+//!
+//! let third = Interval::from_num(N::ONE) / Interval::from_num(N::from_int(3));
+//! assert_eq!((third.lo(), third.hi()), (N::from_f64(0.33), N::from_f64(0.34)));
+//!
+//! // Each third may be off by up to 0.01, and the sum by three times as much
+//! let one = third + third + third;
+//! assert!(one.contains(N::ONE));
+//! assert_eq!(one.width(), N::from_f64(0.03));
+//! ```
+//!
+//! An end that doesn't fit a [`Num`] makes the `checked_*` functions return `None` and the
+//! operators panic, see [`Interval::ENTIRE`] for the `panic-free` feature.
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::{Num, PI_12};
+
+/// 10³⁰, the scale of [`PI_12`] and of the angles [`Interval::sin`] reduces
+const ANGLE_SCALE: i128 = 10i128.pow(30);
+
+/// Radians beyond which π/2 isn't known to enough digits to find the quarter turn,
+/// [`Interval::sin`] is then [-1, 1]
+const SIN_REACH: i128 = 1_000_000;
+
+/// Scale the sine series is summed at
+const SERIES_SCALE: i128 = 10i128.pow(18);
+
+/// Bound on the error of a sine summed by [`sin_quarter`], in units of [`SERIES_SCALE`]
+///
+/// Far more than the actual error: the reduced angle is off by less than 2 units, and each
+/// term is truncated once and carries less than 4 units from the terms before it.
+const SERIES_ERROR: i128 = 1000;
+
+/// Every value between a lower and an upper [`Num`], ends included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Interval<const F: u8, const TF: u8> {
+    lo: Num<F, TF>,
+    hi: Num<F, TF>,
+}
+
+impl<const F: u8, const TF: u8> Interval<F, TF> {
+    /// The whole range of [`Num`], what the operators give with the `panic-free` feature
+    /// where they would panic otherwise
+    pub const ENTIRE: Self = Self {
+        lo: Num(i64::MIN),
+        hi: Num(i64::MAX),
+    };
+
+    /// Values from `lo` to `hi`, `None` if `lo` is the greater
+    #[must_use]
+    pub const fn new(lo: Num<F, TF>, hi: Num<F, TF>) -> Option<Self> {
+        if lo.0 > hi.0 {
+            return None;
+        }
+        Some(Self { lo, hi })
+    }
+
+    /// Just `x`, taken as exact
+    #[inline]
+    #[must_use]
+    pub const fn from_num(x: Num<F, TF>) -> Self {
+        Self { lo: x, hi: x }
+    }
+
+    /// Lower end
+    #[inline]
+    #[must_use]
+    pub const fn lo(self) -> Num<F, TF> {
+        self.lo
+    }
+
+    /// Upper end
+    #[inline]
+    #[must_use]
+    pub const fn hi(self) -> Num<F, TF> {
+        self.hi
+    }
+
+    /// `hi - lo`, the most any value inside is off from the exact one, clamped to the
+    /// range of [`Num`]
+    #[inline]
+    #[must_use]
+    pub const fn width(self) -> Num<F, TF> {
+        Num(self.hi.0.saturating_sub(self.lo.0))
+    }
+
+    /// Whether `x` is in the interval
+    #[inline]
+    #[must_use]
+    pub const fn contains(self, x: Num<F, TF>) -> bool {
+        self.lo.0 <= x.0 && x.0 <= self.hi.0
+    }
+
+    /// `self + rhs`, or `None` if an end overflows
+    #[must_use]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match (
+            self.lo.0.checked_add(rhs.lo.0),
+            self.hi.0.checked_add(rhs.hi.0),
+        ) {
+            (Some(lo), Some(hi)) => Some(Self {
+                lo: Num(lo),
+                hi: Num(hi),
+            }),
+            _ => None,
+        }
+    }
+
+    /// `self - rhs`, or `None` if an end overflows
+    #[must_use]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match (
+            self.lo.0.checked_sub(rhs.hi.0),
+            self.hi.0.checked_sub(rhs.lo.0),
+        ) {
+            (Some(lo), Some(hi)) => Some(Self {
+                lo: Num(lo),
+                hi: Num(hi),
+            }),
+            _ => None,
+        }
+    }
+
+    /// `-self`, or `None` if the lower end is the smallest [`Num`]
+    #[must_use]
+    pub const fn checked_neg(self) -> Option<Self> {
+        match (self.hi.0.checked_neg(), self.lo.0.checked_neg()) {
+            (Some(lo), Some(hi)) => Some(Self {
+                lo: Num(lo),
+                hi: Num(hi),
+            }),
+            _ => None,
+        }
+    }
+
+    /// `self * rhs`, or `None` if an end overflows
+    #[must_use]
+    pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let scale = Num::<F, TF>::SCALE as i128;
+        let products = [
+            self.lo.0 as i128 * rhs.lo.0 as i128,
+            self.lo.0 as i128 * rhs.hi.0 as i128,
+            self.hi.0 as i128 * rhs.lo.0 as i128,
+            self.hi.0 as i128 * rhs.hi.0 as i128,
+        ];
+
+        let (mut lo, mut hi) = (i128::MAX, i128::MIN);
+        let mut i = 0;
+        while i < products.len() {
+            let (down, up) = (floor_div(products[i], scale), ceil_div(products[i], scale));
+            lo = if down < lo { down } else { lo };
+            hi = if up > hi { up } else { hi };
+            i += 1;
+        }
+        Self::narrow(lo, hi)
+    }
+
+    /// `self / rhs`, or `None` if `rhs` contains zero or an end overflows
+    #[must_use]
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.contains(Num::ZERO) {
+            return None;
+        }
+
+        let scale = Num::<F, TF>::SCALE as i128;
+        let (dividends, divisors) = (
+            [self.lo.0 as i128 * scale, self.hi.0 as i128 * scale],
+            [rhs.lo.0 as i128, rhs.hi.0 as i128],
+        );
+
+        let (mut lo, mut hi) = (i128::MAX, i128::MIN);
+        let mut i = 0;
+        while i < 4 {
+            let (n, d) = (dividends[i / 2], divisors[i % 2]);
+            let (down, up) = (floor_div(n, d), ceil_div(n, d));
+            lo = if down < lo { down } else { lo };
+            hi = if up > hi { up } else { hi };
+            i += 1;
+        }
+        Self::narrow(lo, hi)
+    }
+
+    /// Square root of the part at or above zero, `None` if there is none
+    ///
+    /// A lower end just below zero, e.g. from `x - x`, is taken as zero.
+    #[must_use]
+    pub const fn checked_sqrt(self) -> Option<Self> {
+        if self.hi.0 < 0 {
+            return None;
+        }
+
+        let scale = Num::<F, TF>::SCALE as u128;
+        let lo = if self.lo.0 < 0 { 0 } else { self.lo.0 as u128 };
+        let hi = self.hi.0 as u128 * scale;
+        let root = hi.isqrt();
+        let up = if root * root < hi { root + 1 } else { root };
+
+        // Below `i64::MAX` even for the largest value at `F` = 18, like `Num::sqrt`
+        Some(Self {
+            lo: Num((lo * scale).isqrt() as i64),
+            hi: Num(up as i64),
+        })
+    }
+
+    /// Square root of the part at or above zero, see [`Interval::checked_sqrt`]
+    ///
+    /// # Panics
+    /// Will panic if the interval is below zero
+    #[must_use]
+    pub const fn sqrt(self) -> Self {
+        require!(self.hi.0 >= 0, "sqrt of negative interval", Self::ENTIRE);
+
+        match self.checked_sqrt() {
+            Some(root) => root,
+            None => Self::ENTIRE,
+        }
+    }
+
+    /// Sine of every value in the interval
+    ///
+    /// Both ends are evaluated by a series of their own with a known bound on its error,
+    /// not by [`Num::sin`], whose last digits are not guaranteed, and a peak of the sine
+    /// between them widens the result to 1 or -1. Ends beyond a million radians give
+    /// [-1, 1], as do intervals a full turn wide.
+    #[must_use]
+    pub fn sin(self) -> Self {
+        let one = Num::<F, TF>::SCALE;
+        let whole = Self {
+            lo: Num(-one),
+            hi: Num(one),
+        };
+        let (Some((q_lo, r_lo)), Some((q_hi, r_hi))) = (quarter(self.lo), quarter(self.hi)) else {
+            return whole;
+        };
+        if q_hi - q_lo >= 4 {
+            return whole;
+        }
+
+        let (lo_down, lo_up) = sin_bounds::<F, TF>(q_lo, r_lo);
+        let (hi_down, hi_up) = sin_bounds::<F, TF>(q_hi, r_hi);
+        let (mut lo, mut hi) = (lo_down.min(hi_down), lo_up.max(hi_up));
+        // Quarter turns 1 and 3 of every turn start at the peaks
+        for q in q_lo + 1..=q_hi {
+            match q.rem_euclid(4) {
+                1 => hi = one,
+                3 => lo = -one,
+                _ => {}
+            }
+        }
+
+        Self {
+            lo: Num(lo),
+            hi: Num(hi),
+        }
+    }
+
+    /// Interval of raw ends, `None` if one doesn't fit `i64`
+    const fn narrow(lo: i128, hi: i128) -> Option<Self> {
+        match (Num::<F, TF>::narrow(lo), Num::<F, TF>::narrow(hi)) {
+            (Some(lo), Some(hi)) => Some(Self { lo, hi }),
+            _ => None,
+        }
+    }
+
+    /// Result of a checked operation, panics on `None`
+    const fn expect(result: Option<Self>) -> Self {
+        require!(result.is_some(), "interval end overflow", Self::ENTIRE);
+
+        match result {
+            Some(result) => result,
+            None => Self::ENTIRE,
+        }
+    }
+}
+
+/// `n / d` rounded down
+const fn floor_div(n: i128, d: i128) -> i128 {
+    let q = n / d;
+    if n % d != 0 && (n < 0) != (d < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+/// `n / d` rounded up
+const fn ceil_div(n: i128, d: i128) -> i128 {
+    let q = n / d;
+    if n % d != 0 && (n < 0) == (d < 0) {
+        q + 1
+    } else {
+        q
+    }
+}
+
+/// Quarter turn `q` and the rest `r` in [0, π/2) of `x = q·π/2 + r`, `r` scaled by
+/// [`ANGLE_SCALE`], `None` beyond [`SIN_REACH`]
+fn quarter<const F: u8, const TF: u8>(x: Num<F, TF>) -> Option<(i128, i128)> {
+    let scale = Num::<F, TF>::SCALE as i128;
+    if (x.0 as i128).abs() > SIN_REACH * scale {
+        return None;
+    }
+
+    // Off by less than 6 units, which the reach keeps below 4·10⁶ units in `r`
+    let half_pi = 6 * PI_12;
+    let x = x.0 as i128 * (ANGLE_SCALE / scale);
+    let q = x.div_euclid(half_pi);
+    Some((q, x - q * half_pi))
+}
+
+/// Raw bounds of sin(q·π/2 + r) at `F` digits, `r` from [`quarter`]
+fn sin_bounds<const F: u8, const TF: u8>(q: i128, r: i128) -> (i64, i64) {
+    let one = Num::<F, TF>::SCALE as i128;
+    let unit = SERIES_SCALE / one;
+    let v = sin_quarter(q, r / (ANGLE_SCALE / SERIES_SCALE));
+
+    // Within [-1, 1] so within `i64`
+    (
+        floor_div(v - SERIES_ERROR, unit).max(-one) as i64,
+        ceil_div(v + SERIES_ERROR, unit).min(one) as i64,
+    )
+}
+
+/// sin(q·π/2 + r) with `r` in [0, π/2) scaled by [`SERIES_SCALE`], within [`SERIES_ERROR`]
+const fn sin_quarter(q: i128, r: i128) -> i128 {
+    let r2 = r * r / SERIES_SCALE;
+    // Odd quarters are the cosine of `r`, terms are added until they vanish
+    let (mut term, mut n) = if q % 2 == 0 {
+        (r, 1)
+    } else {
+        (SERIES_SCALE, 0)
+    };
+    let mut sum = 0;
+    while term != 0 {
+        sum += term;
+        term = -term * r2 / (SERIES_SCALE * (n + 1) * (n + 2));
+        n += 2;
+    }
+
+    if q.rem_euclid(4) >= 2 { -sum } else { sum }
+}
+
+impl<const F: u8, const TF: u8> Add for Interval<F, TF> {
+    type Output = Self;
+
+    /// # Panics
+    /// Will panic if an end overflows
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::expect(self.checked_add(rhs))
+    }
+}
+
+impl<const F: u8, const TF: u8> Sub for Interval<F, TF> {
+    type Output = Self;
+
+    /// # Panics
+    /// Will panic if an end overflows
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::expect(self.checked_sub(rhs))
+    }
+}
+
+impl<const F: u8, const TF: u8> Neg for Interval<F, TF> {
+    type Output = Self;
+
+    /// # Panics
+    /// Will panic if the lower end is the smallest [`Num`]
+    #[inline]
+    fn neg(self) -> Self {
+        Self::expect(self.checked_neg())
+    }
+}
+
+impl<const F: u8, const TF: u8> Mul for Interval<F, TF> {
+    type Output = Self;
+
+    /// # Panics
+    /// Will panic if an end overflows
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self::expect(self.checked_mul(rhs))
+    }
+}
+
+impl<const F: u8, const TF: u8> Div for Interval<F, TF> {
+    type Output = Self;
+
+    /// # Panics
+    /// Will panic if `rhs` contains zero or an end overflows
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        require!(
+            !rhs.contains(Num::ZERO),
+            "division by interval containing zero",
+            Self::ENTIRE
+        );
+
+        Self::expect(self.checked_div(rhs))
+    }
+}
+
+impl<const F: u8, const TF: u8> From<Num<F, TF>> for Interval<F, TF> {
+    #[inline]
+    fn from(x: Num<F, TF>) -> Self {
+        Self::from_num(x)
+    }
+}
+
+impl<const F: u8, const TF: u8> core::fmt::Display for Interval<F, TF> {
+    /// Ends like `[0.33, 0.34]`
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<const F: u8, const TF: u8> ufmt::uDisplay for Interval<F, TF> {
+    /// Ends like `[0.33, 0.34]`
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        ufmt::uwrite!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interval;
+    use crate::Num;
+
+    type N = Num<2, 8>;
+    type I = Interval<2, 8>;
+
+    fn i(lo: f64, hi: f64) -> I {
+        I::new(N::from_f64(lo), N::from_f64(hi)).unwrap()
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(I::new(N::ONE, N::ZERO), None);
+        assert_eq!(i(-1.5, 2.0) + i(0.25, 0.5), i(-1.25, 2.5));
+        assert_eq!(i(-1.5, 2.0) - i(0.25, 0.5), i(-2.0, 1.75));
+        assert_eq!(-i(-1.5, 2.0), i(-2.0, 1.5));
+
+        // Ends rounded outward, -0.495 down and 0.66 up as they are
+        assert_eq!(i(-1.5, 2.0) * i(0.33, 0.34), i(-0.51, 0.68));
+        assert_eq!(i(1.5, 1.5) * i(-0.33, -0.33), i(-0.5, -0.49));
+        assert_eq!(
+            I::from(N::from_int(2)) / I::from(N::from_int(3)),
+            i(0.66, 0.67)
+        );
+        assert_eq!(i(-1.0, 1.0) / i(-3.0, -3.0), i(-0.34, 0.34));
+        assert_eq!(i(1.0, 2.0).checked_div(i(-1.0, 1.0)), None);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(I::from(N::from_int(2)).sqrt(), i(1.41, 1.42));
+        assert_eq!(i(-0.01, 4.0).sqrt(), i(0.0, 2.0));
+        assert_eq!(i(-2.0, -1.0).checked_sqrt(), None);
+    }
+
+    #[test]
+    fn test_width() {
+        // Rounding error piles up in the width, the exact result stays inside
+        let third = I::from(N::ONE) / I::from(N::from_int(3));
+        let one = third * I::from(N::from_int(3)) - I::from(N::ONE);
+        assert!(one.contains(N::ZERO));
+        assert_eq!(one.width(), N::from_f64(0.03));
+        assert_eq!(third.to_string(), "[0.33, 0.34]");
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(I::ENTIRE.checked_add(I::from(N::ONE)), None);
+        assert_eq!(I::from(N::from_raw(i64::MIN)).checked_neg(), None);
+        assert_eq!(I::ENTIRE.checked_mul(i(2.0, 2.0)), None);
+    }
+
+    #[test]
+    fn test_sin() {
+        assert_eq!(I::from(N::ZERO).sin(), i(-0.01, 0.01));
+        assert_eq!(i(0.0, 3.15).sin(), i(-0.01, 1.0));
+        assert_eq!(i(3.0, 5.0).sin(), i(-1.0, 0.15));
+        assert_eq!(i(-2.0, 5.0).sin(), i(-1.0, 1.0));
+        assert_eq!(I::from(N::from_int(2_000_000)).sin(), i(-1.0, 1.0));
+    }
+
+    #[test]
+    fn test_sin_certified() {
+        let x = Interval::<6, 8>::from(Num::from_int(1)).sin();
+        assert_eq!((x.lo().raw(), x.hi().raw()), (841_470, 841_471));
+        for n in -300..300 {
+            let x = Num::<6, 8>::from_raw(n * 33_333);
+            let sin = Interval::from(x).sin();
+            assert!(sin.contains(Num::from_f64(x.to_f64().sin())), "{x}");
+            assert!(sin.width() <= Num::from_raw(2), "{x}");
+        }
+    }
+}
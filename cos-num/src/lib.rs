@@ -1,3 +1,58 @@
+//! Decimal fixed-point arithmetic for small targets
+//!
+//! [`Num`] is a signed 64-bit integer scaled by `10^F`, with `TF` fractional
+//! digits used internally by the Taylor series behind the transcendental
+//! functions. Everything is `no_std`, allocation free and does not touch floats
+//! except in the conversions [`Num::from_f64`], [`Num::to_f64`] and [`Num::to_f32`].
+//!
+//! ```
+//! use cos_num::Num;
+//!
+//! let x = Num::<4, 8>::from_int(2).sqrt();
+//! assert_eq!(x.raw(), 14142);
+//! assert_eq!(x.to_string(), "1.4142");
+//! ```
+//!
+//! [`small::Num32`] and [`small::Num16`] store the same values in an `i32` or `i16` where
+//! RAM is short, with the basic operations. [`num128::Num128`] stores them in an `i128`, for
+//! sums of products that outgrow an `i64` before they are scaled back down.
+//! [`binary::BinNum`] scales by a power of two instead, for control loops where the
+//! cheaper shifts matter more than exact decimal fractions. [`interval::Interval`] rounds
+//! a lower and an upper end outward, for results with a certified bound on their error.
+//! [`complex::Complex`] pairs two [`Num`]s as the real and imaginary part of a complex
+//! number. [`ratio::Ratio`] keeps an exact fraction of two `i64`s, for results like 1/3
+//! that no number of decimals holds.
+//!
+//! # Features
+//! - `std` (default) - link the standard library, disable for `no_std` targets
+//! - `ufmt` - derive `ufmt::uDebug` for [`Num`], [`Digit`], [`Bcd`] and [`Root`], implement
+//!   `ufmt::uDisplay` for [`Num`] and [`Dms`]
+//! - `defmt` - implement `defmt::Format` for [`Num`], logged as its decimal text
+//! - `num-traits` - implement `Zero`, `One`, `Num`, `Signed`, `Bounded`, `FromPrimitive` and
+//!   `ToPrimitive` for [`Num`], so generic math crates take it as is
+//! - `serde` - implement `Serialize` and `Deserialize` for [`Num`], as decimal text or the
+//!   raw `i64` depending on the format, see `serde`
+//! - `cordic` - compute sine, cosine and arctangent by CORDIC, shifts and additions instead
+//!   of series divisions, smaller and faster on AVR; the sine and cosine steps are then
+//!   ready right away
+//! - `lut` - compute sine and cosine from a quarter-wave table with linear interpolation,
+//!   fast and constant in time, accurate to 4 digits; takes precedence over `cordic`
+//! - `wide` - compute `*` and `/` with `i128` intermediates, exact whenever the result fits
+//! - `shadow` - check results against `f64` at runtime and report divergences, see `shadow`
+//! - `panic-free` - return zero where a function documents a panic, e.g. on division by
+//!   zero or the square root of a negative number, so no panic branch is left in the binary;
+//!   use the `checked_*` functions to tell such results apart
+//!
+//! # Stability
+//! The crate follows semver. The public surface is [`Num`], its inherent methods,
+//! constants, operator and conversion impls, [`TryFromIntError`], [`ParseNumError`],
+//! [`Digit`] / [`Digits`] / [`SqrtDigits`], [`Bcd`], [`Dms`], [`Solver`] / [`Root`], the
+//! [`binary`], [`complex`], [`interval`], [`num128`], [`overflow`], [`ratio`], [`small`] and [`units`] types and the `serde` representations; the exact value a transcendental function returns in
+//! its last digit or the number of steps a [`Solver`] takes are not part of it and may
+//! improve in patch releases.
+//!
+//! The minimum supported Rust version is 1.87, raising it is done in minor releases.
+
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use core::{
@@ -6,16 +61,90 @@ use core::{
         Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub,
         SubAssign,
     },
+    task::Poll,
 };
+#[cfg(feature = "ufmt")]
 use ufmt::derive::uDebug;
 
+#[cfg(feature = "cordic")]
+mod cordic;
+#[cfg(feature = "lut")]
+mod lut;
+#[cfg(feature = "num-traits")]
+mod num_traits;
+pub mod overflow;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "shadow")]
+pub mod shadow;
+pub mod units;
+
+/// Value of `$got`, also checked against `$reference` applied to the `f64` values of the
+/// arguments with the `shadow` feature, see [`shadow`]
+macro_rules! shadowed {
+    ($op:literal, [$($arg:expr),+], $reference:expr, $got:expr) => {{
+        let got = $got;
+        #[cfg(feature = "shadow")]
+        shadow::check($op, [$($arg),+], got, $reference);
+        got
+    }};
+}
+
+/// Panic with `$msg` unless `$cond` holds, with the `panic-free` feature return `$fallback`
+/// instead, so no panic branch is left behind
+#[cfg(not(feature = "panic-free"))]
+macro_rules! require {
+    ($cond:expr, $msg:literal, $fallback:expr) => {
+        assert!($cond, $msg)
+    };
+}
+
+#[cfg(feature = "panic-free")]
+macro_rules! require {
+    ($cond:expr, $msg:literal, $fallback:expr) => {
+        if !$cond {
+            return $fallback;
+        }
+    };
+}
+
+// After the macros, which they use
+pub mod binary;
+pub mod complex;
+pub mod interval;
+pub mod num128;
+pub mod ratio;
+pub mod small;
+
+/// π/180 scaled by 10²⁰, for [`Num::to_radians`]
+const RAD_PER_DEGREE: i128 = 1_745_329_251_994_329_577;
+
+/// 180/π scaled by 10¹⁷, for [`Num::to_degrees`]
+const DEGREES_PER_RAD: i128 = 5_729_577_951_308_232_088;
+
+/// π/12 scaled by 10³⁰, the step between the angles [`Num::special_angle`] recognizes
+const PI_12: i128 = 261_799_387_799_149_436_538_553_615_273;
+
+/// √2/2 scaled by 10³⁰, sine of π/4
+const SQRT_2_HALF: i128 = 707_106_781_186_547_524_400_844_362_105;
+
+/// √3/2 scaled by 10³⁰, sine of π/3
+const SQRT_3_HALF: i128 = 866_025_403_784_438_646_763_723_170_753;
+
+/// √3/3 scaled by 10³⁰, tangent of π/6
+const SQRT_3_THIRD: i128 = 577_350_269_189_625_764_509_148_780_502;
+
+/// √3 scaled by 10³⁰, tangent of π/3
+const SQRT_3: i128 = 1_732_050_807_568_877_293_527_446_341_506;
+
 /// Fixed-point numeric type with compile-time decimal scaling.
 ///
 /// Num stores a signed 64-bit integer that represents a fixed-point value
 /// with F decimal fractional digits. The underlying stored value is the
 /// integer representation scaled by 10^F. For example, `Num::<2>::from_int(3)`
 /// stores 300 and represents 3.00.
-#[derive(Debug, uDebug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "ufmt", derive(uDebug))]
 #[repr(transparent)]
 pub struct Num<const F: u8, const TF: u8>(pub i64);
 
@@ -60,6 +189,40 @@ impl<const F: u8, const TF: u8> Num<F, TF> {
     /// Natural logarithm of 2 (ln(2))
     pub const LN_2: Self = Self::from_2_longs(0, 6931471805599453094);
 
+    /// Natural logarithm of 10 (ln(10))
+    pub const LN_10: Self = Self::from_2_longs(2, 3025850929940456840);
+
+    /// Base 2 logarithm of e (log₂(e))
+    pub const LOG2_E: Self = Self::from_2_longs(1, 4426950408889634074);
+
+    /// Below this magnitude sin(x) = x is exact at current precision
+    ///
+    /// The dropped x³/6 term is less than half of the last digit.
+    pub const SIN_SMALL_ANGLE: Self = {
+        // Largest t with t³ < 3 * SCALE², so (t / SCALE)³ / 6 < 0.5 / SCALE
+        let limit = 3 * (Self::SCALE as i128) * (Self::SCALE as i128);
+        let (mut lo, mut hi) = (0i128, Self::SCALE as i128);
+        while lo < hi {
+            let mid = (lo + hi + 1) / 2;
+            // mid³ < limit without overflowing for big F
+            if mid * mid <= (limit - 1) / mid {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        Self(lo as i64)
+    };
+
+    /// Below this magnitude cos(x) = 1 - x²/2 is exact at current precision
+    ///
+    /// The dropped x⁴/24 term is less than half of the last digit.
+    pub const COS_SMALL_ANGLE: Self = {
+        // t⁴ < 12 * SCALE³ rewritten as t² < SCALE * √(12 * SCALE) to stay in i128
+        let scale = Self::SCALE as i128;
+        Self((scale * (12 * scale).isqrt()).isqrt() as i64)
+    };
+
     /// Create from raw inner representation (no scaling).
     #[inline]
     #[must_use]
@@ -81,6 +244,16 @@ impl<const F: u8, const TF: u8> Num<F, TF> {
         Self(n.saturating_mul(Self::SCALE))
     }
 
+    /// Create from integer, `None` if it doesn't fit instead of saturating
+    #[inline]
+    #[must_use]
+    pub const fn checked_from_int(n: i64) -> Option<Self> {
+        match n.checked_mul(Self::SCALE) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
     /// Create from f64 floating point value
     /// Only f64 present because f32 is very lossy
     ///
@@ -90,7 +263,11 @@ impl<const F: u8, const TF: u8> Num<F, TF> {
     #[must_use]
     pub const fn from_f64(value: f64) -> Self {
         // Handle special cases
-        assert!(!value.is_nan(), "Cannot convert NaN to fixed-point number");
+        require!(
+            !value.is_nan(),
+            "Cannot convert NaN to fixed-point number",
+            Self::ZERO
+        );
 
         if value.is_infinite() {
             if value.is_sign_positive() {
@@ -109,10 +286,34 @@ impl<const F: u8, const TF: u8> Num<F, TF> {
         } else if scaled < i64::MIN as f64 {
             Self(i64::MIN)
         } else {
-            Self(scaled.round() as i64)
+            // `f64::round` needs std, so round half away from zero by hand
+            let int = scaled as i64;
+            let frac = scaled - int as f64;
+
+            Self(if frac >= 0.5 {
+                int + 1
+            } else if frac <= -0.5 {
+                int - 1
+            } else {
+                int
+            })
         }
     }
 
+    /// Closest `f64`, exact while the raw value fits in 53 bits
+    #[inline]
+    #[must_use]
+    pub const fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::SCALE as f64
+    }
+
+    /// Closest `f32` to [`Num::to_f64`], for logging and comparisons only
+    #[inline]
+    #[must_use]
+    pub const fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
     /// Create from integer and fraction
     #[inline]
     #[must_use]
@@ -132,303 +333,1912 @@ impl<const F: u8, const TF: u8> Num<F, TF> {
         }
     }
 
+    /// Parse a decimal number like `-123.45`, `None` if it isn't one or doesn't fit
+    ///
+    /// See [`Num::parse_bytes`] for the error, `str::parse` works too.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<2, 8>;
+    /// assert_eq!(N::from_ascii(b"-123.45"), Some(N::from_raw(-12345)));
+    /// assert_eq!(N::from_ascii(b".125"), Some(N::from_raw(13)));
+    /// assert_eq!(N::from_ascii(b"1e3"), None);
+    /// ```
     #[inline]
     #[must_use]
-    pub const fn abs(self) -> Self {
-        Self(self.0.abs())
+    pub const fn from_ascii(bytes: &[u8]) -> Option<Self> {
+        match Self::parse_bytes(bytes) {
+            Ok(v) => Some(v),
+            Err(_) => None,
+        }
     }
 
-    /// Get square root of self
+    /// Parse a decimal number like `-123.45`
     ///
-    /// # Panics
-    /// Will panic if self is negative
-    #[must_use]
-    pub const fn sqrt(self) -> Self {
-        // Why i dont use `Self(self.0.wrapping_mul(Self::SCALE).isqrt())`?
-        // Cool question, because my code looks weird like why
-        // if we already have 0i32.isqrt(). So, i have answer:
-        // Rust isqrt impl: 12754 bytes to flash
-        // My isqrt impl: 11344 bytes to flash
-        // Idk why this happen
-
-        assert!(self.0 >= 0, "sqrt of negative number");
+    /// A sign, digits and at most one decimal point, at least one digit. Digits past the
+    /// fraction are rounded half away from zero.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there are no digits, on any other character or if the number
+    /// doesn't fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::{Num, ParseNumError};
+    ///
+    /// type N = Num<2, 8>;
+    /// assert_eq!(N::parse_bytes(b"-12.345"), Ok(N::from_raw(-1235)));
+    /// assert_eq!("0.5".parse(), Ok(N::from_raw(50)));
+    /// assert_eq!(N::parse_bytes(b"-"), Err(ParseNumError::Empty));
+    /// assert_eq!(N::parse_bytes(b"1,5"), Err(ParseNumError::InvalidDigit));
+    /// assert_eq!(N::parse_bytes(b"1e20"), Err(ParseNumError::InvalidDigit));
+    /// assert_eq!(N::parse_bytes(b"100000000000000000"), Err(ParseNumError::Overflow));
+    /// ```
+    pub const fn parse_bytes(bytes: &[u8]) -> Result<Self, ParseNumError> {
+        let (negative, mut i) = match bytes {
+            [b'-', ..] => (true, 1),
+            [b'+', ..] => (false, 1),
+            _ => (false, 0),
+        };
 
-        if self.0 == 0 {
-            return Self::ZERO;
+        // Magnitude of the digits kept, i64::MIN has one more than i64::MAX
+        let mut raw: u64 = 0;
+        let mut digits = 0;
+        let mut point = false;
+        let mut frac_digits = 0u8;
+        let mut round_up = false;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'.' if !point => point = true,
+                digit @ b'0'..=b'9' => {
+                    digits += 1;
+                    if point && frac_digits >= F {
+                        // Only the first dropped digit decides the rounding
+                        if frac_digits == F {
+                            round_up = digit >= b'5';
+                            frac_digits += 1;
+                        }
+                    } else {
+                        raw = match raw.checked_mul(10) {
+                            Some(raw) => match raw.checked_add((digit - b'0') as u64) {
+                                Some(raw) => raw,
+                                None => return Err(ParseNumError::Overflow),
+                            },
+                            None => return Err(ParseNumError::Overflow),
+                        };
+                        if point {
+                            frac_digits += 1;
+                        }
+                    }
+                }
+                _ => return Err(ParseNumError::InvalidDigit),
+            }
+            i += 1;
         }
-
-        let n = self.0 * Self::SCALE;
-        let mut x0 = n;
-        let mut x1 = i64::midpoint(x0, n / x0);
-
-        while x1 < x0 {
-            x0 = x1;
-            x1 = i64::midpoint(x0, n / x0);
+        if digits == 0 {
+            return Err(ParseNumError::Empty);
         }
 
-        // Round
-        let diff = n - x0 * x0;
-        if diff * 2 < 2 * x0 + 1 {
-            Self(x0)
+        let kept = if frac_digits > F { F } else { frac_digits };
+        let Some(raw) = raw.checked_mul(10u64.pow((F - kept) as u32)) else {
+            return Err(ParseNumError::Overflow);
+        };
+        let Some(raw) = raw.checked_add(round_up as u64) else {
+            return Err(ParseNumError::Overflow);
+        };
+
+        if negative {
+            if raw > i64::MIN.unsigned_abs() {
+                return Err(ParseNumError::Overflow);
+            }
+            Ok(Self((raw as i64).wrapping_neg()))
+        } else if raw > i64::MAX as u64 {
+            Err(ParseNumError::Overflow)
         } else {
-            Self(x0 + 1)
+            Ok(Self(raw as i64))
         }
     }
 
-    /// Calculate factorial (n!)
-    ///
-    /// # Panics
+    /// Create from `raw` with `digits` fractional digits, e.g. millivolts as volts with 3
     ///
-    /// Will panic if self is negative or self > 20 or self isnt natural number
+    /// If F < `digits` the excess digits are rounded half away from zero,
+    /// so `Num::<2, 2>::from_raw_scaled(3305, 3)` is 3.31.
     #[inline]
     #[must_use]
-    pub const fn factorial(self) -> Self {
-        assert!(self.0 >= 0, "Factorial of negative number");
-        assert!(self.0 % Self::SCALE == 0, "Factorial of non-integer");
-
-        Self(
-            match self.0 / Self::SCALE {
-                0 | 1 => 1,
-                2 => 2,
-                3 => 6,
-                4 => 24,
-                5 => 120,
-                6 => 720,
-                7 => 5040,
-                8 => 40320,
-                9 => 362880,
-                10 => 3628800,
-                11 => 39916800,
-                12 => 479001600,
-                13 => 6227020800,
-                14 => 87178291200,
-                15 => 1307674368000,
-                16 => 20922789888000,
-                17 => 355687428096000,
-                18 => 6402373705728000,
-                19 => 121645100408832000,
-                20 => 2432902008176640000i64,
-                _ => panic!("Factorial will big what i64::MAX (n > 20)"),
-            }
-            .saturating_mul(Self::SCALE),
-        )
+    pub const fn from_raw_scaled(raw: i64, digits: u8) -> Self {
+        Self(rescale(raw, digits, F))
     }
 
-    /// Common Taylor series implementation
+    /// Value as a whole number of units with `digits` fractional digits, e.g. volts as
+    /// millivolts with 3
+    ///
+    /// Excess digits are rounded half away from zero, missing ones saturate on overflow.
     #[inline]
     #[must_use]
-    pub fn taylor_series(
-        first: Num<TF, TF>,
-        acc: usize,
-        mut next: impl FnMut(Num<TF, TF>, usize) -> (Num<TF, TF>, Num<TF, TF>),
-    ) -> Num<TF, TF> {
-        let mut sum = first;
-        let mut dividend = first;
-        let mut result;
-        let mut n = 1 + acc;
-        let max_iterations = 15;
-
-        while n < max_iterations {
-            (dividend, result) = next(dividend, n);
-            sum += result;
-            println!("result {result:?}");
-            n += acc;
-        }
-
-        sum
+    pub const fn to_raw_scaled(self, digits: u8) -> i64 {
+        rescale(self.0, F, digits)
     }
 
-    /// Normalize angle to [-π, π] range
+    /// Create from a duration in milliseconds, treating `Num` as seconds
+    ///
+    /// If F < 3 the excess digits are rounded half away from zero,
+    /// so `Num::<2, 2>::from_millis(1005)` is 1.01 seconds.
     #[inline]
     #[must_use]
-    pub fn normalize_angle(self) -> Self {
-        let mut angle = self;
-
-        // Use remainder division to handle large angles efficiently
-        if angle.0.abs() > Self::TAU.0 {
-            let rotations = angle / Self::TAU;
-            // Use integer division to get the whole number of rotations
-            let whole_rotations = if rotations.0 >= 0 {
-                (rotations.0 + Self::SCALE / 2) / Self::SCALE
-            } else {
-                (rotations.0 - Self::SCALE / 2) / Self::SCALE
-            };
-            angle -= Self::TAU * Self::from_int(whole_rotations);
-        }
-
-        // Normalize to [-π, π]
-        if angle > Self::PI {
-            angle -= Self::TAU;
-        } else if angle < -Self::PI {
-            angle += Self::TAU;
-        }
-
-        angle
+    pub const fn from_millis(ms: i64) -> Self {
+        Self(rescale(ms, 3, F))
     }
 
-    /// Calculate sine using Taylor series expansion
+    /// Create from a duration in microseconds, treating `Num` as seconds
+    ///
+    /// Rounding is the same as in [`Num::from_millis`].
     #[inline]
     #[must_use]
-    pub fn sin(self) -> Self {
-        let mut x = self.increase_frac::<TF>().normalize_angle();
-
-        // For angles in [π/2, π] and [-π, -π/2], use sin(x) = sin(π - x)
-        if x > Num::<TF, TF>::PI / Num::<TF, TF>::from_int(2) {
-            x = Num::<TF, TF>::PI - x;
-        } else if x < -Num::<TF, TF>::PI / Num::<TF, TF>::from_int(2) {
-            x = -Num::<TF, TF>::PI - x;
-        }
-
-        let x2 = x * x;
-        let mut neg = false;
-
-        Num::<TF, TF>::taylor_series(x, 2, |dividend, n| {
-            neg = !neg;
-            let i = dividend * x2;
-            (
-                i,
-                if neg { -i } else { i } / Num::from_int(n as i64).factorial(),
-            )
-        })
-        .decrease_frac::<F>()
+    pub const fn from_micros(us: i64) -> Self {
+        Self(rescale(us, 6, F))
     }
 
-    /// Calculate cosine using identity cos(x) = sin(π/2 - x)
+    /// Convert seconds to whole milliseconds
+    ///
+    /// If F > 3 the value is rounded half away from zero,
+    /// if F < 3 the result saturates on overflow.
     #[inline]
     #[must_use]
-    pub fn cos(self) -> Self {
-        (Self::PI / Self::from_int(2) - self).sin()
+    pub const fn to_millis(self) -> i64 {
+        rescale(self.0, F, 3)
     }
 
-    /// Calculate tangent using identity tan(x) = sin(x) / cos(x)
+    /// Convert seconds to whole microseconds
+    ///
+    /// Rounding is the same as in [`Num::to_millis`].
     #[inline]
     #[must_use]
-    pub fn tan(self) -> Self {
-        self.sin() / self.cos()
+    pub const fn to_micros(self) -> i64 {
+        rescale(self.0, F, 6)
     }
 
-    /// Calculate cotangent using identity ctg(x) = cos(x) / sin(x)
-    #[inline]
+    /// Split degrees into whole degrees, minutes and seconds
+    ///
+    /// The split is exact, seconds keep all `F` digits and minutes and seconds are
+    /// always below 60. Only the degrees carry the sign, so between -1° and 0° it has
+    /// to be taken from self, [`Dms`] does that for text output. Degrees outside the
+    /// `i32` range saturate.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::{Dms, Num};
+    ///
+    /// type N = Num<4, 8>;
+    /// let x = N::from_f64(12.5822);
+    /// assert_eq!(x.to_dms(), (12, 34, N::from_f64(55.92)));
+    /// assert_eq!(N::from_dms(12, 34, N::from_f64(55.92)), x);
+    /// assert_eq!(Dms(x).to_string(), "12°34'55.92\"");
+    /// ```
     #[must_use]
-    pub fn ctg(self) -> Self {
-        self.cos() / self.sin()
+    pub const fn to_dms(self) -> (i32, u8, Self) {
+        let scale = Self::SCALE.unsigned_abs() as u128;
+        // Seconds still have `F` digits, nothing is rounded
+        let seconds = self.0.unsigned_abs() as u128 * 3600;
+
+        let degrees = seconds / (3600 * scale);
+        let degrees = if degrees > i32::MAX as u128 {
+            i32::MAX
+        } else {
+            degrees as i32
+        };
+        let minutes = (seconds / (60 * scale) % 60) as u8;
+
+        (
+            if self.0 < 0 { -degrees } else { degrees },
+            minutes,
+            Self((seconds % (60 * scale)) as i64),
+        )
     }
 
-    /// Calculate hyperbolic sine using Taylor series expansion
-    #[inline]
+    /// Join degrees, minutes and seconds into degrees, the inverse of [`Num::to_dms`]
+    ///
+    /// Negative degrees make the whole angle negative, negate the result for angles
+    /// between -1° and 0°. The result is rounded half away from zero and wraps if it
+    /// does not fit.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if minutes or seconds are outside [0, 60).
     #[must_use]
-    pub fn sinh(self) -> Self {
-        let x = self.increase_frac::<TF>();
-        let x2 = x * x;
+    pub const fn from_dms(degrees: i32, minutes: u8, seconds: Self) -> Self {
+        require!(
+            minutes < 60 && seconds.0 >= 0 && seconds.0 < 60 * Self::SCALE,
+            "from_dms minutes or seconds outside [0, 60)",
+            Self::ZERO
+        );
 
-        Num::<TF, TF>::taylor_series(x, 2, |dividend, n| {
-            let i = dividend * x2;
-            (i, i / Num::from_int(n as i64).factorial())
-        })
-        .decrease_frac::<F>()
-    }
+        let seconds = (degrees.unsigned_abs() as i128 * 3600 + minutes as i128 * 60)
+            * Self::SCALE as i128
+            + seconds.0 as i128;
+        let raw = ((seconds + 1800) / 3600) as i64;
 
-    /// Calculate hyperbolic cosine using identity cosh(x) = sqrt(1 + sinh²(x))
-    #[inline]
-    #[must_use]
-    pub fn cosh(self) -> Self {
-        let sinh = self.sinh();
-        (sinh * sinh + Self::ONE).sqrt()
+        Self(if degrees < 0 { raw.wrapping_neg() } else { raw })
     }
 
-    /// Calculate hyperbolic tangent using identity tanh(x) = sinh(x) / cosh(x)
+    /// Round to `digits` fractional digits, keeping the scale
+    ///
+    /// Rounds half away from zero, `digits >= F` returns the value unchanged. Where that
+    /// rounds past the range it saturates to the largest or smallest value with `digits`
+    /// fractional digits. Use [`Num::decrease_frac`] when the precision is known at compile
+    /// time.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// let x = Num::<4, 8>::from_f64(3.1416).round_frac(2);
+    /// assert_eq!(x.raw(), 31400); // 3.1400
+    /// ```
     #[inline]
     #[must_use]
-    pub fn tanh(self) -> Self {
-        self.sinh() / self.cosh()
+    pub const fn round_frac(self, digits: u8) -> Self {
+        if digits >= F {
+            return self;
+        }
+
+        let step = 10i64.pow((F - digits) as u32);
+        let q = rescale(self.0, F, digits);
+        match q.checked_mul(step) {
+            Some(raw) => Self(raw),
+            // Rounded away from zero past the range, one step back toward it fits
+            None => Self((q - q.signum()) * step),
+        }
     }
 
-    /// Calculate hyperbolic cotangent using identity coth(x) = cosh(x) / sinh(x)
+    /// Largest whole number less than or equal to self
+    ///
+    /// Near the bottom of the range, where that doesn't fit, saturates to the smallest whole
+    /// number that does.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<2, 8>;
+    /// assert_eq!(N::from_f64(-1.25).floor(), N::from_int(-2));
+    /// assert_eq!(N::from_f64(-1.25).ceil(), N::from_int(-1));
+    /// assert_eq!(N::from_f64(-1.5).round(), N::from_int(-2));
+    /// assert_eq!(N::from_f64(-1.25).trunc(), N::from_int(-1));
+    /// assert_eq!(N::from_f64(-1.25).fract(), N::from_f64(-0.25));
+    /// ```
     #[inline]
     #[must_use]
-    pub fn ctgh(self) -> Self {
-        self.cosh() / self.sinh()
+    pub const fn floor(self) -> Self {
+        let q = self.0.div_euclid(Self::SCALE);
+        match q.checked_mul(Self::SCALE) {
+            Some(raw) => Self(raw),
+            None => Self((q + 1) * Self::SCALE),
+        }
     }
 
-    /// Calculate natural logarithm using Taylor series expansion
+    /// Smallest whole number greater than or equal to self
     ///
-    /// # Panics
-    /// Will panic if self is non-positive number
+    /// Near the top of the range, where that doesn't fit, saturates to the largest whole
+    /// number that does.
     #[inline]
     #[must_use]
-    pub fn ln(self) -> Self {
-        assert!(self.0 > 0, "ln of non-positive number");
-
-        // Reduce the argument to range [0.5, 2] by powers of 2
-        let mut n = 0;
-        let mut value = self.increase_frac::<TF>();
-        let two = Num::<TF, TF>::from_int(2);
-
-        while value > two {
-            value /= two;
-            n += 1;
+    pub const fn ceil(self) -> Self {
+        if self.0 % Self::SCALE == 0 {
+            return self;
         }
 
-        while value < Num::<TF, TF>::ONE {
-            value *= two;
-            n -= 1;
+        let q = self.0.div_euclid(Self::SCALE);
+        match (q + 1).checked_mul(Self::SCALE) {
+            Some(raw) => Self(raw),
+            None => Self(q * Self::SCALE),
         }
+    }
 
-        // ln(x) = 2 * artanh((x-1)/(x+1))
-        let x = (value - Num::<TF, TF>::ONE) / (value + Num::<TF, TF>::ONE);
-        let x2 = x * x;
-
-        let mut neg = false;
-        let result = Num::<TF, TF>::taylor_series(x, 2, |dividend, n| {
-            neg = !neg;
-            let i = dividend * x2;
-            (i, i / Num::from_int(n as i64))
-        });
-
-        (result * two + Num::<TF, TF>::from_int(n) * Num::<TF, TF>::LN_2).decrease_frac::<F>()
+    /// Nearest whole number, rounding half away from zero
+    ///
+    /// Saturates like [`Num::round_frac`].
+    #[inline]
+    #[must_use]
+    pub const fn round(self) -> Self {
+        self.round_frac(0)
     }
 
-    /// Calculate area hyperbolic sine using logarithmic identity: arsinh(x) = ln(x + √(x² + 1))
+    /// Whole part, rounding toward zero
     #[inline]
     #[must_use]
-    pub fn arcsinh(self) -> Self {
-        (self + (self * self + Self::ONE).sqrt()).ln()
+    pub const fn trunc(self) -> Self {
+        Self(self.0 / Self::SCALE * Self::SCALE)
     }
 
-    /// Calculate area hyperbolic cosine using logarithmic identity: arcosh(x) = ln(x + √(x² - 1))
+    /// Fractional part, with the sign of self
     #[inline]
     #[must_use]
-    pub fn arccosh(self) -> Self {
-        (self + (self * self - Self::ONE).sqrt()).ln()
+    pub const fn fract(self) -> Self {
+        Self(self.0 % Self::SCALE)
     }
 
-    /// Calculate area hyperbolic tangent using logarithmic identity: artanh(x) = 0.5 * ln((1 + x)/(1 - x))
+    /// Append a decimal digit to the integer part, like typing it on a keypad
+    ///
+    /// The digit extends the magnitude, so for negative values it is subtracted.
+    /// Returns `None` if the result does not fit, checked before multiplying.
     #[inline]
     #[must_use]
-    pub fn arctanh(self) -> Self {
-        ((Self::ONE + self) / (Self::ONE - self)).ln() / Self::from_int(2)
+    pub const fn checked_push_digit(self, digit: u8) -> Option<Self> {
+        let digit = digit as i64 * Self::SCALE;
+
+        if self.0 >= 0 {
+            if self.0 > (i64::MAX - digit) / 10 {
+                return None;
+            }
+            Some(Self(self.0 * 10 + digit))
+        } else {
+            if self.0 < (i64::MIN + digit) / 10 {
+                return None;
+            }
+            Some(Self(self.0 * 10 - digit))
+        }
     }
 
-    /// Calculate area hyperbolic cotangent using logarithmic identity: arcoth(x) = 0.5 * ln((x + 1)/(x - 1))
     #[inline]
     #[must_use]
-    pub fn arcctgh(self) -> Self {
-        ((self + Self::ONE) / (self - Self::ONE)).ln() / Self::from_int(2)
+    pub const fn abs(self) -> Self {
+        Self(self.0.abs())
     }
 
-    /// Increase precision to a higher number of fractional digits
+    /// -1, 0 or 1 depending on the sign of self
+    ///
+    /// Unlike `f64::signum` zero has no sign, so it gives 0.
     ///
     /// # Examples
     /// ```
     /// use cos_num::Num;
     ///
-    /// let num = Num::<2, 4>::from_f64(3.14); // 3.14 with 2 fractional digits
-    /// let increased = num.increase_frac::<4>(); // becomes 3.1400 with 4 fractional digits
+    /// type N = Num<2, 8>;
+    /// assert_eq!(N::from_f64(-2.5).signum(), N::from_int(-1));
+    /// assert_eq!(N::ZERO.signum(), N::ZERO);
+    /// assert!(N::from_f64(0.01).is_positive());
+    /// assert_eq!(N::from_int(3).copysign(N::from_f64(-0.5)), N::from_int(-3));
     /// ```
     #[inline]
     #[must_use]
-    pub fn increase_frac<const NEW_F: u8>(self) -> Num<NEW_F, TF> {
-        assert!(NEW_F >= F, "NEW_F must be >= F when increasing precision");
+    pub const fn signum(self) -> Self {
+        Self(self.0.signum() * Self::SCALE)
+    }
+
+    /// Whether self is greater than zero
+    #[inline]
+    #[must_use]
+    pub const fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    /// Whether self is less than zero
+    #[inline]
+    #[must_use]
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// Magnitude of self with the sign of `sign`, zero counts as positive
+    #[inline]
+    #[must_use]
+    pub const fn copysign(self, sign: Self) -> Self {
+        if sign.is_negative() {
+            Self(-self.0.abs())
+        } else {
+            self.abs()
+        }
+    }
+
+    /// Smaller of self and `other`, usable in `const` unlike [`Ord::min`]
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<2, 8>;
+    /// const LIMIT: N = N::from_int(7).min(N::PI);
+    /// assert_eq!(LIMIT, N::PI);
+    /// assert_eq!(N::from_int(12).clamp(N::ZERO, N::TAU), N::TAU);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn min(self, other: Self) -> Self {
+        if other.0 < self.0 { other } else { self }
+    }
+
+    /// Larger of self and `other`, usable in `const` unlike [`Ord::max`]
+    #[inline]
+    #[must_use]
+    pub const fn max(self, other: Self) -> Self {
+        if other.0 > self.0 { other } else { self }
+    }
+
+    /// Self limited to `lo..=hi`, usable in `const` unlike [`Ord::clamp`]
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `lo > hi`.
+    #[inline]
+    #[must_use]
+    pub const fn clamp(self, lo: Self, hi: Self) -> Self {
+        require!(lo.0 <= hi.0, "clamp with lo > hi", Self::ZERO);
+
+        self.max(lo).min(hi)
+    }
+
+    /// `self + rhs`, or `None` on overflow instead of wrapping
+    ///
+    /// The checked operations give the same result as their operator whenever it
+    /// fits, so the calculator can report overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<2, 8>;
+    /// assert_eq!(N::from_int(2).checked_mul(N::PI), Some(N::TAU));
+    /// assert_eq!(N::from_raw(i64::MAX).checked_add(N::ONE), None);
+    /// assert_eq!(N::ONE.checked_div(N::ZERO), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
+    /// `self - rhs`, or `None` on overflow instead of wrapping
+    #[inline]
+    #[must_use]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
+    /// `-self`, or `None` for the most negative value
+    #[inline]
+    #[must_use]
+    pub const fn checked_neg(self) -> Option<Self> {
+        match self.0.checked_neg() {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
+    /// `|self|`, or `None` for the most negative value
+    #[inline]
+    #[must_use]
+    pub const fn checked_abs(self) -> Option<Self> {
+        match self.0.checked_abs() {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
+    /// `self * rhs`, or `None` if the product or its rounding overflows
+    ///
+    /// With the `wide` feature only the result has to fit, like with `Mul`.
+    #[must_use]
+    pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+        if cfg!(feature = "wide") {
+            return Self::narrow(self.mul_wide(rhs));
+        }
+
+        let Some(r) = self.0.checked_mul(rhs.0) else {
+            return None;
+        };
+
+        let r = if r >= 0 {
+            r.checked_add(Self::SCALE / 2)
+        } else {
+            r.checked_sub(Self::SCALE / 2)
+        };
+        match r {
+            Some(r) => Some(Self(r / Self::SCALE)),
+            None => None,
+        }
+    }
+
+    /// `self / rhs`, or `None` if `rhs` is zero or the quotient overflows
+    ///
+    /// With the `wide` feature only the result has to fit, like with `Div`.
+    #[must_use]
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.0 == 0 {
+            return None;
+        }
+        if cfg!(feature = "wide") {
+            return Self::narrow(self.div_wide(rhs));
+        }
+        let Some(r) = self.0.checked_mul(Self::SCALE) else {
+            return None;
+        };
+
+        // Rounded like `Div`
+        let r = if r >= 0 {
+            r.checked_add(rhs.0 / 2)
+        } else {
+            r.checked_sub(rhs.0 / 2)
+        };
+        match r {
+            Some(r) => match r.checked_div(rhs.0) {
+                Some(raw) => Some(Self(raw)),
+                None => None,
+            },
+            None => None,
+        }
+    }
+
+    /// `1 / self`, or `None` if self is zero or the reciprocal doesn't fit
+    #[must_use]
+    pub const fn checked_recip(self) -> Option<Self> {
+        if self.0 == 0 {
+            return None;
+        }
+        Self::narrow(self.recip_wide())
+    }
+
+    /// `self % rhs`, or `None` if `rhs` is zero or the remainder overflows
+    #[inline]
+    #[must_use]
+    pub const fn checked_rem(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_rem(rhs.0) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
+    /// Square root, or `None` if self is negative
+    #[must_use]
+    pub const fn checked_sqrt(self) -> Option<Self> {
+        if self.0 < 0 { None } else { Some(self.sqrt()) }
+    }
+
+    /// `self!`, or `None` if self is negative, not whole or the factorial doesn't fit
+    #[must_use]
+    pub const fn checked_factorial(self) -> Option<Self> {
+        if self.0 < 0 || self.0 % Self::SCALE != 0 || self.0 / Self::SCALE > 20 {
+            return None;
+        }
+
+        // No factorial is `i64::MAX`, it is where `factorial` saturates
+        match self.factorial() {
+            Self(i64::MAX) => None,
+            n => Some(n),
+        }
+    }
+
+    /// `self + rhs`, clamped to the representable range instead of wrapping
+    ///
+    /// The saturating operations give the same result as their operator whenever it
+    /// fits, otherwise the largest or smallest value, e.g. for outputs where a wrapped
+    /// number would feel more wrong than a clamped one.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<2, 8>;
+    /// let max = N::from_raw(i64::MAX);
+    /// assert_eq!(N::from_int(2).saturating_mul(N::PI), N::TAU);
+    /// assert_eq!(max.saturating_add(N::ONE), max);
+    /// assert_eq!(max.saturating_mul(-N::from_int(2)), N::from_raw(i64::MIN));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// `self - rhs`, clamped to the representable range instead of wrapping
+    #[inline]
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// `self * rhs`, clamped to the representable range instead of wrapping
+    ///
+    /// Computed in `i128`, so it is exact wherever the result fits.
+    #[must_use]
+    pub const fn saturating_mul(self, rhs: Self) -> Self {
+        Self::saturate(self.mul_wide(rhs))
+    }
+
+    /// `self * rhs` as a [`Num128`](num128::Num128), which holds any product of two
+    /// values, for sums of products that run past the range of [`Num`] before they are scaled down
+    #[inline]
+    #[must_use]
+    pub const fn widening_mul(self, rhs: Self) -> num128::Num128<F> {
+        num128::Num128(self.mul_wide(rhs))
+    }
+
+    /// `self / rhs`, clamped to the representable range instead of wrapping
+    ///
+    /// # Panics
+    /// Will panic if `rhs` is zero
+    #[must_use]
+    pub const fn saturating_div(self, rhs: Self) -> Self {
+        require!(rhs.0 != 0, "division by zero", Self::ZERO);
+
+        Self::saturate(self.div_wide(rhs))
+    }
+
+    /// `1 / self`, clamped to the representable range for values close to zero
+    ///
+    /// Computed in `i128`, so unlike `Num::ONE / self` it keeps every digit even where
+    /// the scaled dividend overflows `i64` without the `wide` feature. Only overflows
+    /// with more than 9 fraction digits, e.g. the reciprocal of the smallest step at
+    /// `F = 10` is 10¹⁰, which doesn't fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<2, 8>;
+    /// assert_eq!(N::from_int(4).recip(), N::from_f64(0.25));
+    /// assert_eq!(N::from_int(-3).recip(), N::from_f64(-0.33));
+    /// assert_eq!(N::from_f64(0.01).recip(), N::from_int(100));
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if self is zero
+    #[must_use]
+    pub const fn recip(self) -> Self {
+        require!(self.0 != 0, "division by zero", Self::ZERO);
+
+        Self::saturate(self.recip_wide())
+    }
+
+    /// Raw product rounded like `Mul`, in `i128` so it can't overflow
+    const fn mul_wide(self, rhs: Self) -> i128 {
+        let r = self.0 as i128 * rhs.0 as i128;
+        let scale = Self::SCALE as i128;
+
+        if r >= 0 {
+            (r + scale / 2) / scale
+        } else {
+            (r - scale / 2) / scale
+        }
+    }
+
+    /// Raw quotient rounded like `Div`, in `i128` so it can't overflow, `rhs` must not be zero
+    const fn div_wide(self, rhs: Self) -> i128 {
+        let r = self.0 as i128 * Self::SCALE as i128;
+        let rhs = rhs.0 as i128;
+
+        if r >= 0 {
+            (r + rhs / 2).wrapping_div(rhs)
+        } else {
+            (r - rhs / 2).wrapping_div(rhs)
+        }
+    }
+
+    /// Raw reciprocal rounded half away from zero, in `i128` so it can't overflow, self must
+    /// not be zero
+    const fn recip_wide(self) -> i128 {
+        let scale = Self::SCALE as i128;
+        let raw = self.0.unsigned_abs() as i128;
+        let r = (scale * scale + raw / 2) / raw;
+
+        if self.0 < 0 { -r } else { r }
+    }
+
+    /// Raw value that fits `i64`, `None` otherwise
+    const fn narrow(raw: i128) -> Option<Self> {
+        if raw > i64::MAX as i128 || raw < i64::MIN as i128 {
+            None
+        } else {
+            Some(Self(raw as i64))
+        }
+    }
+
+    /// Raw value clamped to `i64`
+    const fn saturate(raw: i128) -> Self {
+        if raw > i64::MAX as i128 {
+            Self(i64::MAX)
+        } else if raw < i64::MIN as i128 {
+            Self(i64::MIN)
+        } else {
+            Self(raw as i64)
+        }
+    }
+
+    /// Get square root of self
+    ///
+    /// Computed digit by digit on the raw value, so it is exactly rounded, half away from
+    /// zero, at any `F` and for every non-negative value. [`Num::sqrt_digits`] continues
+    /// past `F`.
+    ///
+    /// # Panics
+    /// Will panic if self is negative
+    #[must_use]
+    pub const fn sqrt(self) -> Self {
+        require!(self.0 >= 0, "sqrt of negative number", Self::ZERO);
+
+        // Below `i64::MAX` even for the largest value at `F` = 18
+        Self(SqrtDigits::rounded(self.0 as u128 * Self::SCALE as u128) as i64)
+    }
+
+    /// Decimal digits of the square root, as many past `F` as wanted
+    ///
+    /// Yields the integer part (a single 0 below 1), [`Digit::Dot`] and then the fraction
+    /// digits, truncated instead of rounded. Ends once the root has 37 digits.
+    ///
+    /// # Panics
+    /// Will panic if self is negative
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::{Digit, Num};
+    ///
+    /// let digits = Num::<2, 8>::from_int(2).sqrt_digits().take(12);
+    /// let text: String = digits
+    ///     .map(|digit| match digit {
+    ///         Digit::Num(n) => char::from(b'0' + n),
+    ///         _ => '.',
+    ///     })
+    ///     .collect();
+    /// assert_eq!(text, "1.4142135623");
+    /// ```
+    #[must_use]
+    pub const fn sqrt_digits(self) -> SqrtDigits {
+        require!(
+            self.0 >= 0,
+            "sqrt of negative number",
+            SqrtDigits::new(0, F)
+        );
+
+        SqrtDigits::new(self.0 as u128 * Self::SCALE as u128, F)
+    }
+
+    /// Length of the vector (self, other), √(self² + other²)
+    ///
+    /// Computed on the raw values in `u128`, so the squares can't overflow however large
+    /// the operands are, and rounded like [`Num::sqrt`]. Clamped to the largest value if
+    /// the length doesn't fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<2, 8>;
+    /// assert_eq!(N::from_int(3).hypot(N::from_int(-4)), N::from_int(5));
+    /// assert_eq!(N::ONE.hypot(N::ONE), N::SQRT_2);
+    ///
+    /// // The squares are far beyond `i64` here
+    /// let big = N::from_int(30_000_000_000_000_000);
+    /// let third = N::from_int(10_000_000_000_000_000);
+    /// assert_eq!(big.hypot(third), N::from_raw(3_162_277_660_168_379_332));
+    /// ```
+    #[must_use]
+    pub const fn hypot(self, other: Self) -> Self {
+        let (a, b) = (
+            self.0.unsigned_abs() as u128,
+            other.0.unsigned_abs() as u128,
+        );
+        let root = SqrtDigits::rounded(a * a + b * b);
+        if root > i64::MAX as u128 {
+            Self(i64::MAX)
+        } else {
+            Self(root as i64)
+        }
+    }
+
+    /// Calculate factorial (n!)
+    ///
+    /// # Panics
+    ///
+    /// Will panic if self is negative or self > 20 or self isnt natural number
+    #[inline]
+    #[must_use]
+    pub const fn factorial(self) -> Self {
+        require!(self.0 >= 0, "Factorial of negative number", Self::ZERO);
+        require!(
+            self.0 % Self::SCALE == 0,
+            "Factorial of non-integer",
+            Self::ZERO
+        );
+        require!(
+            self.0 / Self::SCALE <= 20,
+            "Factorial will big what i64::MAX (n > 20)",
+            Self::ZERO
+        );
+
+        Self(
+            match self.0 / Self::SCALE {
+                0 | 1 => 1,
+                2 => 2,
+                3 => 6,
+                4 => 24,
+                5 => 120,
+                6 => 720,
+                7 => 5040,
+                8 => 40320,
+                9 => 362880,
+                10 => 3628800,
+                11 => 39916800,
+                12 => 479001600,
+                13 => 6227020800,
+                14 => 87178291200,
+                15 => 1307674368000,
+                16 => 20922789888000,
+                17 => 355687428096000,
+                18 => 6402373705728000,
+                19 => 121645100408832000,
+                // 20, larger numbers were rejected above
+                _ => 2432902008176640000i64,
+            }
+            .saturating_mul(Self::SCALE),
+        )
+    }
+
+    /// Greatest common divisor of two whole numbers, always non-negative
+    ///
+    /// `gcd(0, 0)` is 0. Saturates to the largest value when the result doesn't fit
+    /// with the fraction, only possible for `gcd(i64::MIN, 0)` without one.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if self or rhs isnt integer
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<2, 8>;
+    /// assert_eq!(N::from_int(12).gcd(N::from_int(-18)), N::from_int(6));
+    /// assert_eq!(N::from_int(7).gcd(N::ZERO), N::from_int(7));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn gcd(self, rhs: Self) -> Self {
+        require!(
+            self.0 % Self::SCALE == 0 && rhs.0 % Self::SCALE == 0,
+            "gcd of non-integer",
+            Self::ZERO
+        );
+
+        Self::from_whole(gcd_u64(
+            (self.0 / Self::SCALE).unsigned_abs(),
+            (rhs.0 / Self::SCALE).unsigned_abs(),
+        ))
+    }
+
+    /// Least common multiple of two whole numbers, always non-negative
+    ///
+    /// 0 if either is 0, saturates to the largest value on overflow.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if self or rhs isnt integer
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<2, 8>;
+    /// assert_eq!(N::from_int(4).lcm(N::from_int(-6)), N::from_int(12));
+    /// assert_eq!(N::from_int(5).lcm(N::ZERO), N::ZERO);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn lcm(self, rhs: Self) -> Self {
+        require!(
+            self.0 % Self::SCALE == 0 && rhs.0 % Self::SCALE == 0,
+            "lcm of non-integer",
+            Self::ZERO
+        );
+
+        let (a, b) = (
+            (self.0 / Self::SCALE).unsigned_abs(),
+            (rhs.0 / Self::SCALE).unsigned_abs(),
+        );
+        if a == 0 || b == 0 {
+            return Self::ZERO;
+        }
+
+        // Divide first, `a * b` may overflow where the result doesn't
+        Self::from_whole((a / gcd_u64(a, b)).saturating_mul(b))
+    }
+
+    /// Whole number `n` with the fraction, saturating to the largest value
+    const fn from_whole(n: u64) -> Self {
+        if n > i64::MAX as u64 {
+            return Self(i64::MAX);
+        }
+
+        Self((n as i64).saturating_mul(Self::SCALE))
+    }
+
+    /// Raise to the integer power `n` by squaring
+    ///
+    /// Saturates to the largest value of the right sign on overflow, negative `n`
+    /// gives the reciprocal of the positive power.
+    ///
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<4, 8>;
+    /// assert_eq!(N::from_f64(1.5).powi(3), N::from_f64(3.375));
+    /// assert_eq!(N::from_int(2).powi(-2), N::from_f64(0.25));
+    /// assert_eq!(N::from_int(10).powi(20).raw(), i64::MAX);
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if self is zero and `n` is negative
+    #[must_use]
+    pub fn powi(self, n: i32) -> Self {
+        let mut result = Self::ONE;
+        let mut base = self;
+        let mut e = n.unsigned_abs();
+
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result.saturating_mul(base);
+            }
+            e >>= 1;
+            if e > 0 {
+                base = base.saturating_mul(base);
+            }
+        }
+
+        let result = if n >= 0 {
+            result
+        } else if result.0 < 0 {
+            // Division rounds toward zero for negative divisors, keep it positive
+            -(Self::ONE / -result)
+        } else {
+            Self::ONE / result
+        };
+        shadowed!("powi", [self], |[x]| x.powi(n), result)
+    }
+
+    /// Degrees to radians, with π/180 to 20 digits instead of [`Num::PI`] at `F`
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<4, 8>;
+    /// assert_eq!(N::from_int(180).to_radians(), N::from_f64(3.1416));
+    /// assert_eq!(N::from_int(1).to_radians(), N::from_f64(0.0175));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn to_radians(self) -> Self {
+        Self(Self::mul_ratio(self.0 as i128, RAD_PER_DEGREE, 10i128.pow(20)) as i64)
+    }
+
+    /// Radians to degrees, clamped to the representable range instead of wrapping
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<4, 8>;
+    /// assert_eq!(N::PI.to_degrees(), N::from_f64(180.0004));
+    /// assert_eq!(N::from_int(1).to_degrees(), N::from_f64(57.2958));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn to_degrees(self) -> Self {
+        Self::saturate(Self::mul_ratio(
+            self.0 as i128,
+            DEGREES_PER_RAD,
+            10i128.pow(17),
+        ))
+    }
+
+    /// `raw * num / den` rounded half away from zero, `den` must be positive
+    const fn mul_ratio(raw: i128, num: i128, den: i128) -> i128 {
+        let r = raw * num;
+
+        if r >= 0 {
+            (r + den / 2) / den
+        } else {
+            (r - den / 2) / den
+        }
+    }
+
+    /// Normalize angle to [-π, π] range
+    #[inline]
+    #[must_use]
+    pub fn normalize_angle(self) -> Self {
+        let mut angle = self;
+
+        // Use remainder division to handle large angles efficiently
+        if angle.0.abs() > Self::TAU.0 {
+            let rotations = angle / Self::TAU;
+            // Use integer division to get the whole number of rotations
+            let whole_rotations = if rotations.0 >= 0 {
+                (rotations.0 + Self::SCALE / 2) / Self::SCALE
+            } else {
+                (rotations.0 - Self::SCALE / 2) / Self::SCALE
+            };
+            angle -= Self::TAU * Self::from_int(whole_rotations);
+        }
+
+        // Normalize to [-π, π]
+        if angle > Self::PI {
+            angle -= Self::TAU;
+        } else if angle < -Self::PI {
+            angle += Self::TAU;
+        }
+
+        angle
+    }
+
+    /// Calculate sine using Taylor series expansion
+    ///
+    /// Multiples of π/6 and π/4 give the exact value rounded to `F` digits, e.g. 0.5 or
+    /// √2/2, see [`Num::special_angle`]. Other angles below [`Num::SIN_SMALL_ANGLE`] after
+    /// reduction return x directly.
+    #[inline]
+    #[must_use]
+    pub fn sin(self) -> Self {
+        shadowed!("sin", [self], |[x]| x.sin(), self.sin_steps().finish())
+    }
+
+    /// [`Num::sin`] computed one series term per [`Steps::poll`]
+    ///
+    /// # Examples
+    /// ```
+    /// use core::task::Poll;
+    ///
+    /// use cos_num::Num;
+    ///
+    /// let mut steps = Num::<4, 8>::from_int(1).sin_steps();
+    /// let sin = loop {
+    ///     // Scan input, refresh the display, ...
+    ///     if let Poll::Ready(v) = steps.poll() {
+    ///         break v;
+    ///     }
+    /// };
+    /// assert_eq!(sin, Num::from_int(1).sin());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn sin_steps(self) -> Steps<F, TF> {
+        if let Some(m) = self.special_angle() {
+            return Steps::Ready(Self::special_sin(m));
+        }
+
+        let mut x = self.increase_frac::<TF>().normalize_angle();
+
+        // For angles in [π/2, π] and [-π, -π/2], use sin(x) = sin(π - x)
+        if x > Num::<TF, TF>::PI / Num::<TF, TF>::from_int(2) {
+            x = Num::<TF, TF>::PI - x;
+        } else if x < -Num::<TF, TF>::PI / Num::<TF, TF>::from_int(2) {
+            x = -Num::<TF, TF>::PI - x;
+        }
+
+        // Small-angle fast path: sin(x) ≈ x
+        if x.abs() < Num::<TF, TF>::SIN_SMALL_ANGLE {
+            return Steps::Ready(x.decrease_frac::<F>());
+        }
+
+        #[cfg(feature = "lut")]
+        return Steps::Ready(lut::sin(x).decrease_frac::<F>());
+
+        #[cfg(all(feature = "cordic", not(feature = "lut")))]
+        return Steps::Ready(cordic::sin_cos(x).0.decrease_frac::<F>());
+
+        #[cfg(not(any(feature = "cordic", feature = "lut")))]
+        Steps::Series(Series::new(x, Term::Sin))
+    }
+
+    /// Calculate cosine using identity cos(x) = sin(π/2 - x)
+    ///
+    /// Multiples of π/6 and π/4 give the exact value like [`Num::sin`]. Other angles
+    /// below [`Num::COS_SMALL_ANGLE`] after reduction use 1 - x²/2.
+    #[inline]
+    #[must_use]
+    pub fn cos(self) -> Self {
+        shadowed!("cos", [self], |[x]| x.cos(), self.cos_steps().finish())
+    }
+
+    /// [`Num::cos`] computed one series term per [`Steps::poll`]
+    #[inline]
+    #[must_use]
+    pub fn cos_steps(self) -> Steps<F, TF> {
+        if let Some(m) = self.special_angle() {
+            return Steps::Ready(Self::special_sin((m + 6) % 24));
+        }
+
+        let x = self.increase_frac::<TF>().normalize_angle();
+
+        // Small-angle fast path: cos(x) ≈ 1 - x²/2
+        if x.abs() < Num::<TF, TF>::COS_SMALL_ANGLE {
+            return Steps::Ready(
+                (Num::<TF, TF>::ONE - x * x / Num::<TF, TF>::from_int(2)).decrease_frac::<F>(),
+            );
+        }
+
+        (Self::PI / Self::from_int(2) - self).sin_steps()
+    }
+
+    /// Calculate tangent using identity tan(x) = sin(x) / cos(x)
+    ///
+    /// Multiples of π/6 and π/4 give the exact value like [`Num::sin`], e.g. √3.
+    ///
+    /// # Panics
+    /// Will panic at odd multiples of π/2, where the tangent is undefined
+    #[inline]
+    #[must_use]
+    pub fn tan(self) -> Self {
+        let tan = self
+            .special_angle()
+            .and_then(Self::special_tan)
+            .unwrap_or_else(|| self.sin() / self.cos());
+        shadowed!("tan", [self], |[x]| x.tan(), tan)
+    }
+
+    /// Calculate cotangent using identity ctg(x) = cos(x) / sin(x)
+    ///
+    /// Multiples of π/6 and π/4 give the exact value like [`Num::tan`].
+    ///
+    /// # Panics
+    /// Will panic at multiples of π, where the cotangent is undefined
+    #[inline]
+    #[must_use]
+    pub fn ctg(self) -> Self {
+        // ctg(x) = tan(π/2 - x)
+        self.special_angle()
+            .and_then(|m| Self::special_tan((30 - m) % 24))
+            .unwrap_or_else(|| self.cos() / self.sin())
+    }
+
+    /// Multiple of π/12 that self is the closest number to, reduced to `0..24`, if it is
+    /// a multiple of π/6 or π/4, whose sine and tangent are known exactly
+    ///
+    /// `None` for other angles and for those too large to tell.
+    const fn special_angle(self) -> Option<u8> {
+        let unit = 10i128.pow(30 - F as u32);
+        let Some(x) = (self.0 as i128).checked_mul(unit) else {
+            return None;
+        };
+
+        let k = Self::mul_ratio(x, 1, PI_12);
+        // Self is the special angle rounded to `F` digits
+        if (x - k * PI_12).abs() * 2 > unit {
+            return None;
+        }
+
+        let m = k.rem_euclid(24) as u8;
+        if m.is_multiple_of(2) || m.is_multiple_of(3) {
+            Some(m)
+        } else {
+            None
+        }
+    }
+
+    /// Sine of `m` π/12 rounded to `F` digits, `m` from [`Num::special_angle`]
+    const fn special_sin(m: u8) -> Self {
+        // sin(x) = -sin(x - π), then sin(x) = sin(π - x)
+        let (m, sign) = if m >= 12 { (m - 12, -1) } else { (m, 1) };
+        let m = if m > 6 { 12 - m } else { m };
+        let sin = match m {
+            0 => 0,
+            2 => 10i128.pow(30) / 2,
+            3 => SQRT_2_HALF,
+            4 => SQRT_3_HALF,
+            // 6, the rest aren't special
+            _ => 10i128.pow(30),
+        };
+
+        Self(Self::mul_ratio(sign * sin, 1, 10i128.pow(30 - F as u32)) as i64)
+    }
+
+    /// Tangent of `m` π/12 rounded to `F` digits, `m` from [`Num::special_angle`],
+    /// `None` at odd multiples of π/2
+    const fn special_tan(m: u8) -> Option<Self> {
+        // tan(x) = tan(x - π), then tan(x) = -tan(π - x)
+        let m = m % 12;
+        let (m, sign) = if m > 6 { (12 - m, -1) } else { (m, 1) };
+        let tan = match m {
+            0 => 0,
+            2 => SQRT_3_THIRD,
+            3 => 10i128.pow(30),
+            4 => SQRT_3,
+            _ => return None,
+        };
+
+        Some(Self(
+            Self::mul_ratio(sign * tan, 1, 10i128.pow(30 - F as u32)) as i64,
+        ))
+    }
+
+    /// Sine of self in degrees
+    ///
+    /// The angle is reduced in degrees, exactly, and converted to radians with `TF` digits,
+    /// so multiples of 90° and 30° give exact results where `x * PI / 180` at `F` doesn't.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<4, 8>;
+    /// assert_eq!(N::from_int(30).sin_deg(), N::from_f64(0.5));
+    /// assert_eq!(N::from_int(180).sin_deg(), N::ZERO);
+    /// assert_eq!(N::from_int(-450).cos_deg(), N::ZERO);
+    /// assert_eq!(N::from_int(45).tan_deg(), N::ONE);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn sin_deg(self) -> Self {
+        let sin = Self::sin_deg_tf(self.0 as i128).decrease_frac::<F>();
+        shadowed!("sin_deg", [self], |[x]| x.to_radians().sin(), sin)
+    }
+
+    /// Cosine of self in degrees, see [`Num::sin_deg`]
+    #[inline]
+    #[must_use]
+    pub fn cos_deg(self) -> Self {
+        let cos = Self::cos_deg_tf(self.0 as i128).decrease_frac::<F>();
+        shadowed!("cos_deg", [self], |[x]| x.to_radians().cos(), cos)
+    }
+
+    /// Tangent of self in degrees, see [`Num::sin_deg`]
+    ///
+    /// # Panics
+    /// Will panic at odd multiples of 90°, where the tangent is undefined
+    #[inline]
+    #[must_use]
+    pub fn tan_deg(self) -> Self {
+        let raw = self.0 as i128;
+        let tan = (Self::sin_deg_tf(raw) / Self::cos_deg_tf(raw)).decrease_frac::<F>();
+        shadowed!("tan_deg", [self], |[x]| x.to_radians().tan(), tan)
+    }
+
+    /// Sine of `raw` degrees scaled like self, at `TF` digits
+    fn sin_deg_tf(raw: i128) -> Num<TF, TF> {
+        let half = 180 * Self::SCALE as i128;
+
+        // Reduce to [-180, 180], then fold to [-90, 90] with sin(x) = sin(±180 - x)
+        let mut d = raw % (2 * half);
+        if d > half {
+            d -= 2 * half;
+        } else if d < -half {
+            d += 2 * half;
+        }
+        if d > half / 2 {
+            d = half - d;
+        } else if d < -half / 2 {
+            d = -half - d;
+        }
+
+        let d = d * (Num::<TF, TF>::SCALE / Self::SCALE) as i128;
+        let x = Self::mul_ratio(d, RAD_PER_DEGREE, 10i128.pow(20));
+        Num::<TF, TF>::from_raw(x as i64).sin()
+    }
+
+    /// Cosine of `raw` degrees scaled like self, at `TF` digits, as sin(90 - x)
+    fn cos_deg_tf(raw: i128) -> Num<TF, TF> {
+        let full = 360 * Self::SCALE as i128;
+        Self::sin_deg_tf(90 * Self::SCALE as i128 - raw % full)
+    }
+
+    /// Calculate arcsine in radians, in range [-π/2, π/2]
+    ///
+    /// # Panics
+    /// Will panic if self is outside [-1, 1]
+    #[inline]
+    #[must_use]
+    pub fn arcsin(self) -> Self {
+        require!(
+            self.abs() <= Self::ONE,
+            "arcsin outside [-1, 1]",
+            Self::ZERO
+        );
+
+        shadowed!(
+            "arcsin",
+            [self],
+            |[x]| x.asin(),
+            Self::arcsin_tf(self.increase_frac::<TF>()).decrease_frac::<F>()
+        )
+    }
+
+    /// Calculate arccosine in radians using identity arccos(x) = π/2 - arcsin(x), in range [0, π]
+    ///
+    /// # Panics
+    /// Will panic if self is outside [-1, 1]
+    #[inline]
+    #[must_use]
+    pub fn arccos(self) -> Self {
+        require!(
+            self.abs() <= Self::ONE,
+            "arccos outside [-1, 1]",
+            Self::ZERO
+        );
+
+        let half_pi = Num::<TF, TF>::PI / Num::<TF, TF>::from_int(2);
+        shadowed!(
+            "arccos",
+            [self],
+            |[x]| x.acos(),
+            (half_pi - Self::arcsin_tf(self.increase_frac::<TF>())).decrease_frac::<F>()
+        )
+    }
+
+    /// Calculate arctangent in radians, in range [-π/2, π/2]
+    #[inline]
+    #[must_use]
+    pub fn atan(self) -> Self {
+        self.atan2(Self::ONE)
+    }
+
+    /// Calculate the angle of point (x, y) in radians, in range [-π, π], with self as y
+    ///
+    /// Like [`f64::atan2`], the angle is 0 for (0, 0).
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<4, 8>;
+    /// // Pointing down and left
+    /// let angle = N::from_int(-1).atan2(N::from_int(-1));
+    /// assert_eq!(angle, N::from_f64(-2.3562));
+    /// ```
+    #[must_use]
+    pub fn atan2(self, x: Self) -> Self {
+        let (y, x_abs, y_abs) = (self, x.abs(), self.abs());
+
+        // Angle in the first quadrant, the ratio is kept in [0, 1], where the series
+        // converges, with atan(r) = π/2 - atan(1/r)
+        let half_pi = Num::<TF, TF>::PI / Num::<TF, TF>::from_int(2);
+        let angle = if x_abs.0 == 0 && y_abs.0 == 0 {
+            Num::ZERO
+        } else if y_abs <= x_abs {
+            Self::atan_tf(Self::ratio_tf(y_abs, x_abs))
+        } else {
+            half_pi - Self::atan_tf(Self::ratio_tf(x_abs, y_abs))
+        };
+
+        // Mirror it into the quadrant of (x, y)
+        let angle = if x.0 < 0 {
+            Num::<TF, TF>::PI - angle
+        } else {
+            angle
+        };
+        let angle = if y.0 < 0 { -angle } else { angle };
+
+        shadowed!(
+            "atan2",
+            [y, x],
+            |[y, x]| y.atan2(x),
+            angle.decrease_frac::<F>()
+        )
+    }
+
+    /// `a / b` kept at `TF` digits, `a` must not exceed `b`
+    fn ratio_tf(a: Self, b: Self) -> Num<TF, TF> {
+        // i128 so large operands can't wrap when scaled up
+        let raw = i128::from(a.0) * i128::from(Num::<TF, TF>::SCALE) / i128::from(b.0);
+        Num::from_raw(raw as i64)
+    }
+
+    /// Arctangent of `x` in [-1, 1] kept at `TF` digits
+    #[cfg(not(feature = "cordic"))]
+    fn atan_tf(x: Num<TF, TF>) -> Num<TF, TF> {
+        let one = Num::<TF, TF>::ONE;
+
+        // Halve the angle with atan(x) = 2 * atan(x / (1 + √(1 + x²))), so |x| <= tan(π/8)
+        // and the series converges in `Series::MAX_N` terms
+        let half = x / (one + (one + x * x).sqrt());
+
+        Num::<TF, TF>::from_int(2) * Series::new(half, Term::Atan).finish()
+    }
+
+    /// Arctangent of `x` in [-1, 1] kept at `TF` digits
+    #[cfg(feature = "cordic")]
+    fn atan_tf(x: Num<TF, TF>) -> Num<TF, TF> {
+        cordic::atan(x)
+    }
+
+    /// Arcsine of `x` in [-1, 1] kept at `TF` digits
+    fn arcsin_tf(x: Num<TF, TF>) -> Num<TF, TF> {
+        let one = Num::<TF, TF>::ONE;
+        let two = Num::<TF, TF>::from_int(2);
+
+        if x < Num::ZERO {
+            return -Self::arcsin_tf(-x);
+        }
+
+        // The series converges slowly near 1, so reduce the argument to [0, 0.5]
+        // with arcsin(x) = π/2 - 2 * arcsin(√((1 - x) / 2))
+        if x > one / two {
+            return Num::<TF, TF>::PI / two - two * Self::arcsin_tf(((one - x) / two).sqrt());
+        }
+
+        // x + (1/2)x³/3 + (1*3/2*4)x⁵/5 + ..., every term is at least 4 times smaller
+        let x2 = x * x;
+        let mut sum = x;
+        let mut dividend = x;
+        let mut n = 3;
+        loop {
+            dividend = dividend * x2 * Num::from_int(n - 2) / Num::from_int(n - 1);
+            if dividend == Num::ZERO {
+                return sum;
+            }
+
+            sum += dividend / Num::from_int(n);
+            n += 2;
+        }
+    }
+
+    /// Calculate hyperbolic sine using Taylor series expansion
+    #[inline]
+    #[must_use]
+    pub fn sinh(self) -> Self {
+        shadowed!(
+            "sinh",
+            [self],
+            |[x]| x.sinh(),
+            Series::new(self.increase_frac::<TF>(), Term::Sinh)
+                .finish()
+                .decrease_frac::<F>()
+        )
+    }
+
+    /// Calculate hyperbolic cosine using identity cosh(x) = (e^x + e^-x) / 2
+    #[inline]
+    #[must_use]
+    pub fn cosh(self) -> Self {
+        let (exp, exp_neg) = self.exps();
+        shadowed!(
+            "cosh",
+            [self],
+            |[x]| x.cosh(),
+            Num::<TF, TF>(i128::midpoint(exp, exp_neg) as i64).decrease_frac::<F>()
+        )
+    }
+
+    /// Calculate hyperbolic tangent using identity tanh(x) = (e^x - e^-x) / (e^x + e^-x)
+    #[inline]
+    #[must_use]
+    pub fn tanh(self) -> Self {
+        let (exp, exp_neg) = self.exps();
+        shadowed!(
+            "tanh",
+            [self],
+            |[x]| x.tanh(),
+            Self(Self::mul_ratio(exp - exp_neg, Self::SCALE as i128, exp + exp_neg) as i64)
+        )
+    }
+
+    /// Calculate hyperbolic cotangent using identity coth(x) = (e^x + e^-x) / (e^x - e^-x)
+    ///
+    /// # Panics
+    /// Will panic if self is zero
+    #[inline]
+    #[must_use]
+    pub fn ctgh(self) -> Self {
+        require!(self.0 != 0, "division by zero", Self::ZERO);
+
+        let (exp, exp_neg) = self.exps();
+        let (num, den) = if self.0 > 0 {
+            (exp + exp_neg, exp - exp_neg)
+        } else {
+            (-exp - exp_neg, exp_neg - exp)
+        };
+        Self(Self::mul_ratio(num, Self::SCALE as i128, den) as i64)
+    }
+
+    /// e^x and e^-x at `TF` digits, in `i128` as e^x alone may be the largest `Num`
+    fn exps(self) -> (i128, i128) {
+        let x = self.increase_frac::<TF>();
+        (
+            i128::from(Num::<TF, TF>::exp_tf(x).0),
+            i128::from(Num::<TF, TF>::exp_tf(-x).0),
+        )
+    }
+
+    /// Calculate natural logarithm using Taylor series expansion
+    ///
+    /// # Panics
+    /// Will panic if self is non-positive number
+    #[inline]
+    #[must_use]
+    pub fn ln(self) -> Self {
+        require!(self.0 > 0, "ln of non-positive number", Self::ZERO);
+
+        shadowed!(
+            "ln",
+            [self],
+            |[x]| x.ln(),
+            Self::ln_tf(self.increase_frac::<TF>()).decrease_frac::<F>()
+        )
+    }
+
+    /// Natural logarithm of a positive `value` kept at `TF` digits
+    fn ln_tf(mut value: Num<TF, TF>) -> Num<TF, TF> {
+        // Reduce the argument to range [0.5, 2] by powers of 2
+        let mut n = 0;
+        let two = Num::<TF, TF>::from_int(2);
+
+        while value > two {
+            // Halve the raw value, `/` scales it up first and wraps above ~92 at 8 digits
+            value = Num::from_raw(value.0 / 2 + (value.0 & 1));
+            n += 1;
+        }
+
+        while value < Num::<TF, TF>::ONE {
+            value *= two;
+            n -= 1;
+        }
+
+        // ln(x) = 2 * artanh((x-1)/(x+1))
+        let x = (value - Num::<TF, TF>::ONE) / (value + Num::<TF, TF>::ONE);
+        let result = Series::new(x, Term::Atanh).finish();
+
+        result * two + Num::<TF, TF>::from_int(n) * Num::<TF, TF>::LN_2
+    }
+
+    /// Calculate base 2 logarithm as ln(x) * log₂(e)
+    ///
+    /// # Panics
+    /// Will panic if self is non-positive number
+    #[inline]
+    #[must_use]
+    pub fn log2(self) -> Self {
+        require!(self.0 > 0, "log2 of non-positive number", Self::ZERO);
+
+        shadowed!(
+            "log2",
+            [self],
+            |[x]| x.log2(),
+            (Self::ln_tf(self.increase_frac::<TF>()) * Num::<TF, TF>::LOG2_E).decrease_frac::<F>()
+        )
+    }
+
+    /// Calculate base 10 logarithm as ln(x) / ln(10)
+    ///
+    /// # Panics
+    /// Will panic if self is non-positive number
+    #[inline]
+    #[must_use]
+    pub fn log10(self) -> Self {
+        require!(self.0 > 0, "log10 of non-positive number", Self::ZERO);
+
+        shadowed!(
+            "log10",
+            [self],
+            |[x]| x.log10(),
+            (Self::ln_tf(self.increase_frac::<TF>()) / Num::<TF, TF>::LN_10).decrease_frac::<F>()
+        )
+    }
+
+    /// Calculate logarithm to any `base` as ln(x) / ln(base)
+    ///
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<4, 8>;
+    /// assert_eq!(N::from_int(125).log(N::from_int(5)), N::from_int(3));
+    /// assert_eq!(N::from_int(4096).log(N::from_int(16)), N::from_int(3));
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if self or `base` is non-positive number, or `base` is 1
+    #[inline]
+    #[must_use]
+    pub fn log(self, base: Self) -> Self {
+        require!(self.0 > 0, "log of non-positive number", Self::ZERO);
+        require!(base.0 > 0, "log to non-positive base", Self::ZERO);
+        require!(base != Self::ONE, "log to base 1", Self::ZERO);
+
+        shadowed!(
+            "log",
+            [self, base],
+            |[x, base]| x.log(base),
+            (Self::ln_tf(self.increase_frac::<TF>()) / Self::ln_tf(base.increase_frac::<TF>()))
+                .decrease_frac::<F>()
+        )
+    }
+
+    /// Calculate e raised to self
+    ///
+    /// Saturates to the largest value when the result does not fit.
+    #[inline]
+    #[must_use]
+    pub fn exp(self) -> Self {
+        shadowed!(
+            "exp",
+            [self],
+            |[x]| x.exp(),
+            Self::exp_tf(self.increase_frac::<TF>())
+        )
+    }
+
+    /// e raised to `x` kept at `TF` digits, rounded to `F` digits at the end
+    fn exp_tf(x: Num<TF, TF>) -> Self {
+        let ln_2 = Num::<TF, TF>::LN_2;
+
+        // exp(x) = 2^k * exp(r), k = round(x / ln 2), |r| <= ln 2 / 2
+        // Rounded on the remainder, `x` may have saturated to the limits of `i64`
+        let (q, rem) = (x.0 / ln_2.0, x.0 % ln_2.0);
+        let k = if rem.unsigned_abs() < (ln_2.0 / 2).unsigned_abs() {
+            q
+        } else if x.0 >= 0 {
+            q + 1
+        } else {
+            q - 1
+        };
+        // 2^63 does not fit any `F`, 2^-64 rounds to 0 for any `TF`
+        if k > 62 {
+            return Self(i64::MAX);
+        }
+        if k < -64 {
+            return Self::ZERO;
+        }
+
+        let r = x - Num::<TF, TF>::from_int(k) * ln_2;
+        // exp(r) = sinh(r) + cosh(r)
+        let sinh = Series::new(r, Term::Sinh).finish();
+        let exp_r = sinh + (Num::<TF, TF>::ONE + sinh * sinh).sqrt();
+
+        // Scale by 2^k and drop the extra digits in one rounded step, in i128 so it can't wrap
+        let mut num = i128::from(exp_r.0);
+        let mut den = 10i128.pow(u32::from(TF - F));
+        if k >= 0 {
+            num <<= k;
+        } else {
+            den <<= -k;
+        }
+        let raw = (num + den / 2) / den;
+
+        Self(i64::try_from(raw).unwrap_or(i64::MAX))
+    }
+
+    /// Raise to the power `exp` as `e^(exp * ln(self))`
+    ///
+    /// Negative bases are allowed with whole exponents only, those go through [`Num::powi`].
+    /// Saturates to the largest value when the result does not fit.
+    ///
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<4, 8>;
+    /// assert_eq!(N::from_int(2).powf(N::from_f64(0.5)), N::SQRT_2);
+    /// assert_eq!(N::from_int(-2).powf(N::from_int(3)), N::from_int(-8));
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if self is negative and `exp` is not whole, or self is zero and `exp` is negative
+    #[must_use]
+    pub fn powf(self, exp: Self) -> Self {
+        let result = if self.0 < 0 {
+            require!(
+                exp.0 % Self::SCALE == 0,
+                "powf of negative number with fractional exponent",
+                Self::ZERO
+            );
+            let n = exp.0 / Self::SCALE;
+            // Any larger exponent saturates or rounds to 0 just the same
+            self.powi(n.clamp(i32::MIN.into(), i32::MAX.into()) as i32)
+        } else if self.0 == 0 {
+            require!(
+                exp.0 >= 0,
+                "powf of zero with negative exponent",
+                Self::ZERO
+            );
+            if exp.0 == 0 { Self::ONE } else { Self::ZERO }
+        } else {
+            let ln = Self::ln_tf(self.increase_frac::<TF>());
+            Self::exp_tf(exp.increase_frac::<TF>().saturating_mul(ln))
+        };
+        shadowed!("powf", [self, exp], |[x, exp]| x.powf(exp), result)
+    }
+
+    /// Gamma function, `Γ(n + 1) = n!` for whole `n` and the factorial in between
+    ///
+    /// Whole arguments are exact through [`Num::factorial`], the rest are computed as
+    /// `exp(lgamma)` with the relative error of `TF` digits, negative ones by the
+    /// reflection `Γ(x) = π / (sin(πx) · Γ(1 - x))`. Saturates to the largest value
+    /// of the right sign when the result does not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<4, 8>;
+    /// assert_eq!(N::from_int(5).gamma(), N::from_int(24));
+    /// assert_eq!(N::from_f64(0.5).gamma(), N::from_f64(1.7725)); // √π
+    /// assert_eq!(N::from_f64(-0.5).gamma(), N::from_f64(-3.5449)); // -2√π
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if self is zero or a negative whole number, where Γ has its poles
+    #[must_use]
+    pub fn gamma(self) -> Self {
+        let whole = self.0 % Self::SCALE == 0;
+        require!(
+            !(whole && self.0 <= 0),
+            "gamma of non-positive integer",
+            Self::ZERO
+        );
+
+        if whole {
+            // Past 21 every `Num` saturates, `factorial` would panic instead
+            return if self.0 / Self::SCALE <= 21 {
+                (self - Self::ONE).factorial()
+            } else {
+                Self(i64::MAX)
+            };
+        }
+        // Γ(172) overflows even f64, and `lgamma` stays far from the edge of `TF`
+        if self > Self::from_int(1000) {
+            return Self(i64::MAX);
+        }
+
+        let x = self.increase_frac::<TF>();
+        if x.0 > 0 {
+            return Self::exp_tf(Self::lgamma_tf(x));
+        }
+
+        let (sin, negative) = Self::sin_pi_tf(x);
+        let ln = (Self::ln_tf(Num::<TF, TF>::PI) - Self::ln_tf(sin))
+            .saturating_sub(Self::lgamma_tf(Num::<TF, TF>::ONE - x));
+        let gamma = Self::exp_tf(ln);
+        if negative { -gamma } else { gamma }
+    }
+
+    /// Natural logarithm of the absolute value of [`Num::gamma`]
+    ///
+    /// Grows much slower than Γ itself, so it is defined where Γ overflows, e.g. for
+    /// ratios of factorials as `exp(lgamma(a) - lgamma(b))`. Saturates to the largest
+    /// value when the result does not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// type N = Num<4, 8>;
+    /// assert_eq!(N::from_int(3).lgamma(), N::from_f64(0.6931)); // ln 2!
+    /// assert_eq!(N::from_int(100).lgamma(), N::from_f64(359.1342)); // ln 99!
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if self is zero or a negative whole number, where Γ has its poles
+    #[must_use]
+    pub fn lgamma(self) -> Self {
+        require!(
+            !(self.0 % Self::SCALE == 0 && self.0 <= 0),
+            "lgamma of non-positive integer",
+            Self::ZERO
+        );
+
+        let x = self.increase_frac::<TF>();
+        let ln = if x.0 > 0 {
+            Self::lgamma_tf(x)
+        } else {
+            let (sin, _) = Self::sin_pi_tf(x);
+            (Self::ln_tf(Num::<TF, TF>::PI) - Self::ln_tf(sin))
+                .saturating_sub(Self::lgamma_tf(Num::<TF, TF>::ONE - x))
+        };
+
+        ln.decrease_frac::<F>()
+    }
+
+    /// Γ as an `Option`, `None` at the poles and when the result does not fit
+    #[must_use]
+    pub fn checked_gamma(self) -> Option<Self> {
+        if self.0 % Self::SCALE == 0 && self.0 <= 0 {
+            return None;
+        }
+
+        // No Γ is this large, it is where `gamma` saturates
+        match self.gamma() {
+            Self(i64::MAX | i64::MIN) => None,
+            gamma => Some(gamma),
+        }
+    }
+
+    /// ln Γ(x) of a positive `x` kept at `TF` digits
+    ///
+    /// Stirling's series is accurate to 12 digits from 10 up, smaller arguments are
+    /// shifted there by ln Γ(x) = ln Γ(x + n) - ln(x (x + 1) ... (x + n - 1)).
+    fn lgamma_tf(x: Num<TF, TF>) -> Num<TF, TF> {
+        let one = Num::<TF, TF>::ONE;
+
+        // Below 1 the product would lose digits, its first factor is taken on its own
+        let (mut z, mut shift) = if x < one {
+            (x + one, Self::ln_tf(x))
+        } else {
+            (x, Num::ZERO)
+        };
+        let mut product = one;
+        while z < Num::from_int(10) {
+            if let Some(next) = product.checked_mul(z) {
+                product = next;
+            } else {
+                shift += Self::ln_tf(product);
+                product = z;
+            }
+            z += one;
+        }
+        shift += Self::ln_tf(product);
+
+        // (z - 1/2) ln z - z + ln(2π) / 2 + 1/(12z) - 1/(360z³) + 1/(1260z⁵) - 1/(1680z⁷)
+        let inv = |n| one / Num::from_int(n);
+        let r = one / z;
+        let r2 = r * r;
+        let tail = r * (inv(12) - r2 * (inv(360) - r2 * (inv(1260) - r2 * inv(1680))));
+        let half_ln_tau = Self::ln_tf(Num::TAU) / Num::from_int(2);
+
+        (z - inv(2))
+            .saturating_mul(Self::ln_tf(z))
+            .saturating_sub(z)
+            .saturating_add(half_ln_tau + tail)
+            .saturating_sub(shift)
+    }
+
+    /// |sin(πx)| of a non-whole `x` kept at `TF` digits, and whether sin(πx) is negative
+    fn sin_pi_tf(x: Num<TF, TF>) -> (Num<TF, TF>, bool) {
+        let floor = x.floor();
+        // sin(πx) = (-1)^floor(x) sin(π(x - floor(x))), the fraction is in (0, 1)
+        let frac = x - floor;
+        let frac = frac.min(Num::ONE - frac);
+        let negative = (floor.0 / Num::<TF, TF>::SCALE) % 2 != 0;
+
+        ((Num::<TF, TF>::PI * frac).sin(), negative)
+    }
+
+    /// Calculate area hyperbolic sine using logarithmic identity: arsinh(x) = ln(x + √(x² + 1))
+    #[inline]
+    #[must_use]
+    pub fn arcsinh(self) -> Self {
+        (self + (self * self + Self::ONE).sqrt()).ln()
+    }
+
+    /// Calculate area hyperbolic cosine using logarithmic identity: arcosh(x) = ln(x + √(x² - 1))
+    #[inline]
+    #[must_use]
+    pub fn arccosh(self) -> Self {
+        (self + (self * self - Self::ONE).sqrt()).ln()
+    }
+
+    /// Calculate area hyperbolic tangent using logarithmic identity: artanh(x) = 0.5 * ln((1 + x)/(1 - x))
+    #[inline]
+    #[must_use]
+    pub fn arctanh(self) -> Self {
+        ((Self::ONE + self) / (Self::ONE - self)).ln() / Self::from_int(2)
+    }
+
+    /// Calculate area hyperbolic cotangent using logarithmic identity: arcoth(x) = 0.5 * ln((x + 1)/(x - 1))
+    #[inline]
+    #[must_use]
+    pub fn arcctgh(self) -> Self {
+        ((self + Self::ONE) / (self - Self::ONE)).ln() / Self::from_int(2)
+    }
+
+    /// Increase precision to a higher number of fractional digits
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// let num = Num::<2, 4>::from_f64(3.14); // 3.14 with 2 fractional digits
+    /// let increased = num.increase_frac::<4>(); // becomes 3.1400 with 4 fractional digits
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if `NEW_F` is less than `F`
+    #[inline]
+    #[must_use]
+    pub fn increase_frac<const NEW_F: u8>(self) -> Num<NEW_F, TF> {
+        assert!(NEW_F >= F, "NEW_F must be >= F when increasing precision");
 
         if NEW_F == F {
             // Same precision, just convert
@@ -440,37 +2250,695 @@ impl<const F: u8, const TF: u8> Num<F, TF> {
         }
     }
 
-    /// Decrease precision to a lower number of fractional digits with rounding
+    /// Decrease precision to a lower number of fractional digits with rounding
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::Num;
+    ///
+    /// let num = Num::<4, 4>::from_f64(3.1416); // 3.1416 with 4 fractional digits
+    /// let decreased = num.decrease_frac::<2>(); // becomes 3.14 with 2 fractional digits
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if `NEW_F` is greater than `F`
+    #[inline]
+    #[must_use]
+    pub fn decrease_frac<const NEW_F: u8>(self) -> Num<NEW_F, TF> {
+        assert!(NEW_F <= F, "NEW_F must be <= F when decreasing precision");
+
+        if NEW_F == F {
+            // Same precision, just convert
+            Num::<NEW_F, TF>::from_raw(self.0)
+        } else {
+            let divisor = 10i64.pow((F - NEW_F) as u32);
+
+            // Round to nearest with half-up rounding
+            let new_raw = if self.0 >= 0 {
+                (self.0 + divisor / 2) / divisor
+            } else {
+                (self.0 - divisor / 2) / divisor
+            };
+
+            Num::<NEW_F, TF>::from_raw(new_raw)
+        }
+    }
+
+    /// Decompose into decimal digits, most significant first
+    ///
+    /// Yields [`Digit::Minus`] for negative values, then the integer part
+    /// (a single 0 for pure fractions) and, if the fraction is non-zero,
+    /// [`Digit::Dot`] followed by the fraction digits up to the last non-zero one.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::{Digit, Num};
+    ///
+    /// let mut digits = Num::<2, 2>::from_f64(-0.05).digits();
+    /// assert_eq!(digits.next(), Some(Digit::Minus));
+    /// assert_eq!(digits.next(), Some(Digit::Num(0)));
+    /// assert_eq!(digits.next(), Some(Digit::Dot));
+    /// assert_eq!(digits.next(), Some(Digit::Num(0)));
+    /// assert_eq!(digits.next(), Some(Digit::Num(5)));
+    /// assert_eq!(digits.next(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn digits(self) -> Digits {
+        let raw = self.0.unsigned_abs();
+        let scale = Self::SCALE.unsigned_abs();
+
+        // Integer part always has at least one digit
+        let mut top = 0i8;
+        let mut int = raw / scale;
+        while int >= 10 {
+            int /= 10;
+            top += 1;
+        }
+
+        // Drop trailing zeros of the fraction
+        let mut bottom = -(F as i8);
+        let mut frac = raw % scale;
+        if frac == 0 {
+            bottom = 0;
+        } else {
+            while frac.is_multiple_of(10) {
+                frac /= 10;
+                bottom += 1;
+            }
+        }
+
+        Digits {
+            raw,
+            frac: F,
+            minus: self.0 < 0,
+            dot: false,
+            pos: top,
+            bottom,
+        }
+    }
+
+    /// Pack the [`Num::digits`] as BCD for decimal display drivers, two per byte
+    ///
+    /// The first digit goes into the high nibble, an odd count leaves the low nibble of
+    /// the last byte 0. Sign and decimal point are not packed, they are in the returned
+    /// [`Bcd`]. Returns `None` if `out` is too short, 10 bytes fit any value.
+    ///
+    /// # Examples
+    /// ```
+    /// use cos_num::{Bcd, Num};
+    ///
+    /// let mut out = [0; 10];
+    /// let bcd = Num::<2, 8>::from_f64(-123.4).to_bcd(&mut out);
+    /// assert_eq!(
+    ///     bcd,
+    ///     Some(Bcd {
+    ///         negative: true,
+    ///         len: 4,
+    ///         int_len: 3
+    ///     })
+    /// );
+    /// assert_eq!(out[..2], [0x12, 0x34]);
+    /// ```
+    #[must_use]
+    pub fn to_bcd(self, out: &mut [u8]) -> Option<Bcd> {
+        let mut bcd = Bcd {
+            negative: false,
+            len: 0,
+            int_len: 0,
+        };
+
+        for digit in self.digits() {
+            let n = match digit {
+                Digit::Minus => {
+                    bcd.negative = true;
+                    continue;
+                }
+                Digit::Dot => {
+                    bcd.int_len = bcd.len;
+                    continue;
+                }
+                Digit::Num(n) => n,
+            };
+
+            let byte = out.get_mut(bcd.len as usize / 2)?;
+            *byte = if bcd.len.is_multiple_of(2) {
+                n << 4
+            } else {
+                *byte | n
+            };
+            bcd.len += 1;
+        }
+
+        // No dot, so every digit is whole
+        if bcd.int_len == 0 {
+            bcd.int_len = bcd.len;
+        }
+
+        Some(bcd)
+    }
+
+    /// Write the [`Num::digits`] as ASCII text like `-0.05`, without `core::fmt`
+    ///
+    /// Returns the length written, or `None` if `out` is too short, 21 bytes fit any
+    /// value. The bytes past the text are left as they were.
     ///
     /// # Examples
     /// ```
     /// use cos_num::Num;
     ///
-    /// let num = Num::<4, 4>::from_f64(3.1416); // 3.1416 with 4 fractional digits
-    /// let decreased = num.decrease_frac::<2>(); // becomes 3.14 with 2 fractional digits
+    /// let mut out = [b' '; 8];
+    /// let len = Num::<2, 8>::from_f64(-123.4).write_decimal(&mut out);
+    /// assert_eq!(len, Some(6));
+    /// assert_eq!(&out, b"-123.4  ");
     /// ```
+    #[must_use]
+    pub fn write_decimal(self, out: &mut [u8]) -> Option<usize> {
+        let mut len = 0;
+        for digit in self.digits() {
+            *out.get_mut(len)? = match digit {
+                Digit::Minus => b'-',
+                Digit::Num(n) => b'0' + n,
+                Digit::Dot => b'.',
+            };
+            len += 1;
+        }
+
+        Some(len)
+    }
+}
+
+/// Kind of Taylor series summed by [`Series`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    any(feature = "cordic", feature = "lut"),
+    expect(dead_code, reason = "CORDIC or the table replace some series")
+)]
+enum Term {
+    /// x - x³/3! + x⁵/5! - ...
+    Sin,
+    /// x + x³/3! + x⁵/5! + ...
+    Sinh,
+    /// x - x³/3 + x⁵/5 - ...
+    Atan,
+    /// x + x³/3 + x⁵/5 + ...
+    Atanh,
+}
+
+/// Taylor series summed one term per [`Series::poll`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Series<const TF: u8> {
+    term: Term,
+    x2: Num<TF, TF>,
+    sum: Num<TF, TF>,
+    /// Last term without its sign for sine, the power of x in it for arctangent
+    ///
+    /// Sine terms are grown from the previous one, a power of x over `n!` would overflow
+    /// `Mul` long before the term gets small.
+    dividend: Num<TF, TF>,
+    n: usize,
+    neg: bool,
+}
+
+impl<const TF: u8> Series<TF> {
+    /// Odd powers up to this one are summed
+    const MAX_N: usize = 15;
+
+    fn new(x: Num<TF, TF>, term: Term) -> Self {
+        Self {
+            term,
+            x2: x * x,
+            sum: x,
+            dividend: x,
+            n: 3,
+            neg: false,
+        }
+    }
+
+    /// Add the next term, `Ready` with the sum once all terms are added
+    pub fn poll(&mut self) -> Poll<Num<TF, TF>> {
+        if self.n >= Self::MAX_N {
+            return Poll::Ready(self.sum);
+        }
+
+        self.neg = !self.neg;
+        self.dividend *= self.x2;
+
+        let n = Num::from_int(self.n as i64);
+        self.sum += match self.term {
+            Term::Sin | Term::Sinh => {
+                self.dividend /= n * (n - Num::ONE);
+                if self.neg && matches!(self.term, Term::Sin) {
+                    -self.dividend
+                } else {
+                    self.dividend
+                }
+            }
+            Term::Atan if self.neg => -self.dividend / n,
+            Term::Atan | Term::Atanh => self.dividend / n,
+        };
+        self.n += 2;
+
+        Poll::Pending
+    }
+
+    /// Add all remaining terms
+    #[must_use]
+    pub fn finish(mut self) -> Num<TF, TF> {
+        loop {
+            if let Poll::Ready(sum) = self.poll() {
+                return sum;
+            }
+        }
+    }
+}
+
+/// Result computed in small steps, see [`Num::sin_steps`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Steps<const F: u8, const TF: u8> {
+    /// Summing a series, rounded to `F` digits at the end
+    Series(Series<TF>),
+    Ready(Num<F, TF>),
+}
+
+impl<const F: u8, const TF: u8> Steps<F, TF> {
+    /// Do one step, `Ready` with the result once done
+    ///
+    /// Polling again after `Ready` returns the same result.
+    pub fn poll(&mut self) -> Poll<Num<F, TF>> {
+        if let Self::Series(series) = self {
+            if let Poll::Ready(sum) = series.poll() {
+                *self = Self::Ready(sum.decrease_frac::<F>());
+            }
+        }
+
+        match *self {
+            Self::Series(_) => Poll::Pending,
+            Self::Ready(v) => Poll::Ready(v),
+        }
+    }
+
+    /// Do all remaining steps
+    #[must_use]
+    pub fn finish(mut self) -> Num<F, TF> {
+        loop {
+            if let Poll::Ready(v) = self.poll() {
+                return v;
+            }
+        }
+    }
+}
+
+/// Approximate root of a function, see [`Solver`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(uDebug))]
+pub struct Root<const F: u8, const TF: u8> {
+    pub x: Num<F, TF>,
+    /// f(x), zero for an exact root
+    pub residual: Num<F, TF>,
+}
+
+/// Root of a function found by bisection, one step per [`Solver::poll`]
+///
+/// Keeps a bracket where the function changes sign and halves it every step,
+/// so it converges on any continuous function within 64 steps. [`Solver::newton`]
+/// also tries a Newton step from every midpoint, which is much faster on smooth functions.
+///
+/// # Examples
+/// ```
+/// use cos_num::{Num, Solver};
+///
+/// type N = Num<4, 8>;
+/// let f = |x: N| x * x - N::from_int(2);
+///
+/// let root = Solver::new(f, N::ZERO, N::from_int(2)).unwrap().finish(f);
+/// assert_eq!(root.x.raw(), 14142); // 1.4142
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Solver<const F: u8, const TF: u8> {
+    lo: Num<F, TF>,
+    hi: Num<F, TF>,
+    /// f(lo) is negative, f(hi) is not
+    lo_neg: bool,
+    newton: bool,
+    /// Point with the smallest residual so far
+    best: Root<F, TF>,
+}
+
+impl<const F: u8, const TF: u8> Solver<F, TF> {
+    /// Search between `a` and `b`, `None` if `f` has the same sign at both
+    pub fn new(f: impl Fn(Num<F, TF>) -> Num<F, TF>, a: Num<F, TF>, b: Num<F, TF>) -> Option<Self> {
+        let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+        let (f_lo, f_hi) = (f(lo), f(hi));
+
+        if f_lo != Num::ZERO && f_hi != Num::ZERO && (f_lo.0 < 0) == (f_hi.0 < 0) {
+            return None;
+        }
+
+        Some(Self {
+            lo,
+            hi,
+            lo_neg: f_lo.0 < 0,
+            newton: false,
+            best: if f_lo.abs() <= f_hi.abs() {
+                Root {
+                    x: lo,
+                    residual: f_lo,
+                }
+            } else {
+                Root {
+                    x: hi,
+                    residual: f_hi,
+                }
+            },
+        })
+    }
+
+    /// Also try a Newton step every step, with the derivative taken numerically
     #[inline]
     #[must_use]
-    pub fn decrease_frac<const NEW_F: u8>(self) -> Num<NEW_F, TF> {
-        assert!(NEW_F <= F, "NEW_F must be <= F when decreasing precision");
-        println!("old: {self:?}");
+    pub const fn newton(mut self) -> Self {
+        self.newton = true;
+        self
+    }
 
-        if NEW_F == F {
-            // Same precision, just convert
-            Num::<NEW_F, TF>::from_raw(self.0)
+    /// Narrow the bracket, `Ready` with the root once it can't be narrowed further
+    ///
+    /// `f` is called once per step, three times with [`Solver::newton`].
+    pub fn poll(&mut self, f: impl Fn(Num<F, TF>) -> Num<F, TF>) -> Poll<Root<F, TF>> {
+        if self.best.residual == Num::ZERO || self.hi.0.abs_diff(self.lo.0) <= 1 {
+            return Poll::Ready(self.best);
+        }
+
+        let mid = Num(self.lo.0.midpoint(self.hi.0));
+        let f_mid = f(mid);
+        self.narrow(mid, f_mid);
+
+        if self.newton {
+            if let Some(x) = self.newton_step(&f, mid, f_mid) {
+                self.narrow(x, f(x));
+            }
+        }
+
+        Poll::Pending
+    }
+
+    /// Do all remaining steps
+    #[must_use]
+    pub fn finish(mut self, f: impl Fn(Num<F, TF>) -> Num<F, TF>) -> Root<F, TF> {
+        loop {
+            if let Poll::Ready(root) = self.poll(&f) {
+                return root;
+            }
+        }
+    }
+
+    /// Replace the end of the bracket with the same sign as `fx`
+    fn narrow(&mut self, x: Num<F, TF>, fx: Num<F, TF>) {
+        if fx.abs() < self.best.residual.abs() {
+            self.best = Root { x, residual: fx };
+        }
+
+        if (fx.0 < 0) == self.lo_neg {
+            self.lo = x;
         } else {
-            let divisor = 10i64.pow((F - NEW_F) as u32);
+            self.hi = x;
+        }
+    }
 
-            // Round to nearest with half-up rounding
-            let new_raw = if self.0 >= 0 {
-                (self.0 + divisor / 2) / divisor
-            } else {
-                (self.0 - divisor / 2) / divisor
-            };
+    /// Newton step from `x`, `None` if it leaves the bracket
+    fn newton_step(
+        &self,
+        f: impl Fn(Num<F, TF>) -> Num<F, TF>,
+        x: Num<F, TF>,
+        fx: Num<F, TF>,
+    ) -> Option<Num<F, TF>> {
+        // Small against the bracket, but big enough for the slope to have some digits
+        let h = Num((self.hi.0.abs_diff(self.lo.0) / 64).max(1) as i64);
+        let slope = (f(x + h) - fx) / h;
+        if slope == Num::ZERO {
+            return None;
+        }
 
-            Num::<NEW_F, TF>::from_raw(new_raw)
+        let next = x - fx / slope;
+        (next > self.lo && next < self.hi).then_some(next)
+    }
+}
+
+/// Element of a decimal readout produced by [`Num::digits`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(uDebug))]
+pub enum Digit {
+    Minus,
+    Num(u8),
+    Dot,
+}
+
+/// Iterator over the decimal digits of a [`Num`]
+#[derive(Debug, Clone)]
+pub struct Digits {
+    raw: u64,
+    frac: u8,
+    minus: bool,
+    dot: bool,
+    /// Decimal position of the next digit, negative inside the fraction
+    pos: i8,
+    /// Decimal position of the last digit
+    bottom: i8,
+}
+
+impl Iterator for Digits {
+    type Item = Digit;
+
+    fn next(&mut self) -> Option<Digit> {
+        if self.minus {
+            self.minus = false;
+            return Some(Digit::Minus);
+        }
+
+        if self.pos < self.bottom {
+            return None;
+        }
+
+        if self.pos == -1 && !self.dot {
+            self.dot = true;
+            return Some(Digit::Dot);
+        }
+
+        let power = (self.frac as i8 + self.pos) as u32;
+        let digit = (self.raw / 10u64.pow(power) % 10) as u8;
+        self.pos -= 1;
+
+        Some(Digit::Num(digit))
+    }
+}
+
+/// Iterator over the decimal digits of a square root, see [`Num::sqrt_digits`]
+///
+/// The pencil-and-paper method: pairs of digits of the radicand are brought down from
+/// the top, each gives the largest next root digit whose square still fits. Past the
+/// radicand the pairs are zeros, so the root goes on as far as `u128` holds it.
+#[derive(Debug, Clone)]
+pub struct SqrtDigits {
+    radicand: u128,
+    /// Pairs of the radicand not brought down yet
+    pairs: u8,
+    /// Integer digits left to yield, 0 for the dot and negative past it
+    int: i8,
+    /// Root of the pairs brought down
+    root: u128,
+    /// Those pairs minus `root²`
+    rem: u128,
+    /// Digits of `root`, leading zeros included
+    len: u8,
+}
+
+impl SqrtDigits {
+    /// Pairs in `u128::MAX`, 39 digits
+    const PAIRS: u8 = 20;
+    /// Root digits the steps stay within `u128` for
+    const LEN: u8 = 37;
+
+    /// Digits of √`radicand` with `frac` of its root's digits after the point
+    const fn new(radicand: u128, frac: u8) -> Self {
+        // Leading zero pairs are skipped, one is left for the 0 of roots below 1
+        let mut pairs = Self::PAIRS;
+        while pairs > frac + 1 && radicand < 100u128.pow(pairs as u32 - 1) {
+            pairs -= 1;
+        }
+
+        Self {
+            radicand,
+            pairs,
+            int: (pairs - frac) as i8,
+            root: 0,
+            rem: 0,
+            len: 0,
+        }
+    }
+
+    /// √`n` rounded half away from zero
+    const fn rounded(n: u128) -> u128 {
+        let mut digits = Self::new(n, 0);
+        while digits.pairs > 0 {
+            digits.step();
+        }
+
+        // n = root² + rem, the root rounds up from root + ½ = √(root² + root + ¼)
+        if digits.rem > digits.root {
+            digits.root + 1
+        } else {
+            digits.root
         }
     }
+
+    /// Bring down the next pair, returns the root digit it gives or `None` once the
+    /// root can't grow without overflowing
+    const fn step(&mut self) -> Option<u8> {
+        // rem ≤ 2·root < 2·10³⁶, so `rem·100 + 99` and `(20·root + 9)·9` still fit
+        if self.len == Self::LEN {
+            return None;
+        }
+
+        let pair = if self.pairs > 0 {
+            self.pairs -= 1;
+            self.radicand / 100u128.pow(self.pairs as u32) % 100
+        } else {
+            0
+        };
+        let rem = self.rem * 100 + pair;
+        let twice = self.root * 20;
+        let mut digit = 9;
+        while (twice + digit) * digit > rem {
+            digit -= 1;
+        }
+
+        self.rem = rem - (twice + digit) * digit;
+        self.root = self.root * 10 + digit;
+        self.len += 1;
+        Some(digit as u8)
+    }
+}
+
+impl Iterator for SqrtDigits {
+    type Item = Digit;
+
+    fn next(&mut self) -> Option<Digit> {
+        if self.int == 0 {
+            self.int = -1;
+            return Some(Digit::Dot);
+        }
+
+        let digit = self.step()?;
+        if self.int > 0 {
+            self.int -= 1;
+        }
+        Some(Digit::Num(digit))
+    }
+}
+
+/// Degrees shown as degrees, minutes and seconds, e.g. `12°34'56.7"`
+///
+/// Seconds keep every nonzero fractional digit, see [`Num::to_dms`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dms<const F: u8, const TF: u8>(pub Num<F, TF>);
+
+impl<const F: u8, const TF: u8> Dms<F, TF> {
+    /// Sign and the unsigned split, so angles between -1° and 0° keep their sign
+    const fn parts(self) -> (bool, u32, u8, Digits) {
+        let (degrees, minutes, seconds) = self.0.to_dms();
+        (
+            self.0.is_negative(),
+            degrees.unsigned_abs(),
+            minutes,
+            seconds.digits(),
+        )
+    }
+}
+
+impl<const F: u8, const TF: u8> core::fmt::Display for Dms<F, TF> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (minus, degrees, minutes, seconds) = self.parts();
+        if minus {
+            f.write_str("-")?;
+        }
+        write!(f, "{degrees}°{minutes}'")?;
+        for digit in seconds {
+            match digit {
+                Digit::Num(n) => write!(f, "{n}")?,
+                Digit::Dot => f.write_str(".")?,
+                Digit::Minus => {}
+            }
+        }
+        f.write_str("\"")
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<const F: u8, const TF: u8> ufmt::uDisplay for Dms<F, TF> {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        let (minus, degrees, minutes, seconds) = self.parts();
+        if minus {
+            f.write_str("-")?;
+        }
+        ufmt::uwrite!(f, "{}°{}'", degrees, minutes)?;
+        for digit in seconds {
+            match digit {
+                Digit::Num(n) => ufmt::uwrite!(f, "{}", n)?,
+                Digit::Dot => f.write_str(".")?,
+                Digit::Minus => {}
+            }
+        }
+        f.write_str("\"")
+    }
+}
+
+/// Sign and decimal point of digits packed by [`Num::to_bcd`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(uDebug))]
+pub struct Bcd {
+    pub negative: bool,
+    /// Digits packed
+    pub len: u8,
+    /// Digits before the decimal point, the rest are the fraction
+    pub int_len: u8,
+}
+
+/// Move `raw` from `from` decimal digits to `to` decimal digits
+///
+/// Rounds half away from zero when digits are dropped and saturates when they are added.
+#[inline]
+const fn rescale(raw: i64, from: u8, to: u8) -> i64 {
+    if from == to {
+        raw
+    } else if to > from {
+        raw.saturating_mul(10i64.pow((to - from) as u32))
+    } else {
+        let divisor = 10i64.pow((from - to) as u32);
+        let (q, r) = (raw / divisor, raw % divisor);
+
+        // Rounded on the remainder, `raw ± divisor / 2` overflows near the limits
+        if r.unsigned_abs() < (divisor / 2) as u64 {
+            q
+        } else if raw >= 0 {
+            q + 1
+        } else {
+            q - 1
+        }
+    }
+}
+
+/// Greatest common divisor by Euclid's algorithm
+#[inline]
+const fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
 }
 
 impl<const F: u8, const TF: u8> Add for Num<F, TF> {
@@ -478,7 +2946,12 @@ impl<const F: u8, const TF: u8> Add for Num<F, TF> {
 
     #[inline]
     fn add(self, rhs: Self) -> Self {
-        Self(self.0.wrapping_add(rhs.0))
+        shadowed!(
+            "add",
+            [self, rhs],
+            |[a, b]| a + b,
+            Self(self.0.wrapping_add(rhs.0))
+        )
     }
 }
 
@@ -487,7 +2960,12 @@ impl<const F: u8, const TF: u8> Sub for Num<F, TF> {
 
     #[inline]
     fn sub(self, rhs: Self) -> Self {
-        Self(self.0.wrapping_sub(rhs.0))
+        shadowed!(
+            "sub",
+            [self, rhs],
+            |[a, b]| a - b,
+            Self(self.0.wrapping_sub(rhs.0))
+        )
     }
 }
 
@@ -505,6 +2983,12 @@ impl<const F: u8, const TF: u8> Mul for Num<F, TF> {
 
     #[inline]
     fn mul(self, rhs: Self) -> Self {
+        // Wraps only if the result doesn't fit, at the cost of i128 code on AVR
+        if cfg!(feature = "wide") {
+            let result = Self(self.mul_wide(rhs) as i64);
+            return shadowed!("mul", [self, rhs], |[a, b]| a * b, result);
+        }
+
         // Compute (a * b) / S with rounding to nearest
         let r = self.0.wrapping_mul(rhs.0);
 
@@ -515,7 +2999,7 @@ impl<const F: u8, const TF: u8> Mul for Num<F, TF> {
             (r - Self::SCALE / 2) / Self::SCALE
         };
 
-        Self(rounded)
+        shadowed!("mul", [self, rhs], |[a, b]| a * b, Self(rounded))
     }
 }
 
@@ -526,18 +3010,23 @@ impl<const F: u8, const TF: u8> Div for Num<F, TF> {
     fn div(self, rhs: Self) -> Self {
         // Panic on zero
         // Idk why but this make program size smaller
-        assert!(rhs.0 != 0, "division by zero");
+        require!(rhs.0 != 0, "division by zero", Self::ZERO);
+
+        if cfg!(feature = "wide") {
+            let result = Self(self.div_wide(rhs) as i64);
+            return shadowed!("div", [self, rhs], |[a, b]| a / b, result);
+        }
 
         let r = self.0.wrapping_mul(Self::SCALE);
 
         // Add half of the divisor for rounding
         let rounded = if r >= 0 {
-            (r + rhs.0 / 2) / rhs.0
+            (r + rhs.0 / 2).wrapping_div(rhs.0)
         } else {
-            (r - rhs.0 / 2) / rhs.0
+            (r - rhs.0 / 2).wrapping_div(rhs.0)
         };
 
-        Self(rounded)
+        shadowed!("div", [self, rhs], |[a, b]| a / b, Self(rounded))
     }
 }
 
@@ -546,7 +3035,9 @@ impl<const F: u8, const TF: u8> Rem for Num<F, TF> {
 
     #[inline]
     fn rem(self, rhs: Self) -> Self {
-        Self(self.0 % rhs.0)
+        require!(rhs.0 != 0, "division by zero", Self::ZERO);
+
+        Self(self.0.wrapping_rem(rhs.0))
     }
 }
 
@@ -578,62 +3069,233 @@ impl<const F: u8, const TF: u8> DivAssign for Num<F, TF> {
     }
 }
 
-impl<const F: u8, const TF: u8> RemAssign for Num<F, TF> {
-    #[inline]
-    fn rem_assign(&mut self, rhs: Self) {
-        *self = *self % rhs;
+impl<const F: u8, const TF: u8> RemAssign for Num<F, TF> {
+    #[inline]
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = *self % rhs;
+    }
+}
+
+impl<const F: u8, const TF: u8> AsRef<i64> for Num<F, TF> {
+    #[inline]
+    fn as_ref(&self) -> &i64 {
+        &self.0
+    }
+}
+
+impl<const F: u8, const TF: u8> AsMut<i64> for Num<F, TF> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut i64 {
+        &mut self.0
+    }
+}
+
+impl<const F: u8, const TF: u8> Borrow<i64> for Num<F, TF> {
+    #[inline]
+    fn borrow(&self) -> &i64 {
+        &self.0
+    }
+}
+
+impl<const F: u8, const TF: u8> BorrowMut<i64> for Num<F, TF> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut i64 {
+        &mut self.0
+    }
+}
+
+impl<const F: u8, const TF: u8> Deref for Num<F, TF> {
+    type Target = i64;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const F: u8, const TF: u8> DerefMut for Num<F, TF> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Lossless `From` for integers of up to 32 bits, checked at compile time
+///
+/// Using it with an `F` too large for every value of the type fails to build, e.g.
+/// `From<i32>` above `F = 9`; `TryFrom<i64>` works at any `F`.
+macro_rules! from_int {
+    ($($t:ty),+) => {$(
+        impl<const F: u8, const TF: u8> From<$t> for Num<F, TF> {
+            #[inline]
+            fn from(n: $t) -> Self {
+                const {
+                    assert!(
+                        (<$t>::MAX as i128) * (Self::SCALE as i128) <= i64::MAX as i128
+                            && (<$t>::MIN as i128) * (Self::SCALE as i128) >= i64::MIN as i128,
+                        "`F` is too large to convert every value, use `TryFrom<i64>`"
+                    );
+                }
+                Self(n as i64 * Self::SCALE)
+            }
+        }
+    )+};
+}
+
+from_int!(i8, i16, i32, u8, u16, u32);
+
+impl<const F: u8, const TF: u8> TryFrom<i64> for Num<F, TF> {
+    type Error = TryFromIntError;
+
+    #[inline]
+    fn try_from(n: i64) -> Result<Self, Self::Error> {
+        Self::checked_from_int(n).ok_or(TryFromIntError)
+    }
+}
+
+impl<const F: u8, const TF: u8> TryFrom<u64> for Num<F, TF> {
+    type Error = TryFromIntError;
+
+    #[inline]
+    fn try_from(n: u64) -> Result<Self, Self::Error> {
+        i64::try_from(n)
+            .ok()
+            .and_then(Self::checked_from_int)
+            .ok_or(TryFromIntError)
+    }
+}
+
+impl<const F: u8, const TF: u8> core::fmt::Display for Num<F, TF> {
+    /// Scaled value like `3.14` or `-0.05`, the [`Num::digits`]
+    ///
+    /// Width, fill, alignment, `+` and `0` work as for integers. A precision rounds to that
+    /// many fractional digits like [`Num::round_frac`] and fills up with zeros, as far as
+    /// 64 chars go.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let value = f.precision().map_or(*self, |precision| {
+            self.round_frac(u8::try_from(precision).unwrap_or(F))
+        });
+
+        let mut buf = [0u8; 64];
+        // 21 bytes fit any value
+        let Some(mut len) = value.write_decimal(&mut buf) else {
+            return Err(core::fmt::Error);
+        };
+        if let Some(precision) = f.precision() {
+            let mut frac = buf[..len]
+                .iter()
+                .position(|&b| b == b'.')
+                .map_or(0, |dot| len - dot - 1);
+            if frac == 0 && precision > 0 {
+                buf[len] = b'.';
+                len += 1;
+            }
+            while frac < precision && len < buf.len() {
+                buf[len] = b'0';
+                len += 1;
+                frac += 1;
+            }
+        }
+
+        let (non_negative, digits) = match buf[..len].split_first() {
+            Some((b'-', digits)) => (false, digits),
+            _ => (true, &buf[..len]),
+        };
+        let Ok(digits) = core::str::from_utf8(digits) else {
+            return Err(core::fmt::Error);
+        };
+        f.pad_integral(non_negative, "", digits)
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<const F: u8, const TF: u8> ufmt::uDisplay for Num<F, TF> {
+    /// Scaled value like `3.14` or `-0.05`, the [`Num::digits`]
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        for digit in self.digits() {
+            match digit {
+                Digit::Num(n) => ufmt::uwrite!(f, "{}", n)?,
+                Digit::Dot => f.write_str(".")?,
+                Digit::Minus => f.write_str("-")?,
+            }
+        }
+        Ok(())
     }
 }
 
-impl<const F: u8, const TF: u8> AsRef<i64> for Num<F, TF> {
-    #[inline]
-    fn as_ref(&self) -> &i64 {
-        &self.0
+#[cfg(feature = "defmt")]
+impl<const F: u8, const TF: u8> defmt::Format for Num<F, TF> {
+    /// Scaled value like `3.14` or `-0.05`, the [`Num::write_decimal`] text
+    fn format(&self, f: defmt::Formatter<'_>) {
+        let mut text = [0; 21];
+        let len = self.write_decimal(&mut text).unwrap_or(0);
+        // Only ASCII digits, sign and dot are written
+        let text = core::str::from_utf8(&text[..len]).unwrap_or("?");
+        defmt::write!(f, "{=str}", text);
     }
 }
 
-impl<const F: u8, const TF: u8> AsMut<i64> for Num<F, TF> {
+impl<const F: u8, const TF: u8> core::str::FromStr for Num<F, TF> {
+    type Err = ParseNumError;
+
     #[inline]
-    fn as_mut(&mut self) -> &mut i64 {
-        &mut self.0
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_bytes(s.as_bytes())
     }
 }
 
-impl<const F: u8, const TF: u8> Borrow<i64> for Num<F, TF> {
-    #[inline]
-    fn borrow(&self) -> &i64 {
-        &self.0
-    }
+/// Why [`Num::parse_bytes`] rejected the text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(uDebug))]
+pub enum ParseNumError {
+    /// No digits, e.g. `""`, `"-"` or `"."`
+    Empty,
+    /// Something other than a sign, digits and one decimal point
+    InvalidDigit,
+    /// Too large for a [`Num`] with its `F`
+    Overflow,
 }
 
-impl<const F: u8, const TF: u8> BorrowMut<i64> for Num<F, TF> {
-    #[inline]
-    fn borrow_mut(&mut self) -> &mut i64 {
-        &mut self.0
+impl core::fmt::Display for ParseNumError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Empty => "cannot parse a number without digits",
+            Self::InvalidDigit => "invalid digit found in the number",
+            Self::Overflow => "number too large for the fixed-point number",
+        })
     }
 }
 
-impl<const F: u8, const TF: u8> Deref for Num<F, TF> {
-    type Target = i64;
+impl core::error::Error for ParseNumError {}
 
-    #[inline]
-    fn deref(&self) -> &Self::Target {
-        &self.0
+/// Integer too large for a [`Num`] once scaled by `10^F`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(uDebug))]
+pub struct TryFromIntError;
+
+impl core::fmt::Display for TryFromIntError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("integer out of range for the fixed-point number")
     }
 }
 
-impl<const F: u8, const TF: u8> DerefMut for Num<F, TF> {
+impl core::error::Error for TryFromIntError {}
+
+impl<const F: u8, const TF: u8> From<Num<F, TF>> for f64 {
     #[inline]
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+    fn from(v: Num<F, TF>) -> Self {
+        v.to_f64()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use core::f64;
+    use core::{f64, task::Poll};
 
-    use super::Num;
+    use super::{Bcd, Digit, Dms, Num, ParseNumError, Root, Solver, TryFromIntError};
 
     // Test with 4 decimal places for good precision
     type TestNum = Num<6, 8>;
@@ -757,6 +3419,601 @@ mod tests {
     }
 
     #[test]
+    fn test_digits() {
+        use super::Digit::{self, Dot, Minus};
+        use super::Digits;
+
+        fn collect(digits: Digits) -> Vec<Digit> {
+            digits.collect()
+        }
+        const fn n(d: u8) -> Digit {
+            Digit::Num(d)
+        }
+
+        type Num2 = Num<2, 2>;
+
+        // Integers
+        assert_eq!(collect(Num2::ZERO.digits()), [n(0)]);
+        assert_eq!(collect(Num2::from_int(7).digits()), [n(7)]);
+        assert_eq!(collect(Num2::from_int(120).digits()), [n(1), n(2), n(0)]);
+        assert_eq!(collect(Num2::from_int(-40).digits()), [Minus, n(4), n(0)]);
+
+        // Fractions with trailing zeros dropped
+        assert_eq!(collect(Num2::from_f64(1.5).digits()), [n(1), Dot, n(5)]);
+        assert_eq!(
+            collect(Num2::from_f64(-12.34).digits()),
+            [Minus, n(1), n(2), Dot, n(3), n(4)]
+        );
+        assert_eq!(
+            collect(Num2::from_f64(10.01).digits()),
+            [n(1), n(0), Dot, n(0), n(1)]
+        );
+
+        // Pure fractions and leading fractional zeros
+        assert_eq!(collect(Num2::from_f64(0.05).digits()), [n(0), Dot, n(0), n(5)]);
+        assert_eq!(collect(Num2::from_f64(0.5).digits()), [n(0), Dot, n(5)]);
+        assert_eq!(
+            collect(Num2::from_f64(-0.01).digits()),
+            [Minus, n(0), Dot, n(0), n(1)]
+        );
+        assert_eq!(
+            collect(TestNum::from_f64(-0.000001).digits()),
+            [Minus, n(0), Dot, n(0), n(0), n(0), n(0), n(0), n(1)]
+        );
+
+        // Zero fractional digits
+        assert_eq!(collect(Num::<0, 0>::from_int(305).digits()), [n(3), n(0), n(5)]);
+
+        // Extremes
+        assert_eq!(collect(Num::<0, 0>(i64::MIN).digits()).len(), 20);
+        assert_eq!(collect(Num::<18, 18>(i64::MAX).digits()).len(), 20);
+
+        // Every value of a small range round-trips through its digits
+        for raw in -10_000..=10_000 {
+            let mut value = 0i64;
+            let mut frac = None;
+            let mut neg = false;
+            for digit in Num2::from_raw(raw).digits() {
+                match digit {
+                    Minus => neg = true,
+                    Dot => frac = Some(0),
+                    Digit::Num(d) => {
+                        value = value * 10 + i64::from(d);
+                        frac = frac.map(|f| f + 1);
+                    }
+                }
+            }
+            let value = value * 10i64.pow(2 - frac.unwrap_or(0));
+            assert_eq!(if neg { -value } else { value }, raw);
+        }
+    }
+
+    #[test]
+    fn test_bcd() {
+        let bcd = |x: TestNum| {
+            let mut out = [0xff; 10];
+            x.to_bcd(&mut out).map(|bcd| (bcd, out))
+        };
+        let meta = |negative, len, int_len| Bcd {
+            negative,
+            len,
+            int_len,
+        };
+
+        let (b, out) = bcd(TestNum::from_f64(1234.5)).unwrap();
+        assert_eq!(b, meta(false, 5, 4));
+        assert_eq!(out[..3], [0x12, 0x34, 0x50]);
+
+        let (b, out) = bcd(TestNum::from_f64(-0.000_001)).unwrap();
+        assert_eq!(b, meta(true, 7, 1));
+        assert_eq!(out[..4], [0x00, 0x00, 0x00, 0x10]);
+
+        let (b, out) = bcd(TestNum::ZERO).unwrap();
+        assert_eq!(b, meta(false, 1, 1));
+        assert_eq!(out[..2], [0x00, 0xff]);
+
+        // Widest value fills all 10 bytes
+        let mut out = [0; 10];
+        let b = Num::<0, 0>(i64::MIN).to_bcd(&mut out).unwrap();
+        assert_eq!(b, meta(true, 19, 19));
+        assert_eq!(
+            out,
+            [0x92, 0x23, 0x37, 0x20, 0x36, 0x85, 0x47, 0x75, 0x80, 0x80]
+        );
+
+        // Too short
+        assert_eq!(TestNum::from_int(123).to_bcd(&mut [0; 1]), None);
+        assert!(TestNum::from_int(12).to_bcd(&mut [0; 1]).is_some());
+    }
+
+    #[test]
+    fn test_write_decimal() {
+        let text = |x: TestNum| {
+            let mut out = [0xff; 21];
+            x.write_decimal(&mut out).map(|len| out[..len].to_vec())
+        };
+
+        assert_eq!(text(TestNum::from_f64(1234.5)).unwrap(), b"1234.5");
+        assert_eq!(text(TestNum::from_f64(-0.000_001)).unwrap(), b"-0.000001");
+        assert_eq!(text(TestNum::ZERO).unwrap(), b"0");
+        for x in [-2.5, 0.05, 1e6, -123.456_789] {
+            let x = TestNum::from_f64(x);
+            assert_eq!(text(x).unwrap(), x.to_string().as_bytes());
+        }
+
+        // Widest values fill all 21 bytes
+        let mut out = [0; 21];
+        assert_eq!(Num::<18, 18>(i64::MIN).write_decimal(&mut out), Some(21));
+        assert_eq!(&out, b"-9.223372036854775808");
+        assert_eq!(Num::<0, 0>(i64::MIN).write_decimal(&mut out), Some(20));
+
+        // Too short, the bytes that fit are written anyway
+        let mut out = [0; 3];
+        assert_eq!(TestNum::from_f64(1.25).write_decimal(&mut out), None);
+        assert_eq!(&out, b"1.2");
+        assert_eq!(TestNum::from_int(-12).write_decimal(&mut out), Some(3));
+    }
+
+    #[test]
+    fn test_checked_push_digit() {
+        fn boundary<const F: u8>() {
+            type N<const F: u8> = Num<F, F>;
+
+            assert_eq!(
+                N::<F>::ZERO.checked_push_digit(7),
+                Some(N::<F>::from_int(7))
+            );
+            // 123 doesn't fit at F = 18
+            if F < 18 {
+                assert_eq!(
+                    N::<F>::from_int(12).checked_push_digit(3),
+                    Some(N::<F>::from_int(123))
+                );
+                assert_eq!(
+                    N::<F>::from_int(-12).checked_push_digit(3),
+                    Some(N::<F>::from_int(-123))
+                );
+            }
+
+            for digit in [0, 1, 9] {
+                let d = i64::from(digit) * N::<F>::SCALE;
+
+                // Largest value that still accepts the digit
+                let max = (i64::MAX - d) / 10;
+                assert_eq!(
+                    N::<F>::from_raw(max).checked_push_digit(digit),
+                    Some(N::<F>::from_raw(max * 10 + d))
+                );
+                assert_eq!(N::<F>::from_raw(max + 1).checked_push_digit(digit), None);
+
+                let min = (i64::MIN + d) / 10;
+                assert_eq!(
+                    N::<F>::from_raw(min).checked_push_digit(digit),
+                    Some(N::<F>::from_raw(min * 10 - d))
+                );
+                assert_eq!(N::<F>::from_raw(min - 1).checked_push_digit(digit), None);
+            }
+        }
+
+        boundary::<0>();
+        boundary::<1>();
+        boundary::<2>();
+        boundary::<6>();
+        boundary::<8>();
+        boundary::<18>();
+    }
+
+    #[test]
+    fn test_round_frac() {
+        assert_eq!(
+            TestNum::from_f64(2.345).round_frac(2),
+            TestNum::from_f64(2.35)
+        );
+        assert_eq!(
+            TestNum::from_f64(-2.345).round_frac(2),
+            TestNum::from_f64(-2.35)
+        );
+        assert_eq!(
+            TestNum::from_f64(2.344).round_frac(2),
+            TestNum::from_f64(2.34)
+        );
+        assert_eq!(TestNum::from_f64(0.5).round_frac(0), TestNum::ONE);
+        assert_eq!(
+            TestNum::from_f64(1.234567).round_frac(6),
+            TestNum::from_f64(1.234567)
+        );
+        assert_eq!(
+            TestNum::from_f64(1.234567).round_frac(9),
+            TestNum::from_f64(1.234567)
+        );
+    }
+
+    #[test]
+    fn test_rounding() {
+        let cases = [
+            // x, floor, ceil, round, trunc, fract
+            (2.7, 2, 3, 3, 2, 0.7),
+            (2.5, 2, 3, 3, 2, 0.5),
+            (2.0, 2, 2, 2, 2, 0.0),
+            (0.000001, 0, 1, 0, 0, 0.000001),
+            (-0.000001, -1, 0, 0, 0, -0.000001),
+            (-2.5, -3, -2, -3, -2, -0.5),
+            (-2.7, -3, -2, -3, -2, -0.7),
+            (-3.0, -3, -3, -3, -3, 0.0),
+        ];
+
+        for (x, floor, ceil, round, trunc, fract) in cases {
+            let x = TestNum::from_f64(x);
+            assert_eq!(x.floor(), TestNum::from_int(floor));
+            assert_eq!(x.ceil(), TestNum::from_int(ceil));
+            assert_eq!(x.round(), TestNum::from_int(round));
+            assert_eq!(x.trunc(), TestNum::from_int(trunc));
+            assert_eq!(x.fract(), TestNum::from_f64(fract));
+            assert_eq!(x.trunc() + x.fract(), x);
+        }
+    }
+
+    #[test]
+    fn test_rounding_limits() {
+        type N = Num<4, 8>;
+        let (max, min) = (N::from_raw(i64::MAX), N::from_raw(i64::MIN));
+        // The largest and smallest whole numbers that fit
+        let (top, bottom) = (
+            N::from_raw(i64::MAX / 10_000 * 10_000),
+            N::from_raw(i64::MIN / 10_000 * 10_000),
+        );
+
+        assert_eq!(max.round(), top);
+        assert_eq!(max.ceil(), top);
+        assert_eq!(max.floor(), top);
+        assert_eq!(min.round(), bottom);
+        assert_eq!(min.floor(), bottom);
+        assert_eq!(min.ceil(), bottom);
+        assert_eq!(min.round_frac(2), N::from_raw(i64::MIN / 100 * 100));
+        assert_eq!(max.round_frac(2), N::from_raw(i64::MAX / 100 * 100));
+        assert_eq!(max.round_frac(3), N::from_raw(i64::MAX / 10 * 10));
+    }
+
+    #[test]
+    fn test_duration_conversions() {
+        // Exact conversions
+        assert_eq!(TestNum::from_millis(1500), TestNum::from_f64(1.5));
+        assert_eq!(TestNum::from_micros(-250), TestNum::from_f64(-0.00025));
+        assert_eq!(TestNum::from_f64(2.5).to_millis(), 2500);
+        assert_eq!(TestNum::from_f64(2.5).to_micros(), 2500000);
+
+        // Rounding half away from zero
+        assert_eq!(TestNum::from_f64(0.0015).to_millis(), 2);
+        assert_eq!(TestNum::from_f64(-0.0015).to_millis(), -2);
+        assert_eq!(Num::<2, 2>::from_millis(1005), Num::<2, 2>::from_f64(1.01));
+        assert_eq!(Num::<2, 2>::from_micros(4999), Num::<2, 2>::ZERO);
+
+        // Saturation
+        assert_eq!(Num::<0, 0>::from_int(i64::MAX).to_micros(), i64::MAX);
+
+        // Rounding at the limits doesn't overflow
+        assert_eq!(Num::<6, 6>(i64::MAX).to_millis(), i64::MAX / 1000 + 1);
+        assert_eq!(Num::<6, 6>(i64::MIN).to_millis(), i64::MIN / 1000 - 1);
+        assert_eq!(Num::<2, 2>::from_millis(i64::MIN), Num(i64::MIN / 10 - 1));
+        assert_eq!(Num::<2, 2>::from_millis(i64::MAX), Num(i64::MAX / 10 + 1));
+    }
+
+    #[test]
+    fn test_raw_scaled() {
+        assert_eq!(TestNum::from_raw_scaled(3305, 3), TestNum::from_f64(3.305));
+        assert_eq!(TestNum::from_raw_scaled(-12, 0), TestNum::from_int(-12));
+        assert_eq!(Num::<2, 2>::from_raw_scaled(3305, 3), Num::<2, 2>::from_f64(3.31));
+        assert_eq!(Num::<2, 2>::from_raw_scaled(-3305, 3), Num::<2, 2>::from_f64(-3.31));
+        assert_eq!(TestNum::from_f64(3.305).to_raw_scaled(3), 3305);
+        assert_eq!(Num::<2, 2>::from_f64(3.31).to_raw_scaled(4), 33100);
+        assert_eq!(TestNum::from_f64(3.3055).to_raw_scaled(3), 3306);
+
+        // Milliseconds are the same scaling
+        assert_eq!(TestNum::from_raw_scaled(1500, 3), TestNum::from_millis(1500));
+    }
+
+    #[test]
+    #[cfg(feature = "wide")]
+    fn test_wide_mul_div() {
+        // Scaled products overflow i64, the results fit
+        let million = TestNum::from_int(1_000_000);
+        let big = TestNum::from_int(100_000_000);
+        let half = TestNum::from_f64(0.5);
+
+        assert_eq!(
+            million * TestNum::from_int(1000),
+            TestNum::from_int(1_000_000_000)
+        );
+        assert_eq!(-million * million, TestNum::from_int(-1_000_000_000_000));
+        assert_eq!(big / half, TestNum::from_int(200_000_000));
+        assert_eq!(
+            -big / TestNum::from_int(3),
+            TestNum::from_raw(-33_333_333_333_333)
+        );
+        assert_eq!(million.checked_mul(million), Some(million * million));
+        assert_eq!(big.checked_div(half), Some(big / half));
+
+        // Results that don't fit still wrap, the checked variants report them
+        let max = TestNum::from_raw(i64::MAX);
+        assert_eq!(
+            max * TestNum::from_int(2),
+            TestNum::from_raw(i64::MAX.wrapping_mul(2))
+        );
+        assert_eq!(max / half, TestNum::from_raw(i64::MAX.wrapping_mul(2)));
+        assert_eq!(max.checked_mul(TestNum::from_int(2)), None);
+        assert_eq!(max.checked_div(half), None);
+    }
+
+    #[test]
+    fn test_saturating_arithmetic() {
+        let values = [0.0, 1.0, -1.0, 0.5, -2.25, 3.5, 1234.567, -987.654_321];
+
+        // Same as the operators when nothing overflows
+        for x in values {
+            for y in values {
+                let (x, y) = (TestNum::from_f64(x), TestNum::from_f64(y));
+                assert_eq!(x.saturating_add(y), x + y);
+                assert_eq!(x.saturating_sub(y), x - y);
+                assert_eq!(x.saturating_mul(y), x * y);
+                if y != TestNum::ZERO {
+                    assert_eq!(x.saturating_div(y), x / y);
+                }
+            }
+        }
+
+        let (max, min) = (TestNum::from_raw(i64::MAX), TestNum::from_raw(i64::MIN));
+        let big = TestNum::from_int(10_000_000);
+        let small = TestNum::from_f64(0.000_001);
+
+        assert_eq!(max.saturating_add(TestNum::from_raw(1)), max);
+        assert_eq!(min.saturating_add(TestNum::from_raw(-1)), min);
+        assert_eq!(min.saturating_sub(TestNum::from_raw(1)), min);
+        assert_eq!(max.saturating_sub(TestNum::from_raw(-1)), max);
+        assert_eq!(big.saturating_mul(big), max);
+        assert_eq!(big.saturating_mul(-big), min);
+        assert_eq!((-big).saturating_mul(-big), max);
+        assert_eq!(big.saturating_div(small), max);
+        assert_eq!(big.saturating_div(-small), min);
+        assert_eq!(min.saturating_div(TestNum::from_f64(-1.0)), max);
+        // Exact where the raw product overflows `i64` but the result fits
+        assert_eq!(
+            TestNum::from_raw(i64::MAX / 2).saturating_mul(TestNum::from_int(2)),
+            TestNum::from_raw(i64::MAX / 2 * 2)
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "division by zero")]
+    fn test_saturating_div_by_zero() {
+        let _result: TestNum = TestNum::ONE.saturating_div(TestNum::ZERO);
+    }
+
+    #[test]
+    fn test_hypot() {
+        assert_eq!(TestNum::ZERO.hypot(TestNum::ZERO), TestNum::ZERO);
+        assert_eq!(
+            TestNum::from_int(-5).hypot(TestNum::ZERO),
+            TestNum::from_int(5)
+        );
+        assert_eq!(
+            TestNum::from_int(5).hypot(TestNum::from_int(12)),
+            TestNum::from_int(13)
+        );
+        assert_eq!(TestNum::ONE.hypot(TestNum::ONE), TestNum::SQRT_2);
+
+        for (x, y) in [(0.5, 0.25), (-1234.567, 89.01), (0.000_001, 0.000_002)] {
+            let (nx, ny) = (TestNum::from_f64(x), TestNum::from_f64(y));
+            assert_eq!(nx.hypot(ny), TestNum::from_f64(x.hypot(y)));
+        }
+
+        // Squares overflow `i64`, the length still fits
+        let big = TestNum::from_raw(i64::MAX / 2);
+        assert_eq!(big.hypot(TestNum::ZERO), big);
+        assert_eq!(big.hypot(big), TestNum::from_raw(6_521_908_912_666_391_105));
+        let (max, min) = (TestNum::from_raw(i64::MAX), TestNum::from_raw(i64::MIN));
+        assert_eq!(max.hypot(min), max);
+    }
+
+    #[test]
+    fn test_recip() {
+        assert_eq!(TestNum::ONE.recip(), TestNum::ONE);
+        assert_eq!(TestNum::from_int(8).recip(), TestNum::from_f64(0.125));
+        assert_eq!(TestNum::from_int(-3).recip(), TestNum::from_f64(-0.333_333));
+        assert_eq!(TestNum::from_int(7).recip(), TestNum::from_f64(0.142_857));
+        assert_eq!(TestNum::from_raw(1).recip(), TestNum::from_int(1_000_000));
+        assert_eq!(
+            TestNum::from_f64(0.4).recip().recip(),
+            TestNum::from_f64(0.4)
+        );
+
+        for x in [1.0, -1.0, 0.5, -2.25, 3.5, 1234.567, -987.654_321] {
+            let x = TestNum::from_f64(x);
+            assert_eq!(x.checked_recip(), Some(x.recip()));
+            assert_eq!(x.checked_abs(), Some(x.abs()));
+        }
+        assert_eq!(TestNum::ZERO.checked_recip(), None);
+        assert_eq!(TestNum::from_raw(i64::MIN).checked_abs(), None);
+
+        // Exact where 1 scaled twice doesn't fit `i64`
+        type N = Num<10, 10>;
+        assert_eq!(N::from_int(4).recip(), N::from_raw(2_500_000_000));
+        assert_eq!(N::from_int(-3).recip(), N::from_raw(-3_333_333_333));
+
+        // Clamped where the reciprocal of the smallest step doesn't fit
+        assert_eq!(N::from_raw(1).recip(), N::from_raw(i64::MAX));
+        assert_eq!(N::from_raw(-1).recip(), N::from_raw(i64::MIN));
+        assert_eq!(N::from_raw(1).checked_recip(), None);
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "division by zero")]
+    fn test_recip_zero() {
+        let _result: TestNum = TestNum::ZERO.recip();
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let values = [0.0, 1.0, -1.0, 0.5, -2.25, 3.5, 1234.567, -987.654_321];
+
+        // Same as the operators when nothing overflows
+        for x in values {
+            for y in values {
+                let (x, y) = (TestNum::from_f64(x), TestNum::from_f64(y));
+                assert_eq!(x.checked_add(y), Some(x + y));
+                assert_eq!(x.checked_sub(y), Some(x - y));
+                assert_eq!(x.checked_mul(y), Some(x * y));
+                if y == TestNum::ZERO {
+                    assert_eq!(x.checked_div(y), None);
+                    assert_eq!(x.checked_rem(y), None);
+                } else {
+                    assert_eq!(x.checked_div(y), Some(x / y));
+                    assert_eq!(x.checked_rem(y), Some(x % y));
+                }
+            }
+            let x = TestNum::from_f64(x);
+            assert_eq!(x.checked_neg(), Some(-x));
+            if x >= TestNum::ZERO {
+                assert_eq!(x.checked_sqrt(), Some(x.sqrt()));
+            }
+        }
+
+        let (max, min) = (TestNum::from_raw(i64::MAX), TestNum::from_raw(i64::MIN));
+        let big = TestNum::from_int(10_000_000);
+
+        assert_eq!(max.checked_add(TestNum::from_raw(1)), None);
+        assert_eq!(min.checked_sub(TestNum::from_raw(1)), None);
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!(big.checked_mul(big), None);
+        // Product fits in i64 only before it is scaled back, rounding included
+        if !cfg!(feature = "wide") {
+            assert_eq!(
+                TestNum::from_raw(i64::MAX / 2).checked_mul(TestNum::from_raw(2)),
+                None
+            );
+        }
+        assert_eq!(big.checked_div(TestNum::from_f64(0.000_001)), None);
+        assert_eq!(min.checked_rem(TestNum::from_raw(-1)), None);
+        assert_eq!(TestNum::from_int(-4).checked_sqrt(), None);
+        assert_eq!(
+            max.checked_sqrt(),
+            Some(TestNum::from_raw(3_037_000_499_976))
+        );
+        assert_eq!(
+            TestNum::from_int(4).checked_sqrt(),
+            Some(TestNum::from_int(2))
+        );
+    }
+
+    #[test]
+    fn test_sign() {
+        let cases = [
+            // x, signum, is_positive, is_negative
+            (2.5, 1, true, false),
+            (0.000_001, 1, true, false),
+            (0.0, 0, false, false),
+            (-0.000_001, -1, false, true),
+            (-2.5, -1, false, true),
+        ];
+
+        for (x, signum, positive, negative) in cases {
+            let x = TestNum::from_f64(x);
+            assert_eq!(x.signum(), TestNum::from_int(signum));
+            assert_eq!(x.is_positive(), positive);
+            assert_eq!(x.is_negative(), negative);
+            assert_eq!(x.signum() * x.abs(), x);
+        }
+
+        let (a, b) = (TestNum::from_f64(1.5), TestNum::from_f64(-0.25));
+        assert_eq!(a.copysign(b), -a);
+        assert_eq!(b.copysign(a), -b);
+        assert_eq!((-a).copysign(-a), -a);
+        assert_eq!((-a).copysign(TestNum::ZERO), a);
+    }
+
+    #[test]
+    fn test_min_max_clamp() {
+        let (lo, hi) = (TestNum::from_f64(-1.5), TestNum::from_f64(2.25));
+
+        assert_eq!(lo.min(hi), lo);
+        assert_eq!(hi.min(lo), lo);
+        assert_eq!(lo.max(hi), hi);
+        assert_eq!(hi.max(lo), hi);
+        assert_eq!(lo.min(lo), lo);
+
+        let cases = [
+            // x, clamped
+            (-3.0, -1.5),
+            (-1.5, -1.5),
+            (0.000_001, 0.000_001),
+            (2.25, 2.25),
+            (2.250_001, 2.25),
+        ];
+        for (x, clamped) in cases {
+            assert_eq!(
+                TestNum::from_f64(x).clamp(lo, hi),
+                TestNum::from_f64(clamped)
+            );
+        }
+
+        // Chaining from the inherent methods stays const
+        const CLAMPED: TestNum = TestNum::PI
+            .clamp(TestNum::ZERO, TestNum::ONE)
+            .max(TestNum::E);
+        assert_eq!(CLAMPED, TestNum::E);
+        assert_eq!(lo.clamp(lo, lo), lo);
+    }
+
+    #[test]
+    fn test_dms_conversions() {
+        let cases = [
+            // degrees, (d, m, s)
+            (12.582_5, (12, 34, 57.0)),
+            (0.5, (0, 30, 0.0)),
+            (-0.5, (0, 30, 0.0)),
+            (-45.000_001, (-45, 0, 0.003_6)),
+            // Just below a whole degree, nothing carries into the minutes
+            (1.999_999, (1, 59, 59.996_4)),
+            (359.999_999, (359, 59, 59.996_4)),
+        ];
+
+        for (x, (d, m, s)) in cases {
+            let x = TestNum::from_f64(x);
+            assert_eq!(x.to_dms(), (d, m, TestNum::from_f64(s)));
+        }
+
+        // Round trip, rounded back to the last digit
+        for x in [12.345_678, -0.000_001, 1.999_999, -179.5] {
+            let x = TestNum::from_f64(x);
+            let (d, m, s) = x.to_dms();
+            let y = TestNum::from_dms(d, m, s);
+            assert_eq!(if x < TestNum::ZERO && d == 0 { -y } else { y }, x);
+        }
+
+        assert_eq!(
+            TestNum::from_dms(-10, 30, TestNum::from_int(36)),
+            TestNum::from_f64(-10.51)
+        );
+        assert_eq!(Num::<0, 0>::from_int(i64::MAX).to_dms().0, i32::MAX);
+    }
+
+    #[test]
+    fn test_dms_display() {
+        let dms = |x| Dms(Num::<2, 8>::from_f64(x)).to_string();
+
+        assert_eq!(dms(12.5), "12°30'0\"");
+        assert_eq!(dms(-0.25), "-0°15'0\"");
+        assert_eq!(dms(1.01), "1°0'36\"");
+        assert_eq!(dms(-100.0), "-100°0'0\"");
+        assert_eq!(
+            Dms(Num::<5, 8>::from_f64(12.582_13)).to_string(),
+            "12°34'55.668\""
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "lut"))]
     fn test_trigonometric_functions() {
         // Test sine function with common angles
         assert_eq!(TestNum::ZERO.sin(), TestNum::ZERO);
@@ -772,7 +4029,7 @@ mod tests {
         ); // 45°
         assert_eq!(
             (TestNum::PI / TestNum::from_int(3)).sin(),
-            TestNum::from_f64(0.866026)
+            TestNum::from_f64(0.866025)
         ); // 60°
 
         // Test cosine function with common angles
@@ -789,49 +4046,329 @@ mod tests {
         ); // 45°
         assert_eq!(
             (TestNum::PI / TestNum::from_int(6)).cos(),
-            TestNum::from_f64(0.866026)
+            TestNum::from_f64(0.866025)
         ); // 30°
 
         // Test tangent function
         assert_eq!(TestNum::ZERO.tan(), TestNum::ZERO);
         assert_eq!((TestNum::PI / TestNum::from_int(4)).tan(), TestNum::ONE); // 45°
         assert_eq!(
-            (TestNum::PI / TestNum::from_int(6)).tan(),
-            TestNum::from_f64(0.577350)
-        ); // 30°
+            (TestNum::PI / TestNum::from_int(6)).tan(),
+            TestNum::from_f64(0.577350)
+        ); // 30°
+        assert_eq!(
+            (TestNum::PI / TestNum::from_int(3)).tan(),
+            TestNum::from_f64(1.732051)
+        ); // 60°
+
+        // Test cotangent function
+        assert_eq!((TestNum::PI / TestNum::from_int(4)).ctg(), TestNum::ONE); // 45°
+        assert_eq!(
+            (TestNum::PI / TestNum::from_int(6)).ctg(),
+            TestNum::from_f64(1.732051)
+        ); // 30°
+        assert_eq!(
+            (TestNum::PI / TestNum::from_int(3)).ctg(),
+            TestNum::from_f64(0.577350)
+        ); // 60°
+
+        // Test angle normalization
+        let angle_2pi = TestNum::TAU + TestNum::PI / TestNum::from_int(4);
+        assert_eq!(
+            angle_2pi.normalize_angle(),
+            TestNum::PI / TestNum::from_int(4)
+        );
+
+        let negative_angle = -TestNum::TAU - TestNum::PI / TestNum::from_int(4);
+        assert_eq!(
+            negative_angle.normalize_angle(),
+            -TestNum::PI / TestNum::from_int(4)
+        );
+
+        let large_angle = TestNum::TAU * TestNum::from_int(3) + TestNum::PI / TestNum::from_int(3);
+        assert_eq!(
+            large_angle.normalize_angle(),
+            TestNum::PI / TestNum::from_int(3)
+        );
+    }
+
+    #[test]
+    fn test_inverse_trigonometric_functions() {
+        let half_pi = TestNum::from_f64(f64::consts::FRAC_PI_2);
+
+        assert_eq!(TestNum::ZERO.arcsin(), TestNum::ZERO);
+        assert_eq!(TestNum::ONE.arcsin(), half_pi);
+        assert_eq!((-TestNum::ONE).arcsin(), -half_pi);
+        assert_eq!(
+            TestNum::from_f64(0.5).arcsin(),
+            TestNum::from_f64(f64::consts::FRAC_PI_6)
+        ); // 30°
+        assert_eq!(
+            TestNum::from_f64(f64::consts::FRAC_1_SQRT_2).arcsin(),
+            TestNum::from_f64(f64::consts::FRAC_PI_4)
+        ); // 45°
+        assert_eq!(
+            TestNum::from_f64(-0.8).arcsin(),
+            TestNum::from_f64(-0.927295)
+        );
+        assert_eq!(TestNum::from_f64(0.1).arcsin(), TestNum::from_f64(0.100167));
+
+        assert_eq!(TestNum::ONE.arccos(), TestNum::ZERO);
+        assert_eq!(TestNum::ZERO.arccos(), half_pi);
+        assert_eq!((-TestNum::ONE).arccos(), TestNum::PI);
+        assert_eq!(
+            TestNum::from_f64(0.5).arccos(),
+            TestNum::from_f64(f64::consts::FRAC_PI_3)
+        ); // 60°
+        assert_eq!(
+            TestNum::from_f64(-0.5).arccos(),
+            TestNum::from_f64(2.094395)
+        ); // 120°
+
+        // Round trip through sin, the table is coarser than the last digit
+        #[cfg(not(feature = "lut"))]
+        {
+            let angle = TestNum::from_f64(0.7);
+            assert_eq!(angle.sin().arcsin(), angle);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "lut"))]
+    fn test_degrees() {
+        // Exact at the angles the reduction handles in degrees
+        for (deg, sin, cos) in [
+            (0, 0.0, 1.0),
+            (30, 0.5, 0.866_025),
+            (90, 1.0, 0.0),
+            (150, 0.5, -0.866_025),
+            (180, 0.0, -1.0),
+            (-90, -1.0, 0.0),
+            (270, -1.0, 0.0),
+            (390, 0.5, 0.866_025),
+            (-3600, 0.0, 1.0),
+        ] {
+            let x = TestNum::from_int(deg);
+            assert_eq!(x.sin_deg(), TestNum::from_f64(sin));
+            assert_eq!(x.cos_deg(), TestNum::from_f64(cos));
+        }
+        assert_eq!(TestNum::from_int(45).tan_deg(), TestNum::ONE);
+        assert_eq!(TestNum::from_int(-135).tan_deg(), TestNum::ONE);
+
+        // Within a unit of the last digit of the correctly rounded result
+        for raw in (-400_000_000..=400_000_000).step_by(1_234_567) {
+            let x = TestNum::from_raw(raw);
+            let f = (raw as f64 / 1e6).to_radians();
+            assert!((x.sin_deg() - TestNum::from_f64(f.sin())).abs() <= TestNum::from_raw(1));
+            assert!((x.cos_deg() - TestNum::from_f64(f.cos())).abs() <= TestNum::from_raw(1));
+        }
+
+        assert_eq!(TestNum::from_int(180).to_radians(), TestNum::PI);
+        // π rounded to 6 digits is slightly over
+        assert_eq!(TestNum::PI.to_degrees(), TestNum::from_f64(180.000_020));
+        assert_eq!(
+            TestNum::from_int(-1).to_degrees(),
+            TestNum::from_f64(-57.295_780)
+        );
+        assert_eq!(
+            TestNum::from_raw(i64::MAX).to_degrees(),
+            TestNum::from_raw(i64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_arctangent() {
+        assert_eq!(TestNum::ZERO.atan(), TestNum::ZERO);
+        assert_eq!(
+            TestNum::ONE.atan(),
+            TestNum::from_f64(f64::consts::FRAC_PI_4)
+        );
         assert_eq!(
-            (TestNum::PI / TestNum::from_int(3)).tan(),
-            TestNum::from_f64(1.732052)
-        ); // 60°
+            TestNum::from_int(-1).atan(),
+            TestNum::from_f64(-f64::consts::FRAC_PI_4)
+        );
+        assert_eq!(TestNum::from_f64(0.5).atan(), TestNum::from_f64(0.463648));
+        assert_eq!(TestNum::from_int(10).atan(), TestNum::from_f64(1.471128));
+        assert_eq!(
+            TestNum::from_int(-1_000_000).atan(),
+            TestNum::from_f64(-1.570795)
+        );
+        // Round trip through tan, the table is coarser than the last digit
+        #[cfg(not(feature = "lut"))]
+        {
+            let angle = TestNum::from_f64(1.2);
+            assert_eq!(angle.tan().atan(), angle);
+        }
+    }
 
-        // Test cotangent function
-        assert_eq!((TestNum::PI / TestNum::from_int(4)).ctg(), TestNum::ONE); // 45°
+    #[test]
+    fn test_atan2_quadrants() {
+        let one = TestNum::ONE;
+        let zero = TestNum::ZERO;
+
+        assert_eq!(zero.atan2(zero), zero);
+        assert_eq!(zero.atan2(one), zero);
+        assert_eq!(zero.atan2(-one), TestNum::from_f64(f64::consts::PI));
+        assert_eq!(one.atan2(zero), TestNum::from_f64(f64::consts::FRAC_PI_2));
         assert_eq!(
-            (TestNum::PI / TestNum::from_int(6)).ctg(),
-            TestNum::from_f64(1.732052)
-        ); // 30°
+            (-one).atan2(zero),
+            TestNum::from_f64(-f64::consts::FRAC_PI_2)
+        );
+
+        assert_eq!(one.atan2(one), TestNum::from_f64(f64::consts::FRAC_PI_4));
+        assert_eq!(one.atan2(-one), TestNum::from_f64(2.356194));
+        assert_eq!((-one).atan2(-one), TestNum::from_f64(-2.356194));
         assert_eq!(
-            (TestNum::PI / TestNum::from_int(3)).ctg(),
-            TestNum::from_f64(0.577350)
-        ); // 60°
+            (-one).atan2(one),
+            TestNum::from_f64(-f64::consts::FRAC_PI_4)
+        );
 
-        // Test angle normalization
-        let angle_2pi = TestNum::TAU + TestNum::PI / TestNum::from_int(4);
+        // Steep and flat vectors, with operands too large to scale to `TF` digits
         assert_eq!(
-            angle_2pi.normalize_angle(),
-            TestNum::PI / TestNum::from_int(4)
+            TestNum::from_int(3).atan2(TestNum::from_int(-4)),
+            TestNum::from_f64(2.498092)
+        );
+        assert_eq!(
+            TestNum::from_int(-40_000).atan2(TestNum::from_int(30_000)),
+            TestNum::from_f64(-0.927295)
         );
+    }
 
-        let negative_angle = -TestNum::TAU - TestNum::PI / TestNum::from_int(4);
+    #[test]
+    fn test_small_angle_identities() {
+        // Exact identities
+        assert_eq!(TestNum::ZERO.sin(), TestNum::ZERO);
+        assert_eq!(TestNum::ZERO.cos(), TestNum::ONE);
+        assert_eq!(TestNum::PI.sin(), TestNum::ZERO);
+        assert_eq!((-TestNum::PI).sin(), TestNum::ZERO);
+        assert_eq!(TestNum::TAU.sin(), TestNum::ZERO);
+        assert_eq!(Num::<2, 2>::PI.sin(), Num::<2, 2>::ZERO);
+        assert_eq!(Num::<2, 8>::PI.sin(), Num::<2, 8>::ZERO);
+
+        // Thresholds keep the dropped term below half of the last digit
+        let x = TestNum::SIN_SMALL_ANGLE.raw() as f64 / 1e6;
+        assert!(x * x * x / 6.0 < 0.5e-6);
+        let x = TestNum::COS_SMALL_ANGLE.raw() as f64 / 1e6;
+        assert!(x * x * x * x / 24.0 < 0.5e-6);
+        assert!(Num::<18, 18>::SIN_SMALL_ANGLE > Num::<18, 18>::ZERO);
+        assert!(Num::<18, 18>::COS_SMALL_ANGLE > Num::<18, 18>::ZERO);
+
+        // Fast paths
+        // 1 - x²/2 has to show in the last digit, at 0.001 it would round to 1
+        let x = TestNum::from_f64(0.002);
+        assert_eq!(x.sin(), x);
+        assert_eq!((-x).sin(), -x);
+        assert_eq!(x.cos(), TestNum::from_raw(999_998));
+        assert_eq!((-x).cos(), TestNum::from_raw(999_998));
+    }
+
+    #[test]
+    fn test_steps() {
+        for raw in (-7_000_000..=7_000_000).step_by(12_345) {
+            let x = TestNum::from_raw(raw);
+
+            for mut steps in [x.sin_steps(), x.cos_steps()] {
+                let mut polls = 1;
+                let v = loop {
+                    if let Poll::Ready(v) = steps.poll() {
+                        break v;
+                    }
+                    polls += 1;
+                };
+
+                // One poll per series term at most
+                assert!(polls <= 7);
+                assert_eq!(steps.poll(), Poll::Ready(v));
+            }
+
+            assert_eq!(x.sin_steps().finish(), x.sin());
+            assert_eq!(x.cos_steps().finish(), x.cos());
+        }
+
+        // Fast path is ready right away
+        assert_eq!(TestNum::ZERO.sin_steps().poll(), Poll::Ready(TestNum::ZERO));
+        #[cfg(not(any(feature = "cordic", feature = "lut")))]
+        assert!(TestNum::ONE.sin_steps().poll().is_pending());
+    }
+
+    #[test]
+    #[cfg(all(feature = "cordic", not(feature = "lut")))]
+    fn test_cordic() {
+        // Within a unit of the last digit of the correctly rounded result
+        for raw in (-7_000_000..=7_000_000).step_by(9_973) {
+            let x = TestNum::from_raw(raw);
+            let f = raw as f64 / 1e6;
+
+            for (got, expected) in [(x.sin(), f.sin()), (x.cos(), f.cos()), (x.atan(), f.atan())] {
+                assert!((got - TestNum::from_f64(expected)).abs() <= TestNum::from_raw(1));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "lut")]
+    fn test_lut() {
+        type N = Num<4, 8>;
+
+        // Within a unit of the last digit of the correctly rounded result
+        for raw in (-70_000..=70_000).step_by(97) {
+            let x = N::from_raw(raw);
+            let f = raw as f64 / 1e4;
+
+            for (got, expected) in [(x.sin(), f.sin()), (x.cos(), f.cos())] {
+                assert!((got - N::from_f64(expected)).abs() <= N::from_raw(1));
+            }
+        }
+        assert_eq!(N::ZERO.sin(), N::ZERO);
+        assert_eq!(N::ZERO.cos(), N::ONE);
+    }
+
+    #[test]
+    fn test_solver() {
+        let f = |x: TestNum| x * x - TestNum::from_int(2);
+        let solver = Solver::new(f, TestNum::from_int(2), TestNum::ZERO).unwrap();
+
+        let polls = |mut solver: Solver<6, 8>| {
+            let mut polls = 1;
+            let root = loop {
+                if let Poll::Ready(root) = solver.poll(f) {
+                    break root;
+                }
+                polls += 1;
+            };
+            assert_eq!(solver.poll(f), Poll::Ready(root));
+            (root, polls)
+        };
+
+        let (root, bisection) = polls(solver);
+        assert!((root.x - TestNum::SQRT_2).abs() <= TestNum::from_raw(1));
+        assert!(root.residual.abs() <= TestNum::from_raw(3));
+
+        let (newton, polls) = polls(solver.newton());
+        assert!((newton.x - TestNum::SQRT_2).abs() <= TestNum::from_raw(1));
+        assert!(polls < bisection);
+
+        // Decreasing function, π/2 in [1, 2]
+        let root = Solver::new(TestNum::cos, TestNum::ONE, TestNum::from_int(2))
+            .unwrap()
+            .finish(TestNum::cos);
+        assert!((root.x - TestNum::PI / TestNum::from_int(2)).abs() <= TestNum::from_raw(1));
+
+        // Root at an end is ready right away
+        let g = |x: TestNum| x - TestNum::ONE;
+        let mut solver = Solver::new(g, TestNum::ONE, TestNum::from_int(2)).unwrap();
         assert_eq!(
-            negative_angle.normalize_angle(),
-            -TestNum::PI / TestNum::from_int(4)
+            solver.poll(g),
+            Poll::Ready(Root {
+                x: TestNum::ONE,
+                residual: TestNum::ZERO
+            })
         );
 
-        let large_angle = TestNum::TAU * TestNum::from_int(3) + TestNum::PI / TestNum::from_int(3);
+        // No sign change
         assert_eq!(
-            large_angle.normalize_angle(),
-            TestNum::PI / TestNum::from_int(3)
+            Solver::new(f, TestNum::from_int(2), TestNum::from_int(3)),
+            None
         );
     }
 
@@ -857,7 +4394,7 @@ mod tests {
 
         // Test hyperbolic cotangent
         assert_eq!(TestNum::ONE.ctgh(), TestNum::from_f64(1.313035));
-        assert_eq!(TestNum::from_int(2).ctgh(), TestNum::from_f64(1.037314));
+        assert_eq!(TestNum::from_int(2).ctgh(), TestNum::from_f64(1.037315));
         assert_eq!(TestNum::from_f64(-1.0).ctgh(), TestNum::from_f64(-1.313035));
     }
 
@@ -872,6 +4409,120 @@ mod tests {
             TestNum::from_f64(f64::consts::LN_10)
         );
         assert_eq!(TestNum::from_f64(0.5).ln(), -TestNum::LN_2);
+        assert_eq!(TestNum::from_int(10).ln(), TestNum::LN_10);
+        assert_eq!(TestNum::from_int(1000).ln(), TestNum::from_f64(6.907755));
+
+        // Test base 2 and base 10 logarithms
+        assert_eq!(TestNum::ONE.log2(), TestNum::ZERO);
+        assert_eq!(TestNum::from_int(8).log2(), TestNum::from_int(3));
+        assert_eq!(TestNum::from_f64(0.25).log2(), TestNum::from_int(-2));
+        assert_eq!(TestNum::E.log2(), TestNum::LOG2_E);
+        assert_eq!(
+            TestNum::from_int(10).log2(),
+            TestNum::from_f64(f64::consts::LOG2_10)
+        );
+        assert_eq!(TestNum::ONE.log10(), TestNum::ZERO);
+        assert_eq!(TestNum::from_int(1000).log10(), TestNum::from_int(3));
+        assert_eq!(TestNum::from_f64(0.01).log10(), TestNum::from_int(-2));
+
+        // Test logarithm to any base
+        assert_eq!(
+            TestNum::from_int(125).log(TestNum::from_int(5)),
+            TestNum::from_int(3)
+        );
+        assert_eq!(
+            TestNum::from_int(256).log(TestNum::from_int(16)),
+            TestNum::from_int(2)
+        );
+        assert_eq!(
+            TestNum::from_int(8).log(TestNum::from_f64(0.5)),
+            TestNum::from_int(-3)
+        );
+        assert_eq!(TestNum::from_int(7).log(TestNum::from_int(7)), TestNum::ONE);
+        assert_eq!(
+            TestNum::from_int(3).log(TestNum::from_int(9)),
+            TestNum::from_f64(0.5)
+        );
+        assert_eq!(
+            TestNum::from_int(1000).log(TestNum::from_int(10)),
+            TestNum::from_int(1000).log10()
+        );
+        assert_eq!(
+            TestNum::from_int(2).log10(),
+            TestNum::from_f64(f64::consts::LOG10_2)
+        );
+    }
+
+    #[test]
+    fn test_ln_limits() {
+        // Halving the top of the range rounds up without overflowing
+        type N = Num<4, 8>;
+        let max = N::from_raw(i64::MAX);
+
+        assert!(max.ln() > N::from_int(25));
+        assert!(max.log10() > N::from_int(10));
+        assert!(N::from_int(100_000_000_000).lgamma() > N::ZERO);
+    }
+
+    #[test]
+    fn test_exp() {
+        assert_eq!(TestNum::ZERO.exp(), TestNum::ONE);
+        assert_eq!(TestNum::ONE.exp(), TestNum::E);
+        assert_eq!(TestNum::LN_2.exp(), TestNum::from_int(2));
+        assert_eq!(TestNum::from_int(-1).exp(), TestNum::from_f64(0.367879));
+        // exp(r) has TF digits, scaling it by 2^14 leaves 4 of them
+        assert!(
+            (TestNum::from_int(10).exp() - TestNum::from_f64(22026.465795)).abs()
+                < TestNum::from_f64(0.001)
+        );
+        assert_eq!(TestNum::from_int(-20).exp(), TestNum::ZERO);
+        assert_eq!(TestNum::from_int(100).exp(), TestNum::from_raw(i64::MAX));
+        assert_eq!(TestNum::from_int(-100).exp(), TestNum::ZERO);
+    }
+
+    #[test]
+    fn test_exp_limits() {
+        // Saturated to the limits of `i64` at `TF` digits on the way
+        type N = Num<4, 8>;
+        let huge = N::from_int(100_000_000_000);
+
+        assert_eq!(huge.exp(), N::from_raw(i64::MAX));
+        assert_eq!((-huge).exp(), N::ZERO);
+        assert_eq!(N::from_int(10).powf(huge), N::from_raw(i64::MAX));
+        assert_eq!(N::from_raw(i64::MAX).exp(), N::from_raw(i64::MAX));
+        assert_eq!(N::from_raw(i64::MIN).exp(), N::ZERO);
+    }
+
+    #[test]
+    fn test_powf() {
+        assert_eq!(
+            TestNum::from_int(2).powf(TestNum::from_f64(0.5)),
+            TestNum::SQRT_2
+        );
+        // Relative error of TF digits, the result has only 3 integer digits to spare
+        assert!(
+            (TestNum::from_int(10).powf(TestNum::from_f64(2.5)) - TestNum::from_f64(316.227766))
+                .abs()
+                < TestNum::from_f64(0.00001)
+        );
+        assert_eq!(
+            TestNum::from_int(27).powf(TestNum::from_f64(-1.0 / 3.0)),
+            TestNum::from_f64(0.333334) // exponent is -0.333333
+        );
+        assert_eq!(
+            TestNum::E.powf(TestNum::from_int(2)),
+            TestNum::from_f64(7.389057) // e is 2.718282
+        );
+        assert_eq!(
+            TestNum::from_int(-2).powf(TestNum::from_int(3)),
+            TestNum::from_int(-8)
+        );
+        assert_eq!(TestNum::ZERO.powf(TestNum::ZERO), TestNum::ONE);
+        assert_eq!(TestNum::ZERO.powf(TestNum::from_f64(0.5)), TestNum::ZERO);
+        assert_eq!(
+            TestNum::from_int(10).powf(TestNum::from_int(20)),
+            TestNum::from_raw(i64::MAX)
+        );
 
         // Test inverse hyperbolic sine
         assert_eq!(TestNum::ZERO.arcsinh(), TestNum::ZERO);
@@ -903,6 +4554,84 @@ mod tests {
             TestNum::from_f64(-0.549308)
         );
     }
+    /// √(raw·10^F) rounded half away from zero, from the integer square root of its
+    /// quadruple
+    fn exact_sqrt<const F: u8, const TF: u8>(raw: i64) -> Num<F, TF> {
+        let n = raw as u128 * Num::<F, TF>::SCALE as u128;
+        Num::from_raw(((4 * n).isqrt().div_ceil(2)) as i64)
+    }
+
+    /// Raw values over the whole non-negative range: edges, squares and their neighbours,
+    /// and pseudo-random values of every magnitude
+    fn sqrt_raws() -> impl Iterator<Item = i64> {
+        let edges = [0, 1, 2, 3, 99, 100, i64::MAX - 1, i64::MAX];
+        let squares = (1..3_037_000_499i64)
+            .step_by(7_919_113)
+            .flat_map(|r| [r * r - 1, r * r, r * r + r, r * r + r + 1]);
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let random = (0..2000).map(move |i| {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            (seed >> (1 + i % 63)) as i64
+        });
+        edges.into_iter().chain(squares).chain(random)
+    }
+
+    #[test]
+    fn test_sqrt_exact() {
+        for raw in sqrt_raws() {
+            assert_eq!(Num::<0, 8>::from_raw(raw).sqrt(), exact_sqrt(raw), "{raw}");
+            assert_eq!(Num::<2, 8>::from_raw(raw).sqrt(), exact_sqrt(raw), "{raw}");
+            assert_eq!(Num::<3, 8>::from_raw(raw).sqrt(), exact_sqrt(raw), "{raw}");
+            assert_eq!(TestNum::from_raw(raw).sqrt(), exact_sqrt(raw), "{raw}");
+            assert_eq!(
+                Num::<18, 18>::from_raw(raw).sqrt(),
+                exact_sqrt(raw),
+                "{raw}"
+            );
+        }
+
+        // Exactly between two roots at F = 0: √(r² + r) is just below r + ½
+        assert_eq!(Num::<0, 8>::from_raw(12).sqrt(), Num::from_raw(3));
+        assert_eq!(Num::<0, 8>::from_raw(13).sqrt(), Num::from_raw(4));
+    }
+
+    #[test]
+    fn test_sqrt_digits() {
+        let text = |x: TestNum, count| {
+            x.sqrt_digits()
+                .take(count)
+                .map(|digit| match digit {
+                    Digit::Num(n) => char::from(b'0' + n),
+                    Digit::Dot => '.',
+                    Digit::Minus => '-',
+                })
+                .collect::<String>()
+        };
+        assert_eq!(text(TestNum::ZERO, 5), "0.000");
+        assert_eq!(text(TestNum::from_int(2), 22), "1.41421356237309504880");
+        assert_eq!(text(TestNum::from_int(144), 6), "12.000");
+        assert_eq!(text(TestNum::from_raw(1), 10), "0.00100000");
+        assert_eq!(text(TestNum::from_f64(0.5), 12), "0.7071067811");
+        assert_eq!(TestNum::ZERO.sqrt_digits().count(), 38);
+
+        // Six digits past F are the root of the radicand scaled by 100⁶, then it ends
+        for raw in sqrt_raws() {
+            let n = raw as u128 * TestNum::SCALE as u128 * 10u128.pow(12);
+            let mut digits = TestNum::from_raw(raw).sqrt_digits();
+            let fold = |root, digit| match digit {
+                Digit::Num(n) => root * 10 + u128::from(n),
+                _ => unreachable!(),
+            };
+            let int = digits
+                .by_ref()
+                .take_while(|&d| d != Digit::Dot)
+                .fold(0, fold);
+            let root = digits.by_ref().take(12).fold(int, fold);
+            assert_eq!(root, n.isqrt(), "{raw}");
+            assert!(digits.count() < 25, "{raw}");
+        }
+    }
+
     #[test]
     fn test_other_mathematical_functions() {
         // Test square root with perfect squares
@@ -931,27 +4660,409 @@ mod tests {
     }
 
     #[test]
+    fn test_gamma() {
+        let close = |x: f64, expected: f64| {
+            let gamma = TestNum::from_f64(x).gamma();
+            let error = (gamma - TestNum::from_f64(expected)).abs();
+            // Relative error of TF digits, at least the last digit
+            let tolerance = TestNum::from_f64(expected.abs() * 1e-7).max(TestNum::from_raw(2));
+            assert!(error <= tolerance, "gamma({x}) = {gamma:?}, not {expected}");
+        };
+
+        // Whole numbers are exact
+        assert_eq!(TestNum::ONE.gamma(), TestNum::ONE);
+        assert_eq!(TestNum::from_int(2).gamma(), TestNum::ONE);
+        assert_eq!(TestNum::from_int(11).gamma(), TestNum::from_int(3_628_800));
+        assert_eq!(TestNum::from_int(21).gamma(), TestNum::from_raw(i64::MAX));
+        assert_eq!(TestNum::from_int(1000).gamma(), TestNum::from_raw(i64::MAX));
+
+        close(0.5, 1.772_453_850_905_516);
+        close(1.5, 0.886_226_925_452_758);
+        close(5.5, 52.342_777_784_553_52);
+        close(0.1, 9.513_507_698_668_732);
+        close(0.000_001, 999_999.422_785_324_2);
+        close(2.75, 1.608_359_421_985_546);
+        close(12.3, 83_385_367.899_970_03);
+        close(-0.5, -3.544_907_701_811_032);
+        close(-1.5, 2.363_271_801_207_355);
+        close(-2.5, -0.945_308_720_482_942);
+        close(-10.1, -2.213_416_583_085_618e-6);
+
+        assert_eq!(
+            TestNum::from_f64(1000.5).gamma(),
+            TestNum::from_raw(i64::MAX)
+        );
+        assert_eq!(
+            TestNum::from_f64(-1000.5).gamma(),
+            TestNum::ZERO,
+            "1 / Γ(1001.5) is far below the last digit"
+        );
+    }
+
+    #[test]
+    fn test_lgamma() {
+        let close = |x: f64, expected: f64| {
+            let lgamma = TestNum::from_f64(x).lgamma();
+            let error = (lgamma - TestNum::from_f64(expected)).abs();
+            // ln z is multiplied by z, its error grows with it
+            let tolerance = TestNum::from_f64(expected.abs() * 1e-7).max(TestNum::from_raw(2));
+            assert!(
+                error <= tolerance,
+                "lgamma({x}) = {lgamma:?}, not {expected}"
+            );
+        };
+
+        close(1.0, 0.0);
+        close(2.0, 0.0);
+        close(0.5, 0.572_364_942_924_700_1);
+        close(3.0, core::f64::consts::LN_2);
+        close(100.0, 359.134_205_369_575_4);
+        close(1000.5, 5_908.674_175_848_678);
+        close(-0.5, 1.265_512_123_484_645_4);
+        close(-2.5, -0.056_243_716_497_674_05);
+    }
+
+    #[test]
+    fn test_checked_gamma() {
+        assert_eq!(
+            TestNum::from_int(4).checked_gamma(),
+            Some(TestNum::from_int(6))
+        );
+        assert_eq!(TestNum::ZERO.checked_gamma(), None);
+        assert_eq!(TestNum::from_int(-3).checked_gamma(), None);
+        assert_eq!(TestNum::from_f64(20.5).checked_gamma(), None);
+        assert!(TestNum::from_f64(-3.5).checked_gamma().is_some());
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "gamma of non-positive integer")]
+    fn test_gamma_pole() {
+        let _result: TestNum = TestNum::from_int(-2).gamma();
+    }
+
+    #[test]
+    fn test_from_ascii() {
+        let parse = TestNum::from_ascii;
+        assert_eq!(parse(b"0"), Some(TestNum::ZERO));
+        assert_eq!(parse(b"-0"), Some(TestNum::ZERO));
+        assert_eq!(parse(b"42"), Some(TestNum::from_int(42)));
+        assert_eq!(parse(b"+1.5"), Some(TestNum::from_f64(1.5)));
+        assert_eq!(parse(b"-123.456789"), Some(TestNum::from_f64(-123.456789)));
+        assert_eq!(parse(b"3."), Some(TestNum::from_int(3)));
+        assert_eq!(parse(b".5"), Some(TestNum::from_f64(0.5)));
+        assert_eq!(parse(b"007"), Some(TestNum::from_int(7)));
+
+        // Past the fraction, half away from zero
+        assert_eq!(parse(b"0.0000005"), Some(TestNum::from_raw(1)));
+        assert_eq!(parse(b"-0.0000005"), Some(TestNum::from_raw(-1)));
+        assert_eq!(parse(b"0.00000049999"), Some(TestNum::ZERO));
+
+        let max = i64::MAX.to_string();
+        let min = i64::MIN.to_string();
+        assert_eq!(
+            Num::<0, 8>::from_ascii(max.as_bytes()),
+            Some(Num::from_raw(i64::MAX))
+        );
+        assert_eq!(
+            Num::<0, 8>::from_ascii(min.as_bytes()),
+            Some(Num::from_raw(i64::MIN))
+        );
+        assert_eq!(parse(b"9223372036855"), None);
+        assert_eq!(parse(b"99999999999999999999999"), None);
+
+        for invalid in [
+            &b""[..],
+            b"-",
+            b".",
+            b"-.",
+            b"1.2.3",
+            b"1e3",
+            b" 1",
+            b"1,5",
+            b"--1",
+        ] {
+            assert_eq!(parse(invalid), None, "{:?}", str::from_utf8(invalid));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(TestNum::from_f64(2.25).to_string(), "2.25");
+        assert_eq!(TestNum::from_f64(-0.05).to_string(), "-0.05");
+        assert_eq!(TestNum::from_f64(1.000_001).to_string(), "1.000001");
+        assert_eq!(TestNum::from_int(-42).to_string(), "-42");
+        assert_eq!(TestNum::ZERO.to_string(), "0");
+        assert_eq!(
+            Num::<0, 8>::from_raw(i64::MIN).to_string(),
+            i64::MIN.to_string()
+        );
+        for text in ["-123.456789", "0.000001", "9223372036854.775807"] {
+            assert_eq!(text.parse::<TestNum>().unwrap().to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_display_format() {
+        let x = TestNum::from_f64(1.5);
+        assert_eq!(format!("{x:>10}"), "       1.5");
+        assert_eq!(format!("{x:<6}|"), "1.5   |");
+        assert_eq!(format!("{x:6}"), "   1.5");
+        assert_eq!(format!("{x:*^7}"), "**1.5**");
+        assert_eq!(format!("{x:+}"), "+1.5");
+        assert_eq!(format!("{:08}", -x), "-00001.5");
+
+        // Precision rounds like round_frac and fills up with zeros
+        assert_eq!(format!("{x:.3}"), "1.500");
+        assert_eq!(format!("{:.2}", TestNum::from_f64(-2.345)), "-2.35");
+        assert_eq!(format!("{:.0}", TestNum::from_f64(2.5)), "3");
+        assert_eq!(format!("{:.2}", TestNum::from_int(7)), "7.00");
+        assert_eq!(format!("{:8.2}", TestNum::PI), "    3.14");
+        assert_eq!(format!("{:.9}", TestNum::PI), "3.141593000");
+    }
+
+    #[test]
+    fn test_parse() {
+        let parse = TestNum::parse_bytes;
+        assert_eq!(parse(b"-1.25"), Ok(TestNum::from_f64(-1.25)));
+        assert_eq!("2.5".parse(), Ok(TestNum::from_f64(2.5)));
+        assert_eq!("0.0000015".parse(), Ok(TestNum::from_raw(2)));
+
+        for empty in [&b""[..], b"-", b"+", b".", b"-."] {
+            assert_eq!(parse(empty), Err(ParseNumError::Empty));
+        }
+        for invalid in [&b"1.2.3"[..], b"1e3", b" 1", b"1,5", b"--1", b"1-"] {
+            assert_eq!(parse(invalid), Err(ParseNumError::InvalidDigit));
+        }
+        for overflow in [
+            &b"9223372036855"[..],
+            b"-9223372036855",
+            b"99999999999999999999999",
+        ] {
+            assert_eq!(parse(overflow), Err(ParseNumError::Overflow));
+        }
+        assert_eq!(
+            "x".parse::<TestNum>().unwrap_err().to_string(),
+            "invalid digit found in the number"
+        );
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        let n = TestNum::from_int;
+        assert_eq!(n(12).gcd(n(18)), n(6));
+        assert_eq!(n(-12).gcd(n(-18)), n(6));
+        assert_eq!(n(17).gcd(n(5)), TestNum::ONE);
+        assert_eq!(n(0).gcd(n(-9)), n(9));
+        assert_eq!(TestNum::ZERO.gcd(TestNum::ZERO), TestNum::ZERO);
+
+        assert_eq!(n(4).lcm(n(6)), n(12));
+        assert_eq!(n(-4).lcm(n(6)), n(12));
+        assert_eq!(n(7).lcm(n(7)), n(7));
+        assert_eq!(n(0).lcm(n(3)), TestNum::ZERO);
+        assert_eq!(
+            n(10_000_000).lcm(n(10_000_001)),
+            TestNum::from_raw(i64::MAX)
+        );
+
+        type Whole = Num<0, 8>;
+        let min = Whole::from_raw(i64::MIN);
+        assert_eq!(min.gcd(Whole::ZERO), Whole::from_raw(i64::MAX));
+        assert_eq!(
+            min.gcd(Whole::from_int(6)),
+            Whole::from_int(2),
+            "the unsigned magnitude of i64::MIN is used"
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "gcd of non-integer")]
+    fn test_gcd_non_integer() {
+        let _result: TestNum = TestNum::from_f64(2.5).gcd(TestNum::ONE);
+    }
+
+    #[test]
+    fn test_checked_factorial() {
+        assert_eq!(TestNum::ZERO.checked_factorial(), Some(TestNum::ONE));
+        assert_eq!(
+            TestNum::from_int(5).checked_factorial(),
+            Some(TestNum::from_int(120))
+        );
+        assert_eq!(TestNum::from_int(-1).checked_factorial(), None);
+        assert_eq!(TestNum::from_f64(2.5).checked_factorial(), None);
+        assert_eq!(TestNum::from_int(21).checked_factorial(), None);
+        // 20! fits without the fraction only
+        assert_eq!(TestNum::from_int(20).checked_factorial(), None);
+        assert_eq!(
+            Num::<0, 8>::from_int(20).checked_factorial(),
+            Some(Num::from_raw(2_432_902_008_176_640_000))
+        );
+    }
+
+    #[test]
+    fn test_powi() {
+        assert_eq!(TestNum::from_int(7).powi(0), TestNum::ONE);
+        assert_eq!(TestNum::ZERO.powi(0), TestNum::ONE);
+        assert_eq!(TestNum::from_int(3).powi(1), TestNum::from_int(3));
+        assert_eq!(TestNum::from_int(2).powi(10), TestNum::from_int(1024));
+        assert_eq!(TestNum::from_int(-2).powi(3), TestNum::from_int(-8));
+        assert_eq!(TestNum::from_int(-2).powi(4), TestNum::from_int(16));
+        assert_eq!(TestNum::from_f64(1.1).powi(2), TestNum::from_f64(1.21));
+        assert_eq!(TestNum::from_f64(0.5).powi(3), TestNum::from_f64(0.125));
+
+        // Negative exponents
+        assert_eq!(TestNum::from_int(4).powi(-1), TestNum::from_f64(0.25));
+        assert_eq!(TestNum::from_int(10).powi(-3), TestNum::from_f64(0.001));
+        assert_eq!(TestNum::from_int(-2).powi(-3), TestNum::from_f64(-0.125));
+
+        // Saturation keeps the sign
+        assert_eq!(TestNum::from_int(10).powi(13), TestNum::from_raw(i64::MAX));
+        assert_eq!(TestNum::from_int(-10).powi(13), TestNum::from_raw(i64::MIN));
+        assert_eq!(TestNum::from_int(-10).powi(14), TestNum::from_raw(i64::MAX));
+        assert_eq!(
+            TestNum::from_int(10).powi(i32::MAX),
+            TestNum::from_raw(i64::MAX)
+        );
+        assert_eq!(TestNum::from_int(10).powi(-13), TestNum::ZERO);
+
+        // Product of raw values overflows i64, the result does not
+        assert_eq!(
+            TestNum::from_int(100_000).powi(2),
+            TestNum::from_int(10_000_000_000)
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
     #[should_panic(expected = "division by zero")]
     fn test_division_by_zero() {
-        let _: TestNum = TestNum::from_int(1) / TestNum::ZERO;
+        let _result: TestNum = TestNum::from_int(1) / TestNum::ZERO;
     }
 
     #[test]
+    #[cfg(not(feature = "panic-free"))]
     #[should_panic(expected = "sqrt of negative number")]
     fn test_sqrt_negative() {
-        let _: TestNum = TestNum::from_int(-1).sqrt();
+        let _result: TestNum = TestNum::from_int(-1).sqrt();
     }
 
     #[test]
+    #[cfg(not(feature = "panic-free"))]
     #[should_panic(expected = "Factorial of negative number")]
     fn test_factorial_negative() {
-        let _: TestNum = TestNum::from_int(-1).factorial();
+        let _result: TestNum = TestNum::from_int(-1).factorial();
     }
 
     #[test]
+    #[cfg(not(feature = "panic-free"))]
     #[should_panic(expected = "ln of non-positive number")]
     fn test_ln_non_positive() {
-        let _: TestNum = TestNum::ZERO.ln();
+        let _result: TestNum = TestNum::ZERO.ln();
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "powf of negative number with fractional exponent")]
+    fn test_powf_negative_fractional() {
+        let _result: TestNum = TestNum::from_int(-8).powf(TestNum::from_f64(0.5));
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "log10 of non-positive number")]
+    fn test_log10_non_positive() {
+        let _result: TestNum = TestNum::ZERO.log10();
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "arcsin outside [-1, 1]")]
+    fn test_arcsin_outside_domain() {
+        let _result: TestNum = TestNum::from_f64(1.000001).arcsin();
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "arccos outside [-1, 1]")]
+    fn test_arccos_outside_domain() {
+        let _result: TestNum = TestNum::from_int(-2).arccos();
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "clamp with lo > hi")]
+    fn test_clamp_inverted_bounds() {
+        let _result: TestNum = TestNum::ZERO.clamp(TestNum::ONE, TestNum::ZERO);
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "from_dms minutes or seconds outside [0, 60)")]
+    fn test_from_dms_outside_range() {
+        let _result: TestNum = TestNum::from_dms(1, 60, TestNum::ZERO);
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "log to base 1")]
+    fn test_log_base_one() {
+        let _result: TestNum = TestNum::from_int(2).log(TestNum::ONE);
+    }
+
+    #[test]
+    #[cfg(feature = "panic-free")]
+    fn test_panic_free() {
+        let (zero, one) = (TestNum::ZERO, TestNum::ONE);
+
+        assert_eq!(one / zero, zero);
+        assert_eq!(one % zero, zero);
+        assert_eq!(one.saturating_div(zero), zero);
+        assert_eq!(zero.recip(), zero);
+        assert_eq!(TestNum::from_int(-1).sqrt(), zero);
+        assert_eq!(TestNum::from_int(-1).factorial(), zero);
+        assert_eq!(TestNum::from_int(21).factorial(), zero);
+        assert_eq!(TestNum::from_f64(0.5).gcd(one), zero);
+        assert_eq!(one.lcm(TestNum::from_f64(0.5)), zero);
+        assert_eq!(zero.gamma(), zero);
+        assert_eq!(TestNum::from_int(-1).lgamma(), zero);
+        assert_eq!(zero.ln(), zero);
+        assert_eq!(TestNum::from_int(-2).arccos(), zero);
+        assert_eq!(TestNum::from_int(2).log(one), zero);
+        assert_eq!(zero.clamp(one, zero), zero);
+        assert_eq!(TestNum::from_f64(f64::NAN), zero);
+        assert_eq!(TestNum::from_raw(i64::MIN) % TestNum::from_raw(-1), zero);
+    }
+
+    #[cfg(feature = "shadow")]
+    #[test]
+    fn test_shadow() {
+        use std::cell::Cell;
+
+        use crate::shadow::{self, Validator};
+
+        thread_local! {
+            static REPORTED: Cell<Option<&'static str>> = const { Cell::new(None) };
+        }
+
+        shadow::install(Some(Validator {
+            tolerance: 1,
+            report: |d| REPORTED.set(Some(d.op)),
+        }));
+
+        assert_eq!(
+            TestNum::from_f64(1.5) * TestNum::from_f64(2.25) + TestNum::ONE,
+            TestNum::from_f64(4.375)
+        );
+        assert_eq!(TestNum::from_int(2).ln(), TestNum::LN_2);
+        assert_eq!(REPORTED.get(), None);
+
+        // Wraps around, and the result is still the wrapped one
+        let wrapped = TestNum::from_raw(i64::MAX) + TestNum::from_raw(1);
+        assert_eq!(wrapped, TestNum::from_raw(i64::MIN));
+        assert_eq!(REPORTED.get(), Some("add"));
+
+        shadow::install(None);
     }
 
     #[test]
@@ -978,4 +5089,91 @@ mod tests {
             HighPrecisionNum::from_int(1)
         );
     }
+
+    #[test]
+    fn test_to_f64() {
+        assert_eq!(TestNum::ZERO.to_f64(), 0.0);
+        assert_eq!(TestNum::from_int(-3).to_f64(), -3.0);
+        assert_eq!(TestNum::from_raw(1_500_000).to_f64(), 1.5);
+        assert_eq!(TestNum::from_raw(1).to_f64(), 1e-6);
+        assert_eq!(TestNum::from_raw(-123_456_789).to_f64(), -123.456789);
+        assert_eq!(TestNum::from_raw(100_000).to_f32(), 0.1);
+        assert_eq!(f64::from(TestNum::from_raw(250_000)), 0.25);
+
+        // Round trip through `from_f64`
+        for raw in [1, -1, 999_999, 123_456_789_012, -987_654_321_098] {
+            let v = TestNum::from_raw(raw);
+            assert_eq!(TestNum::from_f64(v.to_f64()), v);
+        }
+    }
+
+    #[test]
+    fn test_special_angles() {
+        let sqrt2 = f64::consts::FRAC_1_SQRT_2;
+        let sqrt3 = 3f64.sqrt() / 2.0;
+        // sin of k π/6 for k = 0..12
+        let sines = [
+            0.0, 0.5, sqrt3, 1.0, sqrt3, 0.5, 0.0, -0.5, -sqrt3, -1.0, -sqrt3, -0.5,
+        ];
+
+        // The 24 multiples of π/6 in (-2π, 2π]
+        for k in -11..=12i32 {
+            let x = TestNum::from_f64(f64::from(k) * f64::consts::FRAC_PI_6);
+            let sin = sines[k.rem_euclid(12) as usize];
+            let cos = sines[(k + 3).rem_euclid(12) as usize];
+            assert_eq!(x.sin(), TestNum::from_f64(sin), "sin({k}π/6)");
+            assert_eq!(x.cos(), TestNum::from_f64(cos), "cos({k}π/6)");
+            assert_eq!(x.sin_steps().poll(), Poll::Ready(x.sin()));
+            if k.rem_euclid(6) != 3 {
+                assert_eq!(x.tan(), TestNum::from_f64(sin / cos), "tan({k}π/6)");
+            }
+        }
+
+        // Multiples of π/4 in between
+        for k in [-7, -5, -3, -1, 1, 3, 5, 7] {
+            let x = TestNum::from_f64(f64::from(k) * f64::consts::FRAC_PI_4);
+            let sign = |v: f64| TestNum::from_f64(v.signum() * sqrt2);
+            let angle = f64::from(k) * f64::consts::FRAC_PI_4;
+            assert_eq!(x.sin(), sign(angle.sin()), "sin({k}π/4)");
+            assert_eq!(x.cos(), sign(angle.cos()), "cos({k}π/4)");
+            assert_eq!(x.tan().abs(), TestNum::ONE, "tan({k}π/4)");
+            assert_eq!(x.ctg(), x.tan(), "ctg({k}π/4)");
+        }
+
+        // As computed from the constants
+        assert_eq!(
+            (TestNum::PI / TestNum::from_int(3)).ctg(),
+            TestNum::from_f64(0.57735)
+        );
+        assert_eq!((-TestNum::TAU).sin(), TestNum::ZERO);
+        assert_eq!((TestNum::PI * TestNum::from_int(100)).cos(), TestNum::ONE);
+
+        // One digit off isn't snapped
+        let near = TestNum::from_f64(f64::consts::FRAC_PI_6) + TestNum::from_raw(1);
+        assert_ne!(near.sin(), TestNum::from_f64(0.5));
+        // At 2 digits 0.52 is the closest to π/6 there is
+        assert_eq!(Num::<2, 8>::from_f64(0.52).sin(), Num::from_f64(0.5));
+        assert_eq!(Num::<2, 8>::from_f64(1.05).cos(), Num::from_f64(0.5));
+    }
+
+    #[test]
+    fn test_from_int_types() {
+        assert_eq!(TestNum::from(-128i8), TestNum::from_int(-128));
+        assert_eq!(TestNum::from(255u8), TestNum::from_int(255));
+        assert_eq!(TestNum::from(i16::MIN), TestNum::from_int(-32768));
+        assert_eq!(TestNum::from(u16::MAX), TestNum::from_int(65535));
+        assert_eq!(TestNum::from(i32::MIN), TestNum::from_int(i32::MIN.into()));
+        assert_eq!(TestNum::from(u32::MAX), TestNum::from_int(u32::MAX.into()));
+        assert_eq!(Num::<16, 16>::from(-128i8).raw(), -128 * 10i64.pow(16));
+
+        let max = i64::MAX / TestNum::SCALE;
+        assert_eq!(TestNum::try_from(max), Ok(TestNum::from_int(max)));
+        assert_eq!(TestNum::try_from(-max), Ok(TestNum::from_int(-max)));
+        assert_eq!(TestNum::try_from(max + 1), Err(TryFromIntError));
+        assert_eq!(TestNum::try_from(i64::MIN), Err(TryFromIntError));
+        assert_eq!(TestNum::try_from(42u64), Ok(TestNum::from_int(42)));
+        assert_eq!(TestNum::try_from(u64::MAX), Err(TryFromIntError));
+        assert_eq!(Num::<0, 8>::try_from(i64::MIN), Ok(Num::from_raw(i64::MIN)));
+        assert_eq!(TestNum::checked_from_int(max + 1), None);
+    }
 }
@@ -0,0 +1,45 @@
+//! Quarter-wave sine table with linear interpolation, enabled by the `lut` feature
+//!
+//! Faster than the series and with the same time for every angle. Interpolation
+//! stays within 2·10⁻⁵ of the sine, enough for up to 4 digits. The table takes
+//! 258 bytes, which AVR keeps in RAM.
+
+use crate::Num;
+
+/// Table steps over a quarter wave
+const STEPS: u32 = 128;
+
+/// sin(i · π/2 / 128), scaled by [`ONE`]
+const SIN: [u16; STEPS as usize + 1] = [
+    0, 804, 1608, 2412, 3216, 4019, 4821, 5623, 6424, 7223, 8022, 8820, 9616, 10411, 11204, 11996,
+    12785, 13573, 14359, 15142, 15924, 16703, 17479, 18253, 19024, 19792, 20557, 21319, 22078,
+    22834, 23586, 24334, 25079, 25820, 26557, 27291, 28020, 28745, 29465, 30181, 30893, 31600,
+    32302, 32999, 33692, 34379, 35061, 35738, 36409, 37075, 37736, 38390, 39039, 39682, 40319,
+    40950, 41575, 42194, 42806, 43411, 44011, 44603, 45189, 45768, 46340, 46905, 47464, 48014,
+    48558, 49095, 49624, 50145, 50659, 51166, 51664, 52155, 52638, 53113, 53580, 54039, 54490,
+    54933, 55367, 55794, 56211, 56620, 57021, 57413, 57797, 58171, 58537, 58895, 59243, 59582,
+    59913, 60234, 60546, 60850, 61144, 61429, 61704, 61970, 62227, 62475, 62713, 62942, 63161,
+    63371, 63571, 63762, 63943, 64114, 64276, 64428, 64570, 64703, 64826, 64939, 65042, 65136,
+    65219, 65293, 65357, 65412, 65456, 65491, 65515, 65530, 65535,
+];
+
+/// 1 in [`SIN`]
+const ONE: u128 = 65_535;
+
+/// Sine of `x` in [-π/2, π/2]
+pub fn sin<const TF: u8>(x: Num<TF, TF>) -> Num<TF, TF> {
+    let pi = Num::<TF, TF>::PI.0 as u128;
+
+    // Position in steps with 16 fractional bits, π/2 rounding may land past the end
+    let pos = ((x.0.unsigned_abs() as u128 * 2 * u128::from(STEPS)) << 16) / pi;
+    let pos = pos.min(u128::from(STEPS) << 16);
+    let (i, frac) = ((pos >> 16) as usize, pos & 0xFFFF);
+
+    let lo = u128::from(SIN[i]);
+    let hi = SIN.get(i + 1).map_or(lo, |&v| u128::from(v));
+    let v = (lo * (0x1_0000 - frac) + hi * frac) >> 16;
+
+    let scale = Num::<TF, TF>::SCALE as u128;
+    let raw = ((v * scale + ONE / 2) / ONE) as i64;
+    Num::from_raw(if x.is_negative() { -raw } else { raw })
+}
@@ -0,0 +1,457 @@
+//! Wider sibling of [`Num`] for sums and products that outgrow an `i64` on the way
+//!
+//! [`Num128`] stores the same decimal fixed-point value in an `i128`, so a dot product, the
+//! squares summed for a variance or a chain of multiplications can run past the range of [`Num`]
+//! and still come back to a [`Num`] once the result is scaled down again. Every [`Num`]
+//! converts to it losslessly, and [`Num::widening_mul`] multiplies two of them into one
+//! without overflowing:
+//!
+//! ```
+//! use cos_num::{Num, num128::Num128};
+//!
+//! type N = Num<6, 8>;
+//!
+//! let x = [N::from_int(3_000_000), N::from_int(4_000_000)];
+//! // 9·10¹² + 16·10¹², while N only reaches about 9.2·10¹²
+//! let squares: Num128<6> = x.iter().map(|&v| v.widening_mul(v)).sum();
+//! assert_eq!(squares.to_num::<8>(), None);
+//! assert_eq!(squares.to_string(), "25000000000000");
+//!
+//! let mean = squares / Num128::from_int(4);
+//! assert_eq!(mean.to_num(), Some(N::from_int(6_250_000_000_000)));
+//! ```
+//!
+//! It has the basic operations only, the rest of the math is a [`Num128::to_num`] away.
+//! Operators wrap like [`Num`]'s, which takes products above about 1.7 × 10³⁸ / 10^F.
+
+use core::{
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use crate::Num;
+
+/// [`Num`] stored in an `i128`, up to about ±1.7 × 10³⁸ / 10^F
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[repr(transparent)]
+pub struct Num128<const F: u8>(pub i128);
+
+impl<const F: u8> Num128<F> {
+    /// `10^F`, the raw value of 1
+    pub const SCALE: i128 = {
+        assert!(F <= 18, "F is too large for i64");
+        let mut s: i128 = 1;
+        let mut i = 0;
+        while i < F {
+            s *= 10;
+            i += 1;
+        }
+        s
+    };
+
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(Self::SCALE);
+    /// Smallest value
+    pub const MIN: Self = Self(i128::MIN);
+    /// Largest value
+    pub const MAX: Self = Self(i128::MAX);
+
+    /// Create from raw inner representation (no scaling)
+    #[inline]
+    #[must_use]
+    pub const fn from_raw(raw: i128) -> Self {
+        Self(raw)
+    }
+
+    /// Get raw inner
+    #[inline]
+    #[must_use]
+    pub const fn raw(self) -> i128 {
+        self.0
+    }
+
+    /// Create from integer, any `i64` fits
+    #[inline]
+    #[must_use]
+    pub const fn from_int(n: i64) -> Self {
+        Self(n as i128 * Self::SCALE)
+    }
+
+    /// Same value as a [`Num`] with the same `F`, never fails
+    #[inline]
+    #[must_use]
+    pub const fn from_num<const TF: u8>(n: Num<F, TF>) -> Self {
+        Self(n.0 as i128)
+    }
+
+    /// Value as a [`Num`] with the same `F`, `None` if it doesn't fit
+    #[inline]
+    #[must_use]
+    pub const fn to_num<const TF: u8>(self) -> Option<Num<F, TF>> {
+        if self.0 < i64::MIN as i128 || self.0 > i64::MAX as i128 {
+            return None;
+        }
+        Some(Num(self.0 as i64))
+    }
+
+    /// Value as a [`Num`] with the same `F`, clamped to its range
+    #[inline]
+    #[must_use]
+    pub const fn saturating_to_num<const TF: u8>(self) -> Num<F, TF> {
+        if self.0 < i64::MIN as i128 {
+            Num(i64::MIN)
+        } else if self.0 > i64::MAX as i128 {
+            Num(i64::MAX)
+        } else {
+            Num(self.0 as i64)
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn abs(self) -> Self {
+        Self(self.0.wrapping_abs())
+    }
+
+    /// Whether self is greater than zero
+    #[inline]
+    #[must_use]
+    pub const fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    /// Whether self is less than zero
+    #[inline]
+    #[must_use]
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// `self + rhs`, or `None` on overflow instead of wrapping
+    #[inline]
+    #[must_use]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
+    /// `self - rhs`, or `None` on overflow instead of wrapping
+    #[inline]
+    #[must_use]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
+    /// `self * rhs`, or `None` if the product or its rounding overflows
+    #[must_use]
+    pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let Some(r) = self.0.checked_mul(rhs.0) else {
+            return None;
+        };
+
+        let r = if r >= 0 {
+            r.checked_add(Self::SCALE / 2)
+        } else {
+            r.checked_sub(Self::SCALE / 2)
+        };
+        match r {
+            Some(r) => Some(Self(r / Self::SCALE)),
+            None => None,
+        }
+    }
+
+    /// `self / rhs`, or `None` if `rhs` is zero or the quotient overflows
+    #[must_use]
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.0 == 0 {
+            return None;
+        }
+        let Some(r) = self.0.checked_mul(Self::SCALE) else {
+            return None;
+        };
+
+        let r = if r >= 0 {
+            r.checked_add(rhs.0 / 2)
+        } else {
+            r.checked_sub(rhs.0 / 2)
+        };
+        match r {
+            Some(r) => match r.checked_div(rhs.0) {
+                Some(r) => Some(Self(r)),
+                None => None,
+            },
+            None => None,
+        }
+    }
+
+    /// `self + rhs`, clamped to the representable range instead of wrapping
+    #[inline]
+    #[must_use]
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// `self - rhs`, clamped to the representable range instead of wrapping
+    #[inline]
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Sign, integer part and the fraction with its trailing zeros dropped as well as the
+    /// number of its digits left, for printing
+    const fn parts(self) -> (bool, u128, u128, u8) {
+        let abs = self.0.unsigned_abs();
+        let scale = Self::SCALE as u128;
+        let mut frac = abs % scale;
+        let mut len = F;
+        while len > 0 && frac.is_multiple_of(10) {
+            frac /= 10;
+            len -= 1;
+        }
+        (self.0 < 0, abs / scale, frac, len)
+    }
+}
+
+impl<const F: u8> Add for Num128<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_add(rhs.0))
+    }
+}
+
+impl<const F: u8> Sub for Num128<F> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+impl<const F: u8> Neg for Num128<F> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(self.0.wrapping_neg())
+    }
+}
+
+impl<const F: u8> Mul for Num128<F> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        let r = self.0.wrapping_mul(rhs.0);
+        if r >= 0 {
+            Self(r.wrapping_add(Self::SCALE / 2) / Self::SCALE)
+        } else {
+            Self(r.wrapping_sub(Self::SCALE / 2) / Self::SCALE)
+        }
+    }
+}
+
+impl<const F: u8> Div for Num128<F> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        require!(rhs.0 != 0, "division by zero", Self::ZERO);
+
+        let r = self.0.wrapping_mul(Self::SCALE);
+        if r >= 0 {
+            Self(r.wrapping_add(rhs.0 / 2).wrapping_div(rhs.0))
+        } else {
+            Self(r.wrapping_sub(rhs.0 / 2).wrapping_div(rhs.0))
+        }
+    }
+}
+
+macro_rules! assign {
+    ($assign:ident $assign_fn:ident $fn:ident) => {
+        impl<const F: u8> $assign for Num128<F> {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = (*self).$fn(rhs);
+            }
+        }
+    };
+}
+
+assign!(AddAssign add_assign add);
+assign!(SubAssign sub_assign sub);
+assign!(MulAssign mul_assign mul);
+assign!(DivAssign div_assign div);
+
+impl<const F: u8> Sum for Num128<F> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<const F: u8, const TF: u8> Sum<Num<F, TF>> for Num128<F> {
+    /// Sum of [`Num`]s that may run past the range of [`Num`]
+    fn sum<I: Iterator<Item = Num<F, TF>>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, n| acc + Self::from(n))
+    }
+}
+
+impl<const F: u8, const TF: u8> From<Num<F, TF>> for Num128<F> {
+    #[inline]
+    fn from(n: Num<F, TF>) -> Self {
+        Self::from_num(n)
+    }
+}
+
+impl<const F: u8> core::fmt::Display for Num128<F> {
+    /// Scaled value like `3.14` or `-0.05`, trailing zeros dropped like [`Num::digits`]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (negative, int, frac, len) = self.parts();
+        if negative {
+            f.write_str("-")?;
+        }
+        write!(f, "{int}")?;
+        if len > 0 {
+            write!(f, ".{frac:0width$}", width = len as usize)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<const F: u8> ufmt::uDisplay for Num128<F> {
+    /// Scaled value like `3.14` or `-0.05`, trailing zeros dropped like [`Num::digits`]
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        let (negative, int, frac, len) = self.parts();
+        if negative {
+            f.write_str("-")?;
+        }
+        ufmt::uwrite!(f, "{}", int)?;
+        if len > 0 {
+            f.write_str(".")?;
+            // ufmt has no zero padding
+            let mut shown = 1;
+            let mut rest = frac / 10;
+            while rest > 0 {
+                shown += 1;
+                rest /= 10;
+            }
+            for _ in shown..len {
+                f.write_str("0")?;
+            }
+            ufmt::uwrite!(f, "{}", frac)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Num128;
+    use crate::Num;
+
+    type W = Num128<6>;
+    type N = Num<6, 8>;
+
+    #[test]
+    fn test_num_conversions() {
+        assert_eq!(W::SCALE, 1_000_000);
+        assert_eq!(W::from(N::from_raw(-1_500_000)), W::from_raw(-1_500_000));
+        assert_eq!(
+            W::from(N::from_raw(i64::MAX)).to_num(),
+            Some(N::from_raw(i64::MAX))
+        );
+        assert_eq!(
+            W::from(N::from_raw(i64::MAX)) + W::ONE,
+            W::from_raw(i64::MAX as i128 + 1_000_000)
+        );
+        assert_eq!(
+            (W::from(N::from_raw(i64::MAX)) + W::ONE).to_num::<8>(),
+            None
+        );
+        assert_eq!(
+            (W::from(N::from_raw(i64::MIN)) - W::ONE).saturating_to_num::<8>(),
+            N::from_raw(i64::MIN)
+        );
+    }
+
+    #[test]
+    fn test_rounding() {
+        // Rounded half away from zero like Num
+        assert_eq!(W::ONE / W::from_int(3), W::from_raw(333_333));
+        assert_eq!(W::from_int(-2) / W::from_int(3), W::from_raw(-666_667));
+        assert_eq!(W::from_raw(5) * W::from_raw(500_000), W::from_raw(3));
+        assert_eq!(W::from_raw(-5) * W::from_raw(500_000), W::from_raw(-3));
+        assert_eq!(
+            N::from_raw(5).widening_mul(N::from_raw(-500_000)),
+            W::from_raw(-3)
+        );
+    }
+
+    #[test]
+    fn test_variance() {
+        // Variance of values whose squares overflow i64, 10⁶ apart around 3·10⁹
+        let xs = [
+            N::from_int(2_999_000_000),
+            N::from_int(3_000_000_000),
+            N::from_int(3_001_000_000),
+        ];
+        let n = W::from_int(3);
+        let mean = xs.iter().copied().sum::<W>() / n;
+        let squares: W = xs.iter().map(|&x| x.widening_mul(x)).sum();
+        let squared_mean = mean * mean;
+        let variance = squares / n - squared_mean;
+        assert_eq!(mean.to_num(), Some(N::from_int(3_000_000_000)));
+        assert_eq!(
+            variance.to_num::<8>().map(N::sqrt),
+            Some(N::from_raw(816_496_580_928))
+        );
+    }
+
+    #[test]
+    fn test_chained_products() {
+        // Past i64 and back
+        let mut product = W::ONE;
+        for x in [1_000_000, 1_000_000, 1_000_000] {
+            product *= W::from_int(x);
+        }
+        product /= W::from_int(1_000_000_000_000);
+        assert_eq!(product.to_num(), Some(N::from_int(1_000_000)));
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(W::MAX.checked_add(W::ONE), None);
+        assert_eq!(W::MAX.saturating_add(W::ONE), W::MAX);
+        assert_eq!(W::MIN.checked_sub(W::ONE), None);
+        assert_eq!(W::MAX.checked_mul(W::from_int(2)), None);
+        assert_eq!(W::ONE.checked_div(W::ZERO), None);
+        assert_eq!(
+            W::from_int(7).checked_div(W::from_int(2)),
+            Some(W::from_raw(3_500_000))
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(W::from_raw(-50_000).to_string(), "-0.05");
+        assert_eq!(W::from_raw(3_141_593).to_string(), "3.141593");
+        assert_eq!(W::from_int(-12).to_string(), "-12");
+        assert_eq!(
+            W::from_raw(i128::from(i64::MAX) * 10).to_string(),
+            "92233720368547.75807"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! `num-traits` impls for [`Num`], built with the `num-traits` feature
+//!
+//! Lets generic math crates take a [`Num`] where they ask for `T: num_traits::Num` or
+//! `Signed`. The operators wrap like [`Num`]'s own, conversions from other types give
+//! `None` instead of saturating.
+
+use ::num_traits::{Bounded, FromPrimitive, One, Signed, ToPrimitive, Zero};
+
+use crate::{Num, ParseNumError};
+
+impl<const F: u8, const TF: u8> Zero for Num<F, TF> {
+    #[inline]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const F: u8, const TF: u8> One for Num<F, TF> {
+    #[inline]
+    fn one() -> Self {
+        Self::ONE
+    }
+}
+
+impl<const F: u8, const TF: u8> ::num_traits::Num for Num<F, TF> {
+    type FromStrRadixErr = ParseNumError;
+
+    /// Decimal text like [`Num::parse_bytes`], other radixes are rejected as
+    /// [`ParseNumError::InvalidDigit`]
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, ParseNumError> {
+        if radix != 10 {
+            return Err(ParseNumError::InvalidDigit);
+        }
+        Self::parse_bytes(str.as_bytes())
+    }
+}
+
+impl<const F: u8, const TF: u8> Signed for Num<F, TF> {
+    #[inline]
+    fn abs(&self) -> Self {
+        Self::abs(*self)
+    }
+
+    /// `self - other` if positive, else 0
+    #[inline]
+    fn abs_sub(&self, other: &Self) -> Self {
+        if *self <= *other {
+            Self::ZERO
+        } else {
+            *self - *other
+        }
+    }
+
+    #[inline]
+    fn signum(&self) -> Self {
+        Self::signum(*self)
+    }
+
+    #[inline]
+    fn is_positive(&self) -> bool {
+        Self::is_positive(*self)
+    }
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        Self::is_negative(*self)
+    }
+}
+
+impl<const F: u8, const TF: u8> Bounded for Num<F, TF> {
+    #[inline]
+    fn min_value() -> Self {
+        Self(i64::MIN)
+    }
+
+    #[inline]
+    fn max_value() -> Self {
+        Self(i64::MAX)
+    }
+}
+
+impl<const F: u8, const TF: u8> FromPrimitive for Num<F, TF> {
+    #[inline]
+    fn from_i64(n: i64) -> Option<Self> {
+        Self::checked_from_int(n)
+    }
+
+    #[inline]
+    fn from_u64(n: u64) -> Option<Self> {
+        Self::try_from(n).ok()
+    }
+
+    /// Rounded like [`Num::from_f64`], `None` for NaN and values out of range
+    fn from_f64(n: f64) -> Option<Self> {
+        let scaled = n * Self::SCALE as f64;
+        // `i64::MAX as f64` rounds up to 2⁶³, which doesn't fit
+        if n.is_nan() || scaled >= i64::MAX as f64 || scaled < i64::MIN as f64 {
+            return None;
+        }
+        Some(Self::from_f64(n))
+    }
+}
+
+impl<const F: u8, const TF: u8> ToPrimitive for Num<F, TF> {
+    /// Whole part, the fraction is dropped like `f64 as i64` does
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        Some(self.0 / Self::SCALE)
+    }
+
+    /// Whole part, `None` from -1 down
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        u64::try_from(self.0 / Self::SCALE).ok()
+    }
+
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        Some(Self::to_f64(*self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Num, ParseNumError};
+
+    type TestNum = Num<6, 8>;
+
+    #[test]
+    fn test_traits() {
+        use ::num_traits::{
+            Bounded as _, FromPrimitive as _, Num as _, One as _, Signed, ToPrimitive as _,
+            Zero as _,
+        };
+
+        // Generic code sees the same values and operators
+        fn sum_abs<T: Signed + Copy>(xs: &[T]) -> T {
+            xs.iter().fold(T::zero(), |acc, x| acc + x.abs())
+        }
+        let n = TestNum::from_f64;
+        assert_eq!(sum_abs(&[n(1.5), n(-2.25), n(0.25)]), n(4.0));
+
+        assert!(TestNum::zero().is_zero());
+        assert_eq!(TestNum::one(), TestNum::ONE);
+        assert_eq!(TestNum::min_value().raw(), i64::MIN);
+        assert_eq!(TestNum::max_value().raw(), i64::MAX);
+        assert_eq!(Signed::abs_sub(&n(1.0), &n(3.0)), TestNum::ZERO);
+        assert_eq!(Signed::abs_sub(&n(3.0), &n(1.0)), n(2.0));
+        assert_eq!(TestNum::from_str_radix("-1.5", 10), Ok(n(-1.5)));
+        assert_eq!(
+            TestNum::from_str_radix("ff", 16),
+            Err(ParseNumError::InvalidDigit)
+        );
+
+        assert_eq!(TestNum::from_i64(-7), Some(TestNum::from_int(-7)));
+        assert_eq!(TestNum::from_i64(i64::MAX), None);
+        assert_eq!(TestNum::from_u64(u64::MAX), None);
+        assert_eq!(TestNum::from_f64(2.5), n(2.5));
+        assert_eq!(::num_traits::FromPrimitive::from_f64(2.5), Some(n(2.5)));
+        for out in [f64::NAN, f64::INFINITY, 1e13, -1e13] {
+            assert_eq!(
+                <TestNum as ::num_traits::FromPrimitive>::from_f64(out),
+                None
+            );
+        }
+
+        assert_eq!(n(-2.75).to_i64(), Some(-2));
+        assert_eq!(n(-0.5).to_u64(), Some(0));
+        assert_eq!(n(-1.0).to_u64(), None);
+        assert_eq!(n(3.5).to_u8(), Some(3));
+        assert_eq!(::num_traits::ToPrimitive::to_f64(&n(0.125)), Some(0.125));
+    }
+}
@@ -0,0 +1,178 @@
+//! Overflow policy picked by type, so operators need no `checked_*` calls or branching
+//!
+//! [`Num`]'s own operators wrap around, the cheapest on small targets and fine for control
+//! loops that stay in range. [`Saturating`] clamps to the representable range instead and
+//! [`Checked`] carries `None` from the first overflow to the end of a calculation.
+//!
+//! ```
+//! use cos_num::{
+//!     Num,
+//!     overflow::{Checked, Saturating},
+//! };
+//!
+//! type N = Num<2, 8>;
+//! let max = N::from_raw(i64::MAX);
+//!
+//! assert!((max + N::ONE).is_negative());
+//! assert_eq!((Saturating(max) + Saturating(N::ONE)).0, max);
+//! assert_eq!((Checked::from(max) + N::ONE.into() - max.into()).0, None);
+//! assert_eq!((Checked::from(N::ONE) / N::from_int(4).into()).0, Some(N::from_raw(25)));
+//! ```
+
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::Num;
+
+/// [`Num`] whose operators clamp to the representable range instead of wrapping
+///
+/// Division by zero is [`Num::saturating_div`]'s: `Saturating(x) / Saturating(ZERO)` panics,
+/// or is zero with the `panic-free` feature.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Saturating<const F: u8, const TF: u8>(pub Num<F, TF>);
+
+/// [`Num`] whose operators give `None` on overflow and division by zero, and keep it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checked<const F: u8, const TF: u8>(pub Option<Num<F, TF>>);
+
+impl<const F: u8, const TF: u8> From<Num<F, TF>> for Saturating<F, TF> {
+    #[inline]
+    fn from(v: Num<F, TF>) -> Self {
+        Self(v)
+    }
+}
+
+impl<const F: u8, const TF: u8> From<Num<F, TF>> for Checked<F, TF> {
+    #[inline]
+    fn from(v: Num<F, TF>) -> Self {
+        Self(Some(v))
+    }
+}
+
+impl<const F: u8, const TF: u8> Checked<F, TF> {
+    /// `op` of both values, `None` if either is
+    #[inline]
+    fn zip(self, rhs: Self, op: fn(Num<F, TF>, Num<F, TF>) -> Option<Num<F, TF>>) -> Self {
+        Self(match (self.0, rhs.0) {
+            (Some(a), Some(b)) => op(a, b),
+            _ => None,
+        })
+    }
+}
+
+/// Operator impls calling a `Num` method for each policy, and the assigning variants
+macro_rules! policy_ops {
+    ($($op:ident $fn:ident $assign:ident $assign_fn:ident: $saturating:ident $checked:ident;)+) => {$(
+        impl<const F: u8, const TF: u8> $op for Saturating<F, TF> {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: Self) -> Self {
+                Self(self.0.$saturating(rhs.0))
+            }
+        }
+
+        impl<const F: u8, const TF: u8> $op for Checked<F, TF> {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: Self) -> Self {
+                self.zip(rhs, Num::$checked)
+            }
+        }
+
+        impl<const F: u8, const TF: u8> $assign for Saturating<F, TF> {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = $op::$fn(*self, rhs);
+            }
+        }
+
+        impl<const F: u8, const TF: u8> $assign for Checked<F, TF> {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = $op::$fn(*self, rhs);
+            }
+        }
+    )+};
+}
+
+policy_ops! {
+    Add add AddAssign add_assign: saturating_add checked_add;
+    Sub sub SubAssign sub_assign: saturating_sub checked_sub;
+    Mul mul MulAssign mul_assign: saturating_mul checked_mul;
+    Div div DivAssign div_assign: saturating_div checked_div;
+}
+
+impl<const F: u8, const TF: u8> Neg for Saturating<F, TF> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(Num(self.0.0.saturating_neg()))
+    }
+}
+
+impl<const F: u8, const TF: u8> Neg for Checked<F, TF> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(self.0.and_then(Num::checked_neg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Checked, Saturating};
+    use crate::Num;
+
+    type TestNum = Num<6, 8>;
+
+    #[test]
+    fn test_policies() {
+        let max = TestNum::from_raw(i64::MAX);
+        let min = TestNum::from_raw(i64::MIN);
+        let two = TestNum::from_int(2);
+
+        // `Num` wraps
+        assert_eq!(max + TestNum::from_raw(1), min);
+
+        let sat = Saturating;
+        assert_eq!(sat(max) + sat(TestNum::ONE), sat(max));
+        assert_eq!(sat(min) - sat(TestNum::ONE), sat(min));
+        assert_eq!(sat(max) * sat(-two), sat(min));
+        assert_eq!(sat(max) / sat(TestNum::from_f64(0.5)), sat(max));
+        assert_eq!(-sat(min), sat(max));
+        assert_eq!(sat(two) * sat(two), sat(TestNum::from_int(4)));
+        let mut acc = sat(max);
+        acc += sat(two);
+        acc -= sat(TestNum::ONE);
+        assert_eq!(acc, sat(max - TestNum::ONE));
+
+        let some = |v| Checked(Some(v));
+        assert_eq!(some(two) + some(two), some(TestNum::from_int(4)));
+        assert_eq!(some(max) + some(TestNum::ONE), Checked(None));
+        assert_eq!(some(TestNum::ONE) / some(TestNum::ZERO), Checked(None));
+        assert_eq!(-some(min), Checked(None));
+        // Stays `None` once overflowed
+        let mut acc = some(max);
+        acc *= some(two);
+        acc /= some(two);
+        assert_eq!(acc, Checked(None));
+        assert_eq!(Checked::from(two) - two.into(), some(TestNum::ZERO));
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "division by zero")]
+    fn test_saturating_div_by_zero() {
+        let _result = Saturating(TestNum::ONE) / Saturating(TestNum::ZERO);
+    }
+
+    #[test]
+    #[cfg(feature = "panic-free")]
+    fn test_saturating_div_by_zero() {
+        let zero = Saturating(TestNum::ZERO);
+        assert_eq!(Saturating(TestNum::ONE) / zero, zero);
+    }
+}
@@ -0,0 +1,420 @@
+//! Exact fractions of two `i64`s, for sums like 1/3 + 1/6 that no decimal holds
+//!
+//! A [`Ratio`] is kept in lowest terms with a positive denominator, so equal values are
+//! equal [`Ratio`]s and nothing is rounded until it is turned back into a [`Num`]:
+//!
+//! ```
+//! use cos_num::{Num, ratio::Ratio};
+//!
+//! type N = Num<4, 8>;
+//!
+//! let third = Ratio::new(1, 3).unwrap();
+//! let sixth = Ratio::new(1, 6).unwrap();
+//! assert_eq!(third + sixth, Ratio::new(1, 2).unwrap());
+//! assert_eq!((third + third + third).to_num::<4, 8>(), Some(N::ONE));
+//!
+//! // Every `Num` is exactly a fraction with a power of ten below it
+//! assert_eq!(Ratio::from(N::from_f64(0.125)).to_string(), "1/8");
+//! ```
+//!
+//! Numerators and denominators are reduced against each other and multiplied in `i128`,
+//! so a result only fails when its lowest terms don't fit an `i64`; the
+//! `checked_*` functions return `None` then and the operators panic.
+
+use core::cmp::Ordering;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{Num, gcd_u64};
+
+/// `num / den` in lowest terms, `den` positive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Ratio {
+    num: i64,
+    den: i64,
+}
+
+impl Ratio {
+    pub const ZERO: Self = Self::from_int(0);
+    pub const ONE: Self = Self::from_int(1);
+
+    /// `num / den` reduced, or `None` if `den` is zero or the reduced fraction doesn't fit,
+    /// only for `i64::MIN` over a negative denominator
+    #[must_use]
+    pub const fn new(num: i64, den: i64) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+
+        let g = gcd_u64(num.unsigned_abs(), den.unsigned_abs()) as i128;
+        Self::narrow(num as i128 / g, den as i128 / g)
+    }
+
+    /// Whole number `n / 1`
+    #[inline]
+    #[must_use]
+    pub const fn from_int(n: i64) -> Self {
+        Self { num: n, den: 1 }
+    }
+
+    /// Exact value of `x`, its raw value over [`Num::SCALE`] reduced
+    #[must_use]
+    pub const fn from_num<const F: u8, const TF: u8>(x: Num<F, TF>) -> Self {
+        match Self::new(x.0, Num::<F, TF>::SCALE) {
+            Some(ratio) => ratio,
+            // Reducing over a positive denominator never grows the numerator
+            None => Self::ZERO,
+        }
+    }
+
+    /// Nearest [`Num`], rounded half away from zero like `Div`, or `None` if it doesn't fit
+    #[must_use]
+    pub const fn to_num<const F: u8, const TF: u8>(self) -> Option<Num<F, TF>> {
+        Num::narrow(Num::<F, TF>::mul_ratio(
+            self.num as i128,
+            Num::<F, TF>::SCALE as i128,
+            self.den as i128,
+        ))
+    }
+
+    /// Numerator, carrying the sign
+    #[inline]
+    #[must_use]
+    pub const fn numer(self) -> i64 {
+        self.num
+    }
+
+    /// Denominator, always positive
+    #[inline]
+    #[must_use]
+    pub const fn denom(self) -> i64 {
+        self.den
+    }
+
+    /// Whether the denominator is 1
+    #[inline]
+    #[must_use]
+    pub const fn is_integer(self) -> bool {
+        self.den == 1
+    }
+
+    /// `self + rhs`, or `None` if it doesn't fit
+    #[inline]
+    #[must_use]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        Self::sum(self.num as i128, self.den, rhs.num as i128, rhs.den)
+    }
+
+    /// `self - rhs`, or `None` if it doesn't fit
+    #[inline]
+    #[must_use]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Self::sum(self.num as i128, self.den, -(rhs.num as i128), rhs.den)
+    }
+
+    /// `-self`, or `None` for `i64::MIN` as numerator
+    #[must_use]
+    pub const fn checked_neg(self) -> Option<Self> {
+        match self.num.checked_neg() {
+            Some(num) => Some(Self { num, den: self.den }),
+            None => None,
+        }
+    }
+
+    /// `self * rhs`, or `None` if it doesn't fit
+    #[inline]
+    #[must_use]
+    pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Self::product(self.num, self.den, rhs.num, rhs.den)
+    }
+
+    /// `self / rhs`, or `None` if `rhs` is zero or it doesn't fit
+    #[must_use]
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.num == 0 {
+            return None;
+        }
+
+        // Times the reciprocal, whose sign `narrow` moves up
+        Self::product(self.num, self.den, rhs.den, rhs.num)
+    }
+
+    /// `1 / self`, or `None` if `self` is zero or `i64::MIN` as numerator
+    #[must_use]
+    pub const fn checked_recip(self) -> Option<Self> {
+        Self::ONE.checked_div(self)
+    }
+
+    /// `a/b + c/d` for fractions in lowest terms, `b` and `d` positive
+    ///
+    /// Only factors of `gcd(b, d)` can cancel, so every gcd fits an `u64` (Knuth, TAOCP
+    /// 4.5.1). The products stay below 2¹²⁶, their sum can't overflow.
+    const fn sum(a: i128, b: i64, c: i128, d: i64) -> Option<Self> {
+        let g = gcd_u64(b as u64, d as u64) as i128;
+        let (b, d) = (b as i128, d as i128);
+        let t = a * (d / g) + c * (b / g);
+        if t == 0 {
+            return Some(Self::ZERO);
+        }
+
+        let g2 = gcd_u64((t % g).unsigned_abs() as u64, g as u64) as i128;
+        Self::narrow(t / g2, (b / g) * (d / g2))
+    }
+
+    /// `a/b * c/d` for fractions in lowest terms, `b` positive and `d` not zero
+    ///
+    /// Numerators are reduced against the other denominator before multiplying, which
+    /// leaves the product in lowest terms.
+    const fn product(a: i64, b: i64, c: i64, d: i64) -> Option<Self> {
+        if a == 0 || c == 0 {
+            return Some(Self::ZERO);
+        }
+
+        let g1 = gcd_u64(a.unsigned_abs(), d.unsigned_abs()) as i128;
+        let g2 = gcd_u64(c.unsigned_abs(), b.unsigned_abs()) as i128;
+        Self::narrow(
+            (a as i128 / g1) * (c as i128 / g2),
+            (b as i128 / g2) * (d as i128 / g1),
+        )
+    }
+
+    /// Fraction in lowest terms with the sign moved to `num`, `None` if it doesn't fit
+    const fn narrow(mut num: i128, mut den: i128) -> Option<Self> {
+        if den < 0 {
+            num = -num;
+            den = -den;
+        }
+
+        if num > i64::MAX as i128 || num < i64::MIN as i128 || den > i64::MAX as i128 {
+            None
+        } else {
+            Some(Self {
+                num: num as i64,
+                den: den as i64,
+            })
+        }
+    }
+
+    /// Result of an operator, panics where it doesn't fit
+    const fn expect(result: Option<Self>) -> Self {
+        require!(result.is_some(), "ratio overflow", Self::ZERO);
+
+        match result {
+            Some(result) => result,
+            None => Self::ZERO,
+        }
+    }
+}
+
+impl Default for Ratio {
+    /// Zero, `0/1`
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Denominators are positive, cross multiplying keeps the order
+        (self.num as i128 * other.den as i128).cmp(&(other.num as i128 * self.den as i128))
+    }
+}
+
+impl PartialOrd for Ratio {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Ratio {
+    type Output = Self;
+
+    /// # Panics
+    /// Will panic if the sum doesn't fit
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::expect(self.checked_add(rhs))
+    }
+}
+
+impl Sub for Ratio {
+    type Output = Self;
+
+    /// # Panics
+    /// Will panic if the difference doesn't fit
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::expect(self.checked_sub(rhs))
+    }
+}
+
+impl Neg for Ratio {
+    type Output = Self;
+
+    /// # Panics
+    /// Will panic for `i64::MIN` as numerator
+    #[inline]
+    fn neg(self) -> Self {
+        Self::expect(self.checked_neg())
+    }
+}
+
+impl Mul for Ratio {
+    type Output = Self;
+
+    /// # Panics
+    /// Will panic if the product doesn't fit
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self::expect(self.checked_mul(rhs))
+    }
+}
+
+impl Div for Ratio {
+    type Output = Self;
+
+    /// # Panics
+    /// Will panic if `rhs` is zero or the quotient doesn't fit
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        require!(rhs.num != 0, "division by zero", Self::ZERO);
+
+        Self::expect(self.checked_div(rhs))
+    }
+}
+
+macro_rules! assign {
+    ($assign:ident $assign_fn:ident $fn:ident) => {
+        impl $assign for Ratio {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = (*self).$fn(rhs);
+            }
+        }
+    };
+}
+
+assign!(AddAssign add_assign add);
+assign!(SubAssign sub_assign sub);
+assign!(MulAssign mul_assign mul);
+assign!(DivAssign div_assign div);
+
+impl From<i64> for Ratio {
+    #[inline]
+    fn from(n: i64) -> Self {
+        Self::from_int(n)
+    }
+}
+
+impl<const F: u8, const TF: u8> From<Num<F, TF>> for Ratio {
+    /// Exact, see [`Ratio::from_num`]
+    #[inline]
+    fn from(x: Num<F, TF>) -> Self {
+        Self::from_num(x)
+    }
+}
+
+impl core::fmt::Display for Ratio {
+    /// Like `-1/3`, whole numbers without the denominator
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for Ratio {
+    /// Like `-1/3`, whole numbers without the denominator
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        if self.den == 1 {
+            ufmt::uwrite!(f, "{}", self.num)
+        } else {
+            ufmt::uwrite!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ratio;
+    use crate::Num;
+
+    type N = Num<4, 8>;
+
+    fn r(num: i64, den: i64) -> Ratio {
+        Ratio::new(num, den).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(r(2, -4), r(-1, 2));
+        assert_eq!(r(2, -4).denom(), 2);
+        assert_eq!(r(6, 3), Ratio::from_int(2));
+        assert!(r(6, 3).is_integer());
+        assert_eq!(r(0, -7), Ratio::ZERO);
+        assert_eq!(Ratio::new(1, 0), None);
+        assert_eq!(Ratio::new(i64::MIN, -1), None);
+        assert_eq!(Ratio::default(), Ratio::ZERO);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(r(1, 3) + r(1, 6), r(1, 2));
+        assert_eq!(r(1, 3) - r(1, 2), r(-1, 6));
+        assert_eq!(r(1, 6) - r(1, 6), Ratio::ZERO);
+        assert_eq!(r(3, 4) * r(2, 9), r(1, 6));
+        assert_eq!(r(3, 4) / r(-9, 2), r(-1, 6));
+        assert_eq!(Ratio::ZERO / r(-9, 2), Ratio::ZERO);
+        assert_eq!(-r(1, 3), r(-1, 3));
+        assert_eq!(r(-1, 2).checked_recip(), Some(Ratio::from_int(-2)));
+        assert!(r(1, 3) < r(1, 2) && r(-1, 2) < r(-1, 3));
+
+        // Adding thirds never drifts, unlike 0.3333 + 0.3333 + 0.3333
+        let mut sum = Ratio::ZERO;
+        for _ in 0..3 {
+            sum += r(1, 3);
+        }
+        assert_eq!(sum, Ratio::ONE);
+    }
+
+    #[test]
+    fn test_num_conversions() {
+        assert_eq!(r(1, 3).to_num(), Some(N::from_f64(0.3333)));
+        assert_eq!(r(2, 3).to_num(), Some(N::from_f64(0.6667)));
+        assert_eq!(r(-2, 3).to_num(), Some(N::from_f64(-0.6667)));
+        assert_eq!(Ratio::from(N::from_f64(-2.35)), r(-47, 20));
+        assert_eq!(
+            Ratio::from(N::from_raw(i64::MIN)).to_num(),
+            Some(N::from_raw(i64::MIN))
+        );
+        assert_eq!(Ratio::from_int(i64::MAX).to_num::<4, 8>(), None);
+    }
+
+    #[test]
+    fn test_limits() {
+        // Reduced before narrowing, only lowest terms that don't fit fail
+        let big = r(i64::MAX, 2);
+        assert_eq!(big * r(2, i64::MAX), Ratio::ONE);
+        assert_eq!(big + big, Ratio::from_int(i64::MAX));
+        assert_eq!(r(1, i64::MAX - 1) + r(1, i64::MAX - 1), r(1, i64::MAX / 2));
+        assert_eq!(big.checked_add(r(1, 3)), None);
+        assert_eq!(big.checked_mul(big), None);
+        assert_eq!(Ratio::from_int(i64::MIN).checked_neg(), None);
+        assert_eq!(Ratio::from_int(i64::MIN).checked_recip(), None);
+        assert_eq!(Ratio::ONE.checked_div(Ratio::ZERO), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(r(-1, 3).to_string(), "-1/3");
+        assert_eq!(r(4, 2).to_string(), "2");
+    }
+}
@@ -0,0 +1,178 @@
+//! `Serialize` and `Deserialize` for [`Num`], built with the `serde` feature
+//!
+//! Human-readable formats such as JSON get the decimal text of [`Num::write_decimal`],
+//! `"-123.45"`, readable in a log and independent of `F`. Compact formats such as postcard
+//! or bincode get the raw `i64`. Both round-trip every value exactly. A field can pick one
+//! whatever the format with [`raw`] or [`decimal`]:
+//!
+//! ```
+//! use cos_num::Num;
+//! use serde::{Deserialize, Serialize};
+//!
+//! type N = Num<2, 8>;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Step {
+//!     shown: N,
+//!     #[serde(with = "cos_num::serde::raw")]
+//!     raw: N,
+//! }
+//! ```
+//!
+//! Decimal text with more than `F` fraction digits is rounded like [`Num::parse_bytes`].
+
+use core::fmt;
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::Num;
+
+impl<const F: u8, const TF: u8> Serialize for Num<F, TF> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            decimal::serialize(self, serializer)
+        } else {
+            raw::serialize(self, serializer)
+        }
+    }
+}
+
+impl<'de, const F: u8, const TF: u8> Deserialize<'de> for Num<F, TF> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            decimal::deserialize(deserializer)
+        } else {
+            raw::deserialize(deserializer)
+        }
+    }
+}
+
+/// The raw `i64`, for `#[serde(with = "cos_num::serde::raw")]`
+pub mod raw {
+    use ::serde::{Deserialize as _, Deserializer, Serializer};
+
+    use crate::Num;
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the serializer fails.
+    pub fn serialize<const F: u8, const TF: u8, S: Serializer>(
+        v: &Num<F, TF>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(v.0)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the input isn't an integer that fits an `i64`.
+    pub fn deserialize<'de, const F: u8, const TF: u8, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Num<F, TF>, D::Error> {
+        i64::deserialize(deserializer).map(Num)
+    }
+}
+
+/// Decimal text like `"-123.45"`, for `#[serde(with = "cos_num::serde::decimal")]`
+pub mod decimal {
+    use ::serde::{Deserializer, Serializer, ser};
+
+    use super::DecimalVisitor;
+    use crate::Num;
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the serializer fails.
+    pub fn serialize<const F: u8, const TF: u8, S: Serializer>(
+        v: &Num<F, TF>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut text = [0; 21];
+        let len = v
+            .write_decimal(&mut text)
+            .ok_or_else(|| ser::Error::custom("decimal text longer than 21 bytes"))?;
+        serializer.serialize_str(core::str::from_utf8(&text[..len]).map_err(ser::Error::custom)?)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the input isn't a string [`Num::parse_bytes`] accepts.
+    pub fn deserialize<'de, const F: u8, const TF: u8, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Num<F, TF>, D::Error> {
+        deserializer.deserialize_str(DecimalVisitor)
+    }
+}
+
+/// Parses a string with [`Num::parse_bytes`]
+struct DecimalVisitor<const F: u8, const TF: u8>;
+
+impl<const F: u8, const TF: u8> de::Visitor<'_> for DecimalVisitor<F, TF> {
+    type Value = Num<F, TF>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a decimal number as a string, like \"-123.45\"")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Num::parse_bytes(v.as_bytes()).map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Num;
+
+    type TestNum = Num<6, 8>;
+
+    #[test]
+    fn test_tokens() {
+        use ::serde::{Deserialize, Serialize};
+        use serde_test::{Configure as _, Token, assert_de_tokens_error, assert_tokens};
+
+        let x = TestNum::from_f64(-123.45);
+        assert_tokens(&x.readable(), &[Token::Str("-123.45")]);
+        assert_tokens(&x.compact(), &[Token::I64(-123_450_000)]);
+        for raw in [i64::MIN, -1, 0, 1, i64::MAX] {
+            let x = TestNum::from_raw(raw);
+            assert_tokens(&x.compact(), &[Token::I64(raw)]);
+            assert_tokens(&x.readable(), &[Token::Str(x.to_string().leak())]);
+        }
+
+        assert_de_tokens_error::<serde_test::Readable<TestNum>>(
+            &[Token::Str("1e3")],
+            "invalid digit found in the number",
+        );
+        assert_de_tokens_error::<serde_test::Readable<TestNum>>(
+            &[Token::I64(5)],
+            "invalid type: integer `5`, expected a decimal number as a string, like \"-123.45\"",
+        );
+
+        // Forced representations, whatever the format
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Step {
+            #[serde(with = "crate::serde::raw")]
+            raw: TestNum,
+            #[serde(with = "crate::serde::decimal")]
+            text: TestNum,
+        }
+        let step = Step {
+            raw: TestNum::ONE,
+            text: TestNum::ONE,
+        };
+        assert_tokens(
+            &step,
+            &[
+                Token::Struct {
+                    name: "Step",
+                    len: 2,
+                },
+                Token::Str("raw"),
+                Token::I64(1_000_000),
+                Token::Str("text"),
+                Token::Str("1"),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! Validation of every operation against `f64`, enabled by the `shadow` feature
+//!
+//! Results stay exactly what cos-num computes, the `f64` result is only compared
+//! with them. `const fn`s such as [`Num::sqrt`] are not shadowed, they can't call
+//! into the reporter.
+//!
+//! ```
+//! use cos_num::{
+//!     Num,
+//!     shadow::{self, Validator},
+//! };
+//!
+//! shadow::install(Some(Validator {
+//!     tolerance: 1,
+//!     report: |d| println!("{}{:?} = {}, f64 gives {}", d.op, d.args, d.got, d.expected),
+//! }));
+//!
+//! // Wraps around and gets reported
+//! let _ = Num::<2, 8>::from_raw(i64::MAX) + Num::ONE;
+//! ```
+
+use std::cell::Cell;
+
+use crate::Num;
+
+/// Result that differs from the `f64` reference by more than the tolerance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Divergence<'a> {
+    /// Operation, e.g. `"mul"` or `"sin"`
+    pub op: &'static str,
+    pub args: &'a [f64],
+    /// `f64` result
+    pub expected: f64,
+    /// What cos-num returned
+    pub got: f64,
+}
+
+/// Where divergences go and how large they may get unreported
+#[derive(Debug, Clone, Copy)]
+pub struct Validator {
+    /// Allowed difference in units of the last digit of the result
+    ///
+    /// `f64` rounding of large values is allowed on top of it.
+    pub tolerance: i64,
+    pub report: fn(&Divergence<'_>),
+}
+
+thread_local! {
+    // Per thread, so tests and simulated devices don't see each other's divergences
+    static VALIDATOR: Cell<Option<Validator>> = const { Cell::new(None) };
+}
+
+/// Start validating operations on this thread, or stop with `None`
+pub fn install(validator: Option<Validator>) {
+    VALIDATOR.set(validator);
+}
+
+/// Compare `got` with `reference` computed over the `args`
+pub(crate) fn check<const F: u8, const TF: u8, const N: usize>(
+    op: &'static str,
+    args: [Num<F, TF>; N],
+    got: Num<F, TF>,
+    reference: impl FnOnce([f64; N]) -> f64,
+) {
+    let Some(validator) = VALIDATOR.get() else {
+        return;
+    };
+
+    let scale = Num::<F, TF>::SCALE as f64;
+    let args = args.map(Num::to_f64);
+    let expected = reference(args);
+    let got = got.to_f64();
+
+    let allowed = (expected * scale)
+        .abs()
+        .mul_add(f64::EPSILON, validator.tolerance as f64)
+        / scale;
+    // NaN and infinities can't be represented, so they always diverge
+    if expected.is_finite() && (got - expected).abs() <= allowed {
+        return;
+    }
+
+    (validator.report)(&Divergence {
+        op,
+        args: &args,
+        expected,
+        got,
+    });
+}
@@ -0,0 +1,454 @@
+//! Narrower siblings of [`Num`] for targets where every byte of RAM and flash counts
+//!
+//! [`Num32`] and [`Num16`] store the same decimal fixed-point value in an `i32` or `i16`,
+//! half or a quarter of the memory, and multiply and divide in the next wider integer
+//! instead of `i64`, which on 8-bit targets is most of the cost of the arithmetic. They
+//! have the basic operations only, the rest of the math is a [`Num::from`] away:
+//!
+//! ```
+//! use cos_num::{Num, small::Num16};
+//!
+//! type N = Num16<2>;
+//!
+//! let price = N::from_int(12) + N::from_raw(99); // 12.99
+//! let total = price * N::from_int(3);
+//! assert_eq!(total.to_string(), "38.97");
+//! assert_eq!(N::from_int(400), N::MAX); // 327.67 is the largest
+//!
+//! let root = Num::<2, 8>::from(total).sqrt();
+//! assert_eq!(N::from_num(root), Some(N::from_raw(624)));
+//! ```
+//!
+//! `F` can be up to 9 for [`Num32`] and 4 for [`Num16`], larger ones fail to compile.
+//! Operators wrap like [`Num`]'s, but are exact whenever the result fits.
+
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+use crate::{Digits, Num};
+
+/// Type, constants, methods and operator impls of a narrow `Num` stored in `$int`, with
+/// products and quotients computed in `$wide`
+macro_rules! small_num {
+    ($(#[$attr:meta])* $name:ident($int:ty), $wide:ty, $max_f:literal) => {
+        $(#[$attr])*
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+        #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+        #[repr(transparent)]
+        pub struct $name<const F: u8>(pub $int);
+
+        impl<const F: u8> $name<F> {
+            /// `10^F`, the raw value of 1
+            pub const SCALE: $int = {
+                assert!(F <= $max_f, concat!("F is too large for ", stringify!($int)));
+                let mut s: $int = 1;
+                let mut i = 0;
+                while i < F {
+                    s *= 10;
+                    i += 1;
+                }
+                s
+            };
+
+            pub const ZERO: Self = Self(0);
+            pub const ONE: Self = Self(Self::SCALE);
+            /// Smallest value
+            pub const MIN: Self = Self(<$int>::MIN);
+            /// Largest value
+            pub const MAX: Self = Self(<$int>::MAX);
+
+            /// Create from raw inner representation (no scaling)
+            #[inline]
+            #[must_use]
+            pub const fn from_raw(raw: $int) -> Self {
+                Self(raw)
+            }
+
+            /// Get raw inner
+            #[inline]
+            #[must_use]
+            pub const fn raw(self) -> $int {
+                self.0
+            }
+
+            /// Create from integer, clamped to [`Self::MIN`] and [`Self::MAX`]
+            #[inline]
+            #[must_use]
+            pub const fn from_int(n: $int) -> Self {
+                Self(n.saturating_mul(Self::SCALE))
+            }
+
+            /// Create from integer, `None` if it doesn't fit instead of saturating
+            #[inline]
+            #[must_use]
+            pub const fn checked_from_int(n: $int) -> Option<Self> {
+                match n.checked_mul(Self::SCALE) {
+                    Some(raw) => Some(Self(raw)),
+                    None => None,
+                }
+            }
+
+            /// Same value as a [`Num`] with the same `F`, for the math this type lacks
+            #[inline]
+            #[must_use]
+            pub const fn to_num<const TF: u8>(self) -> Num<F, TF> {
+                Num(self.0 as i64)
+            }
+
+            /// Value of a [`Num`] with the same `F`, `None` if it doesn't fit
+            #[inline]
+            #[must_use]
+            pub const fn from_num<const TF: u8>(n: Num<F, TF>) -> Option<Self> {
+                if n.0 < <$int>::MIN as i64 || n.0 > <$int>::MAX as i64 {
+                    return None;
+                }
+                Some(Self(n.0 as $int))
+            }
+
+            /// Digits like [`Num::digits`], to read out or print the value
+            #[inline]
+            #[must_use]
+            pub const fn digits(self) -> Digits {
+                self.to_num::<F>().digits()
+            }
+
+            #[inline]
+            #[must_use]
+            pub const fn abs(self) -> Self {
+                Self(self.0.wrapping_abs())
+            }
+
+            /// Whether self is greater than zero
+            #[inline]
+            #[must_use]
+            pub const fn is_positive(self) -> bool {
+                self.0 > 0
+            }
+
+            /// Whether self is less than zero
+            #[inline]
+            #[must_use]
+            pub const fn is_negative(self) -> bool {
+                self.0 < 0
+            }
+
+            /// `self + rhs`, or `None` on overflow instead of wrapping
+            #[inline]
+            #[must_use]
+            pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+                match self.0.checked_add(rhs.0) {
+                    Some(raw) => Some(Self(raw)),
+                    None => None,
+                }
+            }
+
+            /// `self - rhs`, or `None` on overflow instead of wrapping
+            #[inline]
+            #[must_use]
+            pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+                match self.0.checked_sub(rhs.0) {
+                    Some(raw) => Some(Self(raw)),
+                    None => None,
+                }
+            }
+
+            /// `-self`, or `None` for [`Self::MIN`]
+            #[inline]
+            #[must_use]
+            pub const fn checked_neg(self) -> Option<Self> {
+                match self.0.checked_neg() {
+                    Some(raw) => Some(Self(raw)),
+                    None => None,
+                }
+            }
+
+            /// `self * rhs`, or `None` if the product doesn't fit
+            #[inline]
+            #[must_use]
+            pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+                Self::narrow(self.mul_wide(rhs))
+            }
+
+            /// `self / rhs`, or `None` if `rhs` is zero or the quotient doesn't fit
+            #[inline]
+            #[must_use]
+            pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+                if rhs.0 == 0 {
+                    return None;
+                }
+                Self::narrow(self.div_wide(rhs))
+            }
+
+            /// `self % rhs`, or `None` if `rhs` is zero or the remainder overflows
+            #[inline]
+            #[must_use]
+            pub const fn checked_rem(self, rhs: Self) -> Option<Self> {
+                match self.0.checked_rem(rhs.0) {
+                    Some(raw) => Some(Self(raw)),
+                    None => None,
+                }
+            }
+
+            /// `self + rhs`, clamped to the representable range instead of wrapping
+            #[inline]
+            #[must_use]
+            pub const fn saturating_add(self, rhs: Self) -> Self {
+                Self(self.0.saturating_add(rhs.0))
+            }
+
+            /// `self - rhs`, clamped to the representable range instead of wrapping
+            #[inline]
+            #[must_use]
+            pub const fn saturating_sub(self, rhs: Self) -> Self {
+                Self(self.0.saturating_sub(rhs.0))
+            }
+
+            /// `self * rhs`, clamped to the representable range instead of wrapping
+            #[inline]
+            #[must_use]
+            pub const fn saturating_mul(self, rhs: Self) -> Self {
+                Self::saturate(self.mul_wide(rhs))
+            }
+
+            /// `self / rhs`, clamped to the representable range instead of wrapping
+            ///
+            /// # Panics
+            /// Will panic if `rhs` is zero
+            #[inline]
+            #[must_use]
+            pub const fn saturating_div(self, rhs: Self) -> Self {
+                require!(rhs.0 != 0, "division by zero", Self::ZERO);
+
+                Self::saturate(self.div_wide(rhs))
+            }
+
+            /// Raw product rounded half away from zero, before it is narrowed
+            const fn mul_wide(self, rhs: Self) -> $wide {
+                let r = self.0 as $wide * rhs.0 as $wide;
+                let half = (Self::SCALE / 2) as $wide;
+                if r >= 0 {
+                    (r + half) / Self::SCALE as $wide
+                } else {
+                    (r - half) / Self::SCALE as $wide
+                }
+            }
+
+            /// Raw quotient rounded half away from zero, before it is narrowed
+            ///
+            /// `10^F` times any `$int` fits in `$wide`, so this never overflows.
+            const fn div_wide(self, rhs: Self) -> $wide {
+                let r = self.0 as $wide * Self::SCALE as $wide;
+                let rhs = rhs.0 as $wide;
+                if r >= 0 {
+                    (r + rhs / 2) / rhs
+                } else {
+                    (r - rhs / 2) / rhs
+                }
+            }
+
+            const fn narrow(raw: $wide) -> Option<Self> {
+                if raw < <$int>::MIN as $wide || raw > <$int>::MAX as $wide {
+                    None
+                } else {
+                    Some(Self(raw as $int))
+                }
+            }
+
+            const fn saturate(raw: $wide) -> Self {
+                if raw < <$int>::MIN as $wide {
+                    Self::MIN
+                } else if raw > <$int>::MAX as $wide {
+                    Self::MAX
+                } else {
+                    Self(raw as $int)
+                }
+            }
+        }
+
+        impl<const F: u8> Add for $name<F> {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0.wrapping_add(rhs.0))
+            }
+        }
+
+        impl<const F: u8> Sub for $name<F> {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0.wrapping_sub(rhs.0))
+            }
+        }
+
+        impl<const F: u8> Neg for $name<F> {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                Self(self.0.wrapping_neg())
+            }
+        }
+
+        impl<const F: u8> Mul for $name<F> {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: Self) -> Self {
+                Self(self.mul_wide(rhs) as $int)
+            }
+        }
+
+        impl<const F: u8> Div for $name<F> {
+            type Output = Self;
+
+            #[inline]
+            fn div(self, rhs: Self) -> Self {
+                require!(rhs.0 != 0, "division by zero", Self::ZERO);
+
+                Self(self.div_wide(rhs) as $int)
+            }
+        }
+
+        impl<const F: u8> Rem for $name<F> {
+            type Output = Self;
+
+            #[inline]
+            fn rem(self, rhs: Self) -> Self {
+                require!(rhs.0 != 0, "division by zero", Self::ZERO);
+
+                Self(self.0.wrapping_rem(rhs.0))
+            }
+        }
+
+        small_num!(@assign $name AddAssign add_assign add);
+        small_num!(@assign $name SubAssign sub_assign sub);
+        small_num!(@assign $name MulAssign mul_assign mul);
+        small_num!(@assign $name DivAssign div_assign div);
+        small_num!(@assign $name RemAssign rem_assign rem);
+
+        impl<const F: u8, const TF: u8> From<$name<F>> for Num<F, TF> {
+            #[inline]
+            fn from(v: $name<F>) -> Self {
+                v.to_num()
+            }
+        }
+
+        impl<const F: u8> core::fmt::Display for $name<F> {
+            /// Scaled value like `3.14` or `-0.05`, the [`Num::digits`]
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Display::fmt(&self.to_num::<F>(), f)
+            }
+        }
+
+        #[cfg(feature = "ufmt")]
+        impl<const F: u8> ufmt::uDisplay for $name<F> {
+            /// Scaled value like `3.14` or `-0.05`, the [`Num::digits`]
+            fn fmt<W: ufmt::uWrite + ?Sized>(
+                &self,
+                f: &mut ufmt::Formatter<'_, W>,
+            ) -> Result<(), W::Error> {
+                ufmt::uDisplay::fmt(&self.to_num::<F>(), f)
+            }
+        }
+    };
+    (@assign $name:ident $assign:ident $assign_fn:ident $fn:ident) => {
+        impl<const F: u8> $assign for $name<F> {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = (*self).$fn(rhs);
+            }
+        }
+    };
+}
+
+small_num! {
+    /// [`Num`] stored in an `i32`, up to about ±2.1 × 10⁹ / 10^F
+    Num32(i32), i64, 9
+}
+
+small_num! {
+    /// [`Num`] stored in an `i16`, up to ±327.67 at `F = 2`
+    Num16(i16), i32, 4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Num16, Num32};
+    use crate::Num;
+
+    type N32 = Num32<6>;
+    type N16 = Num16<2>;
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(N32::SCALE, 1_000_000);
+        assert_eq!(
+            N32::from_int(3) + N32::from_raw(500_000),
+            N32::from_raw(3_500_000)
+        );
+        assert_eq!(N32::from_int(2) - N32::from_int(5), N32::from_int(-3));
+        assert_eq!(N32::from_int(7) % N32::from_int(4), N32::from_int(3));
+        assert_eq!(-N32::ONE, N32::from_int(-1));
+    }
+
+    #[test]
+    fn test_rounding() {
+        // Rounded half away from zero like Num
+        assert_eq!(N16::ONE / N16::from_int(3), N16::from_raw(33));
+        assert_eq!(N16::from_int(2) / N16::from_int(3), N16::from_raw(67));
+        assert_eq!(N16::from_int(-2) / N16::from_int(3), N16::from_raw(-67));
+        assert_eq!(N16::from_raw(5) * N16::from_raw(50), N16::from_raw(3));
+        assert_eq!(N16::from_raw(-5) * N16::from_raw(50), N16::from_raw(-3));
+
+        // Exact as long as the result fits, the scaled product needn't
+        assert_eq!(
+            N32::from_int(2000) * N32::from_raw(500_000),
+            N32::from_int(1000)
+        );
+        assert_eq!(N32::from_int(2000) / N32::from_int(1000), N32::from_int(2));
+        assert_eq!(N16::from_int(300) / N16::from_int(2), N16::from_int(150));
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(N16::from_int(400), N16::MAX);
+        assert_eq!(N16::checked_from_int(400), None);
+        assert_eq!(N16::checked_from_int(327), Some(N16::from_int(327)));
+        assert_eq!(N16::from_int(200).checked_mul(N16::from_int(2)), None);
+        assert_eq!(
+            N16::from_int(200).saturating_mul(N16::from_int(-2)),
+            N16::MIN
+        );
+        assert_eq!(N16::from_int(200).checked_add(N16::from_int(200)), None);
+        assert_eq!(
+            N16::from_int(200).saturating_add(N16::from_int(200)),
+            N16::MAX
+        );
+        assert_eq!(N16::ONE.checked_div(N16::ZERO), None);
+        assert_eq!(N16::from_int(10).checked_div(N16::from_raw(1)), None);
+        assert_eq!(N16::MIN.checked_neg(), None);
+    }
+
+    #[test]
+    fn test_num_conversions() {
+        // The rest of the math through Num
+        let root = Num::<6, 8>::from(N32::from_int(2)).sqrt();
+        assert_eq!(N32::from_num(root), Some(N32::from_raw(1_414_214)));
+        assert_eq!(N32::from_num(Num::<6, 8>::from_int(10_000)), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(N16::from_raw(-5).to_string(), "-0.05");
+        assert_eq!(N32::from_raw(3_141_593).to_string(), "3.141593");
+        assert!(
+            N16::from_raw(1234)
+                .digits()
+                .eq(Num::<2, 8>::from_raw(1234).digits())
+        );
+    }
+}
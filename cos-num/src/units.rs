@@ -0,0 +1,463 @@
+//! Values tagged with a unit, for dimensional analysis over [`Num`]
+//!
+//! A [`Unit`] is a product of powers of the SI base units, so `m/s` and `m*s^-1` are the
+//! same unit. [`Quantity`] pairs a value with one: adding or subtracting checks that the
+//! units match, multiplying and dividing composes them, so a wrong formula shows up as a
+//! [`UnitError::Mismatch`] instead of a plausible number.
+//!
+//! ```
+//! use cos_num::{Num, units::{Quantity, Unit, UnitError}};
+//!
+//! type N = Num<2, 8>;
+//!
+//! let distance = Quantity::new(N::from_int(100), Unit::METER);
+//! let time = Quantity::new(N::from_int(8), Unit::SECOND);
+//! let speed = distance.checked_div(time).unwrap();
+//! assert_eq!(speed.value, N::from_raw(1250)); // 12.5
+//! assert_eq!(speed.unit.to_string(), "m/s");
+//!
+//! assert_eq!(speed.checked_add(distance), Err(UnitError::Mismatch));
+//! ```
+//!
+//! Scaled units such as km or h are not tags of their own, values are kept in base units.
+//! A [`Conversion`] goes between two of them of the same dimension, e.g. km and mi.
+
+use crate::Num;
+
+/// SI base units in the order of [`Unit`]'s exponents
+const BASES: [&str; 7] = ["m", "kg", "s", "A", "K", "mol", "cd"];
+
+/// Product of powers of the SI base units, [`Unit::NONE`] for plain numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Unit {
+    /// Exponent of each of [`BASES`]
+    exps: [i8; BASES.len()],
+}
+
+impl Unit {
+    /// Dimensionless, e.g. a count or a ratio
+    pub const NONE: Self = Self { exps: [0; 7] };
+    pub const METER: Self = Self::base(0);
+    pub const KILOGRAM: Self = Self::base(1);
+    pub const SECOND: Self = Self::base(2);
+    pub const AMPERE: Self = Self::base(3);
+    pub const KELVIN: Self = Self::base(4);
+    pub const MOLE: Self = Self::base(5);
+    pub const CANDELA: Self = Self::base(6);
+
+    const fn base(i: usize) -> Self {
+        let mut exps = [0; BASES.len()];
+        exps[i] = 1;
+        Self { exps }
+    }
+
+    /// Exponent of the `i`th base unit, in the order m, kg, s, A, K, mol, cd
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `i` is 7 or more.
+    #[inline]
+    #[must_use]
+    pub const fn exp(self, i: usize) -> i8 {
+        self.exps[i]
+    }
+
+    /// Whether this is [`Unit::NONE`]
+    #[inline]
+    #[must_use]
+    pub const fn is_none(self) -> bool {
+        self.same(Self::NONE)
+    }
+
+    /// Unit of a product, `None` if an exponent leaves `i8`
+    #[must_use]
+    pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.combine(rhs, false)
+    }
+
+    /// Unit of a quotient, `None` if an exponent leaves `i8`
+    #[must_use]
+    pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.combine(rhs, true)
+    }
+
+    /// `==` usable in `const fn`
+    const fn same(self, other: Self) -> bool {
+        let mut i = 0;
+        while i < BASES.len() {
+            if self.exps[i] != other.exps[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Add the exponents of `rhs`, or subtract them if `div`
+    const fn combine(self, rhs: Self, div: bool) -> Option<Self> {
+        let mut exps = self.exps;
+        let mut i = 0;
+        while i < BASES.len() {
+            let exp = if div {
+                exps[i].checked_sub(rhs.exps[i])
+            } else {
+                exps[i].checked_add(rhs.exps[i])
+            };
+            let Some(exp) = exp else {
+                return None;
+            };
+            exps[i] = exp;
+            i += 1;
+        }
+        Some(Self { exps })
+    }
+
+    /// Base units with their exponent, those above the fraction bar first if `above`,
+    /// exponents of those below are made positive
+    fn factors(self, above: bool) -> impl Iterator<Item = (&'static str, u8)> {
+        BASES
+            .into_iter()
+            .zip(self.exps)
+            .filter(move |&(_, exp)| if above { exp > 0 } else { exp < 0 })
+            .map(|(base, exp)| (base, exp.unsigned_abs()))
+    }
+}
+
+impl core::fmt::Display for Unit {
+    /// `m^2*kg/s^2`, `mol/(A*K)`, `1/s` if nothing is above the fraction bar and `1` for
+    /// [`Unit::NONE`]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.factors(true).next().is_none() {
+            f.write_str("1")?;
+        }
+        let grouped = self.factors(false).count() > 1;
+        for above in [true, false] {
+            for (n, (base, exp)) in self.factors(above).enumerate() {
+                f.write_str(match (above, n) {
+                    (true, 0) => "",
+                    (false, 0) if grouped => "/(",
+                    (false, 0) => "/",
+                    _ => "*",
+                })?;
+                f.write_str(base)?;
+                if exp != 1 {
+                    write!(f, "^{exp}")?;
+                }
+            }
+        }
+        if grouped {
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for Unit {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        if self.factors(true).next().is_none() {
+            f.write_str("1")?;
+        }
+        let grouped = self.factors(false).count() > 1;
+        for above in [true, false] {
+            for (n, (base, exp)) in self.factors(above).enumerate() {
+                f.write_str(match (above, n) {
+                    (true, 0) => "",
+                    (false, 0) if grouped => "/(",
+                    (false, 0) => "/",
+                    _ => "*",
+                })?;
+                f.write_str(base)?;
+                if exp != 1 {
+                    ufmt::uwrite!(f, "^{}", exp)?;
+                }
+            }
+        }
+        if grouped {
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+/// Why an operation on [`Quantity`]s has no result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum UnitError {
+    /// Added or subtracted values of different units
+    Mismatch,
+    /// Value overflowed or was divided by zero
+    Value,
+    /// Exponent of the unit left `i8`
+    Exponent,
+}
+
+/// Value in a [`Unit`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Quantity<const F: u8, const TF: u8> {
+    pub value: Num<F, TF>,
+    pub unit: Unit,
+}
+
+impl<const F: u8, const TF: u8> Quantity<F, TF> {
+    #[inline]
+    #[must_use]
+    pub const fn new(value: Num<F, TF>, unit: Unit) -> Self {
+        Self { value, unit }
+    }
+
+    /// Sum of values of the same unit
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the units differ or the sum overflows.
+    pub const fn checked_add(self, rhs: Self) -> Result<Self, UnitError> {
+        self.same_unit(rhs, self.value.checked_add(rhs.value))
+    }
+
+    /// Difference of values of the same unit
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the units differ or the difference overflows.
+    pub const fn checked_sub(self, rhs: Self) -> Result<Self, UnitError> {
+        self.same_unit(rhs, self.value.checked_sub(rhs.value))
+    }
+
+    /// Product, its unit is the product of the units
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the product or an exponent of its unit overflows.
+    pub const fn checked_mul(self, rhs: Self) -> Result<Self, UnitError> {
+        let Some(unit) = self.unit.checked_mul(rhs.unit) else {
+            return Err(UnitError::Exponent);
+        };
+        match self.value.checked_mul(rhs.value) {
+            Some(value) => Ok(Self { value, unit }),
+            None => Err(UnitError::Value),
+        }
+    }
+
+    /// Quotient, its unit is the quotient of the units
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `rhs` is zero or the quotient or an exponent of its unit
+    /// overflows.
+    pub const fn checked_div(self, rhs: Self) -> Result<Self, UnitError> {
+        let Some(unit) = self.unit.checked_div(rhs.unit) else {
+            return Err(UnitError::Exponent);
+        };
+        match self.value.checked_div(rhs.value) {
+            Some(value) => Ok(Self { value, unit }),
+            None => Err(UnitError::Value),
+        }
+    }
+
+    /// Result of an operation on the values that keeps the unit, if the units match
+    const fn same_unit(self, rhs: Self, value: Option<Num<F, TF>>) -> Result<Self, UnitError> {
+        if !self.unit.same(rhs.unit) {
+            return Err(UnitError::Mismatch);
+        }
+        match value {
+            Some(value) => Ok(Self {
+                value,
+                unit: self.unit,
+            }),
+            None => Err(UnitError::Value),
+        }
+    }
+}
+
+/// Conversion between two units of the same dimension, e.g. km to mi or °C to °F, as
+/// `to = from * num / den + offset`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Conversion<const F: u8, const TF: u8> {
+    /// Dimension of both units, values of another one are a [`UnitError::Mismatch`]
+    pub unit: Unit,
+    pub num: i64,
+    pub den: i64,
+    pub offset: Num<F, TF>,
+}
+
+impl<const F: u8, const TF: u8> Conversion<F, TF> {
+    /// Value in the second unit, rounded to the nearest digit
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `from` is of another dimension or the value overflows.
+    pub const fn apply(self, from: Quantity<F, TF>) -> Result<Quantity<F, TF>, UnitError> {
+        let value = match scale(from.value, self.num, self.den) {
+            Some(value) => value.checked_add(self.offset),
+            None => None,
+        };
+        self.checked(from, value)
+    }
+
+    /// Value back in the first unit, `from = (to - offset) * den / num`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `to` is of another dimension or the value overflows.
+    pub const fn apply_back(self, to: Quantity<F, TF>) -> Result<Quantity<F, TF>, UnitError> {
+        let value = match to.value.checked_sub(self.offset) {
+            Some(value) => scale(value, self.den, self.num),
+            None => None,
+        };
+        self.checked(to, value)
+    }
+
+    /// Converted `value` of `q`, if `q` is of the dimension
+    const fn checked(
+        self,
+        q: Quantity<F, TF>,
+        value: Option<Num<F, TF>>,
+    ) -> Result<Quantity<F, TF>, UnitError> {
+        if !q.unit.same(self.unit) {
+            return Err(UnitError::Mismatch);
+        }
+        match value {
+            Some(value) => Ok(Quantity::new(value, self.unit)),
+            None => Err(UnitError::Value),
+        }
+    }
+}
+
+/// `value * num / den` rounded half away from zero, `None` if it overflows or `den` is 0
+const fn scale<const F: u8, const TF: u8>(
+    value: Num<F, TF>,
+    num: i64,
+    den: i64,
+) -> Option<Num<F, TF>> {
+    if den == 0 {
+        return None;
+    }
+
+    let n = value.raw() as i128 * num as i128;
+    let d = den as i128;
+    let mut q = n / d;
+    if 2 * (n % d).abs() >= d.abs() {
+        q += if (n < 0) == (d < 0) { 1 } else { -1 };
+    }
+
+    if q < i64::MIN as i128 || q > i64::MAX as i128 {
+        return None;
+    }
+    Some(Num::from_raw(q as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Conversion, Quantity, Unit, UnitError};
+    use crate::Num;
+
+    type N = Num<2, 8>;
+    type TestNum = Num<6, 8>;
+
+    #[test]
+    fn test_conversion() {
+        let n = |text: &str| text.parse::<N>().unwrap();
+        let m = |v| Quantity::new(n(v), Unit::METER);
+        let kelvin = |v| Quantity::new(n(v), Unit::KELVIN);
+
+        // km to mi, 1 mi is 1.609344 km
+        let km_mi = Conversion {
+            unit: Unit::METER,
+            num: 15_625,
+            den: 25_146,
+            offset: N::ZERO,
+        };
+        assert_eq!(km_mi.apply(m("100")), Ok(m("62.14")));
+        assert_eq!(km_mi.apply(m("-100")), Ok(m("-62.14")));
+        assert_eq!(km_mi.apply_back(m("62.14")), Ok(m("100")));
+        assert_eq!(km_mi.apply_back(m("1")), Ok(m("1.61")));
+
+        let c_f = Conversion {
+            unit: Unit::KELVIN,
+            num: 9,
+            den: 5,
+            offset: N::from_int(32),
+        };
+        assert_eq!(c_f.apply(kelvin("100")), Ok(kelvin("212")));
+        assert_eq!(c_f.apply(kelvin("-40")), Ok(kelvin("-40")));
+        assert_eq!(c_f.apply_back(kelvin("0")), Ok(kelvin("-17.78")));
+        assert_eq!(c_f.apply_back(kelvin("98.6")), Ok(kelvin("37")));
+
+        assert_eq!(c_f.apply(m("1")), Err(UnitError::Mismatch));
+        assert_eq!(
+            km_mi.apply_back(Quantity::new(N::from_raw(i64::MAX), Unit::METER)),
+            Err(UnitError::Value)
+        );
+        let none = Conversion { den: 0, ..km_mi };
+        assert_eq!(none.apply(m("1")), Err(UnitError::Value));
+    }
+
+    #[test]
+    fn test_units() {
+        let m = |v| Quantity::new(TestNum::from_int(v), Unit::METER);
+        let s = |v| Quantity::new(TestNum::from_int(v), Unit::SECOND);
+
+        assert_eq!(m(2).checked_add(m(3)), Ok(m(5)));
+        assert_eq!(m(2).checked_sub(m(3)), Ok(m(-1)));
+        assert_eq!(m(2).checked_add(s(3)), Err(UnitError::Mismatch));
+        assert_eq!(m(2).checked_sub(s(3)), Err(UnitError::Mismatch));
+
+        let area = m(2).checked_mul(m(3)).unwrap();
+        assert_eq!(area.value, TestNum::from_int(6));
+        assert_eq!(area.unit, Unit::METER.checked_mul(Unit::METER).unwrap());
+        assert_eq!(area.checked_div(m(3)), Ok(m(2)));
+
+        let speed = m(10).checked_div(s(4)).unwrap();
+        assert_eq!(speed.value, TestNum::from_f64(2.5));
+        assert_eq!(speed.unit.exp(0), 1);
+        assert_eq!(speed.unit.exp(2), -1);
+        // Back to meters once multiplied by the time
+        assert_eq!(speed.checked_mul(s(4)), Ok(m(10)));
+
+        let ratio = m(6).checked_div(m(3)).unwrap();
+        assert!(ratio.unit.is_none());
+        assert_eq!(ratio.unit, Unit::NONE);
+
+        assert_eq!(m(1).checked_div(m(0)), Err(UnitError::Value));
+        assert_eq!(
+            Quantity::new(TestNum::from_raw(i64::MAX), Unit::METER).checked_add(m(1)),
+            Err(UnitError::Value)
+        );
+
+        let mut unit = Unit::METER;
+        for _ in 1..127 {
+            unit = unit.checked_mul(Unit::METER).unwrap();
+        }
+        assert_eq!(unit.exp(0), 127);
+        let big = Quantity::new(TestNum::ONE, unit);
+        assert_eq!(big.checked_mul(m(1)), Err(UnitError::Exponent));
+    }
+
+    #[test]
+    fn test_unit_display() {
+        let per = |a: Unit, b: Unit| a.checked_div(b).unwrap();
+        let times = |a: Unit, b: Unit| a.checked_mul(b).unwrap();
+
+        assert_eq!(Unit::NONE.to_string(), "1");
+        assert_eq!(Unit::METER.to_string(), "m");
+        assert_eq!(per(Unit::METER, Unit::SECOND).to_string(), "m/s");
+        assert_eq!(per(Unit::NONE, Unit::SECOND).to_string(), "1/s");
+
+        let joule = per(
+            times(times(Unit::KILOGRAM, Unit::METER), Unit::METER),
+            times(Unit::SECOND, Unit::SECOND),
+        );
+        assert_eq!(joule.to_string(), "m^2*kg/s^2");
+        assert_eq!(
+            per(Unit::MOLE, times(Unit::KELVIN, Unit::AMPERE)).to_string(),
+            "mol/(A*K)"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! Golden vectors: what cos-num computes next to the correctly rounded `f64` result
+//!
+//! Values are raw, scaled by `10^6`. Rows where `error` is not 0 are the ones
+//! to look at, either a known precision limit or a regression.
+
+use std::io::{self, Write};
+
+use cos_num::Num;
+
+type N = Num<6, 8>;
+
+/// Name, function under test, its `f64` reference and the range of inputs
+type Function = (&'static str, fn(N) -> N, fn(f64) -> f64, (f64, f64));
+
+const FUNCTIONS: [Function; 6] = [
+    ("sqrt", N::sqrt, f64::sqrt, (0.0, 100.0)),
+    ("sin", N::sin, f64::sin, (-10.0, 10.0)),
+    ("cos", N::cos, f64::cos, (-10.0, 10.0)),
+    ("tan", N::tan, f64::tan, (-1.5, 1.5)),
+    ("ln", N::ln, f64::ln, (0.01, 100.0)),
+    ("sinh", N::sinh, f64::sinh, (-3.0, 3.0)),
+];
+
+/// Inputs per function, spread evenly over its range
+const STEPS: u32 = 64;
+
+/// Write every vector as a CSV row
+///
+/// # Errors
+///
+/// Will return `Err` if writing to `out` fails.
+pub fn write(out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "fn,x,expected,got,error")?;
+
+    for (name, f, reference, (lo, hi)) in FUNCTIONS {
+        for i in 0..=STEPS {
+            let x = N::from_f64(lo + (hi - lo) * f64::from(i) / f64::from(STEPS));
+            let expected = N::from_f64(reference(x.to_f64()));
+            let got = f(x);
+
+            writeln!(
+                out,
+                "{name},{},{},{},{}",
+                x.raw(),
+                expected.raw(),
+                got.raw(),
+                got.raw().wrapping_sub(expected.raw())
+            )?;
+        }
+    }
+
+    Ok(())
+}
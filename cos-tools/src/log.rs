@@ -0,0 +1,63 @@
+//! Serial log viewer: timestamps firmware log lines and filters them by tag
+//!
+//! The firmware logs plain text, most lines start with a tag
+//! (`boot: Normal`, `self-test: math true`, `slider: 150`). Input is a serial
+//! device already set to 57600 baud (`stty -F /dev/ttyUSB0 57600 raw`) or stdin.
+
+use std::{
+    io::{self, BufRead, Write},
+    time::Instant,
+};
+
+const DIM: &str = "\x1b[2m";
+const CYAN: &str = "\x1b[36m";
+const RED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+/// Copy lines from `input` to `out` with the time since start
+///
+/// Only lines with one of `tags` are kept, unless `tags` is empty.
+///
+/// # Errors
+///
+/// Will return `Err` if reading `input` or writing `out` fails.
+pub fn run(
+    input: impl BufRead,
+    out: &mut impl Write,
+    tags: &[String],
+    color: bool,
+) -> io::Result<()> {
+    let start = Instant::now();
+    let paint = |code: &'static str| if color { code } else { "" };
+
+    for line in input.split(b'\n') {
+        let line = line?;
+        let line = String::from_utf8_lossy(&line);
+        let line = line.trim_end_matches('\r');
+
+        let tag = tag(line);
+        if !tags.is_empty() && !tag.is_some_and(|tag| tags.iter().any(|t| t == tag)) {
+            continue;
+        }
+
+        let time = start.elapsed().as_secs_f64();
+        write!(out, "{}[{time:9.3}]{} ", paint(DIM), paint(RESET))?;
+
+        if line.contains("panic") {
+            writeln!(out, "{}{line}{}", paint(RED), paint(RESET))?;
+        } else if let Some(tag) = tag {
+            let rest = &line[tag.len()..];
+            writeln!(out, "{}{tag}{}{rest}", paint(CYAN), paint(RESET))?;
+        } else {
+            writeln!(out, "{line}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Word before the first `:`, e.g. `boot` in `boot: Normal`
+fn tag(line: &str) -> Option<&str> {
+    let (tag, _) = line.split_once(':')?;
+    (!tag.is_empty() && !tag.contains(char::is_whitespace)).then_some(tag)
+}
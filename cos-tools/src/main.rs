@@ -0,0 +1,115 @@
+//! Host-side tools for COS, run with `cargo run -p cos-tools -- <tool>` in the repository root
+//!
+//! - `golden` - print cos-num results next to the `f64` reference as CSV
+//! - `log [--tag TAG]... [PORT]` - show the firmware log with timestamps, from `PORT` or stdin
+//! - `simulate [FILE]` - run a script of keys, waits and expected outputs against the
+//!   firmware's calculator and layout, from `FILE` or stdin
+//! - `transcript [--name NAME] [FILE]` - turn a logged session into a calculator test,
+//!   from `FILE` or stdin
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader},
+    process::ExitCode,
+};
+
+mod golden;
+mod log;
+mod simulate;
+mod transcript;
+
+const USAGE: &str = "usage: cos-tools golden | cos-tools log [--tag TAG]... [PORT] \
+                     | cos-tools simulate [FILE] | cos-tools transcript [--name NAME] [FILE]";
+
+// The simulator has no plugins, their cells stay reserved
+cos::plugins!();
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.split_first() {
+        Some((tool, _)) if tool == "golden" => {
+            golden::write(&mut io::stdout().lock()).map_err(|e| e.to_string())
+        }
+        Some((tool, args)) if tool == "log" => log(args),
+        Some((tool, args)) if tool == "simulate" => simulate(args),
+        Some((tool, args)) if tool == "transcript" => transcript(args),
+        _ => Err(USAGE.to_owned()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            #[expect(clippy::print_stderr, reason = "cos-tools is a CLI")]
+            {
+                eprintln!("error: {e}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn log(args: &[String]) -> Result<(), String> {
+    let mut tags = Vec::new();
+    let mut port = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--tag" => tags.push(args.next().ok_or(USAGE)?.clone()),
+            _ if port.is_none() => port = Some(arg),
+            _ => return Err(USAGE.to_owned()),
+        }
+    }
+
+    // https://no-color.org
+    let color = env::var_os("NO_COLOR").is_none();
+    let out = &mut io::stdout().lock();
+
+    match port {
+        Some(port) => {
+            let file = File::open(port).map_err(|e| format!("{port}: {e}"))?;
+            log::run(BufReader::new(file), out, &tags, color)
+        }
+        None => log::run(io::stdin().lock(), out, &tags, color),
+    }
+    .map_err(|e| e.to_string())
+}
+
+fn simulate(args: &[String]) -> Result<(), String> {
+    match args {
+        [] => simulate::run(io::stdin().lock()),
+        [path] => {
+            let file = File::open(path).map_err(|e| format!("{path}: {e}"))?;
+            simulate::run(BufReader::new(file))
+        }
+        _ => return Err(USAGE.to_owned()),
+    }
+    .map_err(|e| e.to_string())
+}
+
+fn transcript(args: &[String]) -> Result<(), String> {
+    let mut name = "transcript";
+    let mut file = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--name" => name = args.next().ok_or(USAGE)?,
+            _ if file.is_none() => file = Some(arg),
+            _ => return Err(USAGE.to_owned()),
+        }
+    }
+
+    let out = &mut io::stdout().lock();
+
+    match file {
+        Some(path) => {
+            let file = File::open(path).map_err(|e| format!("{path}: {e}"))?;
+            transcript::run(BufReader::new(file), out, name)
+        }
+        None => transcript::run(io::stdin().lock(), out, name),
+    }
+    .map_err(|e| e.to_string())
+}
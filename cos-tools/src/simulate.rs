@@ -0,0 +1,491 @@
+//! Simulator: the firmware's calculator and keyboard layout driven by a script, for
+//! end-to-end tests of whole sessions on the host
+//!
+//! The script has a command per line, lines starting with `#` are comments:
+//!
+//! - `press KEY...` presses the keys wherever they are in the layout, named as the firmware
+//!   logs them, e.g. `press 1 + 2 =`; names with a space like `alarm >` work too
+//! - `move DIR...` moves the cursor `up`, `down`, `left` or `right`, `click` presses the
+//!   key under it, the cursor then goes back to the start like after any press
+//! - `wait MS` lets the main loop run for `MS` milliseconds, a long operator is computed a
+//!   step per iteration like on the device
+//! - `expect REQUEST` checks that the outputs were asked for `REQUEST` since the last
+//!   `expect`, requests before it are skipped: `number 3`, `time 5400`, `truth true`,
+//!   `entered +`, `failed div by zero`, `error`, `alarm`, `tick 30`, `blink 1 250`, `stop`
+//! - `expect nothing` checks that the outputs were asked for nothing else
+//! - `expect at KEY` checks that the cursor is on the key
+//!
+//! Every command runs the stages of the firmware's main loop over a [`Bus`]: input,
+//! calculator, the hooks that turn what it did into [`OutputRequest`]s, and the outputs.
+//! The hardware is left out, live sources read 0 and the keys with a hook of their own
+//! (settings, slider, help, recorder, power) only reach the calculator.
+
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead},
+};
+
+use cos::{
+    CalcError, Calculator,
+    bus::{Bus, CalcEvent, InputEvent, OutputRequest},
+    clock::{Clock as _, Period, VirtualClock},
+    config::{FRACTION_COUNT, keyboard_layout},
+    input::Dir,
+    layout::{self, Cell},
+    num::Num,
+};
+use ufmt::{uDisplay, uwrite};
+
+/// Period of the firmware's main loop
+const PERIOD_MS: u16 = 10;
+
+/// Errors by the name the firmware shows
+const ERRORS: [CalcError; 6] = [
+    CalcError::Calc,
+    CalcError::Overflow,
+    CalcError::DivByZero,
+    CalcError::Domain,
+    CalcError::Cancelled,
+    CalcError::Empty,
+];
+
+/// Run the script from `input`, it stops at the first command that fails
+///
+/// # Errors
+///
+/// Will return `Err` if reading `input` fails, on invalid commands and on expectations that
+/// aren't met, with the line.
+pub fn run(input: impl BufRead) -> io::Result<()> {
+    let mut sim = Simulator::new();
+
+    for (n, line) in input.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        sim.command(line).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {e}", n + 1))
+        })?;
+    }
+
+    Ok(())
+}
+
+/// The device as far as the script sees it
+struct Simulator {
+    calc: Calculator<FRACTION_COUNT>,
+    bus: Bus,
+    clock: VirtualClock,
+    period: Period,
+    /// Cell of the cursor as (x, y), like [`Cell::at`]
+    pos: (u8, u8),
+    /// Requests the outputs got that no `expect` took yet
+    output: VecDeque<OutputRequest>,
+}
+
+impl Simulator {
+    fn new() -> Self {
+        let clock = VirtualClock::new();
+        Self {
+            calc: Calculator::new(),
+            bus: Bus::new(),
+            period: Period::new(&clock, PERIOD_MS),
+            clock,
+            pos: layout::default_pos(),
+            output: VecDeque::new(),
+        }
+    }
+
+    fn command(&mut self, line: &str) -> Result<(), String> {
+        let (command, args) = line.split_once(' ').unwrap_or((line, ""));
+        let args = args.trim();
+
+        match command {
+            "press" => {
+                for pos in keys(args)? {
+                    self.press(Cell::at(pos));
+                }
+            }
+            "move" => {
+                for dir in args.split_whitespace() {
+                    self.move_cursor(
+                        parse_dir(dir).ok_or_else(|| format!("invalid direction `{dir}`"))?,
+                    );
+                }
+            }
+            "click" if args.is_empty() => self.press(Cell::at(self.pos)),
+            "wait" => {
+                let ms: u32 = args
+                    .parse()
+                    .map_err(|e| format!("invalid time `{args}`: {e}"))?;
+                let start = self.clock.now_ms();
+                while self.clock.elapsed_since(start) < ms {
+                    self.tick();
+                }
+            }
+            "expect" => self.expect(args)?,
+            _ => return Err(format!("unknown command `{line}`")),
+        }
+
+        Ok(())
+    }
+
+    /// Press the cell like the button does, the cursor goes back to the start
+    fn press(&mut self, cell: Cell) {
+        self.bus.publish(InputEvent::Press(cell));
+        self.pos = layout::default_pos();
+        self.tick();
+    }
+
+    /// Move the cursor by `dir`, it stays at the edge of the layout
+    fn move_cursor(&mut self, dir: Dir) {
+        let (x, y) = self.pos;
+        let pos = match dir {
+            Dir::Up => (x, y.saturating_add(1)),
+            Dir::Down => (x, y.saturating_sub(1)),
+            Dir::Left => (x.saturating_sub(1), y),
+            Dir::Right => (x.saturating_add(1), y),
+            Dir::Center => return,
+        };
+        if Cell::at(pos) != Cell::Outside {
+            self.pos = pos;
+        }
+
+        self.bus.publish(InputEvent::Move(Cell::at(self.pos)));
+        self.tick();
+    }
+
+    /// One iteration of the main loop: input, long operator, hooks, outputs
+    fn tick(&mut self) {
+        while let Some(event) = self.bus.take() {
+            self.handle_input(event);
+        }
+        self.poll();
+        while let Some(event) = self.bus.take() {
+            self.handle_calc(event);
+        }
+        while let Some(request) = self.bus.take() {
+            self.output.push_back(request);
+        }
+
+        self.period.wait(&mut self.clock);
+    }
+
+    fn handle_input(&mut self, event: InputEvent) {
+        // Any input cuts the readout short
+        self.bus.publish(OutputRequest::Stop);
+
+        if let InputEvent::Press(cell) = event {
+            self.calc.refresh(|_| Num::ZERO);
+            if self.run_key(cell) {
+                return;
+            }
+        }
+
+        self.bus.publish(OutputRequest::Blink { count: 1, ms: 250 });
+    }
+
+    /// Run the key in `cell` through the calculator, `true` if it computed a result
+    fn run_key(&mut self, cell: Cell) -> bool {
+        let Cell::Key(key) = cell else {
+            // Empty cells are meant to be pressed through, the rest have no key yet
+            if cell != Cell::Empty {
+                self.bus.publish(OutputRequest::Error);
+            }
+            return false;
+        };
+
+        match self.calc.handle_input(key) {
+            Ok(v) => {
+                self.bus.publish(CalcEvent::Key(key));
+
+                if let Some(v) = v {
+                    self.bus.publish(CalcEvent::Result(v));
+                    return true;
+                }
+            }
+            Err(e) => self.bus.publish(CalcEvent::Error(e)),
+        }
+
+        false
+    }
+
+    /// Advance a long operator, finishing it like a pressed key would
+    fn poll(&mut self) {
+        match self.calc.poll() {
+            Ok(Some(v)) => self.bus.publish(CalcEvent::Result(v)),
+            Ok(None) => {}
+            Err(e) => self.bus.publish(CalcEvent::Error(e)),
+        }
+    }
+
+    /// What the firmware's `display_result` and `check_alarm` hooks ask the outputs for
+    fn handle_calc(&mut self, event: CalcEvent) {
+        let request = match event {
+            CalcEvent::Key(key) => OutputRequest::Entered(key),
+            CalcEvent::Result(v) if self.calc.is_comparison() => {
+                OutputRequest::Truth(v == Num::ONE)
+            }
+            CalcEvent::Result(v) if self.calc.is_time() => OutputRequest::Time(v),
+            CalcEvent::Result(v) => OutputRequest::Number(v),
+            CalcEvent::Error(e) => OutputRequest::Failed(e),
+        };
+        self.bus.publish(request);
+
+        if let CalcEvent::Result(v) = event
+            && self.calc.alarm().is_triggered(v)
+        {
+            self.bus.publish(OutputRequest::Alarm);
+        }
+    }
+
+    fn expect(&mut self, what: &str) -> Result<(), String> {
+        if what == "nothing" {
+            return if self.output.is_empty() {
+                Ok(())
+            } else {
+                Err(format!("expected nothing, got {}", self.pending()))
+            };
+        }
+
+        if let Some(name) = what.strip_prefix("at ") {
+            let pos = find(name).ok_or_else(|| format!("unknown key `{name}`"))?;
+            let cell = Cell::at(self.pos);
+            return if cell == Cell::at(pos) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "expected the cursor at `{name}`, it is at `{}`",
+                    text(&cell)
+                ))
+            };
+        }
+
+        let expected = parse_request(what).ok_or_else(|| format!("invalid request `{what}`"))?;
+        match self.output.iter().position(|&request| request == expected) {
+            Some(i) => {
+                self.output.drain(..=i);
+                Ok(())
+            }
+            None => Err(format!("expected {what}, got {}", self.pending())),
+        }
+    }
+
+    /// Requests no `expect` took yet, for a failure
+    fn pending(&self) -> String {
+        if self.output.is_empty() {
+            return "nothing".to_owned();
+        }
+
+        self.output
+            .iter()
+            .map(|&request| describe(request))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Positions of the keys named in `names`, a name may take two words like `alarm >`
+fn keys(names: &str) -> Result<Vec<(u8, u8)>, String> {
+    let mut keys = Vec::new();
+
+    let mut words = names.split_whitespace();
+    while let Some(word) = words.next() {
+        let pos = find(word).or_else(|| {
+            let next = words.next()?;
+            find(&format!("{word} {next}"))
+        });
+        keys.push(pos.ok_or_else(|| format!("unknown key `{word}`"))?);
+    }
+
+    Ok(keys)
+}
+
+/// Position of the key named `name` in the layout
+fn find(name: &str) -> Option<(u8, u8)> {
+    let layout = keyboard_layout();
+    let cols = u8::try_from(layout.rows.first()?.len()).ok()?;
+    let rows = u8::try_from(layout.rows.len()).ok()?;
+
+    (0..cols)
+        .flat_map(|x| (0..rows).map(move |y| (x, y)))
+        .find(|&pos| matches!(Cell::at(pos), Cell::Key(key) if text(&key) == name))
+}
+
+fn parse_dir(name: &str) -> Option<Dir> {
+    Some(match name {
+        "up" => Dir::Up,
+        "down" => Dir::Down,
+        "left" => Dir::Left,
+        "right" => Dir::Right,
+        _ => return None,
+    })
+}
+
+/// Request written like [`describe`] does
+fn parse_request(text: &str) -> Option<OutputRequest> {
+    let (name, arg) = text.split_once(' ').unwrap_or((text, ""));
+
+    Some(match name {
+        "stop" => OutputRequest::Stop,
+        "pause" => OutputRequest::Pause(arg.parse().ok()?),
+        "blink" => {
+            let (count, ms) = arg.split_once(' ')?;
+            OutputRequest::Blink {
+                count: count.parse().ok()?,
+                ms: ms.parse().ok()?,
+            }
+        }
+        "tick" => OutputRequest::Tick(arg.parse().ok()?),
+        "entered" => match Cell::at(find(arg)?) {
+            Cell::Key(key) => OutputRequest::Entered(key),
+            _ => return None,
+        },
+        "error" => OutputRequest::Error,
+        "failed" => OutputRequest::Failed(ERRORS.into_iter().find(|e| self::text(e) == arg)?),
+        "truth" => OutputRequest::Truth(arg.parse().ok()?),
+        "alarm" => OutputRequest::Alarm,
+        "corrupted" => OutputRequest::Corrupted,
+        "number" => OutputRequest::Number(arg.parse().ok()?),
+        "time" => OutputRequest::Time(arg.parse().ok()?),
+        "lesson" => OutputRequest::Lesson(arg.parse().ok()?),
+        "passed" => OutputRequest::Passed,
+        _ => return None,
+    })
+}
+
+/// Request as a script writes it
+fn describe(request: OutputRequest) -> String {
+    match request {
+        OutputRequest::Stop => "stop".to_owned(),
+        OutputRequest::Pause(ms) => format!("pause {ms}"),
+        OutputRequest::Blink { count, ms } => format!("blink {count} {ms}"),
+        OutputRequest::Tick(ms) => format!("tick {ms}"),
+        OutputRequest::Entered(key) => format!("entered {}", text(&key)),
+        OutputRequest::Error => "error".to_owned(),
+        OutputRequest::Failed(e) => format!("failed {}", text(&e)),
+        OutputRequest::Truth(v) => format!("truth {v}"),
+        OutputRequest::Alarm => "alarm".to_owned(),
+        OutputRequest::Corrupted => "corrupted".to_owned(),
+        OutputRequest::Number(v) => format!("number {v}"),
+        OutputRequest::Time(v) => format!("time {v}"),
+        OutputRequest::Lesson(n) => format!("lesson {n}"),
+        OutputRequest::Passed => "passed".to_owned(),
+    }
+}
+
+/// Name the firmware shows for `value`
+fn text(value: &impl uDisplay) -> String {
+    let mut text = String::new();
+    let Ok(()) = uwrite!(text, "{}", value);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+
+    /// Message of the line that failed, `None` if the script passed
+    fn failure(script: &str) -> Option<String> {
+        run(script.as_bytes()).err().map(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_press() {
+        let script = "
+            # 1 + 2
+            press 1 + 2 =
+            expect entered +
+            expect entered =
+            expect number 3
+            expect nothing
+
+            press reset 1 / 0 =
+            expect failed div by zero
+        ";
+        assert_eq!(failure(script), None);
+    }
+
+    #[test]
+    fn test_cursor() {
+        let script = "
+            expect at 5
+            move up left
+            expect at 7
+            click
+            expect entered 7
+            expect at 5
+
+            # The cursor stays at the edge
+            move down down down down
+            expect at del
+        ";
+        assert_eq!(failure(script), None);
+    }
+
+    #[test]
+    fn test_wait() {
+        let script = "
+            press 1 sin
+            expect entered sin
+            expect nothing
+            wait 1000
+            expect number 0.84
+        ";
+        assert_eq!(failure(script), None);
+
+        // Any key cancels it
+        let script = "
+            press 1 sin 2
+            expect failed cancelled
+            wait 1000
+            expect nothing
+        ";
+        assert_eq!(failure(script), None);
+    }
+
+    #[test]
+    fn test_feedback() {
+        let script = "
+            press 3 < 4 =
+            expect truth true
+            press reset 5 alarm >
+            press reset 2 * 3 =
+            expect number 6
+            expect alarm
+        ";
+        assert_eq!(failure(script), None);
+    }
+
+    #[test]
+    fn test_failures() {
+        assert_eq!(
+            failure("press 7\nexpect number 7").as_deref(),
+            Some("line 2: expected number 7, got stop, blink 1 250, entered 7")
+        );
+        assert_eq!(
+            failure("press 7 =\nexpect nothing").as_deref(),
+            Some(
+                "line 2: expected nothing, got stop, blink 1 250, entered 7, stop, blink 1 250, failed no operator"
+            )
+        );
+        assert_eq!(
+            failure("\n\nmove up\nexpect at 5").as_deref(),
+            Some("line 4: expected the cursor at `5`, it is at `8`")
+        );
+        assert_eq!(
+            failure("press 1 nope").as_deref(),
+            Some("line 1: unknown key `nope`")
+        );
+        assert_eq!(
+            failure("expect number x").as_deref(),
+            Some("line 1: invalid request `number x`")
+        );
+        assert_eq!(
+            failure("jump").as_deref(),
+            Some("line 1: unknown command `jump`")
+        );
+    }
+}
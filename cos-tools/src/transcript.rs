@@ -0,0 +1,473 @@
+//! Transcripts: a recorded serial session turned into a Rust test over the `Calculator`
+//!
+//! Needs the log of a debug build in English, it has a `pressed <key>` line before the
+//! result (`= 300`) or error (`+: overflow`) of every key. Timestamps and colors added by
+//! `cos-tools log` are skipped, other lines are ignored. Keys that read the hardware, the
+//! live sources, the slider and the cursor moves of edit, can't be replayed and are rejected.
+
+use std::{
+    collections::BTreeSet,
+    io::{self, BufRead, Write},
+};
+
+use cos_num::Num;
+
+/// Name the firmware logs for a key and the key in the test, digits aside
+const KEYS: [(&str, &str); 54] = [
+    (".", "Key::Dot"),
+    ("+", "Key::BinOp(BinOp::Add)"),
+    ("-", "Key::BinOp(BinOp::Sub)"),
+    ("*", "Key::BinOp(BinOp::Mul)"),
+    ("/", "Key::BinOp(BinOp::Div)"),
+    ("==", "Key::BinOp(BinOp::CmpEq)"),
+    ("<", "Key::BinOp(BinOp::CmpLt)"),
+    (">", "Key::BinOp(BinOp::CmpGt)"),
+    ("if", "Key::BinOp(BinOp::If)"),
+    ("neg", "Key::UnOp(UnOp::Neg)"),
+    ("sqrt", "Key::UnOp(UnOp::Sqrt)"),
+    ("x^2", "Key::UnOp(UnOp::Pow2)"),
+    ("x^3", "Key::UnOp(UnOp::Pow3)"),
+    ("!", "Key::UnOp(UnOp::Factorial)"),
+    ("1/x", "Key::UnOp(UnOp::Recip)"),
+    ("abs", "Key::UnOp(UnOp::Abs)"),
+    ("sin", "Key::UnOp(UnOp::Sin)"),
+    ("cos", "Key::UnOp(UnOp::Cos)"),
+    ("tan", "Key::UnOp(UnOp::Tan)"),
+    ("dms", "Key::UnOp(UnOp::Dms)"),
+    ("pi", "Key::Const(Const::Pi)"),
+    ("tau", "Key::Const(Const::Tau)"),
+    ("phi", "Key::Const(Const::Phi)"),
+    ("gamma", "Key::Const(Const::EGamma)"),
+    ("sqrt2", "Key::Const(Const::Sqrt2)"),
+    ("e", "Key::Const(Const::E)"),
+    ("km>mi", "Key::Convert(Conversion::KmMi)"),
+    ("C>F", "Key::Convert(Conversion::CelsiusFahrenheit)"),
+    ("kg>lb", "Key::Convert(Conversion::KgLb)"),
+    ("convert again", "Key::ConvertAgain"),
+    ("=", "Key::Result"),
+    ("del", "Key::Delete"),
+    ("CE", "Key::Clear"),
+    ("reset", "Key::Reset"),
+    ("swap", "Key::Swap"),
+    ("alarm off", "Key::Alarm(AlarmMode::Off)"),
+    ("alarm >", "Key::Alarm(AlarmMode::Above)"),
+    ("alarm <", "Key::Alarm(AlarmMode::Below)"),
+    ("power off", "Key::Power"),
+    ("help", "Key::Help"),
+    ("fix", "Key::Precision"),
+    ("sum", "Key::Stat(Stat::Sum)"),
+    ("mean", "Key::Stat(Stat::Mean)"),
+    ("min", "Key::Stat(Stat::Min)"),
+    ("max", "Key::Stat(Stat::Max)"),
+    ("rec", "Key::Record"),
+    ("replay", "Key::Replay"),
+    ("vibro", "Key::Intensity"),
+    ("chords", "Key::Chords"),
+    ("time", "Key::Time"),
+    ("rounding", "Key::Rounding"),
+    ("adjust", "Key::Adjust"),
+    ("photomath", "Key::Photomath"),
+    ("GPT-5", "Key::GPT5"),
+];
+
+/// Keys whose value comes from a sensor or the potentiometer, or that hand the stick to the
+/// cursor of the number being typed, which the log doesn't have
+const HARDWARE: [&str; 5] = ["A2", "battery", "temp", "slider", "edit"];
+
+/// Keys that start a long operator, their result comes from `Calculator::poll`
+const LONG: [&str; 3] = ["sin", "cos", "tan"];
+
+const ERRORS: [(&str, &str); 6] = [
+    ("no operator", "CalcError::Calc"),
+    ("overflow", "CalcError::Overflow"),
+    ("div by zero", "CalcError::DivByZero"),
+    ("out of domain", "CalcError::Domain"),
+    ("cancelled", "CalcError::Cancelled"),
+    ("no results", "CalcError::Empty"),
+];
+
+/// Where the generated items come from, by the name they are used with
+const PATHS: [(&str, &str); 8] = [
+    ("AlarmMode", "alarm::AlarmMode"),
+    ("BinOp", "BinOp"),
+    ("CalcError", "CalcError"),
+    ("Const", "Const"),
+    ("Conversion", "Conversion"),
+    ("Num", "num::Num"),
+    ("Stat", "history::Stat"),
+    ("UnOp", "UnOp"),
+];
+
+/// Replays the steps, the table of the session comes before it
+const REPLAY: &str = "
+/// Poll the long operator until it is done
+fn finish(calc: &mut Calculator<FRACTION_COUNT>) -> Result<Option<Num<FRACTION_COUNT>>, CalcError> {
+    loop {
+        let result = calc.poll();
+        if result != Ok(None) || !calc.is_busy() {
+            return result;
+        }
+    }
+}
+
+/// Number as the firmware logged it, checked at compile time
+const fn num(text: &str) -> Num<FRACTION_COUNT> {
+    match Num::parse_bytes(text.as_bytes()) {
+        Ok(num) => num,
+        Err(_) => panic!(\"not a number\"),
+    }
+}
+
+/// Pass every key to the calculator, `None` finishes the long operator
+fn replay(steps: &[Step]) {
+    let mut calc = Calculator::<FRACTION_COUNT>::new();
+
+    for (i, &(key, expected)) in steps.iter().enumerate() {
+        let result = match key {
+            Some(key) => calc.handle_input(key),
+            None => finish(&mut calc),
+        };
+        assert_eq!(result, expected, \"step {i}\");
+    }
+}
+";
+
+/// One call the test makes and what it expects back
+struct Step {
+    /// Key to pass to `Calculator::handle_input`, `None` to finish the long operator
+    key: Option<String>,
+    /// Expected result as Rust source
+    expected: String,
+}
+
+/// Read the session from `input` and write it to `out` as a test named `name`
+///
+/// # Errors
+///
+/// Will return `Err` if reading `input` or writing `out` fails,
+/// on unknown keys and on keys that read the hardware.
+pub fn run(input: impl BufRead, out: &mut impl Write, name: &str) -> io::Result<()> {
+    let steps = parse(input)?;
+
+    let mut rows = String::new();
+    for step in &steps {
+        let key = step
+            .key
+            .as_ref()
+            .map_or_else(|| "None".to_owned(), |key| format!("Some({key})"));
+        rows += &format!("    ({key}, {}),\n", step.expected);
+    }
+
+    // Import only what the test uses, unused imports would warn
+    let mut uses = BTreeSet::from([
+        "CalcError",
+        "Calculator",
+        "Key",
+        "config::FRACTION_COUNT",
+        "num::Num",
+    ]);
+    for (item, path) in PATHS {
+        if rows.contains(&format!("{item}::")) {
+            uses.insert(path);
+        }
+    }
+    let uses = uses.into_iter().collect::<Vec<_>>().join(", ");
+
+    // The basic edition compiles the other unary operators out
+    let full = steps
+        .iter()
+        .filter_map(|step| step.key.as_deref())
+        .any(|key| key.starts_with("Key::UnOp(") && key != "Key::UnOp(UnOp::Neg)");
+
+    // A table instead of an assert per key, long sessions would be too complex for clippy
+    writeln!(out, "// Generated by `cos-tools transcript`")?;
+    if full {
+        writeln!(out, "#![cfg(not(feature = \"basic-layout\"))]")?;
+        writeln!(out)?;
+    }
+    writeln!(out, "use cos::{{{uses}}};")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "/// Key, `None` to finish the long operator, and what it gave"
+    )?;
+    writeln!(
+        out,
+        "type Step = (Option<Key>, Result<Option<Num<FRACTION_COUNT>>, CalcError>);"
+    )?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "const {}: [Step; {}] = [",
+        name.to_uppercase(),
+        steps.len()
+    )?;
+    writeln!(out, "{rows}];")?;
+    writeln!(out)?;
+    writeln!(out, "#[test]")?;
+    writeln!(out, "fn {name}() {{")?;
+    writeln!(out, "    replay(&{});", name.to_uppercase())?;
+    write!(out, "}}\n{REPLAY}")
+}
+
+/// Steps of the session, each key with the result or error logged after it
+fn parse(input: impl BufRead) -> io::Result<Vec<Step>> {
+    let mut steps: Vec<Step> = Vec::new();
+    // Name of the last key while nothing was logged for it yet
+    let mut open: Option<String> = None;
+    // Whether a long operator is computed
+    let mut busy = false;
+
+    for (n, line) in input.split(b'\n').enumerate() {
+        let line = line?;
+        let line = clean(&String::from_utf8_lossy(&line));
+        let invalid =
+            |e: String| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {e}", n + 1));
+
+        if let Some(key) = line.strip_prefix("pressed") {
+            // Empty cells are logged without a name and don't reach the calculator
+            let key = key.trim_start();
+            if key.is_empty() {
+                continue;
+            }
+            if HARDWARE.contains(&key) {
+                return Err(invalid(format!(
+                    "`{key}` reads the hardware, can't be replayed"
+                )));
+            }
+            steps.push(Step {
+                key: Some(expr(key).ok_or_else(|| invalid(format!("unknown key `{key}`")))?),
+                expected: "Ok(None)".to_owned(),
+            });
+            // Any key cancels a long operator, a long key starts one unless it cancelled
+            busy = LONG.contains(&key);
+            open = Some(key.to_owned());
+        } else if let Some(result) = line.strip_prefix("= ") {
+            // Number as shown, maybe followed by the DMS or time readout
+            let number = result
+                .split(' ')
+                .next()
+                .filter(|number| number.parse::<Num<8, 8>>().is_ok())
+                .ok_or_else(|| invalid(format!("invalid result `{result}`")))?;
+            let expected = format!("Ok(Some(num(\"{number}\")))");
+
+            if busy {
+                steps.push(Step {
+                    key: None,
+                    expected,
+                });
+                open = None;
+                busy = false;
+            } else if open.take().is_some()
+                && let Some(step) = steps.last_mut()
+            {
+                step.expected = expected;
+            } else {
+                return Err(invalid("result without a key".to_owned()));
+            }
+        } else if let Some(error) = open.as_deref().and_then(|key| error(&line, Some(key))) {
+            steps.last_mut().expect("an open key has a step").expected = format!("Err({error})");
+            open = None;
+            busy = false;
+        } else if busy && let Some(error) = error(&line, None) {
+            steps.push(Step {
+                key: None,
+                expected: format!("Err({error})"),
+            });
+            open = None;
+            busy = false;
+        }
+    }
+
+    Ok(steps)
+}
+
+/// Line without the timestamp and colors of `cos-tools log`
+fn clean(line: &str) -> String {
+    let mut clean = String::with_capacity(line.len());
+    let mut chars = line.trim_end_matches('\r').chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip the sequence up to its final letter, e.g. `\x1b[36m`
+            chars.by_ref().find(char::is_ascii_alphabetic);
+        } else {
+            clean.push(c);
+        }
+    }
+
+    match clean.split_once("] ") {
+        Some((time, rest)) if time.starts_with('[') => rest.to_owned(),
+        _ => clean,
+    }
+}
+
+/// Key in the test for the name the firmware logs
+fn expr(name: &str) -> Option<String> {
+    if let [digit @ b'0'..=b'9'] = name.as_bytes() {
+        return Some(format!("Key::Num({})", digit - b'0'));
+    }
+
+    KEYS.iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, expr)| (*expr).to_owned())
+}
+
+/// Error of `key` logged as `key: error`, or of a long operator as a bare `error` with `None`
+fn error(line: &str, key: Option<&str>) -> Option<&'static str> {
+    let error = match key {
+        Some(key) => line.strip_prefix(key)?.strip_prefix(": ")?,
+        None => line,
+    };
+
+    ERRORS
+        .iter()
+        .find(|(name, _)| *name == error)
+        .map(|(_, error)| *error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Step, clean, error, expr, parse, run};
+
+    fn steps(log: &str) -> Vec<(Option<String>, String)> {
+        parse(log.as_bytes())
+            .expect("the log parses")
+            .into_iter()
+            .map(|Step { key, expected }| (key, expected))
+            .collect()
+    }
+
+    fn step(key: Option<&str>, expected: &str) -> (Option<String>, String) {
+        (key.map(str::to_owned), expected.to_owned())
+    }
+
+    #[test]
+    fn test_clean() {
+        assert_eq!(clean("pressed 1"), "pressed 1");
+        assert_eq!(clean("pressed 1\r"), "pressed 1");
+        assert_eq!(clean("[    1.200] pressed 1"), "pressed 1");
+        assert_eq!(
+            clean("\x1b[2m[    1.200]\x1b[0m \x1b[36m=\x1b[0m: div by zero"),
+            "=: div by zero"
+        );
+        // Only a leading timestamp is dropped
+        assert_eq!(clean("pressed ] x"), "pressed ] x");
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(expr("7").as_deref(), Some("Key::Num(7)"));
+        assert_eq!(expr("sqrt").as_deref(), Some("Key::UnOp(UnOp::Sqrt)"));
+        assert_eq!(
+            expr("alarm >").as_deref(),
+            Some("Key::Alarm(AlarmMode::Above)")
+        );
+        assert_eq!(expr("12"), None);
+        assert_eq!(expr("nope"), None);
+
+        assert_eq!(error("+: overflow", Some("+")), Some("CalcError::Overflow"));
+        assert_eq!(error("+: overflow", Some("-")), None);
+        assert_eq!(error("+: tempo", Some("+")), None);
+        assert_eq!(error("cancelled", None), Some("CalcError::Cancelled"));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            steps("boot: ok\npressed 1\npressed +\npressed 2\npressed =\n= 3\n"),
+            [
+                step(Some("Key::Num(1)"), "Ok(None)"),
+                step(Some("Key::BinOp(BinOp::Add)"), "Ok(None)"),
+                step(Some("Key::Num(2)"), "Ok(None)"),
+                step(Some("Key::Result"), "Ok(Some(num(\"3\")))"),
+            ]
+        );
+        // Readouts after the number are skipped, empty cells don't reach the calculator
+        assert_eq!(
+            steps("pressed\npressed time\n= 1.5 (1:30:00)\npressed /\n/: no operator"),
+            [
+                step(Some("Key::Time"), "Ok(Some(num(\"1.5\")))"),
+                step(Some("Key::BinOp(BinOp::Div)"), "Err(CalcError::Calc)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_long() {
+        assert_eq!(
+            steps("pressed sin\n= 0.84\npressed cos\npressed 1\npressed tan\ncancelled"),
+            [
+                step(Some("Key::UnOp(UnOp::Sin)"), "Ok(None)"),
+                step(None, "Ok(Some(num(\"0.84\")))"),
+                step(Some("Key::UnOp(UnOp::Cos)"), "Ok(None)"),
+                step(Some("Key::Num(1)"), "Ok(None)"),
+                step(Some("Key::UnOp(UnOp::Tan)"), "Ok(None)"),
+                step(None, "Err(CalcError::Cancelled)"),
+            ]
+        );
+        // A key that fails right away starts nothing to finish
+        assert_eq!(
+            steps("pressed sin\nsin: no operator\ncancelled"),
+            [step(Some("Key::UnOp(UnOp::Sin)"), "Err(CalcError::Calc)")]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let message = |log: &str| {
+            parse(log.as_bytes())
+                .err()
+                .map(|e| e.to_string())
+                .expect("the log is rejected")
+        };
+
+        assert_eq!(
+            message("pressed 1\npressed A2"),
+            "line 2: `A2` reads the hardware, can't be replayed"
+        );
+        assert_eq!(message("pressed nope"), "line 1: unknown key `nope`");
+        assert_eq!(message("= 3"), "line 1: result without a key");
+        assert_eq!(
+            message("pressed =\n= 3\n= 4"),
+            "line 3: result without a key"
+        );
+        assert_eq!(message("pressed =\n= x"), "line 2: invalid result `x`");
+    }
+
+    #[test]
+    fn test_run() {
+        let generate = |log: &str| {
+            let mut out = Vec::new();
+            run(log.as_bytes(), &mut out, "session").expect("the log parses");
+            String::from_utf8(out).expect("the test is UTF-8")
+        };
+
+        let test = generate("pressed 1\npressed neg\n= -1\npressed sin\n= -0.84\n");
+        assert!(test.contains(
+            "use cos::{CalcError, Calculator, Key, UnOp, config::FRACTION_COUNT, num::Num};\n"
+        ));
+        assert!(test.contains(
+            "const SESSION: [Step; 4] = [
+    (Some(Key::Num(1)), Ok(None)),
+    (Some(Key::UnOp(UnOp::Neg)), Ok(Some(num(\"-1\")))),
+    (Some(Key::UnOp(UnOp::Sin)), Ok(None)),
+    (None, Ok(Some(num(\"-0.84\")))),
+];
+"
+        ));
+        assert!(test.contains("#[test]\nfn session() {\n    replay(&SESSION);\n}\n"));
+
+        // Only the items the keys name are imported
+        let test = generate("pressed pi\npressed alarm off\n");
+        assert!(test.contains(
+            "use cos::{CalcError, Calculator, Const, Key, alarm::AlarmMode, config::FRACTION_COUNT, \
+             num::Num};\n"
+        ));
+        assert!(generate("").contains("const SESSION: [Step; 0] = [\n];\n"));
+
+        // Sessions with keys of the full layout only are left out of the basic edition
+        let basic = "#![cfg(not(feature = \"basic-layout\"))]";
+        assert!(generate("pressed 1\npressed sin\n= 0.84\n").contains(basic));
+        assert!(!generate("pressed 1\npressed neg\n= -1\n").contains(basic));
+    }
+}
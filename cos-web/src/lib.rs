@@ -0,0 +1,155 @@
+//! The calculator in the browser, with the firmware's `Calculator` and keyboard layout
+//!
+//! Built for `wasm32-unknown-unknown` with `cargo build --release` in this directory, the
+//! page in `www` is a demo and a way to learn the layout without the device. The exports
+//! are plain functions, text is passed through a buffer in the wasm memory:
+//!
+//! - `key_in(x, y)` presses the key in the cell at (x, y), y counts rows from the bottom
+//!   like on the device; returns whether a key was there
+//! - `get_display_string()` writes what the calculator shows, the number being entered
+//!   with the pending operator, the result or the error, and returns its length
+//! - `cell_name(x, y)` writes the name of the key in the cell, nothing for the others, and
+//!   returns its length
+//! - `text_ptr()` is where the last two wrote their UTF-8 text
+//! - `layout_cols()` and `layout_rows()` give the size of the layout
+//!
+//! There are no sensors, live sources read 0, and long operators are computed right away.
+
+#![no_std]
+#![feature(sync_unsafe_cell)]
+
+use core::{cell::SyncUnsafeCell, convert::Infallible, panic::PanicInfo};
+
+use cos::{
+    CalcError, Calculator,
+    config::{FRACTION_COUNT, keyboard_layout},
+    layout::Cell,
+    num::Num,
+};
+use ufmt::{uWrite, uwrite};
+
+cos::plugins!();
+
+/// Bytes of text passed at most, enough for two numbers and an operator
+const TEXT_LEN: usize = 96;
+
+/// What [`get_display_string`] shows
+#[derive(Clone, Copy)]
+enum Shown {
+    /// Operands and the pending operator
+    Entry,
+    Result(Num<FRACTION_COUNT>),
+    Error(CalcError),
+}
+
+/// UTF-8 text handed to JavaScript, cut at [`TEXT_LEN`]
+struct Text {
+    bytes: [u8; TEXT_LEN],
+    len: usize,
+}
+
+impl uWrite for Text {
+    type Error = Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Infallible> {
+        let end = (self.len + s.len()).min(TEXT_LEN);
+        self.bytes[self.len..end].copy_from_slice(&s.as_bytes()[..end - self.len]);
+        self.len = end;
+        Ok(())
+    }
+}
+
+struct Demo {
+    calc: Calculator<FRACTION_COUNT>,
+    shown: Shown,
+    text: Text,
+}
+
+static DEMO: SyncUnsafeCell<Demo> = SyncUnsafeCell::new(Demo {
+    calc: Calculator::new(),
+    shown: Shown::Entry,
+    text: Text {
+        bytes: [0; TEXT_LEN],
+        len: 0,
+    },
+});
+
+fn demo() -> &'static mut Demo {
+    // SAFETY: the page calls the exports one at a time on its only thread, and none of them
+    // keeps the reference past its return.
+    unsafe { &mut *DEMO.get() }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn key_in(x: u8, y: u8) -> bool {
+    let Cell::Key(key) = Cell::at((x, y)) else {
+        return false;
+    };
+
+    let demo = demo();
+    demo.calc.refresh(|_| Num::ZERO);
+    let mut result = demo.calc.handle_input(key);
+    while demo.calc.is_busy() {
+        result = demo.calc.poll();
+    }
+
+    demo.shown = match result {
+        Ok(Some(v)) => Shown::Result(v),
+        Ok(None) => Shown::Entry,
+        Err(e) => Shown::Error(e),
+    };
+    true
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn get_display_string() -> usize {
+    let demo = demo();
+    let text = &mut demo.text;
+    text.len = 0;
+
+    let Ok(()) = match demo.shown {
+        Shown::Entry => {
+            let state = demo.calc.state();
+            if let Some(op) = state.op {
+                uwrite!(text, "{} {} {}", state.a, op.name(), state.b)
+            } else {
+                uwrite!(text, "{}", state.a)
+            }
+        }
+        Shown::Result(v) => uwrite!(text, "{}", v),
+        Shown::Error(e) => uwrite!(text, "{}", e),
+    };
+    text.len
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn cell_name(x: u8, y: u8) -> usize {
+    let text = &mut demo().text;
+    text.len = 0;
+
+    if let Cell::Key(key) = Cell::at((x, y)) {
+        let Ok(()) = uwrite!(text, "{}", key);
+    }
+    text.len
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn text_ptr() -> *const u8 {
+    demo().text.bytes.as_ptr()
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn layout_cols() -> usize {
+    keyboard_layout().rows[0].len()
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn layout_rows() -> usize {
+    keyboard_layout().rows.len()
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo<'_>) -> ! {
+    // Traps, the page sees a `RuntimeError`
+    core::arch::wasm32::unreachable()
+}
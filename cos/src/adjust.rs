@@ -0,0 +1,354 @@
+//! Settings stepped through with the stick instead of typed, see [`Key::Adjust`](crate::Key::Adjust)
+//!
+//! Up and down work like a rotary knob: a held direction repeats, and the longer it is
+//! held the bigger the steps get. The value is read out whenever it rests for a while,
+//! so it can be followed without looking at anything.
+
+use ufmt::derive::uDebug;
+
+#[cfg(feature = "rtc")]
+use crate::date::{FIRST_YEAR, LAST_YEAR};
+#[cfg(feature = "dfplayer")]
+use crate::dfplayer::MAX_VOLUME;
+use crate::{
+    clock::Clock,
+    config::{ADJUST_ECHO_MS, TEMPO_RANGE, TEMPO_STEP, THRESHOLD_RANGE},
+    input::{Dir, Repeat},
+    lang::Text,
+    num::Num,
+};
+
+/// Repeats after which each step is ten times bigger, again and again
+const ACCELERATE_EVERY: u8 = 8;
+
+/// Setting [`Adjuster`] changes
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    /// Vibro output speed in percent of the default
+    Tempo,
+    /// Fractional digits entered and shown
+    Precision,
+    /// Number the alarm compares results with
+    Threshold,
+    /// 1 runs the [`tutorial`](crate::tutorial) once saved, 0 leaves it
+    Tutorial,
+    /// Loudness of the `DFPlayer`, with the `dfplayer` feature
+    #[cfg(feature = "dfplayer")]
+    Volume,
+    /// Fields of the clock's date and time, with the `rtc` feature, see [`rtc`](crate::rtc)
+    #[cfg(feature = "rtc")]
+    Year,
+    #[cfg(feature = "rtc")]
+    Month,
+    #[cfg(feature = "rtc")]
+    Day,
+    #[cfg(feature = "rtc")]
+    Hour,
+    /// Saving a changed minute starts it at second 0
+    #[cfg(feature = "rtc")]
+    Minute,
+}
+
+/// Values a setting takes, `min..=max` moved through in multiples of `step`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Bounds<const F: u8> {
+    pub min: Num<F>,
+    pub max: Num<F>,
+    pub step: Num<F>,
+}
+
+impl Setting {
+    pub const ALL: &[Self] = &[
+        Self::Tempo,
+        Self::Precision,
+        Self::Threshold,
+        Self::Tutorial,
+        #[cfg(feature = "dfplayer")]
+        Self::Volume,
+        #[cfg(feature = "rtc")]
+        Self::Year,
+        #[cfg(feature = "rtc")]
+        Self::Month,
+        #[cfg(feature = "rtc")]
+        Self::Day,
+        #[cfg(feature = "rtc")]
+        Self::Hour,
+        #[cfg(feature = "rtc")]
+        Self::Minute,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> Text {
+        match self {
+            Self::Tempo => Text::Tempo,
+            Self::Precision => Text::Precision,
+            Self::Threshold => Text::Threshold,
+            Self::Tutorial => Text::Tutorial,
+            #[cfg(feature = "dfplayer")]
+            Self::Volume => Text::Volume,
+            #[cfg(feature = "rtc")]
+            Self::Year => Text::Year,
+            #[cfg(feature = "rtc")]
+            Self::Month => Text::Month,
+            #[cfg(feature = "rtc")]
+            Self::Day => Text::Day,
+            #[cfg(feature = "rtc")]
+            Self::Hour => Text::Hour,
+            #[cfg(feature = "rtc")]
+            Self::Minute => Text::Minute,
+        }
+    }
+
+    /// Whether it is a field of the clock's date and time, left out while there is no clock
+    #[cfg(feature = "rtc")]
+    #[must_use]
+    pub const fn is_clock(self) -> bool {
+        matches!(
+            self,
+            Self::Year | Self::Month | Self::Day | Self::Hour | Self::Minute
+        )
+    }
+
+    #[must_use]
+    pub fn bounds<const F: u8>(self) -> Bounds<F> {
+        match self {
+            Self::Tempo => Bounds {
+                min: Num::from_int(TEMPO_RANGE.0.into()),
+                max: Num::from_int(TEMPO_RANGE.1.into()),
+                step: Num::from_int(TEMPO_STEP.into()),
+            },
+            Self::Precision => Bounds {
+                min: Num::ZERO,
+                max: Num::from_int(F.into()),
+                step: Num::ONE,
+            },
+            // Smallest digit, held it speeds up to whole numbers and beyond
+            Self::Threshold => Bounds {
+                min: Num::from_int(THRESHOLD_RANGE.0),
+                max: Num::from_int(THRESHOLD_RANGE.1),
+                step: Num::from_raw(1),
+            },
+            Self::Tutorial => Bounds {
+                min: Num::ZERO,
+                max: Num::ONE,
+                step: Num::ONE,
+            },
+            #[cfg(feature = "dfplayer")]
+            Self::Volume => Bounds {
+                min: Num::ZERO,
+                max: Num::from_int(MAX_VOLUME.into()),
+                step: Num::ONE,
+            },
+            #[cfg(feature = "rtc")]
+            Self::Year => Bounds {
+                min: Num::from_int(FIRST_YEAR.into()),
+                max: Num::from_int(LAST_YEAR.into()),
+                step: Num::ONE,
+            },
+            // Days past the end of a month are moved back to its last
+            #[cfg(feature = "rtc")]
+            Self::Month | Self::Day => Bounds {
+                min: Num::ONE,
+                max: Num::from_int(if self == Self::Month { 12 } else { 31 }),
+                step: Num::ONE,
+            },
+            #[cfg(feature = "rtc")]
+            Self::Hour | Self::Minute => Bounds {
+                min: Num::ZERO,
+                max: Num::from_int(if self == Self::Hour { 23 } else { 59 }),
+                step: Num::ONE,
+            },
+        }
+    }
+
+    /// Setting after this one, back to the first after the last
+    #[must_use]
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&s| s == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// Setting before this one, the last before the first
+    #[must_use]
+    pub fn prev(self) -> Self {
+        let i = Self::ALL.iter().position(|&s| s == self).unwrap_or(0);
+        Self::ALL[(i + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// Value of one [`Setting`] being adjusted
+pub struct Adjuster<const F: u8> {
+    setting: Setting,
+    bounds: Bounds<F>,
+    value: Num<F>,
+    /// Up or down held
+    repeat: Repeat,
+    /// When the value last changed or was read out
+    echoed: u32,
+}
+
+impl<const F: u8> Adjuster<F> {
+    /// Start adjusting `setting` from its current `value`
+    #[must_use]
+    pub fn new(setting: Setting, value: Num<F>, clock: &impl Clock) -> Self {
+        let bounds = setting.bounds();
+
+        Self {
+            setting,
+            bounds,
+            value: value.clamp(bounds.min, bounds.max),
+            repeat: Repeat::new(),
+            echoed: clock.now_ms(),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn setting(&self) -> Setting {
+        self.setting
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn value(&self) -> Num<F> {
+        self.value
+    }
+
+    /// Feed the stick direction, returns the new value if it changed
+    ///
+    /// Should be called on every main loop iteration, left and right are up to the caller.
+    pub fn update(&mut self, clock: &impl Clock, dir: Dir) -> Option<Num<F>> {
+        let up = match dir {
+            Dir::Up => true,
+            Dir::Down => false,
+            _ => {
+                self.repeat.update(clock, Dir::Center);
+                return None;
+            }
+        };
+        let repeats = self.repeat.update(clock, dir)?;
+
+        let step = self.step(repeats);
+        let value = if up {
+            self.value.saturating_add(step)
+        } else {
+            self.value.saturating_sub(step)
+        }
+        .clamp(self.bounds.min, self.bounds.max);
+        if value == self.value {
+            return None;
+        }
+
+        self.value = value;
+        self.echoed = clock.now_ms();
+        Some(value)
+    }
+
+    /// Value to read out, once it rested for [`ADJUST_ECHO_MS`] and again every as long
+    pub fn echo(&mut self, clock: &impl Clock) -> Option<Num<F>> {
+        if clock.elapsed_since(self.echoed) < ADJUST_ECHO_MS {
+            return None;
+        }
+
+        self.echoed = clock.now_ms();
+        Some(self.value)
+    }
+
+    /// Step after `repeats`, ten times bigger every [`ACCELERATE_EVERY`] repeats but at most
+    /// a tenth of the range
+    fn step(&self, repeats: u8) -> Num<F> {
+        let limit =
+            (self.bounds.max.saturating_sub(self.bounds.min)).saturating_div(Num::from_int(10));
+
+        let mut step = self.bounds.step;
+        for _ in 0..repeats / ACCELERATE_EVERY {
+            match step.checked_mul(Num::from_int(10)) {
+                Some(bigger) if bigger <= limit => step = bigger,
+                _ => break,
+            }
+        }
+
+        step
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ACCELERATE_EVERY, Adjuster, Setting};
+    use crate::{
+        clock::{Clock as _, VirtualClock},
+        config::{ADJUST_ECHO_MS, TEMPO_RANGE},
+        input::Dir,
+        num::Num,
+    };
+
+    #[test]
+    fn test_step() {
+        let mut clock = VirtualClock::new();
+        let mut adjuster = Adjuster::<2>::new(Setting::Tempo, Num::from_int(100), &clock);
+
+        assert_eq!(adjuster.update(&clock, Dir::Up), Some(Num::from_int(125)));
+        // Held, it waits before repeating
+        clock.advance(100);
+        assert_eq!(adjuster.update(&clock, Dir::Up), None);
+        clock.advance(400);
+        assert_eq!(adjuster.update(&clock, Dir::Up), Some(Num::from_int(150)));
+
+        // Released and pushed again it steps right away
+        assert_eq!(adjuster.update(&clock, Dir::Center), None);
+        assert_eq!(adjuster.update(&clock, Dir::Down), Some(Num::from_int(125)));
+        // Left and right are up to the caller
+        assert_eq!(adjuster.update(&clock, Dir::Left), None);
+        assert_eq!(adjuster.value(), Num::from_int(125));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut clock = VirtualClock::new();
+        let max = Num::from_int(TEMPO_RANGE.1.into());
+        let mut adjuster = Adjuster::<2>::new(Setting::Tempo, Num::from_int(1000), &clock);
+        assert_eq!(adjuster.value(), max);
+
+        assert_eq!(adjuster.update(&clock, Dir::Up), None);
+        clock.advance(1000);
+        assert_eq!(adjuster.update(&clock, Dir::Up), None);
+        assert_eq!(adjuster.value(), max);
+    }
+
+    #[test]
+    fn test_accelerate() {
+        let mut clock = VirtualClock::new();
+        let mut adjuster = Adjuster::<2>::new(Setting::Threshold, Num::ZERO, &clock);
+
+        // Every repeat of the first round steps by the smallest digit
+        let mut last = Num::ZERO;
+        for _ in 0..ACCELERATE_EVERY {
+            last = adjuster.update(&clock, Dir::Up).expect("a step");
+            clock.advance(500);
+        }
+        assert_eq!(last, Num::from_raw(ACCELERATE_EVERY.into()));
+
+        // Then by ten of them
+        let next = adjuster.update(&clock, Dir::Up).expect("a step");
+        assert_eq!(next.saturating_sub(last), Num::from_raw(10));
+    }
+
+    #[test]
+    fn test_echo() {
+        let mut clock = VirtualClock::new();
+        let mut adjuster = Adjuster::<2>::new(Setting::Tempo, Num::from_int(100), &clock);
+
+        clock.advance(ADJUST_ECHO_MS - 1);
+        assert_eq!(adjuster.echo(&clock), None);
+        clock.advance(1);
+        assert_eq!(adjuster.echo(&clock), Some(Num::from_int(100)));
+
+        // A change puts the readout off again
+        clock.advance(ADJUST_ECHO_MS - 1);
+        assert!(adjuster.update(&clock, Dir::Down).is_some());
+        clock.delay_ms(1);
+        assert_eq!(adjuster.echo(&clock), None);
+        clock.advance(ADJUST_ECHO_MS);
+        assert_eq!(adjuster.echo(&clock), Some(Num::from_int(75)));
+    }
+}
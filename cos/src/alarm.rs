@@ -0,0 +1,56 @@
+use ufmt::derive::uDebug;
+
+use crate::{
+    lang::{Symbol, Text, Word},
+    num::Num,
+};
+
+/// When a computed result should trigger the alarm
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AlarmMode {
+    Off,
+    Above,
+    Below,
+}
+
+impl AlarmMode {
+    #[must_use]
+    pub const fn name(self) -> Word {
+        match self {
+            Self::Off => Word::Text(Text::Off),
+            Self::Above => Word::Symbol(Symbol::Gt),
+            Self::Below => Word::Symbol(Symbol::Lt),
+        }
+    }
+}
+
+/// Threshold checked against every computed result
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Alarm<const F: u8> {
+    pub mode: AlarmMode,
+    pub threshold: Num<F>,
+}
+
+impl<const F: u8> Default for Alarm<F> {
+    fn default() -> Self {
+        Self::OFF
+    }
+}
+
+impl<const F: u8> Alarm<F> {
+    pub const OFF: Self = Self {
+        mode: AlarmMode::Off,
+        threshold: Num::ZERO,
+    };
+
+    /// Whether `result` crosses the threshold
+    #[must_use]
+    pub fn is_triggered(&self, result: Num<F>) -> bool {
+        match self.mode {
+            AlarmMode::Off => false,
+            AlarmMode::Above => result > self.threshold,
+            AlarmMode::Below => result < self.threshold,
+        }
+    }
+}
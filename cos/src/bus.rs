@@ -0,0 +1,140 @@
+#[cfg(feature = "avr")]
+use core::cell::RefCell;
+
+#[cfg(feature = "avr")]
+use avr_device::interrupt::{self, Mutex};
+use heapless::Deque;
+use ufmt::derive::uDebug;
+
+use crate::{CalcError, Key, config::FRACTION_COUNT, layout::Cell, num::Num};
+
+/// Events queued per topic at most, every stage drains its topic once per loop iteration
+const BUS_LEN: usize = 8;
+
+/// Joystick input, after it was turned into moves and presses
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum InputEvent {
+    /// Cursor moved onto the cell
+    Move(Cell),
+    /// Button pressed with the cursor on the cell
+    Press(Cell),
+}
+
+/// What the calculator made of the input
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CalcEvent {
+    /// Key was accepted by [`Calculator::handle_input`](crate::Calculator::handle_input)
+    Key(Key),
+    /// Result was computed
+    Result(Num<FRACTION_COUNT>),
+    /// Key or long operator failed
+    Error(CalcError),
+}
+
+/// Something to let the user feel, every output picks the requests it can show
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OutputRequest {
+    /// Cut what is playing short
+    Stop,
+    /// Silence for `ms`
+    Pause(u16),
+    /// Confirm an action with `count` pulses of `ms`
+    Blink {
+        count: u8,
+        ms: u16,
+    },
+    /// Frequent feedback, a single soft pulse of `ms`
+    Tick(u16),
+    /// Key was accepted, only said, see [`speech`](crate::speech)
+    Entered(Key),
+    Error,
+    /// Calculation failed, played like [`OutputRequest::Error`]
+    Failed(CalcError),
+    /// Comparison result
+    Truth(bool),
+    Alarm,
+    /// Saved settings failed their checksum and were reset to defaults
+    Corrupted,
+    Number(Num<FRACTION_COUNT>),
+    /// Seconds read out as hours, minutes and seconds
+    Time(Num<FRACTION_COUNT>),
+    /// Step `n` of the [`tutorial`](crate::tutorial) begins
+    Lesson(u8),
+    /// Step of the tutorial was passed
+    Passed,
+}
+
+/// Queues of every topic, the firmware shares one through [`publish`] and [`take`]
+pub struct Bus {
+    input: Deque<InputEvent, BUS_LEN>,
+    calc: Deque<CalcEvent, BUS_LEN>,
+    output: Deque<OutputRequest, BUS_LEN>,
+}
+
+impl Default for Bus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bus {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            input: Deque::new(),
+            calc: Deque::new(),
+            output: Deque::new(),
+        }
+    }
+
+    /// Queue `event` for the stage reading its topic, it is dropped if the queue is full
+    pub fn publish<T: Topic>(&mut self, event: T) {
+        T::queue(self).push_back(event).ok();
+    }
+
+    /// Oldest event of the topic, `None` once the queue is drained
+    pub fn take<T: Topic>(&mut self) -> Option<T> {
+        T::queue(self).pop_front()
+    }
+}
+
+/// Event type with its own queue on the [`Bus`]
+pub trait Topic: Sized {
+    fn queue(bus: &mut Bus) -> &mut Deque<Self, BUS_LEN>;
+}
+
+impl Topic for InputEvent {
+    fn queue(bus: &mut Bus) -> &mut Deque<Self, BUS_LEN> {
+        &mut bus.input
+    }
+}
+
+impl Topic for CalcEvent {
+    fn queue(bus: &mut Bus) -> &mut Deque<Self, BUS_LEN> {
+        &mut bus.calc
+    }
+}
+
+impl Topic for OutputRequest {
+    fn queue(bus: &mut Bus) -> &mut Deque<Self, BUS_LEN> {
+        &mut bus.output
+    }
+}
+
+#[cfg(feature = "avr")]
+static BUS: Mutex<RefCell<Bus>> = Mutex::new(RefCell::new(Bus::new()));
+
+/// Queue `event` for the stage reading its topic, it is dropped if the queue is full
+///
+/// Safe to call from interrupt handlers.
+#[cfg(feature = "avr")]
+pub fn publish<T: Topic>(event: T) {
+    interrupt::free(|cs| BUS.borrow(cs).borrow_mut().publish(event));
+}
+
+/// Oldest event of the topic, `None` once the queue is drained
+#[cfg(feature = "avr")]
+#[must_use]
+pub fn take<T: Topic>() -> Option<T> {
+    interrupt::free(|cs| BUS.borrow(cs).borrow_mut().take())
+}
@@ -0,0 +1,65 @@
+use arduino_hal::pac::TC1;
+
+use crate::pattern;
+
+/// TC1 ticks per second with the /1024 prescaler at 16 MHz
+const TICKS_PER_SECOND: u16 = 15625;
+/// Time between busy pulses
+const PERIOD: u16 = TICKS_PER_SECOND / 4;
+/// Length of one busy pulse
+const PULSE: u16 = TICKS_PER_SECOND / 32;
+
+/// Short vibro pulse every 250 ms while a computation runs, so a long one doesn't look like a hang
+///
+/// TC1 compare A starts a pulse and compare B ends it. The firmware has to provide
+/// `TIMER1_COMPA` and `TIMER1_COMPB` handlers calling [`pattern::busy_pulse`], which
+/// leaves the vibro to the [`Player`](pattern::Player) while it plays.
+pub struct Busy {
+    tc1: TC1,
+}
+
+impl Busy {
+    #[must_use]
+    pub const fn new(tc1: TC1) -> Self {
+        Self { tc1 }
+    }
+
+    /// Run `f` with the busy pulses on
+    ///
+    /// Anything faster than 250 ms finishes before the first pulse.
+    pub fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.start();
+        let result = f();
+        self.stop();
+
+        result
+    }
+
+    /// Start the busy pulses, they go on until [`Busy::stop`]
+    pub fn start(&self) {
+        // CTC mode, counter restarts on compare A
+        self.tc1.tccr1a().write(|w| w.wgm1().bits(0b00));
+        self.tc1.ocr1a().write(|w| w.set(PERIOD));
+        self.tc1.ocr1b().write(|w| w.set(PULSE));
+        self.tc1.tcnt1().write(|w| w.set(0));
+        self.tc1
+            .timsk1()
+            .write(|w| w.ocie1a().set_bit().ocie1b().set_bit());
+        self.tc1
+            .tccr1b()
+            .write(|w| w.wgm1().bits(0b01).cs1().prescale_1024());
+
+        // SAFETY: the TC1 handlers only touch the vibro pin behind the pattern queue's `Mutex`,
+        // main() and the TC2 handler share it the same way.
+        unsafe { avr_device::interrupt::enable() };
+    }
+
+    /// Stop the busy pulses, does nothing if they are not running
+    pub fn stop(&self) {
+        // Interrupts stay on for the pattern player
+        self.tc1.tccr1b().reset();
+        self.tc1.timsk1().reset();
+        // Stopped in the middle of a pulse
+        pattern::busy_pulse(false);
+    }
+}
@@ -0,0 +1,41 @@
+//! Copies of the entry in EEPROM every few seconds, so a reset or a battery blip doesn't lose it
+//!
+//! Power-off saves the state on its own, checkpoints cover what comes without a warning.
+//! The boot asks before restoring one, see [`Storage::load_checkpoint`](crate::storage::Storage::load_checkpoint).
+
+use crate::{State, config::CHECKPOINT_MS};
+
+/// Decides when the state is worth writing to EEPROM again
+pub struct Checkpoint<const F: u8> {
+    /// Last state written, `None` before the first write
+    saved: Option<State<F>>,
+    /// [`Clock::now_ms`](crate::clock::Clock::now_ms) of the last write
+    since: u32,
+}
+
+impl<const F: u8> Default for Checkpoint<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const F: u8> Checkpoint<F> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            saved: None,
+            since: 0,
+        }
+    }
+
+    /// `state` if it changed since the last write and [`CHECKPOINT_MS`] passed, it counts as written
+    pub fn poll(&mut self, now: u32, state: State<F>) -> Option<State<F>> {
+        if now.wrapping_sub(self.since) < CHECKPOINT_MS || self.saved == Some(state) {
+            return None;
+        }
+
+        self.saved = Some(state);
+        self.since = now;
+        Some(state)
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(feature = "avr")]
+use crate::time;
+
+/// Source of time for everything paced or timed
+///
+/// Firmware uses [`AvrClock`], [`VirtualClock`] runs the timing logic in host tests without
+/// waiting.
+pub trait Clock {
+    /// Milliseconds since start, wraps after ~49 days
+    fn now_ms(&self) -> u32;
+
+    /// Wait for `ms` milliseconds
+    fn delay_ms(&mut self, ms: u16);
+
+    /// Milliseconds passed since `start`, a previous [`Clock::now_ms`]
+    fn elapsed_since(&self, start: u32) -> u32 {
+        self.now_ms().wrapping_sub(start)
+    }
+}
+
+/// Clock reading [`time::millis`], delays busy-wait
+///
+/// Needs [`time::start`], before that the time stands at 0.
+#[cfg(feature = "avr")]
+#[derive(Default)]
+pub struct AvrClock;
+
+#[cfg(feature = "avr")]
+impl AvrClock {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "avr")]
+impl Clock for AvrClock {
+    fn now_ms(&self) -> u32 {
+        time::millis()
+    }
+
+    fn delay_ms(&mut self, ms: u16) {
+        arduino_hal::delay_ms(ms.into());
+    }
+}
+
+/// Clock whose delays return right away, time only moves by [`VirtualClock::advance`] and delays
+#[derive(Default)]
+pub struct VirtualClock {
+    now: u32,
+}
+
+impl VirtualClock {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { now: 0 }
+    }
+
+    /// Let `ms` milliseconds pass, like work done between delays
+    pub const fn advance(&mut self, ms: u32) {
+        self.now = self.now.wrapping_add(ms);
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now_ms(&self) -> u32 {
+        self.now
+    }
+
+    fn delay_ms(&mut self, ms: u16) {
+        self.advance(ms.into());
+    }
+}
+
+/// Period of a loop that should run every `ms` however long its work takes
+pub struct Period {
+    ms: u16,
+    /// When the current period began
+    start: u32,
+}
+
+impl Period {
+    #[must_use]
+    pub fn new(clock: &impl Clock, ms: u16) -> Self {
+        Self {
+            ms,
+            start: clock.now_ms(),
+        }
+    }
+
+    /// Wait out the rest of the period, not at all if the work took longer, and begin the next
+    pub fn wait(&mut self, clock: &mut impl Clock) {
+        let left = u32::from(self.ms).saturating_sub(clock.elapsed_since(self.start));
+        // Fits, it is at most `ms`
+        clock.delay_ms(left as u16);
+        self.start = clock.now_ms();
+    }
+}
+
+/// Deadline `ms` after the last [`Timeout::restart`], e.g. for turning off after a while
+/// without input
+pub struct Timeout {
+    ms: u32,
+    start: u32,
+}
+
+impl Timeout {
+    #[must_use]
+    pub fn new(clock: &impl Clock, ms: u32) -> Self {
+        Self {
+            ms,
+            start: clock.now_ms(),
+        }
+    }
+
+    /// Count the `ms` from now again
+    pub fn restart(&mut self, clock: &impl Clock) {
+        self.start = clock.now_ms();
+    }
+
+    #[must_use]
+    pub fn is_over(&self, clock: &impl Clock) -> bool {
+        clock.elapsed_since(self.start) >= self.ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Clock as _, Period, Timeout, VirtualClock};
+
+    #[test]
+    fn test_virtual_clock() {
+        let mut clock = VirtualClock::new();
+        clock.advance(5);
+        clock.delay_ms(10);
+        assert_eq!(clock.now_ms(), 15);
+        assert_eq!(clock.elapsed_since(10), 5);
+
+        // Wraps like the AVR millis counter
+        clock.advance(u32::MAX);
+        assert_eq!(clock.now_ms(), 14);
+        assert_eq!(clock.elapsed_since(u32::MAX - 5), 20);
+    }
+
+    #[test]
+    fn test_period() {
+        let mut clock = VirtualClock::new();
+        let mut period = Period::new(&clock, 10);
+
+        // Work that took 3 ms leaves 7 to wait
+        clock.advance(3);
+        period.wait(&mut clock);
+        assert_eq!(clock.now_ms(), 10);
+
+        period.wait(&mut clock);
+        assert_eq!(clock.now_ms(), 20);
+
+        // Work longer than the period isn't waited for
+        clock.advance(25);
+        period.wait(&mut clock);
+        assert_eq!(clock.now_ms(), 45);
+        clock.advance(4);
+        period.wait(&mut clock);
+        assert_eq!(clock.now_ms(), 55);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mut clock = VirtualClock::new();
+        let mut timeout = Timeout::new(&clock, 1000);
+
+        clock.advance(999);
+        assert!(!timeout.is_over(&clock));
+        clock.advance(1);
+        assert!(timeout.is_over(&clock));
+
+        timeout.restart(&clock);
+        assert!(!timeout.is_over(&clock));
+        clock.advance(1500);
+        assert!(timeout.is_over(&clock));
+
+        // Across the wrap of the counter
+        clock.advance(u32::MAX - 2000);
+        timeout.restart(&clock);
+        clock.advance(999);
+        assert!(!timeout.is_over(&clock));
+        clock.advance(1);
+        assert!(timeout.is_over(&clock));
+    }
+}
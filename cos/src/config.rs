@@ -1,20 +1,76 @@
-use crate::{BinOp, Const, Key, UnOp};
+use crate::{BinOp, Key, UnOp, input::StickRole, layout::Layout, num::Num};
 
 pub const FRACTION_COUNT: u8 = 2;
-// Default pos need to be on number 5
-// Coords is in format (x, y)
-pub const DEFAULT_POS: (u8, u8) = (2, 3);
+/// Fractional digits used inside Taylor series (sin, cos, ln, ...), must be >= `FRACTION_COUNT`
+pub const TAYLOR_FRACTION_COUNT: u8 = 8;
+/// Second joystick on A3 (x) and A4 (y), `None` if it is not connected
+pub const SECOND_STICK: Option<StickRole> = None;
+/// Vibro output speed in percent of the default, see [`StickRole::Tempo`]
+pub const TEMPO_RANGE: (u16, u16) = (50, 200);
+pub const TEMPO_STEP: u16 = 25;
+/// Whole numbers the alarm threshold is adjusted between, see [`Key::Adjust`]
+pub const THRESHOLD_RANGE: (i64, i64) = (-1000, 1000);
+/// Rest after which an adjusted value is read out, and again every as long
+pub const ADJUST_ECHO_MS: u32 = 3000;
+/// Range of the potentiometer on A5, `None` if it is not connected
+pub const SLIDER_RANGE: Option<(Num<FRACTION_COUNT>, Num<FRACTION_COUNT>)> = None;
+/// Hold of the button that turns the device off like [`Key::Power`], `None` to only use the key
+pub const LONG_PRESS_MS: Option<u32> = Some(2000);
+/// Time without input after which the device turns off like [`Key::Power`], `None` to stay
+/// on, e.g. while the alarm watches a live source
+pub const AUTO_OFF_MS: Option<u32> = None;
+/// Results kept for [`Key::Stat`]
+pub const HISTORY_LEN: usize = 8;
+/// Joystick changes kept by [`Key::Record`], 4 bytes of RAM each
+pub const RECORD_LEN: usize = 32;
+/// Volume of the `DFPlayer` Mini from 0 to 30 until one is saved, see `dfplayer`
+pub const DFPLAYER_VOLUME: u8 = 20;
+/// Longest a `DFPlayer` clip may take, the next word starts then even if BUSY stays low
+pub const DFPLAYER_CLIP_MS: u32 = 2000;
+/// Real-time clock on A4 (SDA) and A5 (SCL) with the `rtc` feature, see [`rtc`](crate::rtc)
+#[cfg(feature = "rtc")]
+pub const RTC_CHIP: crate::rtc::Chip = crate::rtc::Chip::Ds3231;
+/// Least time between two checkpoints of the entry in EEPROM, see [`checkpoint`](crate::checkpoint)
+pub const CHECKPOINT_MS: u32 = 5000;
 
+/// Keyboard the cursor moves over, the cursor starts on number 5
+#[cfg(not(feature = "basic-layout"))]
 #[rustfmt::skip]
-#[must_use] 
-pub fn keyboard_layout() -> [[Key; 7]; 7] {
-    [
-        [UnOp::Sin.into(), Key::None,              Const::Phi.into(),    Const::Tau.into(), Const::Sqrt2.into(), Key::None,         Key::None],
-        [UnOp::Cos.into(), Key::None,              Const::EGamma.into(), Const::Pi.into(),  Const::E.into(),     Key::None,         Key::None],
-        [UnOp::Tan.into(), UnOp::Sqrt.into(),      Key::Num(7),          Key::Num(8),       Key::Num(9),         BinOp::Div.into(), Key::None],
-        [Key::None,        UnOp::Neg.into(),       Key::Num(4),          Key::Num(5),       Key::Num(6),         BinOp::Mul.into(), Key::None],
-        [Key::None,        UnOp::Pow2.into(),      Key::Num(1),          Key::Num(2),       Key::Num(3),         BinOp::Add.into(), Key::None],
-        [Key::None,        UnOp::Pow3.into(),      Key::Dot,             Key::Num(0),       Key::Result,         BinOp::Sub.into(), Key::None],
-        [Key::None,        UnOp::Factorial.into(), Key::Clear,           Key::Delete,       Key::Reset,          Key::None,         Key::None],
-    ]
+#[must_use]
+pub fn keyboard_layout() -> Layout<7, 10> {
+    use crate::{Const, Conversion, Source, alarm::AlarmMode, history::Stat, layout::Cell};
+
+    Layout {
+        default_pos: (3, 3),
+        rows: [
+            [UnOp::Sin.into(),        Key::Chords.into(),     Const::Phi.into(),    Const::Tau.into(),  Const::Sqrt2.into(), Source::A2.into(),      Source::Temperature.into(), Key::Record.into(),    Key::Time.into(),     Conversion::KmMi.into()],
+            [UnOp::Cos.into(),        UnOp::Dms.into(),       Const::EGamma.into(), Const::Pi.into(),   Const::E.into(),     Source::Battery.into(), Key::Precision.into(),      Key::Replay.into(),    Key::Rounding.into(), Conversion::CelsiusFahrenheit.into()],
+            [UnOp::Tan.into(),        UnOp::Sqrt.into(),      Key::Num(7).into(),   Key::Num(8).into(), Key::Num(9).into(),  BinOp::Div.into(),      Key::Swap.into(),           Stat::Sum.into(),      UnOp::Recip.into(),   Conversion::KgLb.into()],
+            [AlarmMode::Above.into(), UnOp::Neg.into(),       Key::Num(4).into(),   Key::Num(5).into(), Key::Num(6).into(),  BinOp::Mul.into(),      BinOp::CmpEq.into(),        Stat::Mean.into(),     UnOp::Abs.into(),     BinOp::If.into()],
+            [AlarmMode::Below.into(), UnOp::Pow2.into(),      Key::Num(1).into(),   Key::Num(2).into(), Key::Num(3).into(),  BinOp::Add.into(),      BinOp::CmpLt.into(),        Stat::Min.into(),      Key::Adjust.into(),   Key::ConvertAgain.into()],
+            [AlarmMode::Off.into(),   UnOp::Pow3.into(),      Key::Dot.into(),      Key::Num(0).into(), Key::Result.into(),  BinOp::Sub.into(),      BinOp::CmpGt.into(),        Stat::Max.into(),      Key::Edit.into(),     Cell::Empty],
+            [Key::Help.into(),        UnOp::Factorial.into(), Key::Clear.into(),    Key::Delete.into(), Key::Reset.into(),   Key::Power.into(),      Key::Slider.into(),         Key::Intensity.into(), Cell::Reserved,       Cell::Empty],
+        ],
+    }
+}
+
+/// Compact keyboard with the digits and the four operators only, quicker to learn and to
+/// move over
+///
+/// The unary operators other than [`UnOp::Neg`] don't exist in this edition, so neither
+/// does their math and a layout placing them doesn't compile.
+#[cfg(feature = "basic-layout")]
+#[rustfmt::skip]
+#[must_use]
+pub fn keyboard_layout() -> Layout<5, 4> {
+    Layout {
+        default_pos: (1, 2),
+        rows: [
+            [Key::Clear.into(),  Key::Delete.into(), Key::Reset.into(),  BinOp::Div.into()],
+            [Key::Num(7).into(), Key::Num(8).into(), Key::Num(9).into(), BinOp::Mul.into()],
+            [Key::Num(4).into(), Key::Num(5).into(), Key::Num(6).into(), BinOp::Sub.into()],
+            [Key::Num(1).into(), Key::Num(2).into(), Key::Num(3).into(), BinOp::Add.into()],
+            [Key::Num(0).into(), Key::Dot.into(),    UnOp::Neg.into(),   Key::Result.into()],
+        ],
+    }
 }
@@ -0,0 +1,170 @@
+//! Calendar math for dates and times of day from 2000 to 2099, what a clock chip keeps
+//!
+//! A [`DateTime`] also counts as whole seconds since [`DateTime::EPOCH`], which an `u32`
+//! holds well past 2099, so two of them are compared or subtracted without walking the
+//! calendar, see [`rtc`](crate::rtc).
+
+use ufmt::{Formatter, derive::uDebug, uDisplay, uWrite};
+
+/// Earliest year of a [`DateTime`], clock chips only keep its last two digits
+pub const FIRST_YEAR: u16 = 2000;
+/// Latest year of a [`DateTime`]
+pub const LAST_YEAR: u16 = 2099;
+
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+/// Days in the year before the first of each month, February with 28
+const DAYS_BEFORE: [u16; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+
+/// Whether February of `year` has 29 days
+#[must_use]
+pub const fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+/// Days of `month` (1 to 12) in `year`
+#[must_use]
+pub const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days of `year`
+const fn days_in_year(year: u16) -> u32 {
+    if is_leap_year(year) { 366 } else { 365 }
+}
+
+/// Date and time of day on a 24-hour clock, no time zone
+#[derive(uDebug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    pub year: u16,
+    /// 1 to 12
+    pub month: u8,
+    /// 1 to the days of the month
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl DateTime {
+    /// 2000-01-01 00:00:00, the first [`DateTime`] and where a stopped clock is set from
+    pub const EPOCH: Self = Self {
+        year: FIRST_YEAR,
+        month: 1,
+        day: 1,
+        hour: 0,
+        minute: 0,
+        second: 0,
+    };
+
+    /// Whether every field is in its range, the day within its month
+    #[must_use]
+    pub const fn is_valid(self) -> bool {
+        FIRST_YEAR <= self.year
+            && self.year <= LAST_YEAR
+            && 1 <= self.month
+            && self.month <= 12
+            && 1 <= self.day
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+    }
+
+    /// Same date with the day moved back to the last of its month if that is shorter,
+    /// after the month or the year was changed
+    #[must_use]
+    pub const fn clamp_day(self) -> Self {
+        let last = days_in_month(self.year, self.month);
+        Self {
+            day: if self.day > last { last } else { self.day },
+            ..self
+        }
+    }
+
+    /// Whole seconds since [`DateTime::EPOCH`]
+    #[must_use]
+    pub const fn seconds(self) -> u32 {
+        self.days() * SECONDS_PER_DAY
+            + self.hour as u32 * 3600
+            + self.minute as u32 * 60
+            + self.second as u32
+    }
+
+    /// Date and time `seconds` after [`DateTime::EPOCH`], `None` past [`LAST_YEAR`]
+    #[must_use]
+    pub const fn from_seconds(seconds: u32) -> Option<Self> {
+        let mut days = seconds / SECONDS_PER_DAY;
+        let time = seconds % SECONDS_PER_DAY;
+
+        let mut year = FIRST_YEAR;
+        while days >= days_in_year(year) {
+            days -= days_in_year(year);
+            year += 1;
+        }
+        if year > LAST_YEAR {
+            return None;
+        }
+
+        let mut month = 1;
+        while days >= days_in_month(year, month) as u32 {
+            days -= days_in_month(year, month) as u32;
+            month += 1;
+        }
+
+        Some(Self {
+            year,
+            month,
+            day: days as u8 + 1,
+            hour: (time / 3600) as u8,
+            minute: (time / 60 % 60) as u8,
+            second: (time % 60) as u8,
+        })
+    }
+
+    /// Day of the week, 1 for Monday to 7 for Sunday
+    #[must_use]
+    pub const fn weekday(self) -> u8 {
+        // The epoch is a Saturday
+        ((self.days() + 5) % 7 + 1) as u8
+    }
+
+    /// Days since [`DateTime::EPOCH`] to the start of the day
+    const fn days(self) -> u32 {
+        let years = (self.year - FIRST_YEAR) as u32;
+        // Every fourth year from 2000 on is a leap year until 2100
+        let mut days = years * 365 + years.div_ceil(4);
+        days += DAYS_BEFORE[self.month as usize - 1] as u32;
+        if self.month > 2 && is_leap_year(self.year) {
+            days += 1;
+        }
+
+        days + self.day as u32 - 1
+    }
+}
+
+impl uDisplay for DateTime {
+    /// ISO 8601 without the `T`, like `2026-10-16 14:03:00`
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        ufmt::uwrite!(f, "{}-", self.year)?;
+        for (n, sep) in [
+            (self.month, "-"),
+            (self.day, " "),
+            (self.hour, ":"),
+            (self.minute, ":"),
+            (self.second, ""),
+        ] {
+            // ufmt has no zero padding
+            if n < 10 {
+                f.write_char('0')?;
+            }
+            ufmt::uwrite!(f, "{}{}", n, sep)?;
+        }
+
+        Ok(())
+    }
+}
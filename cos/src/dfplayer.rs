@@ -0,0 +1,161 @@
+//! `DFPlayer` Mini audio module saying entries, results and errors, with the `dfplayer`
+//! feature
+//!
+//! The module's RX is wired to D4 through a 1 kΩ resistor and its BUSY to D5, its TX is
+//! left unconnected: commands are sent one way at 9600 baud by toggling the pin, the only
+//! hardware serial port carries the log. The SD card holds an `mp3` folder with a clip per
+//! [`Word`], see [`Word::clip`]. A word starts once BUSY went high after the one before,
+//! or after [`DFPLAYER_CLIP_MS`] if it never went low, e.g. for a missing clip.
+
+use arduino_hal::{
+    hal::port::{PD4, PD5},
+    port::{
+        Pin,
+        mode::{Input, Output, PullUp},
+    },
+};
+use avr_device::interrupt;
+use heapless::Deque;
+
+use crate::{
+    config::{DFPLAYER_CLIP_MS, DFPLAYER_VOLUME},
+    speech::Word,
+};
+
+/// Microseconds per bit at 9600 baud
+const BIT_US: u32 = 104;
+/// Time the module takes to start after power-up, commands sent earlier are lost
+const BOOT_MS: u32 = 1500;
+/// Time the module takes to pull BUSY low once a clip was started
+const BUSY_DELAY_MS: u32 = 150;
+/// Words queued at most, enough for equals and any number with its sign and point
+const QUEUE_LEN: usize = 32;
+/// Loudest volume the module takes
+pub const MAX_VOLUME: u8 = 30;
+
+/// Play `mp3/NNNN.mp3`
+const PLAY_MP3: u8 = 0x12;
+/// Volume from 0 to [`MAX_VOLUME`]
+const SET_VOLUME: u8 = 0x06;
+const STOP: u8 = 0x16;
+
+/// Words waiting for their clip, played one at a time from [`DfPlayer::poll`]
+pub struct DfPlayer {
+    tx: Pin<Output, PD4>,
+    /// Low while a clip plays
+    busy: Pin<Input<PullUp>, PD5>,
+    queue: Deque<Word, QUEUE_LEN>,
+    /// When the clip playing was started
+    started: Option<u32>,
+    volume: u8,
+    /// Module has booted and got its volume
+    ready: bool,
+}
+
+impl DfPlayer {
+    #[must_use]
+    pub fn new(mut tx: Pin<Output, PD4>, busy: Pin<Input<PullUp>, PD5>) -> Self {
+        // Idle line
+        tx.set_high();
+
+        Self {
+            tx,
+            busy,
+            queue: Deque::new(),
+            started: None,
+            volume: DFPLAYER_VOLUME,
+            ready: false,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn volume(&self) -> u8 {
+        self.volume
+    }
+
+    /// Change the volume, clamped to [`MAX_VOLUME`]; sent once the module has booted
+    pub fn set_volume(&mut self, volume: u8) {
+        self.volume = volume.min(MAX_VOLUME);
+        if self.ready {
+            self.send(SET_VOLUME, self.volume.into());
+        }
+    }
+
+    /// Say `words` after the queued ones, those that don't fit are dropped
+    pub fn say(&mut self, words: impl Iterator<Item = Word>) {
+        for word in words {
+            if self.queue.push_back(word).is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Cut the clip playing short and forget the queued words
+    pub fn stop(&mut self) {
+        self.queue.clear();
+        if self.started.take().is_some() {
+            self.send(STOP, 0);
+        }
+    }
+
+    /// Start the next word once the clip before has ended
+    ///
+    /// Should be called on every main loop iteration.
+    pub fn poll(&mut self, now: u32) {
+        if !self.ready {
+            if now < BOOT_MS {
+                return;
+            }
+            self.send(SET_VOLUME, self.volume.into());
+            self.ready = true;
+        }
+        if let Some(start) = self.started {
+            let elapsed = now.wrapping_sub(start);
+            let playing = elapsed < BUSY_DELAY_MS || self.busy.is_low();
+            if playing && elapsed < DFPLAYER_CLIP_MS {
+                return;
+            }
+        }
+
+        let next = self.queue.pop_front();
+        self.started = next.map(|word| {
+            self.send(PLAY_MP3, word.clip());
+            now
+        });
+    }
+
+    /// Frame of `command` without feedback, checksummed over everything but its ends
+    fn send(&mut self, command: u8, param: u16) {
+        let [hi, lo] = param.to_be_bytes();
+        let mut frame = [0x7E, 0xFF, 0x06, command, 0x00, hi, lo, 0, 0, 0xEF];
+        let sum = frame[1..7]
+            .iter()
+            .fold(0u16, |sum, &byte| sum.wrapping_add(byte.into()));
+        [frame[7], frame[8]] = 0u16.wrapping_sub(sum).to_be_bytes();
+
+        for byte in frame {
+            self.write_byte(byte);
+        }
+    }
+
+    /// Start bit, 8 data bits from the lowest and stop bit
+    fn write_byte(&mut self, byte: u8) {
+        // An interrupt would stretch a bit; a byte takes about a millisecond, so the timer
+        // ticks held back meanwhile are still pending afterwards, not lost
+        interrupt::free(|_| {
+            self.tx.set_low();
+            arduino_hal::delay_us(BIT_US);
+            for i in 0..8 {
+                if (byte >> i) & 1 == 1 {
+                    self.tx.set_high();
+                } else {
+                    self.tx.set_low();
+                }
+                arduino_hal::delay_us(BIT_US);
+            }
+            self.tx.set_high();
+            arduino_hal::delay_us(BIT_US);
+        });
+    }
+}
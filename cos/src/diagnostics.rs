@@ -0,0 +1,132 @@
+//! Counts of the errors users run into, since boot and over the life of the device
+//!
+//! Shows which errors are hit in practice and whether a change to input validation made
+//! them rarer. The totals are kept in EEPROM, at most every [`CHECKPOINT_MS`] so a burst
+//! of errors doesn't wear it out, and read out by the self-test and over serial.
+
+use ufmt::{Formatter, derive::uDebug, uDisplay, uWrite};
+
+use crate::{CalcError, config::CHECKPOINT_MS, lang::Text};
+
+/// Kinds of errors counted
+pub const KINDS: usize = 7;
+
+/// Error counted by [`ErrorCounts`]
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Key or long operator failed
+    Calc(CalcError),
+    /// Cell without a key was pressed
+    Rejected,
+}
+
+impl ErrorKind {
+    pub const ALL: [Self; KINDS] = [
+        Self::Calc(CalcError::Calc),
+        Self::Calc(CalcError::Overflow),
+        Self::Calc(CalcError::DivByZero),
+        Self::Calc(CalcError::Domain),
+        Self::Calc(CalcError::Cancelled),
+        Self::Calc(CalcError::Empty),
+        Self::Rejected,
+    ];
+
+    /// Position in [`ErrorKind::ALL`] and in the saved totals
+    const fn index(self) -> usize {
+        match self {
+            Self::Calc(CalcError::Calc) => 0,
+            Self::Calc(CalcError::Overflow) => 1,
+            Self::Calc(CalcError::DivByZero) => 2,
+            Self::Calc(CalcError::Domain) => 3,
+            Self::Calc(CalcError::Cancelled) => 4,
+            Self::Calc(CalcError::Empty) => 5,
+            Self::Rejected => 6,
+        }
+    }
+}
+
+impl uDisplay for ErrorKind {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        match self {
+            Self::Calc(e) => e.fmt(f),
+            Self::Rejected => uDisplay::fmt(&Text::Rejected, f),
+        }
+    }
+}
+
+/// Errors counted since boot and in total
+pub struct ErrorCounts {
+    boot: [u16; KINDS],
+    total: [u32; KINDS],
+    /// Totals changed since they were last saved
+    dirty: bool,
+    /// [`Clock::now_ms`](crate::clock::Clock::now_ms) of the last save
+    since: u32,
+}
+
+impl Default for ErrorCounts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ErrorCounts {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            boot: [0; KINDS],
+            total: [0; KINDS],
+            dirty: false,
+            since: 0,
+        }
+    }
+
+    /// Continue from the totals saved before, errors since boot are kept
+    pub fn restore(&mut self, total: [u32; KINDS]) {
+        for (total, (saved, boot)) in self.total.iter_mut().zip(total.iter().zip(self.boot)) {
+            *total = saved.saturating_add(boot.into());
+        }
+    }
+
+    /// Count one error of `kind`
+    pub const fn count(&mut self, kind: ErrorKind) {
+        let i = kind.index();
+        self.boot[i] = self.boot[i].saturating_add(1);
+        self.total[i] = self.total[i].saturating_add(1);
+        self.dirty = true;
+    }
+
+    /// Count of `kind` since boot and in total
+    #[must_use]
+    pub const fn get(&self, kind: ErrorKind) -> (u16, u32) {
+        let i = kind.index();
+        (self.boot[i], self.total[i])
+    }
+
+    /// Forget every count, since boot and in total
+    pub const fn clear(&mut self) {
+        self.boot = [0; KINDS];
+        self.total = [0; KINDS];
+        self.dirty = true;
+    }
+
+    /// Totals if they changed and [`CHECKPOINT_MS`] passed, they count as saved
+    pub const fn poll(&mut self, now: u32) -> Option<[u32; KINDS]> {
+        if !self.dirty || now.wrapping_sub(self.since) < CHECKPOINT_MS {
+            return None;
+        }
+
+        self.since = now;
+        self.take()
+    }
+
+    /// Totals if they changed since the last save, right away, e.g. before power-off
+    pub const fn take(&mut self) -> Option<[u32; KINDS]> {
+        if !self.dirty {
+            return None;
+        }
+
+        self.dirty = false;
+        Some(self.total)
+    }
+}
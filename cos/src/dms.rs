@@ -0,0 +1,107 @@
+//! Angles packed as D.MMSS, e.g. 12°34'57" is 12.3457, for a readout that only has numbers
+
+use crate::num::Num;
+
+/// Seconds in the last packed digit with fewer than 4 fractional digits
+const RESOLUTION: [i128; 4] = [3600, 600, 60, 10];
+
+/// Degrees as D.MMSS with `digits` fractional digits, at most `F`
+///
+/// Rounded in seconds to the last packed digit, so a carry reaches the minutes and degrees
+/// instead of packing 60 seconds, e.g. 59'45" is the next whole degree with 2 digits.
+/// Wraps if the degrees don't fit.
+#[must_use]
+pub fn pack<const F: u8>(a: Num<F>, digits: u8) -> Num<F> {
+    let scale = Num::<F>::SCALE as i128;
+    let digits = digits.min(F);
+
+    // Seconds scaled by `10^F`, like the raw value
+    let step = RESOLUTION.get(digits as usize).map_or_else(
+        || 10i128.pow((F + 4 - digits) as u32),
+        |seconds| seconds * scale,
+    );
+    let seconds = a.0.unsigned_abs() as i128 * 3600;
+    let seconds = (seconds + step / 2) / step * step;
+
+    let degrees = seconds / (3600 * scale);
+    let minutes = seconds / (60 * scale) % 60;
+    let packed = (degrees * scale + minutes * scale / 100 + seconds % (60 * scale) / 10_000) as i64;
+
+    Num::from_raw(if a.is_negative() {
+        packed.wrapping_neg()
+    } else {
+        packed
+    })
+}
+
+/// Degrees from D.MMSS, the inverse of [`pack`]
+///
+/// `None` if the minutes or seconds are 60 or more, or the degrees don't fit an `i32`.
+#[must_use]
+pub fn unpack<const F: u8>(packed: Num<F>) -> Option<Num<F>> {
+    let scale = Num::<F>::SCALE as u64;
+    let raw = packed.0.unsigned_abs();
+
+    // MM.SS, then SS.ss, both scaled by `10^F`
+    let minutes = raw % scale * 100;
+    let seconds = minutes % scale * 100;
+    let minutes = minutes / scale;
+    if minutes >= 60 || seconds >= 60 * scale {
+        return None;
+    }
+
+    let degrees = i32::try_from(raw / scale).ok()?;
+    let a = Num::from_dms(degrees, minutes as u8, Num::from_raw(seconds as i64));
+
+    Some(if packed.is_negative() { -a } else { a })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pack, unpack};
+    use crate::num::Num;
+
+    type N = Num<4>;
+
+    #[test]
+    fn test_pack() {
+        // 12.5825° is 12°34'57"
+        assert_eq!(pack(N::from_raw(125_825), 4), N::from_raw(123_457));
+        assert_eq!(pack(N::from_raw(-125_825), 4), N::from_raw(-123_457));
+        // Only the minutes with 2 digits
+        assert_eq!(pack(N::from_raw(125_825), 2), N::from_raw(123_500));
+
+        // 12°34'59.52" rounds to 60 seconds, they carry into the minutes
+        assert_eq!(pack(N::from_raw(125_832), 4), N::from_raw(123_500));
+        // 12°59'45" rounds to 60 minutes with 2 digits, they carry into the degrees
+        assert_eq!(pack(N::from_raw(129_958), 2), N::from_int(13));
+        assert_eq!(pack(N::from_raw(-129_958), 2), N::from_int(-13));
+        assert_eq!(pack(N::from_raw(129_958), 4), N::from_raw(125_945));
+    }
+
+    #[test]
+    fn test_unpack() {
+        assert_eq!(unpack(N::from_raw(123_457)), Some(N::from_raw(125_825)));
+        assert_eq!(unpack(N::from_raw(-123_457)), Some(N::from_raw(-125_825)));
+        assert_eq!(unpack(N::from_raw(125_959)), Some(N::from_raw(129_997)));
+
+        // Minutes or seconds of 60 and more aren't packed values
+        assert_eq!(unpack(N::from_raw(126_000)), None);
+        assert_eq!(unpack(N::from_raw(129_900)), None);
+        assert_eq!(unpack(N::from_raw(123_460)), None);
+        assert_eq!(unpack(N::from_raw(-123_499)), None);
+        // Nor are degrees beyond an `i32`
+        let degrees = i64::from(i32::MAX) + 1;
+        assert_eq!(unpack(Num::<0>::from_int(degrees)), None);
+        assert!(unpack(Num::<0>::from_int(degrees - 1)).is_some());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for raw in (-3_600_000..=3_600_000).step_by(997) {
+            let packed = pack(N::from_raw(raw), 4);
+            let a = unpack(packed).expect("packed values unpack");
+            assert_eq!(pack(a, 4), packed, "{raw}");
+        }
+    }
+}
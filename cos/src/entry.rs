@@ -0,0 +1,415 @@
+//! Number being typed, kept as the digits typed until another key uses it
+//!
+//! Digits and the point go in at a cursor and delete removes the one before it, so a
+//! mistake in the middle of a number is fixed without retyping what follows, see
+//! [`Key::Edit`](crate::Key::Edit). The value is only worked out by [`Entry::value`] once
+//! the number is committed, nothing is rounded or scaled on the way.
+
+use heapless::Vec;
+use ufmt::{Formatter, uDisplay, uWrite};
+
+use crate::{
+    CalcError,
+    num::{Digit, Num},
+};
+
+/// Digits of `i64::MAX`, the most a number has whole and fractional together
+const MAX_DIGITS: u8 = 19;
+
+/// Digits and the point of a number being typed, with the cursor between them
+#[derive(Clone, PartialEq, Eq)]
+pub struct Entry<const F: u8> {
+    negative: bool,
+    /// [`Digit::Num`]s and at most one [`Digit::Dot`], no leading zeros
+    chars: Vec<Digit, { MAX_DIGITS as usize + 1 }>,
+    /// Chars before the cursor
+    cursor: u8,
+}
+
+impl<const F: u8> Default for Entry<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const F: u8> Entry<F> {
+    /// Whole digits at most, the rest of the capacity is left to the `F` fractional ones
+    pub const WHOLE_DIGITS: u8 = MAX_DIGITS - F;
+
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            negative: false,
+            chars: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Digits of `value`, to go on typing onto it, with the cursor at the end
+    #[must_use]
+    pub fn from_num(value: Num<F>) -> Self {
+        let mut entry = Self::new();
+        for digit in value.digits() {
+            if digit == Digit::Minus {
+                entry.negative = true;
+            } else if entry.chars.push(digit).is_err() {
+                // Never more than the digits of an i64 and the point
+                break;
+            }
+        }
+        entry.trim();
+        entry.cursor = entry.chars.len() as u8;
+
+        entry
+    }
+
+    /// Whether nothing is typed, the value is then zero
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// Chars before the cursor, the point counts as one
+    #[inline]
+    #[must_use]
+    pub const fn cursor(&self) -> u8 {
+        self.cursor
+    }
+
+    /// Insert digit `n` at the cursor, a fractional digit beyond `precision` is ignored
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the number would have more than [`Self::WHOLE_DIGITS`] whole
+    /// digits or not fit a [`Num`].
+    pub fn insert(&mut self, n: u8, precision: u8) -> Result<(), CalcError> {
+        let cursor = usize::from(self.cursor);
+        match self.point() {
+            Some(point) if cursor > point => {
+                if self.chars.len() - point > usize::from(precision) {
+                    return Ok(());
+                }
+            }
+            // A leading zero adds nothing
+            _ if n == 0 && cursor == 0 => return Ok(()),
+            _ => {}
+        }
+
+        if self.chars.insert(cursor, Digit::Num(n)).is_err() {
+            return Err(CalcError::Overflow);
+        }
+        if !self.fits() {
+            self.chars.remove(cursor);
+            return Err(CalcError::Overflow);
+        }
+        self.cursor += 1;
+
+        Ok(())
+    }
+
+    /// Insert the point at the cursor, the digits after it become the fraction and those
+    /// beyond `precision` are dropped; nothing happens if there already is one
+    pub fn insert_point(&mut self, precision: u8) {
+        if self.point().is_some() {
+            return;
+        }
+
+        let cursor = usize::from(self.cursor);
+        self.chars.truncate(cursor + usize::from(precision));
+        // Room is left by the missing point
+        if self.chars.insert(cursor, Digit::Dot).is_ok() {
+            self.cursor += 1;
+        }
+    }
+
+    /// Remove the digit or point before the cursor, the point is kept if the number would
+    /// not fit without it
+    pub fn delete(&mut self) {
+        let Some(before) = self.cursor.checked_sub(1) else {
+            return;
+        };
+
+        let mut shorter = self.clone();
+        shorter.chars.remove(usize::from(before));
+        // Only without the point can it grow too long
+        if !shorter.fits() {
+            return;
+        }
+        shorter.cursor = before;
+        shorter.trim();
+        *self = shorter;
+    }
+
+    /// Move the cursor one char to the left, `false` at the start
+    pub const fn left(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.cursor -= 1;
+        true
+    }
+
+    /// Move the cursor one char to the right, `false` at the end
+    pub fn right(&mut self) -> bool {
+        if usize::from(self.cursor) >= self.chars.len() {
+            return false;
+        }
+        self.cursor += 1;
+        true
+    }
+
+    /// Value typed, `None` if it doesn't fit a [`Num`], which [`Entry::insert`] prevents
+    #[must_use]
+    pub fn value(&self) -> Option<Num<F>> {
+        // Summed with the sign, so `i64::MIN` fits too
+        let sign = if self.negative { -1 } else { 1 };
+        let mut raw = 0i64;
+        let mut frac = 0;
+        let mut point = false;
+        for digit in &self.chars {
+            match *digit {
+                Digit::Num(n) => {
+                    raw = raw.checked_mul(10)?.checked_add(sign * i64::from(n))?;
+                    if point {
+                        frac += 1;
+                    }
+                }
+                Digit::Dot => point = true,
+                Digit::Minus => {}
+            }
+        }
+        raw = raw.checked_mul(10i64.checked_pow(u32::from(F.checked_sub(frac)?))?)?;
+
+        Some(Num::from_raw(raw))
+    }
+
+    /// Index of the point, `None` while the number is whole
+    fn point(&self) -> Option<usize> {
+        self.chars.iter().position(|&digit| digit == Digit::Dot)
+    }
+
+    /// Whether the whole digits are few enough and the value fits a [`Num`]
+    fn fits(&self) -> bool {
+        let whole = self.point().unwrap_or(self.chars.len());
+        whole <= usize::from(Self::WHOLE_DIGITS) && self.value().is_some()
+    }
+
+    /// Drop leading zeros, and the sign once nothing is left
+    fn trim(&mut self) {
+        while self.chars.first() == Some(&Digit::Num(0)) {
+            self.chars.remove(0);
+            self.cursor = self.cursor.saturating_sub(1);
+        }
+        if self.chars.is_empty() {
+            self.negative = false;
+        }
+    }
+}
+
+impl<const F: u8> uDisplay for Entry<F> {
+    /// Chars typed with `|` at the cursor, like `-12|.5`
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        if self.negative {
+            f.write_char('-')?;
+        }
+        for (i, digit) in self.chars.iter().enumerate() {
+            if i == usize::from(self.cursor) {
+                f.write_char('|')?;
+            }
+            match *digit {
+                Digit::Num(n) => uDisplay::fmt(&n, f)?,
+                Digit::Dot => f.write_char('.')?,
+                Digit::Minus => f.write_char('-')?,
+            }
+        }
+        if usize::from(self.cursor) == self.chars.len() {
+            f.write_char('|')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::{CapacityError, String};
+    use ufmt::{uWrite, uwrite};
+
+    use super::Entry;
+    use crate::{CalcError, num::Num};
+
+    struct Shown(String<32>);
+
+    impl uWrite for Shown {
+        type Error = CapacityError;
+
+        fn write_str(&mut self, s: &str) -> Result<(), CapacityError> {
+            self.0.push_str(s)
+        }
+    }
+
+    /// Entry as displayed, like `-12|.5`
+    fn shown<const F: u8>(entry: &Entry<F>) -> String<32> {
+        let mut shown = Shown(String::new());
+        uwrite!(shown, "{}", entry).expect("an entry fits 32 chars");
+        shown.0
+    }
+
+    /// Type `keys`, digits and `.`, each accepted
+    fn typed<const F: u8>(keys: &str, precision: u8) -> Entry<F> {
+        let mut entry = Entry::new();
+        for key in keys.bytes() {
+            if key == b'.' {
+                entry.insert_point(precision);
+            } else {
+                assert_eq!(entry.insert(key - b'0', precision), Ok(()));
+            }
+        }
+        entry
+    }
+
+    #[test]
+    fn test_leading_zero() {
+        let entry = typed::<2>("007", 2);
+        assert_eq!(shown(&entry), "7|");
+        assert_eq!(entry.cursor(), 1);
+
+        // Not even before the point
+        let entry = typed::<2>("0.5", 2);
+        assert_eq!(shown(&entry), ".5|");
+        assert_eq!(entry.value(), Some(Num::from_raw(50)));
+
+        // Nor in front of what is typed
+        let mut entry = typed::<2>("5", 2);
+        entry.left();
+        assert_eq!(entry.insert(0, 2), Ok(()));
+        assert_eq!(shown(&entry), "|5");
+
+        // Anywhere else it counts
+        entry.right();
+        assert_eq!(entry.insert(0, 2), Ok(()));
+        assert_eq!(entry.value(), Some(Num::from_raw(5000)));
+    }
+
+    #[test]
+    fn test_precision() {
+        let entry = typed::<4>("1.23456", 2);
+        assert_eq!(shown(&entry), "1.23|");
+        assert_eq!(entry.value(), Some(Num::from_raw(12300)));
+
+        let entry = typed::<4>("1.23456", 4);
+        assert_eq!(entry.value(), Some(Num::from_raw(12345)));
+
+        // Whole digits typed after the point still go in
+        let mut entry = typed::<4>("1.23", 2);
+        entry.left();
+        entry.left();
+        entry.left();
+        assert_eq!(entry.insert(4, 2), Ok(()));
+        assert_eq!(shown(&entry), "14|.23");
+    }
+
+    #[test]
+    fn test_insert_point() {
+        let mut entry = typed::<4>("12345", 4);
+        for _ in 0..4 {
+            entry.left();
+        }
+        entry.insert_point(2);
+        assert_eq!(shown(&entry), "1.|23");
+        assert_eq!(entry.value(), Some(Num::from_raw(12300)));
+
+        // A second point does nothing
+        entry.insert_point(2);
+        assert_eq!(shown(&entry), "1.|23");
+    }
+
+    #[test]
+    fn test_delete() {
+        // In the middle, the zeros left in front are dropped and the cursor moves with them
+        let mut entry = typed::<2>("1005", 2);
+        for _ in 0..3 {
+            entry.left();
+        }
+        entry.delete();
+        assert_eq!(shown(&entry), "|5");
+        assert_eq!(entry.cursor(), 0);
+
+        // Nothing before the cursor
+        entry.delete();
+        assert_eq!(shown(&entry), "|5");
+
+        // Without the point it would be 18 whole digits, more than the 11 of `Entry<8>`
+        let mut entry = typed::<8>("12345678901.2345678", 8);
+        assert_eq!(Entry::<8>::WHOLE_DIGITS, 11);
+        for _ in 0..7 {
+            entry.left();
+        }
+        entry.delete();
+        assert_eq!(shown(&entry), "12345678901.|2345678");
+
+        // Once short enough it goes
+        let mut entry = typed::<8>("1.5", 8);
+        entry.left();
+        entry.delete();
+        assert_eq!(shown(&entry), "1|5");
+        assert_eq!(entry.value(), Some(Num::from_raw(15_0000_0000)));
+    }
+
+    #[test]
+    fn test_cursor() {
+        let mut entry = Entry::<2>::new();
+        assert!(!entry.left());
+        assert!(!entry.right());
+
+        let mut entry = typed::<2>("1.2", 2);
+        assert!(!entry.right());
+        assert!(entry.left() && entry.left() && entry.left());
+        assert!(!entry.left());
+        assert_eq!(shown(&entry), "|1.2");
+        assert!(entry.right());
+        assert_eq!(shown(&entry), "1|.2");
+    }
+
+    #[test]
+    fn test_from_num() {
+        let entry = Entry::<2>::from_num(Num::from_raw(-1250));
+        assert_eq!(shown(&entry), "-12.5|");
+        assert_eq!(entry.value(), Some(Num::from_raw(-1250)));
+
+        // The zero in front is dropped, the sign is kept
+        let entry = Entry::<2>::from_num(Num::from_raw(-5));
+        assert_eq!(shown(&entry), "-.05|");
+        assert_eq!(entry.value(), Some(Num::from_raw(-5)));
+
+        for raw in [i64::MAX, i64::MIN, i64::MIN + 1] {
+            assert_eq!(
+                Entry::<2>::from_num(Num::from_raw(raw)).value(),
+                Some(Num::from_raw(raw))
+            );
+            assert_eq!(
+                Entry::<8>::from_num(Num::from_raw(raw)).value(),
+                Some(Num::from_raw(raw))
+            );
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        // `i64::MAX` typed in full, one more at the last digit doesn't fit
+        let mut entry = typed::<2>("92233720368547758.0", 2);
+        assert_eq!(entry.insert(8, 2), Err(CalcError::Overflow));
+        assert_eq!(entry.insert(7, 2), Ok(()));
+        assert_eq!(entry.value(), Some(Num::from_raw(i64::MAX)));
+
+        let mut entry = typed::<8>("92233720368.5477580", 8);
+        assert_eq!(entry.insert(8, 8), Err(CalcError::Overflow));
+        assert_eq!(entry.insert(7, 8), Ok(()));
+        assert_eq!(entry.value(), Some(Num::from_raw(i64::MAX)));
+
+        // Whole digits past `WHOLE_DIGITS`, though the value would fit
+        let mut entry = typed::<8>("12345678901", 8);
+        assert_eq!(entry.insert(2, 8), Err(CalcError::Overflow));
+        assert_eq!(shown(&entry), "12345678901|");
+    }
+}
@@ -0,0 +1,207 @@
+use avr_progmem::progmem;
+use ufmt::{Formatter, uDisplay, uWrite};
+
+use crate::{BinOp, Key, UnOp, alarm::AlarmMode, history::Stat, lang::write_line, layout::Cell};
+
+progmem! {
+    /// One description per line, see [`Help::line`]
+    ///
+    /// Kept in flash and read a char at a time, copying it to 2 KiB of RAM at startup would not fit.
+    #[cfg(not(feature = "ru"))]
+    static progmem string HELP = "empty\n\
+        enters a digit\n\
+        starts the fraction\n\
+        adds the next number\n\
+        subtracts the next number\n\
+        multiplies by the next number\n\
+        divides by the next number, not by 0\n\
+        changes the sign\n\
+        square root, x >= 0\n\
+        square\n\
+        cube\n\
+        factorial, whole x up to 20, fractions by gamma\n\
+        sine, x in radians, any key cancels\n\
+        cosine, x in radians, any key cancels\n\
+        tangent, x in radians, not at pi/2\n\
+        enters the constant\n\
+        computes the result\n\
+        removes the digit before the cursor\n\
+        clears the number\n\
+        clears everything\n\
+        swaps the numbers around the operator\n\
+        second number follows the sensor\n\
+        vibrates when a result is above the number\n\
+        vibrates when a result is below the number\n\
+        turns the alarm off\n\
+        saves and sleeps, press to wake up\n\
+        enters the number with the potentiometer\n\
+        move to hear about keys, press to leave\n\
+        whole number is the count of fraction digits\n\
+        1 if the numbers are equal, else 0\n\
+        1 if the first number is less, else 0\n\
+        1 if the first number is greater, else 0\n\
+        enters the sum of the last results\n\
+        enters the mean of the last results\n\
+        enters the smallest of the last results\n\
+        enters the largest of the last results\n\
+        records the joystick, press again to stop and dump it over serial\n\
+        moves and presses like the recording\n\
+        not available yet\n\
+        outside the keyboard\n\
+        switches the vibro strength: soft, medium, strong\n\
+        digits by pressing the stick held towards them, 7 8 9 on top, 5 in the center\n\
+        degrees to D.MMSS, pressed again converts back\n\
+        hours to hours:minutes:seconds and back, the dot types a colon, * and / take a number\n\
+        results keep every digit or only the shown ones\n\
+        reciprocal, 1 divided by x, not 0\n\
+        absolute value, drops the minus\n\
+        added by the board\n\
+        tempo, precision, alarm threshold, tutorial, the date and time of a clock: up and down change it, held faster, left and right pick another, press saves\n\
+        left and right move the cursor through the number, digits and delete work there, press leaves\n\
+        the next number if this one is not 0, else 0, pressed again the number after is used for 0\n\
+        converts the unit, pressed again on the result converts back\n\
+        converts the number like the last conversion";
+
+    #[cfg(feature = "ru")]
+    static progmem string HELP = "пусто\n\
+        вводит цифру\n\
+        начинает дробную часть\n\
+        прибавляет следующее число\n\
+        вычитает следующее число\n\
+        умножает на следующее число\n\
+        делит на следующее число, не на 0\n\
+        меняет знак\n\
+        квадратный корень, x >= 0\n\
+        квадрат\n\
+        куб\n\
+        факториал, целое x до 20, дробное через гамма-функцию\n\
+        синус, x в радианах, любая клавиша отменяет\n\
+        косинус, x в радианах, любая клавиша отменяет\n\
+        тангенс, x в радианах, не в pi/2\n\
+        вводит константу\n\
+        вычисляет результат\n\
+        удаляет цифру перед курсором\n\
+        очищает число\n\
+        очищает всё\n\
+        меняет числа вокруг операции местами\n\
+        второе число следует за датчиком\n\
+        вибрирует, когда результат больше числа\n\
+        вибрирует, когда результат меньше числа\n\
+        выключает тревогу\n\
+        сохраняет и засыпает, нажмите для пробуждения\n\
+        вводит число потенциометром\n\
+        двигайте, чтобы узнать о клавишах, нажмите для выхода\n\
+        целое число задаёт количество знаков после точки\n\
+        1, если числа равны, иначе 0\n\
+        1, если первое число меньше, иначе 0\n\
+        1, если первое число больше, иначе 0\n\
+        вводит сумму последних результатов\n\
+        вводит среднее последних результатов\n\
+        вводит наименьший из последних результатов\n\
+        вводит наибольший из последних результатов\n\
+        записывает джойстик, нажмите снова, чтобы остановить и вывести запись\n\
+        двигает и нажимает как в записи\n\
+        пока недоступно\n\
+        за краем клавиатуры\n\
+        переключает силу вибрации: слабо, средне, сильно\n\
+        цифры нажатием джойстика, отклонённого к ним, 7 8 9 сверху, 5 в центре\n\
+        градусы в Г.ММСС, повторное нажатие переводит обратно\n\
+        часы в часы:минуты:секунды и обратно, точка вводит двоеточие, * и / принимают число\n\
+        результаты хранят все знаки или только показанные\n\
+        обратное число, 1 делить на x, не 0\n\
+        модуль, убирает минус\n\
+        добавлено платой\n\
+        скорость, точность, порог тревоги, обучение, дата и время часов: вверх и вниз меняют, удержание быстрее, влево и вправо выбирают другую, нажатие сохраняет\n\
+        влево и вправо двигают курсор по числу, цифры и удаление работают там, нажатие выходит\n\
+        следующее число, если это не 0, иначе 0, повторное нажатие берёт для 0 число после него\n\
+        переводит единицы, повторное нажатие на результате переводит обратно\n\
+        переводит число как последний перевод";
+}
+
+/// Name and description of a layout cell, e.g. `sqrt: square root, x >= 0`
+pub struct Help(pub Cell);
+
+impl Help {
+    /// Line of [`HELP`] describing the cell
+    const fn line(&self) -> u8 {
+        let key = match self.0 {
+            Cell::Key(key) => key,
+            Cell::Empty => return 0,
+            Cell::Reserved => return 38,
+            Cell::Outside => return 39,
+        };
+
+        match key {
+            Key::Num(_) => 1,
+            Key::Dot => 2,
+            Key::BinOp(BinOp::Add) => 3,
+            Key::BinOp(BinOp::Sub) => 4,
+            Key::BinOp(BinOp::Mul) => 5,
+            Key::BinOp(BinOp::Div) => 6,
+            Key::UnOp(UnOp::Neg) => 7,
+            #[cfg(not(feature = "basic-layout"))]
+            Key::UnOp(UnOp::Sqrt) => 8,
+            #[cfg(not(feature = "basic-layout"))]
+            Key::UnOp(UnOp::Pow2) => 9,
+            #[cfg(not(feature = "basic-layout"))]
+            Key::UnOp(UnOp::Pow3) => 10,
+            #[cfg(not(feature = "basic-layout"))]
+            Key::UnOp(UnOp::Factorial) => 11,
+            #[cfg(not(feature = "basic-layout"))]
+            Key::UnOp(UnOp::Sin) => 12,
+            #[cfg(not(feature = "basic-layout"))]
+            Key::UnOp(UnOp::Cos) => 13,
+            #[cfg(not(feature = "basic-layout"))]
+            Key::UnOp(UnOp::Tan) => 14,
+            Key::Const(_) => 15,
+            Key::Result => 16,
+            Key::Delete => 17,
+            Key::Clear => 18,
+            Key::Reset => 19,
+            Key::Swap => 20,
+            Key::Live(_) => 21,
+            Key::Alarm(AlarmMode::Above) => 22,
+            Key::Alarm(AlarmMode::Below) => 23,
+            Key::Alarm(AlarmMode::Off) => 24,
+            Key::Power => 25,
+            Key::Slider => 26,
+            Key::Help => 27,
+            Key::Precision => 28,
+            Key::BinOp(BinOp::CmpEq) => 29,
+            Key::BinOp(BinOp::CmpLt) => 30,
+            Key::BinOp(BinOp::CmpGt) => 31,
+            Key::BinOp(BinOp::If) => 50,
+            Key::Stat(Stat::Sum) => 32,
+            Key::Stat(Stat::Mean) => 33,
+            Key::Stat(Stat::Min) => 34,
+            Key::Stat(Stat::Max) => 35,
+            Key::Record => 36,
+            Key::Replay => 37,
+            Key::Photomath | Key::GPT5 => 38,
+            Key::Intensity => 40,
+            Key::Chords => 41,
+            #[cfg(not(feature = "basic-layout"))]
+            Key::UnOp(UnOp::Dms) => 42,
+            Key::Time => 43,
+            Key::Rounding => 44,
+            #[cfg(not(feature = "basic-layout"))]
+            Key::UnOp(UnOp::Recip) => 45,
+            #[cfg(not(feature = "basic-layout"))]
+            Key::UnOp(UnOp::Abs) => 46,
+            Key::Plugin(_) => 47,
+            Key::Adjust => 48,
+            Key::Edit => 49,
+            Key::Convert(_) => 51,
+            Key::ConvertAgain => 52,
+        }
+    }
+}
+
+impl uDisplay for Help {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        uDisplay::fmt(&self.0, f)?;
+        f.write_str(": ")?;
+
+        write_line(HELP.chars(), self.line(), f)
+    }
+}
@@ -0,0 +1,67 @@
+use heapless::HistoryBuf;
+use ufmt::derive::uDebug;
+
+use crate::{config::HISTORY_LEN, date::DateTime, lang::Text, num::Num};
+
+/// Aggregate over the [`History`]
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Stat {
+    Sum,
+    Mean,
+    Min,
+    Max,
+}
+
+impl Stat {
+    #[must_use]
+    pub const fn name(self) -> Text {
+        match self {
+            Self::Sum => Text::Sum,
+            Self::Mean => Text::Mean,
+            Self::Min => Text::Min,
+            Self::Max => Text::Max,
+        }
+    }
+}
+
+/// Last [`HISTORY_LEN`] computed results with when they were computed if a clock knew,
+/// the oldest ones are overwritten
+pub struct History<const F: u8>(HistoryBuf<(Num<F>, Option<DateTime>), HISTORY_LEN>);
+
+impl<const F: u8> Default for History<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const F: u8> History<F> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(HistoryBuf::new())
+    }
+
+    pub fn push(&mut self, result: Num<F>, at: Option<DateTime>) {
+        self.0.write((result, at));
+    }
+
+    /// Stored results with their date and time, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = (Num<F>, Option<DateTime>)> {
+        self.0.oldest_ordered().copied()
+    }
+
+    /// `stat` over the stored results, `None` if there are none
+    #[must_use]
+    pub fn stat(&self, stat: Stat) -> Option<Num<F>> {
+        let results = self.0.as_slice().iter().map(|&(result, _)| result);
+
+        match stat {
+            Stat::Sum => (!self.0.is_empty()).then(|| results.fold(Num::ZERO, |sum, v| sum + v)),
+            Stat::Mean => self
+                .stat(Stat::Sum)
+                .map(|sum| sum / Num::from_int(self.0.len() as i64)),
+            Stat::Min => results.min(),
+            Stat::Max => results.max(),
+        }
+    }
+}
@@ -0,0 +1,275 @@
+//! Durations as hours:minutes:seconds, see [`Key::Time`](crate::Key::Time)
+//!
+//! Values are kept in seconds, so sums of whole seconds stay exact at any `F`.
+
+use ufmt::{Formatter, uDisplay, uWrite};
+
+use crate::num::{Digit, Num};
+
+pub const SECONDS_PER_HOUR: i64 = 3600;
+
+/// Seconds split into hours, minutes and seconds, shown as `-1:05:07.25`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Hms<const F: u8> {
+    pub negative: bool,
+    pub hours: u64,
+    /// Below 60
+    pub minutes: u8,
+    /// Below 60, with the fraction of `F` digits
+    pub seconds: Num<F>,
+}
+
+impl<const F: u8> Hms<F> {
+    #[must_use]
+    pub const fn new(seconds: Num<F>) -> Self {
+        let scale = Num::<F>::SCALE.unsigned_abs() as u128;
+        let raw = seconds.0.unsigned_abs() as u128;
+
+        Self {
+            negative: seconds.is_negative(),
+            hours: (raw / (3600 * scale)) as u64,
+            minutes: (raw / (60 * scale) % 60) as u8,
+            seconds: Num::from_raw((raw % (60 * scale)) as i64),
+        }
+    }
+}
+
+impl<const F: u8> uDisplay for Hms<F> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        if self.negative {
+            f.write_char('-')?;
+        }
+        uDisplay::fmt(&self.hours, f)?;
+
+        f.write_char(':')?;
+        if self.minutes < 10 {
+            f.write_char('0')?;
+        }
+        uDisplay::fmt(&self.minutes, f)?;
+
+        f.write_char(':')?;
+        if self.seconds < Num::from_int(10) {
+            f.write_char('0')?;
+        }
+        for digit in self.seconds.digits() {
+            match digit {
+                Digit::Num(n) => uDisplay::fmt(&n, f)?,
+                Digit::Dot => f.write_char('.')?,
+                Digit::Minus => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Seconds in a unit of the field typed after `colons` separators
+const fn field_unit(colons: u8) -> i64 {
+    match colons {
+        0 => SECONDS_PER_HOUR,
+        1 => 60,
+        _ => 1,
+    }
+}
+
+/// Magnitude of `v` and the field typed after `colons` separators, both raw
+const fn field<const F: u8>(v: Num<F>, colons: u8) -> (u64, u64, u64) {
+    let unit = (field_unit(colons) * Num::<F>::SCALE).unsigned_abs();
+    let raw = v.0.unsigned_abs();
+
+    // Hours take every digit typed, minutes and seconds wrap at 60
+    let field = if colons == 0 {
+        raw / unit
+    } else {
+        raw % (unit * 60) / unit
+    };
+
+    (raw, field, unit)
+}
+
+/// Append digit `n` to the field typed after `colons` separators, 0 for hours
+///
+/// `None` if minutes or seconds would reach 60 or the value would overflow.
+#[must_use]
+pub fn push_digit<const F: u8>(v: Num<F>, colons: u8, n: u8) -> Option<Num<F>> {
+    let (raw, field, unit) = field(v, colons);
+
+    let new = field.checked_mul(10)?.checked_add(n.into())?;
+    if colons > 0 && new >= 60 {
+        return None;
+    }
+
+    let raw = i64::try_from(raw.checked_add((new - field).checked_mul(unit)?)?).ok()?;
+    Some(Num::from_raw(if v.is_negative() { -raw } else { raw }))
+}
+
+/// Remove the last digit of the field typed after `colons` separators
+///
+/// Returns `false` if the field is empty and the separator before it should go instead.
+pub fn delete_digit<const F: u8>(v: &mut Num<F>, colons: u8) -> bool {
+    let (raw, field, unit) = field(*v, colons);
+    if field == 0 && colons > 0 {
+        return false;
+    }
+
+    let raw = (raw - (field - field / 10) * unit) as i64;
+    *v = Num::from_raw(if v.is_negative() { -raw } else { raw });
+    true
+}
+
+/// Decimal hours to seconds, wrapping on overflow like [`Num`]
+#[must_use]
+pub fn from_hours<const F: u8>(hours: Num<F>) -> Num<F> {
+    Num::from_raw(hours.0.wrapping_mul(SECONDS_PER_HOUR))
+}
+
+/// Seconds to decimal hours, rounded to `digits` fractional digits
+#[must_use]
+pub fn to_hours<const F: u8>(seconds: Num<F>, digits: u8) -> Num<F> {
+    (seconds / Num::from_int(SECONDS_PER_HOUR)).round_frac(digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::{CapacityError, String};
+    use ufmt::{uWrite, uwrite};
+
+    use super::{Hms, delete_digit, from_hours, push_digit, to_hours};
+    use crate::num::Num;
+
+    struct Shown(String<32>);
+
+    impl uWrite for Shown {
+        type Error = CapacityError;
+
+        fn write_str(&mut self, s: &str) -> Result<(), CapacityError> {
+            self.0.push_str(s)
+        }
+    }
+
+    /// Seconds shown as hours:minutes:seconds
+    fn shown<const F: u8>(seconds: Num<F>) -> String<32> {
+        let mut shown = Shown(String::new());
+        uwrite!(shown, "{}", Hms::new(seconds)).expect("a time fits 32 chars");
+        shown.0
+    }
+
+    fn secs(h: i64, m: i64, s: i64) -> Num<2> {
+        Num::from_int(h * 3600 + m * 60 + s)
+    }
+
+    /// Type `keys`, digits and `:`, onto `v`, `None` once a digit is rejected
+    fn typed(mut v: Num<2>, keys: &str) -> Option<Num<2>> {
+        let mut colons = 0;
+        for key in keys.bytes() {
+            if key == b':' {
+                colons += 1;
+            } else {
+                v = push_digit(v, colons, key - b'0')?;
+            }
+        }
+        Some(v)
+    }
+
+    #[test]
+    fn test_push_digit() {
+        assert_eq!(typed(Num::ZERO, "1:30:15"), Some(secs(1, 30, 15)));
+        assert_eq!(typed(Num::ZERO, "125"), Some(secs(125, 0, 0)));
+        assert_eq!(typed(Num::ZERO, "::7"), Some(secs(0, 0, 7)));
+        assert_eq!(typed(Num::ZERO, "0:59:59"), Some(secs(0, 59, 59)));
+
+        // Minutes and seconds stop short of 60
+        assert_eq!(typed(Num::ZERO, "0:60"), None);
+        assert_eq!(typed(Num::ZERO, "0:0:60"), None);
+        assert_eq!(typed(Num::ZERO, "0:5:599"), None);
+        assert_eq!(typed(Num::ZERO, "0:599"), None);
+
+        // Typing into a field keeps the others
+        let v = secs(2, 0, 45);
+        assert_eq!(push_digit(v, 1, 7), Some(secs(2, 7, 45)));
+        assert_eq!(push_digit(v, 0, 3), Some(secs(23, 0, 45)));
+
+        // Overflow is rejected, not wrapped
+        assert_eq!(push_digit(Num::<2>::from_raw(i64::MAX / 2), 0, 9), None);
+    }
+
+    #[test]
+    fn test_delete_digit() {
+        let mut v = secs(1, 30, 15);
+        assert!(delete_digit(&mut v, 2));
+        assert_eq!(v, secs(1, 30, 1));
+        assert!(delete_digit(&mut v, 2));
+        assert_eq!(v, secs(1, 30, 0));
+        // Empty seconds leave the separator to go
+        assert!(!delete_digit(&mut v, 2));
+        assert_eq!(v, secs(1, 30, 0));
+
+        assert!(delete_digit(&mut v, 1));
+        assert!(delete_digit(&mut v, 1));
+        assert!(!delete_digit(&mut v, 1));
+        assert_eq!(v, secs(1, 0, 0));
+
+        // Hours go down to 0 and stay
+        assert!(delete_digit(&mut v, 0));
+        assert_eq!(v, Num::ZERO);
+        assert!(delete_digit(&mut v, 0));
+        assert_eq!(v, Num::ZERO);
+    }
+
+    #[test]
+    fn test_carries() {
+        assert_eq!(shown(secs(0, 0, 59)), "0:00:59");
+        assert_eq!(shown(secs(0, 0, 60)), "0:01:00");
+        assert_eq!(shown(secs(0, 59, 59)), "0:59:59");
+        assert_eq!(shown(secs(0, 59, 60)), "1:00:00");
+        assert_eq!(shown(secs(1, 59, 59) + Num::from_raw(99)), "1:59:59.99");
+        assert_eq!(shown(secs(1, 59, 59) + Num::ONE), "2:00:00");
+
+        let hms = Hms::new(secs(0, 59, 60));
+        assert_eq!((hms.hours, hms.minutes), (1, 0));
+        assert_eq!(hms.seconds, Num::ZERO);
+    }
+
+    #[test]
+    fn test_hours() {
+        assert_eq!(from_hours(Num::<2>::from_raw(150)), secs(1, 30, 0));
+        assert_eq!(to_hours(secs(1, 30, 0), 2), Num::from_raw(150));
+        // 1:30:15 is 1.504166... hours
+        assert_eq!(to_hours(secs(1, 30, 15), 2), Num::from_raw(150));
+        assert_eq!(to_hours(secs(1, 30, 15), 0), Num::from_int(2));
+        assert_eq!(
+            to_hours(Num::<4>::from_int(5415), 4),
+            Num::<4>::from_raw(15042)
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for raw in (-100_000..=100_000).step_by(137) {
+            let hours = Num::<2>::from_raw(raw);
+            assert_eq!(to_hours(from_hours(hours), 2), hours);
+        }
+
+        // Seconds make it back from hours of 2 digits in steps of 0.01 h, 36 s
+        for n in -1000..=1000 {
+            let seconds = Num::<2>::from_int(n * 36);
+            assert_eq!(from_hours(to_hours(seconds, 2)), seconds);
+        }
+    }
+
+    #[test]
+    fn test_negative() {
+        let v = secs(-1, -2, -5) - Num::from_raw(50);
+        assert_eq!(shown(v), "-1:02:05.5");
+        assert_eq!(from_hours(Num::<2>::from_raw(-150)), secs(-1, -30, 0));
+        assert_eq!(to_hours(secs(-1, -30, 0), 2), Num::from_raw(-150));
+
+        // Digits and delete work on the magnitude and keep the sign
+        let v = secs(-1, 0, 0);
+        assert_eq!(push_digit(v, 1, 3), Some(secs(-1, -3, 0)));
+        assert_eq!(push_digit(v, 2, 7), Some(secs(-1, 0, -7)));
+        let mut v = secs(-1, -30, -15);
+        assert!(delete_digit(&mut v, 2));
+        assert_eq!(v, secs(-1, -30, -1));
+    }
+}
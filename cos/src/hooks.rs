@@ -0,0 +1,27 @@
+use crate::{Calculator, Key, num::Num};
+
+/// Something the calculator did that hooks may react to
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Event<const F: u8> {
+    /// Key was accepted by [`Calculator::handle_input`]
+    Key(Key),
+    /// Result was computed
+    Result(Num<F>),
+}
+
+/// Callback run on every [`Event`]
+///
+/// `C` is the context owned by the firmware (vibro motor, storage, etc.),
+/// so hooks can drive hardware without global state.
+pub type Hook<C, const F: u8> = fn(&mut C, &Calculator<F>, Event<F>);
+
+/// Static pipeline of hooks, run in order
+pub struct Hooks<C, const F: u8, const N: usize>(pub [Hook<C, F>; N]);
+
+impl<C, const F: u8, const N: usize> Hooks<C, F, N> {
+    pub fn run(&self, ctx: &mut C, calc: &Calculator<F>, event: Event<F>) {
+        for hook in self.0 {
+            hook(ctx, calc, event);
+        }
+    }
+}
@@ -0,0 +1,456 @@
+#[cfg(feature = "avr")]
+use arduino_hal::{Adc, adc::Channel};
+use ufmt::derive::uDebug;
+
+use crate::{clock::Clock, config::LONG_PRESS_MS};
+
+/// Distance from the center an axis must move to count as a direction
+const DEADZONE: u16 = 200;
+/// Contacts bounce for a few ms, a new button level counts once it held this long
+const DEBOUNCE_MS: u32 = 20;
+/// Wait before a held direction repeats
+const REPEAT_DELAY_MS: u32 = 500;
+/// Wait between repeats of a held direction
+const REPEAT_MS: u32 = 150;
+/// Readings averaged by [`Stick::calibrate`], 5 ms apart
+#[cfg(feature = "avr")]
+const CALIBRATION_SAMPLES: u32 = 32;
+
+#[derive(Debug, uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+    Center,
+}
+
+/// Analog joystick on any two ADC channels
+#[cfg(feature = "avr")]
+pub struct Stick {
+    x: Channel,
+    y: Channel,
+    /// Readings of the released stick as (x, y)
+    center: (u16, u16),
+}
+
+#[cfg(feature = "avr")]
+impl Stick {
+    /// Middle of the ADC range, where an ideal stick rests
+    pub const DEFAULT_CENTER: (u16, u16) = (512, 512);
+
+    #[must_use]
+    pub const fn new(x: Channel, y: Channel) -> Self {
+        Self {
+            x,
+            y,
+            center: Self::DEFAULT_CENTER,
+        }
+    }
+
+    pub const fn set_center(&mut self, center: (u16, u16)) {
+        self.center = center;
+    }
+
+    pub fn dir(&self, adc: &mut Adc) -> Dir {
+        read_joystick_direction(
+            adc.read_blocking(&self.x),
+            adc.read_blocking(&self.y),
+            self.center,
+        )
+    }
+
+    /// Digit the stick points to, see [`read_chord_digit`]
+    pub fn chord_digit(&self, adc: &mut Adc) -> u8 {
+        read_chord_digit(
+            adc.read_blocking(&self.x),
+            adc.read_blocking(&self.y),
+            self.center,
+        )
+    }
+
+    /// Average the readings of the released stick and use them as its center
+    ///
+    /// Returns the new center, or `None` if the stick was held in some direction.
+    pub fn calibrate(&mut self, adc: &mut Adc, clock: &mut impl Clock) -> Option<(u16, u16)> {
+        let (mut x, mut y) = (0u32, 0u32);
+        for _ in 0..CALIBRATION_SAMPLES {
+            x += u32::from(adc.read_blocking(&self.x));
+            y += u32::from(adc.read_blocking(&self.y));
+            clock.delay_ms(5);
+        }
+
+        let center = (
+            u16::try_from(x / CALIBRATION_SAMPLES).ok()?,
+            u16::try_from(y / CALIBRATION_SAMPLES).ok()?,
+        );
+        if read_joystick_direction(center.0, center.1, Self::DEFAULT_CENTER) != Dir::Center {
+            return None;
+        }
+
+        self.center = center;
+        Some(center)
+    }
+}
+
+/// What the second joystick does
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum StickRole {
+    /// Move the cursor, same as the main joystick
+    Navigate,
+    /// Left / right make the vibro output slower / faster
+    Tempo,
+}
+
+/// Direction of a reading relative to the `center` reading
+#[must_use]
+pub const fn read_joystick_direction(x: u16, y: u16, center: (u16, u16)) -> Dir {
+    let (cx, cy) = center;
+
+    match (x, y) {
+        (x, _) if x > cx.saturating_add(DEADZONE) => Dir::Right,
+        (x, _) if x < cx.saturating_sub(DEADZONE) => Dir::Left,
+        (_, y) if y > cy.saturating_add(DEADZONE) => Dir::Down,
+        (_, y) if y < cy.saturating_sub(DEADZONE) => Dir::Up,
+        _ => Dir::Center,
+    }
+}
+
+/// Digit a reading points to relative to the `center` reading, laid out as on the keypad
+///
+/// ```text
+/// 7 8 9
+/// 4 5 6
+/// 1 2 3
+/// ```
+#[must_use]
+pub const fn read_chord_digit(x: u16, y: u16, center: (u16, u16)) -> u8 {
+    let (cx, cy) = center;
+
+    let col = if x > cx.saturating_add(DEADZONE) {
+        2
+    } else if x < cx.saturating_sub(DEADZONE) {
+        0
+    } else {
+        1
+    };
+    // y grows downwards
+    let row = if y > cy.saturating_add(DEADZONE) {
+        0
+    } else if y < cy.saturating_sub(DEADZONE) {
+        2
+    } else {
+        1
+    };
+
+    row * 3 + col + 1
+}
+
+/// What [`Chords::update`] made of a sample
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum Chord {
+    /// Stick was released without a press
+    Move(Dir),
+    /// Pressed while held towards the digit
+    Digit(u8),
+    /// Pressed in the center, the key under the cursor is meant
+    Press,
+}
+
+/// Digit entry by pressing the stick while it is held in a direction
+///
+/// A direction only moves the cursor once the stick is released without a press,
+/// so entering a chord leaves the cursor where it was.
+pub struct Chords {
+    /// Last direction the stick was held in, `Center` once released
+    held: Dir,
+    /// Chord was entered since the stick left the center
+    chorded: bool,
+}
+
+impl Default for Chords {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Chords {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            held: Dir::Center,
+            chorded: false,
+        }
+    }
+
+    /// Feed a sample, `digit` is the [`Stick::chord_digit`] reading and `pressed` is only
+    /// `true` for the sample the button went down in
+    pub const fn update(&mut self, dir: Dir, digit: u8, pressed: bool) -> Option<Chord> {
+        if pressed {
+            if digit == 5 {
+                return Some(Chord::Press);
+            }
+            self.chorded = true;
+            return Some(Chord::Digit(digit));
+        }
+
+        if !matches!(dir, Dir::Center) {
+            self.held = dir;
+            return None;
+        }
+
+        let held = self.held;
+        let chorded = self.chorded;
+        self.held = Dir::Center;
+        self.chorded = false;
+        if chorded || matches!(held, Dir::Center) {
+            None
+        } else {
+            Some(Chord::Move(held))
+        }
+    }
+}
+
+/// What [`Button::update`] made of a sample
+#[derive(Debug, uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum Press {
+    /// Went down
+    Down,
+    /// Is down for [`LONG_PRESS_MS`], once per press
+    Long,
+}
+
+/// Push button read every main loop iteration, with the bounces of its contacts filtered out
+pub struct Button {
+    /// Level once it was steady for [`DEBOUNCE_MS`]
+    pressed: bool,
+    /// Level of the last sample
+    raw: bool,
+    /// When `raw` last changed
+    changed: u32,
+    /// The press was long already, or is not timed
+    long: bool,
+}
+
+impl Default for Button {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Button {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            pressed: false,
+            raw: false,
+            changed: 0,
+            long: false,
+        }
+    }
+
+    /// Take the button as down already without timing it, e.g. held at power-on, so only its
+    /// next press counts
+    pub const fn ignore_press(&mut self) {
+        self.pressed = true;
+        self.raw = true;
+        self.long = true;
+    }
+
+    /// Level once the bounces settled
+    #[inline]
+    #[must_use]
+    pub const fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Feed the level read, `true` while the button is down
+    pub fn update(&mut self, clock: &impl Clock, raw: bool) -> Option<Press> {
+        if raw != self.raw {
+            self.raw = raw;
+            self.changed = clock.now_ms();
+            return None;
+        }
+
+        let steady = clock.elapsed_since(self.changed);
+        if raw != self.pressed {
+            if steady < DEBOUNCE_MS {
+                return None;
+            }
+            self.pressed = raw;
+            self.long = false;
+            return raw.then_some(Press::Down);
+        }
+
+        if raw && !self.long && LONG_PRESS_MS.is_some_and(|ms| steady >= ms) {
+            self.long = true;
+            return Some(Press::Long);
+        }
+
+        None
+    }
+}
+
+/// Direction that acts when it is pushed and then again and again while it is held, like a
+/// key of a keyboard
+pub struct Repeat {
+    /// Direction held, `Center` once released
+    held: Dir,
+    /// Repeats of the held direction so far
+    repeats: u8,
+    /// When the direction last acted
+    acted: u32,
+}
+
+impl Default for Repeat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Repeat {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            held: Dir::Center,
+            repeats: 0,
+            acted: 0,
+        }
+    }
+
+    /// Feed the stick direction, returns the repeats before this one whenever it acts
+    ///
+    /// It first repeats after [`REPEAT_DELAY_MS`], then every [`REPEAT_MS`].
+    pub fn update(&mut self, clock: &impl Clock, dir: Dir) -> Option<u8> {
+        if dir == Dir::Center {
+            self.held = Dir::Center;
+            return None;
+        }
+
+        if dir == self.held {
+            let wait = if self.repeats == 0 {
+                REPEAT_DELAY_MS
+            } else {
+                REPEAT_MS
+            };
+            if clock.elapsed_since(self.acted) < wait {
+                return None;
+            }
+            self.repeats = self.repeats.saturating_add(1);
+        } else {
+            self.held = dir;
+            self.repeats = 0;
+        }
+        self.acted = clock.now_ms();
+
+        Some(self.repeats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Button, DEBOUNCE_MS, Dir, LONG_PRESS_MS, Press, REPEAT_DELAY_MS, REPEAT_MS, Repeat,
+    };
+    use heapless::Vec;
+
+    use crate::clock::VirtualClock;
+
+    /// Feed `raw` every 10 ms for `ms`, like the main loop, and collect what came out
+    fn hold(button: &mut Button, clock: &mut VirtualClock, raw: bool, ms: u32) -> Vec<Press, 4> {
+        let mut presses = Vec::new();
+        for _ in 0..ms / 10 {
+            if let Some(press) = button.update(clock, raw) {
+                presses.push(press).expect("a few presses at most");
+            }
+            clock.advance(10);
+        }
+        presses
+    }
+
+    #[test]
+    fn test_debounce() {
+        let mut clock = VirtualClock::new();
+        let mut button = Button::new();
+
+        // Bounces shorter than the debounce time are ignored
+        for raw in [true, false, true, false] {
+            assert_eq!(button.update(&clock, raw), None);
+            clock.advance(DEBOUNCE_MS / 2);
+        }
+        assert!(!button.is_pressed());
+
+        assert_eq!(hold(&mut button, &mut clock, true, 100), [Press::Down]);
+        assert!(button.is_pressed());
+
+        // Bouncing on release doesn't press again
+        assert_eq!(button.update(&clock, false), None);
+        clock.advance(5);
+        assert_eq!(button.update(&clock, true), None);
+        clock.advance(5);
+        assert_eq!(hold(&mut button, &mut clock, false, 100), []);
+        assert!(!button.is_pressed());
+
+        assert_eq!(hold(&mut button, &mut clock, true, 100), [Press::Down]);
+    }
+
+    #[test]
+    fn test_long_press() {
+        let Some(long) = LONG_PRESS_MS else {
+            return;
+        };
+        let mut clock = VirtualClock::new();
+        let mut button = Button::new();
+
+        // Once per press, however long it is held
+        assert_eq!(
+            hold(&mut button, &mut clock, true, long - 20),
+            [Press::Down]
+        );
+        assert_eq!(hold(&mut button, &mut clock, true, 30), [Press::Long]);
+        assert_eq!(hold(&mut button, &mut clock, true, 2 * long), []);
+
+        assert_eq!(hold(&mut button, &mut clock, false, 100), []);
+        assert_eq!(
+            hold(&mut button, &mut clock, true, long + 20),
+            [Press::Down, Press::Long]
+        );
+    }
+
+    #[test]
+    fn test_ignore_press() {
+        let mut clock = VirtualClock::new();
+        let mut button = Button::new();
+        button.ignore_press();
+
+        let held = LONG_PRESS_MS.unwrap_or(0) + 100;
+        assert_eq!(hold(&mut button, &mut clock, true, held), []);
+        assert_eq!(hold(&mut button, &mut clock, false, 100), []);
+        assert_eq!(hold(&mut button, &mut clock, true, 100), [Press::Down]);
+    }
+
+    #[test]
+    fn test_repeat() {
+        let mut clock = VirtualClock::new();
+        let mut repeat = Repeat::new();
+
+        assert_eq!(repeat.update(&clock, Dir::Up), Some(0));
+        clock.advance(REPEAT_DELAY_MS - 1);
+        assert_eq!(repeat.update(&clock, Dir::Up), None);
+        clock.advance(1);
+        assert_eq!(repeat.update(&clock, Dir::Up), Some(1));
+        clock.advance(REPEAT_MS);
+        assert_eq!(repeat.update(&clock, Dir::Up), Some(2));
+        clock.advance(REPEAT_MS - 1);
+        assert_eq!(repeat.update(&clock, Dir::Up), None);
+
+        // Another direction acts right away and starts over
+        assert_eq!(repeat.update(&clock, Dir::Down), Some(0));
+        clock.advance(REPEAT_MS);
+        assert_eq!(repeat.update(&clock, Dir::Down), None);
+
+        // So does the same one once released
+        assert_eq!(repeat.update(&clock, Dir::Center), None);
+        assert_eq!(repeat.update(&clock, Dir::Down), Some(0));
+    }
+}
@@ -0,0 +1,374 @@
+//! Words shown to the user, in English or with the `ru` feature in Russian
+//!
+//! Every [`Text`] and [`Symbol`] is a line of a table of lines. On the board the tables are
+//! `progmem` strings read a char at a time, like the text of [`Key::Help`](crate::Key::Help), so none of
+//! them is copied to the 2 KiB of RAM at startup; only the language built in is linked.
+
+use ufmt::{Formatter, derive::uDebug, uDisplay, uWrite};
+
+/// Table of lines, in flash on the board and a plain `str` elsewhere
+macro_rules! lines {
+    ($(#[$attr:meta])* static $name:ident = $value:literal;) => {
+        #[cfg(feature = "avr")]
+        avr_progmem::progmem! {
+            $(#[$attr])*
+            static progmem string $name = $value;
+        }
+
+        #[cfg(not(feature = "avr"))]
+        $(#[$attr])*
+        static $name: &str = $value;
+    };
+}
+
+lines! {
+    /// One [`Text`] per line, in its order
+    #[cfg(not(feature = "ru"))]
+    static TEXTS = "no operator\n\
+        overflow\n\
+        div by zero\n\
+        out of domain\n\
+        cancelled\n\
+        no results\n\
+        neg\n\
+        del\n\
+        CE\n\
+        reset\n\
+        swap\n\
+        battery\n\
+        temp\n\
+        alarm\n\
+        off\n\
+        power off\n\
+        slider\n\
+        help\n\
+        fix\n\
+        sum\n\
+        mean\n\
+        min\n\
+        max\n\
+        rec\n\
+        replay\n\
+        vibro\n\
+        soft\n\
+        medium\n\
+        strong\n\
+        chords\n\
+        time\n\
+        rounding\n\
+        convert again\n\
+        restore the entry? press: yes, move: no\n\
+        adjust\n\
+        edit\n\
+        tempo\n\
+        volume\n\
+        threshold\n\
+        year\n\
+        month\n\
+        day\n\
+        hour\n\
+        minute\n\
+        clock drift, ppm\n\
+        no clock\n\
+        clock stopped, set it with adjust\n\
+        saved settings were damaged, defaults restored\n\
+        unknown command\n\
+        invalid number\n\
+        line too long\n\
+        bytes lost, send again\n\
+        pasted\n\
+        factory defaults restored\n\
+        errors since boot / total\n\
+        no key\n\
+        op: count, min / avg / max us\n\
+        plugins\n\
+        tutorial\n\
+        start the tutorial? press: yes, move: no\n\
+        move the stick, the cursor walks over the keys starting from 5\n\
+        press the stick to enter the key under the cursor, the cursor jumps back to 5\n\
+        enter 1 + 2 =, the result is read out: n pulses for the digit n, two short for 0, five short for the dot, one long for minus\n\
+        divide by 0, five short strong pulses mean an error\n\
+        tutorial done, adjust runs it again\n\
+        tutorial skipped, adjust runs it again";
+}
+
+lines! {
+    /// One [`Text`] per line, in its order
+    #[cfg(feature = "ru")]
+    static TEXTS = "нет операции\n\
+        переполнение\n\
+        деление на 0\n\
+        вне области\n\
+        отменено\n\
+        нет результатов\n\
+        минус\n\
+        удал\n\
+        СЕ\n\
+        сброс\n\
+        обмен\n\
+        батарея\n\
+        темп\n\
+        тревога\n\
+        выкл\n\
+        выключить\n\
+        ползунок\n\
+        справка\n\
+        точность\n\
+        сумма\n\
+        среднее\n\
+        мин\n\
+        макс\n\
+        запись\n\
+        повтор\n\
+        вибро\n\
+        слабо\n\
+        средне\n\
+        сильно\n\
+        аккорды\n\
+        время\n\
+        округление\n\
+        перевести снова\n\
+        восстановить ввод? нажатие: да, движение: нет\n\
+        настройка\n\
+        правка\n\
+        скорость\n\
+        громкость\n\
+        порог\n\
+        год\n\
+        месяц\n\
+        день\n\
+        час\n\
+        минута\n\
+        уход часов, ppm\n\
+        нет часов\n\
+        часы остановились, установите их в настройке\n\
+        сохранённые настройки повреждены, восстановлены стандартные\n\
+        неизвестная команда\n\
+        неверное число\n\
+        слишком длинная строка\n\
+        байты потеряны, отправьте снова\n\
+        вставлено\n\
+        заводские настройки восстановлены\n\
+        ошибки с включения / всего\n\
+        нет клавиши\n\
+        операция: раз, мин / сред / макс мкс\n\
+        плагины\n\
+        обучение\n\
+        начать обучение? нажатие: да, движение: нет\n\
+        двигайте джойстик, курсор ходит по клавишам, начиная с 5\n\
+        нажмите джойстик, чтобы ввести клавишу под курсором, курсор вернётся на 5\n\
+        введите 1 + 2 =, результат вибрируется: n импульсов для цифры n, два коротких для 0, пять коротких для точки, один длинный для минуса\n\
+        разделите на 0, пять коротких сильных импульсов означают ошибку\n\
+        обучение пройдено, настройка запускает его снова\n\
+        обучение пропущено, настройка запускает его снова";
+}
+
+lines! {
+    /// One [`Symbol`] per line, in its order
+    static SYMBOLS = ".\n\
+        =\n\
+        +\n\
+        -\n\
+        *\n\
+        /\n\
+        ==\n\
+        <\n\
+        >\n\
+        if\n\
+        sqrt\n\
+        x^2\n\
+        x^3\n\
+        !\n\
+        1/x\n\
+        abs\n\
+        sin\n\
+        cos\n\
+        tan\n\
+        dms\n\
+        km>mi\n\
+        C>F\n\
+        kg>lb\n\
+        pi\n\
+        tau\n\
+        phi\n\
+        gamma\n\
+        sqrt2\n\
+        e\n\
+        A2\n\
+        photomath\n\
+        GPT-5\n\
+        ?";
+}
+
+/// Write line `line` of the `chars` of a table, the first is line 0
+pub(crate) fn write_line<W: uWrite + ?Sized>(
+    chars: impl Iterator<Item = char>,
+    line: u8,
+    f: &mut Formatter<'_, W>,
+) -> Result<(), W::Error> {
+    let mut n = 0;
+    for c in chars {
+        if c == '\n' {
+            n += 1;
+            if n > line {
+                break;
+            }
+        } else if n == line {
+            f.write_char(c)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Word or sentence in the language built in, everything universal is a [`Symbol`]
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum Text {
+    NoOperator,
+    Overflow,
+    DivByZero,
+    Domain,
+    Cancelled,
+    Empty,
+    Neg,
+    Delete,
+    Clear,
+    Reset,
+    Swap,
+    Battery,
+    Temperature,
+    Alarm,
+    Off,
+    PowerOff,
+    Slider,
+    Help,
+    Precision,
+    Sum,
+    Mean,
+    Min,
+    Max,
+    Record,
+    Replay,
+    Intensity,
+    Soft,
+    Medium,
+    Strong,
+    Chords,
+    Time,
+    Rounding,
+    ConvertAgain,
+    Restore,
+    Adjust,
+    Edit,
+    Tempo,
+    Volume,
+    Threshold,
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Drift,
+    NoClock,
+    ClockStopped,
+    Corrupted,
+    UnknownCommand,
+    InvalidNumber,
+    LineTooLong,
+    BytesLost,
+    Pasted,
+    Defaults,
+    Errors,
+    Rejected,
+    Profile,
+    Plugins,
+    Tutorial,
+    TutorialAsk,
+    TutorialMove,
+    TutorialPress,
+    TutorialResult,
+    TutorialError,
+    TutorialDone,
+    TutorialSkipped,
+}
+
+impl uDisplay for Text {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        write_line(TEXTS.chars(), *self as u8, f)
+    }
+}
+
+/// Name that reads the same in every language: operators, functions and constants
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbol {
+    Dot,
+    Result,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eq,
+    Lt,
+    Gt,
+    If,
+    Sqrt,
+    Pow2,
+    Pow3,
+    Factorial,
+    Recip,
+    Abs,
+    Sin,
+    Cos,
+    Tan,
+    Dms,
+    KmMi,
+    CelsiusFahrenheit,
+    KgLb,
+    Pi,
+    Tau,
+    Phi,
+    EGamma,
+    Sqrt2,
+    E,
+    A2,
+    Photomath,
+    Gpt5,
+    /// Missing name, e.g. of a plugin entry that isn't registered
+    Unknown,
+}
+
+impl uDisplay for Symbol {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        write_line(SYMBOLS.chars(), *self as u8, f)
+    }
+}
+
+/// [`Text`] or [`Symbol`], the name of something that has either
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum Word {
+    Text(Text),
+    Symbol(Symbol),
+}
+
+impl From<Text> for Word {
+    #[inline]
+    fn from(v: Text) -> Self {
+        Self::Text(v)
+    }
+}
+
+impl From<Symbol> for Word {
+    #[inline]
+    fn from(v: Symbol) -> Self {
+        Self::Symbol(v)
+    }
+}
+
+impl uDisplay for Word {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        match self {
+            Self::Text(text) => uDisplay::fmt(text, f),
+            Self::Symbol(symbol) => uDisplay::fmt(symbol, f),
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use ufmt::{Formatter, derive::uDebug, uDisplay, uWrite};
+
+use crate::{
+    BinOp, Const, Conversion, Key, Source, UnOp, alarm::AlarmMode, config::keyboard_layout,
+    history::Stat, plugin,
+};
+
+/// Keys every layout needs to enter a number and get a result
+const REQUIRED: [Key; 13] = [
+    Key::Num(0),
+    Key::Num(1),
+    Key::Num(2),
+    Key::Num(3),
+    Key::Num(4),
+    Key::Num(5),
+    Key::Num(6),
+    Key::Num(7),
+    Key::Num(8),
+    Key::Num(9),
+    Key::Dot,
+    Key::Result,
+    Key::Clear,
+];
+
+/// Grid of `R` rows of `C` cells the cursor moves over, the top row first
+pub struct Layout<const R: usize, const C: usize> {
+    pub rows: [[Cell; C]; R],
+    /// Cell the cursor starts on and goes back to after a press, as (x, y) like [`Cell::at`]
+    pub default_pos: (u8, u8),
+}
+
+impl<const R: usize, const C: usize> Layout<R, C> {
+    /// Cell at `pos` as (x, y), y counts rows from the bottom
+    ///
+    /// Reserved cells hold the [`plugin::registered`] entries in reading order.
+    #[must_use]
+    pub fn cell(&self, pos: (u8, u8)) -> Cell {
+        const { assert!(R <= 256 && C <= 256, "positions are `u8`") };
+
+        let (x, y) = (pos.0 as usize, pos.1 as usize);
+        if x >= C || y >= R {
+            return Cell::Outside;
+        }
+        let row = R - 1 - y;
+        let cell = self.rows[row][x];
+        if cell != Cell::Reserved {
+            return cell;
+        }
+
+        let before = self
+            .rows
+            .iter()
+            .flatten()
+            .take(row * C + x)
+            .filter(|&&cell| cell == Cell::Reserved)
+            .count();
+        u8::try_from(before)
+            .ok()
+            .filter(|&id| plugin::registered().get(id).is_some())
+            .map_or(Cell::Reserved, |id| Cell::Key(Key::Plugin(id)))
+    }
+
+    /// Check the keys against [`REQUIRED`], the plugins and the default position
+    ///
+    /// The layout is a full grid the cursor moves over cell by cell, so every cell is
+    /// reachable and only the contents need checking.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with the first problem found.
+    pub fn validate(&self) -> Result<(), LayoutError> {
+        let keys = || {
+            self.rows.iter().flatten().filter_map(|cell| match *cell {
+                Cell::Key(key) => Some(key),
+                _ => None,
+            })
+        };
+
+        if let Some(key) = REQUIRED.into_iter().find(|&key| !keys().any(|k| k == key)) {
+            return Err(LayoutError::Missing(key));
+        }
+
+        for (i, key) in keys().enumerate() {
+            if keys().skip(i + 1).any(|k| k == key) {
+                return Err(LayoutError::Duplicate(key));
+            }
+        }
+
+        let reserved = self
+            .rows
+            .iter()
+            .flatten()
+            .filter(|&&cell| cell == Cell::Reserved)
+            .count();
+        if let Some(left) = plugin::registered().len().checked_sub(reserved)
+            && left > 0
+        {
+            return Err(LayoutError::Plugins(left));
+        }
+
+        match self.cell(self.default_pos) {
+            Cell::Key(_) => Ok(()),
+            cell => Err(LayoutError::DefaultPos(cell)),
+        }
+    }
+}
+
+/// Cell of the [`keyboard_layout`]
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Key(Key),
+    /// Nothing here on purpose, e.g. to keep groups of keys apart
+    Empty,
+    /// Free for a key that is not there yet, or a [`Key::Plugin`] once one is registered
+    Reserved,
+    /// Past the edge of the layout
+    Outside,
+}
+
+impl Cell {
+    /// Cell of the [`keyboard_layout`] at `pos`, see [`Layout::cell`]
+    #[must_use]
+    pub fn at(pos: (u8, u8)) -> Self {
+        keyboard_layout().cell(pos)
+    }
+}
+
+/// Cell the cursor starts on, see [`Layout::default_pos`]
+#[must_use]
+pub fn default_pos() -> (u8, u8) {
+    keyboard_layout().default_pos
+}
+
+impl uDisplay for Cell {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        match self {
+            Self::Key(key) => uDisplay::fmt(key, f),
+            Self::Empty | Self::Reserved | Self::Outside => Ok(()),
+        }
+    }
+}
+
+/// Why [`validate_layout`] rejected the layout
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutError {
+    /// Key can't be pressed, so some input is impossible
+    Missing(Key),
+    /// Key is placed twice, the second one takes a cell for nothing
+    Duplicate(Key),
+    /// [`Layout::default_pos`] is not on a key
+    DefaultPos(Cell),
+    /// Plugins left without a reserved cell, they can't be pressed
+    Plugins(usize),
+}
+
+/// Check the [`keyboard_layout`], see [`Layout::validate`]
+///
+/// # Errors
+///
+/// Will return `Err` with the first problem found.
+pub fn validate_layout() -> Result<(), LayoutError> {
+    keyboard_layout().validate()
+}
+
+macro_rules! cell_from {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Cell {
+                #[inline]
+                fn from(v: $t) -> Self {
+                    Self::Key(v.into())
+                }
+            }
+        )*
+    };
+}
+
+cell_from!(Key, BinOp, UnOp, Const, Conversion, Source, AlarmMode, Stat);
@@ -1,19 +1,107 @@
 #![no_std]
 // For logging
-#![feature(sync_unsafe_cell)]
+#![cfg_attr(feature = "avr", feature(sync_unsafe_cell))]
 
-use cos_num::Num;
-use ufmt::derive::uDebug;
+use core::task::Poll;
 
+use ufmt::{Formatter, derive::uDebug, uDisplay, uWrite};
+
+use cos_num::units::{self, Quantity, Unit};
+
+use crate::{
+    alarm::{Alarm, AlarmMode},
+    config::TAYLOR_FRACTION_COUNT,
+    date::DateTime,
+    entry::Entry,
+    history::{History, Stat},
+    lang::{Symbol, Text, Word},
+    num::Num,
+    plugin::{Plugin, Plugins},
+};
+
+pub mod adjust;
+pub mod alarm;
+pub mod bus;
+#[cfg(feature = "avr")]
+pub mod busy;
+pub mod checkpoint;
+pub mod clock;
 pub mod config;
+pub mod date;
+#[cfg(feature = "dfplayer")]
+pub mod dfplayer;
+pub mod diagnostics;
+pub mod dms;
+pub mod entry;
+#[cfg(feature = "avr")]
+pub mod help;
+pub mod history;
+pub mod hms;
+pub mod hooks;
+pub mod input;
+pub mod lang;
+pub mod layout;
 pub mod log;
+pub mod num;
+#[cfg(feature = "avr")]
+pub mod pattern;
+pub mod plugin;
+#[cfg(feature = "avr")]
+pub mod power;
+#[cfg(feature = "profiling")]
+pub mod profile;
+pub mod recorder;
+pub mod remote;
+#[cfg(feature = "rtc")]
+pub mod rtc;
+#[cfg(feature = "avr")]
+pub mod sensors;
+#[cfg(feature = "avr")]
+pub mod slider;
+pub mod speech;
+#[cfg(feature = "avr")]
+pub mod storage;
+#[cfg(feature = "avr")]
+pub mod time;
+pub mod tutorial;
+
+#[cfg(not(feature = "basic-layout"))]
+use crate::num::Steps;
 
 pub struct Calculator<const F: u8> {
     a: Num<F>,
     op: Option<Op>,
     b: Num<F>,
-    frac: bool,
-    frac_digits: u8,
+    /// `b` of [`BinOp::If`] once it was pressed again, `b` is then the value for 0
+    then: Option<Num<F>>,
+    /// Live source `b` is bound to
+    live: Option<Source>,
+    alarm: Alarm<F>,
+    /// Unary operator being computed by [`Calculator::poll`]
+    eval: Option<Eval<F>>,
+    /// Digits of the operand being typed, it is set from them once another key uses it
+    entry: Option<Entry<F>>,
+    /// Separators typed into a time, see [`Key::Time`]
+    colons: u8,
+    /// Fractional digits entered and shown in results, at most `F`
+    precision: u8,
+    /// Results are kept as shown, rounded to `precision`, instead of with every digit
+    rounded: bool,
+    /// Last result came from a comparison
+    comparison: bool,
+    /// Last result is packed by [`UnOp::Dms`], pressing it again unpacks it
+    dms: bool,
+    /// Numbers are seconds entered and shown as hours:minutes:seconds
+    time: bool,
+    /// Last conversion and whether it went back, [`Key::ConvertAgain`] repeats it
+    conversion: Option<(Conversion, bool)>,
+    /// Last result came from [`Calculator::conversion`], its key then converts back
+    converted: bool,
+    history: History<F>,
+    /// Date and time the results are stamped with in the history, see [`Calculator::set_now`]
+    now: Option<DateTime>,
+    /// Entries of [`Key::Plugin`]
+    plugins: Plugins<F>,
 }
 
 impl<const F: u8> Default for Calculator<F> {
@@ -29,54 +117,278 @@ impl<const F: u8> Calculator<F> {
             a: Num::ZERO,
             op: None,
             b: Num::ZERO,
-            frac: false,
-            frac_digits: 0,
+            then: None,
+            live: None,
+            alarm: Alarm::OFF,
+            eval: None,
+            entry: None,
+            colons: 0,
+            precision: F,
+            rounded: false,
+            comparison: false,
+            dms: false,
+            time: false,
+            conversion: None,
+            converted: false,
+            history: History::new(),
+            now: None,
+            plugins: Plugins::NONE,
         }
     }
 
+    #[inline]
+    #[must_use]
+    pub const fn alarm(&self) -> Alarm<F> {
+        self.alarm
+    }
+
+    #[inline]
+    pub const fn set_alarm(&mut self, alarm: Alarm<F>) {
+        self.alarm = alarm;
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Act like a calculator with `digits` fractional digits, at most `F`
+    #[inline]
+    pub const fn set_precision(&mut self, digits: u8) {
+        self.precision = if digits < F { digits } else { F };
+    }
+
+    /// Whether results are kept as shown, so what you see is what you calculate
+    ///
+    /// Otherwise they keep all `F` digits and only the shown result is rounded to
+    /// the precision, so follow-on calculations don't compound the rounding.
+    #[inline]
+    #[must_use]
+    pub const fn is_rounded(&self) -> bool {
+        self.rounded
+    }
+
+    #[inline]
+    pub const fn set_rounded(&mut self, rounded: bool) {
+        self.rounded = rounded;
+    }
+
+    /// Use `plugins` for [`Key::Plugin`], usually [`plugin::registered`]
+    #[inline]
+    pub const fn set_plugins(&mut self, plugins: Plugins<F>) {
+        self.plugins = plugins;
+    }
+
+    /// Stamp the results from now on with `now` in the history, `None` without a clock
+    #[inline]
+    pub const fn set_now(&mut self, now: Option<DateTime>) {
+        self.now = now;
+    }
+
+    /// Last results, for [`Key::Stat`]
+    #[inline]
+    #[must_use]
+    pub const fn history(&self) -> &History<F> {
+        &self.history
+    }
+
+    /// Whether the last result is a comparison, 1 for true and 0 for false
+    #[inline]
+    #[must_use]
+    pub const fn is_comparison(&self) -> bool {
+        self.comparison
+    }
+
+    /// Whether the last result is an angle packed as D.MMSS, see [`dms::pack`]
+    #[inline]
+    #[must_use]
+    pub const fn is_dms(&self) -> bool {
+        self.dms
+    }
+
+    /// Whether numbers are seconds shown as hours:minutes:seconds, see [`Key::Time`]
+    #[inline]
+    #[must_use]
+    pub const fn is_time(&self) -> bool {
+        self.time
+    }
+
+    /// Operands and pending operator, what survives a power-off
+    ///
+    /// The number being typed is in it already, though not committed yet. A conditional
+    /// with both values has no room in it and is kept as the value it chooses.
+    #[must_use]
+    pub fn state(&self) -> State<F> {
+        let (mut a, mut b) = (self.a, self.b);
+        if let Some(v) = self.entry.as_ref().and_then(Entry::value) {
+            if self.op.is_none() {
+                a = v;
+            } else {
+                b = v;
+            }
+        }
+
+        if let Some(then) = self.then {
+            return State {
+                a: if a == Num::ZERO { b } else { then },
+                op: None,
+                b: Num::ZERO,
+            };
+        }
+
+        State {
+            a,
+            op: match self.op {
+                Some(Op::BinOp(op)) => Some(op),
+                _ => None,
+            },
+            b,
+        }
+    }
+
+    /// Number being typed with its cursor, `None` until a digit, the point, delete or a
+    /// cursor move starts one from the operand
+    #[inline]
+    #[must_use]
+    pub const fn entry(&self) -> Option<&Entry<F>> {
+        self.entry.as_ref()
+    }
+
+    /// Move the cursor of the number being typed one char right or left, `false` if it
+    /// is at the end already or the operand is a time or follows a live source
+    pub fn move_cursor(&mut self, right: bool) -> bool {
+        if self.time_entry() || self.live.is_some() {
+            return false;
+        }
+
+        let entry = self.editing();
+        if right { entry.right() } else { entry.left() }
+    }
+
+    /// Continue from a saved [`State`]
+    pub fn restore(&mut self, state: State<F>) {
+        self.entry = None;
+        self.a = state.a;
+        self.op = state.op.map(Op::BinOp);
+        self.b = state.b;
+        self.then = None;
+        self.live = None;
+        self.resume_entry();
+    }
+
+    /// Use `value` as the operand being edited, e.g. from the slider
+    ///
+    /// Cancels a unary operator being computed, like a key would.
+    pub fn enter(&mut self, value: Num<F>) {
+        self.eval = None;
+        self.converted = false;
+        self.entry = None;
+        if self.op.is_some() {
+            self.live = None;
+        }
+
+        *self.operand_mut() = value.round_frac(self.precision);
+        self.resume_entry();
+    }
+
+    /// Whether a unary operator is still being computed
+    #[inline]
+    #[must_use]
+    pub const fn is_busy(&self) -> bool {
+        self.eval.is_some()
+    }
+
+    /// Do one step of the pending unary operator
+    ///
+    /// Should be called on every main loop iteration, so long computations
+    /// (sin, cos, tan) don't block input. Returns the result once it is done.
+    ///
     /// # Errors
     ///
-    /// Will return `Err` if `self.op` is none.
+    /// Will return `Err` if the result is undefined, e.g. tan(π/2).
+    pub fn poll(&mut self) -> Result<Option<Num<F>>, CalcError> {
+        let Some(eval) = &mut self.eval else {
+            return Ok(None);
+        };
+        #[cfg(feature = "profiling")]
+        let start = time::micros();
+        let poll = eval.poll();
+        #[cfg(feature = "profiling")]
+        profile::step(
+            profile::elapsed_since(start),
+            matches!(poll, Poll::Ready(Ok(_))),
+        );
+        let Poll::Ready(result) = poll else {
+            return Ok(None);
+        };
+
+        self.eval = None;
+        self.comparison = false;
+        self.dms = false;
+        Ok(Some(self.keep(result?)))
+    }
+
+    /// Reload `b` from its live source, if it is bound to one
+    ///
+    /// Should be called right before [`Calculator::handle_input`],
+    /// so results are computed against the instantaneous reading.
+    pub fn refresh(&mut self, read: impl FnOnce(Source) -> Num<F>) {
+        if let Some(source) = self.live {
+            self.b = read(source);
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if `self.op` is none,
+    /// on `Key::Swap` and `Key::Live` without a pending binary operator,
+    /// if an entered digit would overflow the operand or make minutes or seconds of a time 60,
+    /// on `Key::Precision` unless the operand is a whole number from 0 to `F`,
+    /// on `Key::Stat` before any result was computed,
+    /// if the operator is not defined for the operands,
+    /// or while a unary operator is computed, the key then cancels it.
     pub fn handle_input(&mut self, key: Key) -> Result<Option<Num<F>>, CalcError> {
-        match key {
-            Key::Num(n) => {
-                let v = if self.op.is_none() {
-                    &mut self.a
-                } else {
-                    &mut self.b
-                };
+        if self.eval.take().is_some() {
+            return Err(CalcError::Cancelled);
+        }
+        let converted = core::mem::take(&mut self.converted);
 
-                if self.frac {
-                    if self.frac_digits < F {
-                        let scale_factor = 10i64.pow((F - self.frac_digits - 1) as u32);
-                        *v += Num::from_raw((n as i64) * scale_factor);
-                        self.frac_digits += 1;
-                    }
-                } else {
-                    *v = *v * Num::from_int(10) + Num::from_int(n as i64);
-                }
-            }
-            Key::Dot => {
-                self.frac = true;
-                self.frac_digits = 0;
-            }
-            Key::BinOp(op) => {
-                self.op = Some(Op::BinOp(op));
-                self.frac = false;
-                self.frac_digits = 0;
+        // Entering `b` by hand drops its live binding
+        if matches!(
+            key,
+            Key::Num(_) | Key::Dot | Key::Const(_) | Key::Plugin(_) | Key::Clear
+        ) && self.op.is_some()
+            && self.live.take().is_some()
+        {
+            self.b = Num::ZERO;
+        }
+        // Any other key uses the number typed
+        if !matches!(key, Key::Num(_) | Key::Dot | Key::Delete | Key::Edit) {
+            self.commit();
+        }
+
+        match key {
+            Key::Num(n) => self.push_digit(n)?,
+            Key::Dot if self.time_entry() => self.colons = (self.colons + 1).min(2),
+            Key::Dot => self.push_point(),
+            Key::BinOp(op) => self.push_op(op),
+            // Computed step by step in `Calculator::poll`
+            #[cfg(not(feature = "basic-layout"))]
+            Key::UnOp(op @ (UnOp::Sin | UnOp::Cos | UnOp::Tan)) => {
+                self.op = None;
+                self.then = None;
+                self.end_entry();
+                self.eval = Some(Eval::new(op, self.a));
             }
             Key::UnOp(op) => {
                 self.op = Some(Op::UnOp(op));
-                self.frac = false;
-                self.frac_digits = 0;
+                self.then = None;
+                self.end_entry();
                 return Ok(Some(self.calc()?));
             }
             Key::Const(c) => {
-                *if self.op.is_none() {
-                    &mut self.a
-                } else {
-                    &mut self.b
-                } = match c {
+                *self.operand_mut() = match c {
                     Const::Pi => Num::PI,
                     Const::Tau => Num::TAU,
                     Const::Phi => Num::PHI,
@@ -84,53 +396,63 @@ impl<const F: u8> Calculator<F> {
                     Const::Sqrt2 => Num::SQRT_2,
                     Const::E => Num::E,
                 }
+                .round_frac(self.precision);
+            }
+            Key::Plugin(id) => return self.use_plugin(id),
+            Key::Convert(conversion) => {
+                // Pressed again right on its result it converts back, like `UnOp::Dms`
+                let back = converted && self.conversion == Some((conversion, false));
+                return self.convert(conversion, back);
+            }
+            Key::ConvertAgain => {
+                let (conversion, back) = self.conversion.ok_or(CalcError::Calc)?;
+                return self.convert(conversion, back);
             }
+            Key::Stat(stat) => self.enter(self.history.stat(stat).ok_or(CalcError::Empty)?),
             Key::Result => {
                 let result = self.calc()?;
-                self.frac = false;
-                self.frac_digits = 0;
+                self.end_entry();
                 return Ok(Some(result));
             }
-            Key::Delete => {
-                let v = if self.op.is_none() {
-                    &mut self.a
-                } else {
-                    &mut self.b
+            Key::Delete => self.delete(),
+            Key::Clear => {
+                *self.operand_mut() = Num::ZERO;
+
+                self.end_entry();
+            }
+            Key::Live(source) => {
+                let Some(Op::BinOp(_)) = self.op else {
+                    return Err(CalcError::Calc);
                 };
 
-                if self.frac {
-                    if self.frac_digits > 0 {
-                        let digit_scale = 10i64.pow((F - self.frac_digits) as u32);
-                        let last_digit = (v.0 / digit_scale) % 10;
-                        v.0 -= last_digit * digit_scale;
-                        self.frac_digits -= 1;
-
-                        if self.frac_digits == 0 {
-                            self.frac = false;
-                        }
-                    } else {
-                        self.frac = false;
-                    }
-                } else {
-                    *v /= Num::from_int(10);
-                }
+                self.live = Some(source);
             }
-            Key::Clear => {
-                *if self.op.is_none() {
-                    &mut self.a
-                } else {
-                    &mut self.b
-                } = Num::ZERO;
+            Key::Precision => self.precision_from_operand()?,
+            Key::Rounding => self.rounded = !self.rounded,
+            Key::Alarm(mode) => {
+                self.alarm = Alarm {
+                    mode,
+                    threshold: *self.operand_mut(),
+                };
+            }
+            Key::Swap => {
+                let Some(Op::BinOp(_)) = self.op else {
+                    return Err(CalcError::Calc);
+                };
 
-                self.frac = false;
-                self.frac_digits = 0;
+                core::mem::swap(&mut self.a, &mut self.b);
+                self.live = None;
+                self.resume_entry();
             }
+            Key::Time => self.toggle_time(),
             Key::Reset => {
                 self.a = Num::ZERO;
+                self.dms = false;
                 self.op = None;
                 self.b = Num::ZERO;
-                self.frac = false;
-                self.frac_digits = 0;
+                self.then = None;
+                self.live = None;
+                self.end_entry();
             }
             _ => {}
         }
@@ -138,48 +460,415 @@ impl<const F: u8> Calculator<F> {
         Ok(None)
     }
 
+    /// Make `op` the pending operator, [`BinOp::If`] pressed again keeps `b` as the value
+    /// for a condition that isn't 0 and starts the one for 0
+    fn push_op(&mut self, op: BinOp) {
+        if op == BinOp::If && self.op == Some(Op::BinOp(BinOp::If)) {
+            if self.then.is_none() {
+                self.then = Some(self.b);
+                self.b = Num::ZERO;
+                self.live = None;
+            }
+        } else {
+            // While `b` is still empty this just replaces the pending operator
+            self.op = Some(Op::BinOp(op));
+            self.then = None;
+        }
+        self.end_entry();
+    }
+
+    /// Drop the live binding, the last digit, or with empty `b` go back to the previous operand
+    fn delete(&mut self) {
+        if self.live.take().is_some() {
+            self.b = Num::ZERO;
+        } else if self.op.is_some() && self.b_empty() {
+            // Back to the value of a conditional that isn't 0, else to editing `a`
+            match self.then.take() {
+                Some(then) => self.b = then,
+                None => self.op = None,
+            }
+            self.resume_entry();
+        } else {
+            self.delete_digit();
+        }
+    }
+
+    /// Enter the constant or apply the operator of plugin `id`
+    fn use_plugin(&mut self, id: u8) -> Result<Option<Num<F>>, CalcError> {
+        match self.plugins.get(id) {
+            Some(Plugin::Const { value, .. }) => {
+                *self.operand_mut() = value.round_frac(self.precision);
+            }
+            Some(Plugin::UnOp { .. }) => {
+                self.op = Some(Op::Plugin(id));
+                self.then = None;
+                self.end_entry();
+                return Ok(Some(self.calc()?));
+            }
+            None => {}
+        }
+
+        Ok(None)
+    }
+
+    /// Convert `a` with `conversion`, from its second unit to the first with `back`
+    fn convert(&mut self, conversion: Conversion, back: bool) -> Result<Option<Num<F>>, CalcError> {
+        self.op = Some(Op::Convert { conversion, back });
+        self.then = None;
+        self.end_entry();
+        Ok(Some(self.calc()?))
+    }
+
+    /// Insert digit `n` at the cursor of the number being typed
+    ///
+    /// Rejects the digit instead of wrapping when the operand would overflow.
+    fn push_digit(&mut self, n: u8) -> Result<(), CalcError> {
+        let precision = self.precision;
+        if !self.time_entry() {
+            return self.editing().insert(n, precision);
+        }
+
+        self.round_operand();
+        let colons = self.colons;
+        let v = self.operand_mut();
+        *v = hms::push_digit(*v, colons, n).ok_or(CalcError::Overflow)?;
+        Ok(())
+    }
+
+    /// Insert the point at the cursor of the number being typed
+    fn push_point(&mut self) {
+        let precision = self.precision;
+        self.editing().insert_point(precision);
+    }
+
+    /// Use the operand being edited as the count of fractional digits
+    fn precision_from_operand(&mut self) -> Result<(), CalcError> {
+        let v = *self.operand_mut();
+        if v.is_negative() || v.0 % Num::<F>::SCALE != 0 || v > Num::from_int(F.into()) {
+            return Err(CalcError::Domain);
+        }
+
+        self.precision = (v.0 / Num::<F>::SCALE) as u8;
+        Ok(())
+    }
+
+    /// Remove the digit before the cursor of the number being typed
+    fn delete_digit(&mut self) {
+        if !self.time_entry() {
+            self.editing().delete();
+            return;
+        }
+
+        self.round_operand();
+        let colons = self.colons;
+        if !hms::delete_digit(self.operand_mut(), colons) {
+            self.colons -= 1;
+        }
+    }
+
+    /// Whether `b` is still empty, delete then drops the operator
+    fn b_empty(&self) -> bool {
+        self.colons == 0
+            && self
+                .entry
+                .as_ref()
+                .map_or(self.b == Num::ZERO, Entry::is_empty)
+    }
+
+    /// Number being typed, started from the operand as shown if there is none
+    fn editing(&mut self) -> &mut Entry<F> {
+        if self.entry.is_none() {
+            let shown = self.operand_mut().round_frac(self.precision);
+            self.entry = Some(Entry::from_num(shown));
+        }
+        self.entry.get_or_insert_default()
+    }
+
+    /// Set the operand being edited from the number typed
+    fn commit(&mut self) {
+        if let Some(v) = self.entry.take().as_ref().and_then(Entry::value) {
+            *self.operand_mut() = v;
+        }
+    }
+
+    /// Round the operand being edited as shown, digits are typed onto what the user sees
+    const fn round_operand(&mut self) {
+        let precision = self.precision;
+        let v = self.operand_mut();
+        *v = v.round_frac(precision);
+    }
+
+    /// Operand currently being edited
+    const fn operand_mut(&mut self) -> &mut Num<F> {
+        if self.op.is_none() {
+            &mut self.a
+        } else {
+            &mut self.b
+        }
+    }
+
+    /// Whether the operand being edited is a time
+    const fn time_entry(&self) -> bool {
+        self.time && !self.factor_pending()
+    }
+
+    /// Whether `b` is the factor of `*` or `/`, which stays a number in time mode
+    const fn factor_pending(&self) -> bool {
+        matches!(self.op, Some(Op::BinOp(BinOp::Mul | BinOp::Div)))
+    }
+
+    /// Switch between decimal hours and seconds shown as hours:minutes:seconds
+    fn toggle_time(&mut self) {
+        let (time, precision) = (self.time, self.precision);
+        let convert = |v| {
+            if time {
+                hms::to_hours(v, precision)
+            } else {
+                hms::from_hours(v)
+            }
+        };
+
+        self.a = convert(self.a);
+        if !self.factor_pending() {
+            self.b = convert(self.b);
+        }
+        self.time = !time;
+        self.end_entry();
+    }
+
+    /// Start the next number from its whole part
+    fn end_entry(&mut self) {
+        self.entry = None;
+        self.colons = 0;
+    }
+
+    /// Go on typing onto the operand being edited, its digits are picked up by the next key
+    fn resume_entry(&mut self) {
+        self.entry = None;
+        // A time is resumed at its seconds
+        self.colons = if self.time_entry() { 2 } else { 0 };
+    }
+
     /// # Errors
     ///
-    /// Will return `Err` if `self.op` is none.
+    /// Will return `Err` if `self.op` is none,
+    /// if the operator is not defined for the operands,
+    /// or if the result overflows.
     pub fn calc(&mut self) -> Result<Num<F>, CalcError> {
         let Some(op) = self.op.take() else {
             return Err(CalcError::Calc);
         };
 
-        debug!("a = {}; op = {:?}; b = {}", self.a.0, self.op, self.b.0);
-
-        let a = self.a;
-
-        self.a = match op {
-            Op::BinOp(op) => {
-                let b = self.b;
-                self.b = Num::ZERO;
-                match op {
-                    BinOp::Add => a + b,
-                    BinOp::Sub => a - b,
-                    BinOp::Mul => a * b,
-                    BinOp::Div => a / b,
+        let result = match self.check(op).and_then(|()| self.timed(op)) {
+            Ok(result) => result,
+            Err(e) => {
+                // Keep a binary operator pending so `b` can be corrected
+                if matches!(op, Op::BinOp(_)) {
+                    self.op = Some(op);
                 }
+                return Err(e);
             }
+        };
+
+        debug!("a = {}; op = {:?}; b = {}", self.a, self.op, self.b);
+
+        if matches!(op, Op::BinOp(_)) {
+            self.b = Num::ZERO;
+            self.live = None;
+        }
+        self.then = None;
+        self.comparison = matches!(op, Op::BinOp(op) if op.is_comparison());
+        #[cfg(not(feature = "basic-layout"))]
+        {
+            self.dms = op == Op::UnOp(UnOp::Dms) && !self.dms;
+        }
+        if let Op::Convert { conversion, back } = op {
+            self.conversion = Some((conversion, back));
+            self.converted = true;
+        }
+
+        Ok(self.keep(result))
+    }
+
+    /// Store `result` as `a` and in the history, returns it rounded as shown
+    fn keep(&mut self, result: Num<F>) -> Num<F> {
+        let shown = result.round_frac(self.precision);
+
+        self.a = if self.rounded { shown } else { result };
+        self.history.push(self.a, self.now);
+        shown
+    }
+
+    /// [`Calculator::compute`], with the `profiling` feature counted in `profile` if it succeeds
+    #[inline]
+    fn timed(&self, op: Op) -> Result<Num<F>, CalcError> {
+        #[cfg(feature = "profiling")]
+        let start = time::micros();
+        let result = self.compute(op);
+        #[cfg(feature = "profiling")]
+        if result.is_ok() {
+            profile::record(op, profile::elapsed_since(start));
+        }
+
+        result
+    }
+
+    /// Result of `op`, `Err` if it overflows instead of wrapping
+    fn compute(&self, op: Op) -> Result<Num<F>, CalcError> {
+        let (a, b) = (self.a, self.b);
+
+        match op {
+            Op::BinOp(op) => match op {
+                BinOp::Add => a.checked_add(b),
+                BinOp::Sub => a.checked_sub(b),
+                BinOp::Mul => a.checked_mul(b),
+                BinOp::Div => a.checked_div(b),
+                BinOp::CmpEq => Some(Num::from_int((a == b).into())),
+                BinOp::CmpLt => Some(Num::from_int((a < b).into())),
+                BinOp::CmpGt => Some(Num::from_int((a > b).into())),
+                BinOp::If => Some(match (a == Num::ZERO, self.then) {
+                    (false, Some(then)) => then,
+                    (false, None) | (true, Some(_)) => b,
+                    (true, None) => Num::ZERO,
+                }),
+            },
             Op::UnOp(op) => match op {
-                UnOp::Neg => -a,
-                UnOp::Sqrt => a.sqrt(),
-                UnOp::Pow2 => a * a,
-                UnOp::Pow3 => a * a * a,
-                UnOp::Factorial => a.factorial(),
-                UnOp::Sin => a.sin(),
-                UnOp::Cos => a.cos(),
-                UnOp::Tan => a.tan(),
+                UnOp::Neg => a.checked_neg(),
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Sqrt => a.checked_sqrt(),
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Pow2 => a.checked_mul(a),
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Pow3 => a.checked_mul(a).and_then(|a2| a2.checked_mul(a)),
+                #[cfg(not(feature = "basic-layout"))]
+                // x! = Γ(x + 1) between the whole numbers
+                UnOp::Factorial if a.fract() == Num::ZERO => a.checked_factorial(),
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Factorial => a.checked_add(Num::ONE).and_then(Num::checked_gamma),
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Recip => a.checked_recip(),
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Abs => a.checked_abs(),
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Sin => Some(a.sin()),
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Cos => Some(a.cos()),
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Tan => Some(a.tan()),
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Dms if self.dms => dms::unpack(a),
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Dms => Some(dms::pack(a, self.precision)),
             },
-        };
+            Op::Convert { conversion, back } => {
+                let factors = conversion.factors();
+                let q = Quantity::new(a, factors.unit);
+                let converted = if back {
+                    factors.apply_back(q)
+                } else {
+                    factors.apply(q)
+                };
+                converted.ok().map(|q| q.value)
+            }
+            Op::Plugin(id) => {
+                return match self.plugins.get(id) {
+                    Some(Plugin::UnOp { op, .. }) => op(a),
+                    _ => Err(CalcError::Calc),
+                };
+            }
+        }
+        .ok_or(CalcError::Overflow)
+    }
+
+    /// Reject operands `op` would panic on
+    fn check(&self, op: Op) -> Result<(), CalcError> {
+        match op {
+            Op::BinOp(BinOp::Div) if self.b == Num::ZERO => Err(CalcError::DivByZero),
+            #[cfg(not(feature = "basic-layout"))]
+            Op::UnOp(UnOp::Recip) if self.a == Num::ZERO => Err(CalcError::DivByZero),
+            #[cfg(not(feature = "basic-layout"))]
+            Op::UnOp(UnOp::Sqrt) if self.a.is_negative() => Err(CalcError::Domain),
+            #[cfg(not(feature = "basic-layout"))]
+            Op::UnOp(UnOp::Dms) if self.dms && dms::unpack(self.a).is_none() => {
+                Err(CalcError::Domain)
+            }
+            // Γ has its poles at the negative whole numbers
+            #[cfg(not(feature = "basic-layout"))]
+            Op::UnOp(UnOp::Factorial)
+                if self.a.fract() == Num::ZERO
+                    && (self.a.is_negative() || self.a > Num::from_int(20)) =>
+            {
+                Err(CalcError::Domain)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Trigonometric operator being computed one series term at a time
+#[cfg(not(feature = "basic-layout"))]
+#[derive(Clone, Copy)]
+enum Eval<const F: u8> {
+    Value(Steps<F>),
+    Tan { sin: Steps<F>, cos: Steps<F> },
+}
+
+#[cfg(not(feature = "basic-layout"))]
+impl<const F: u8> Eval<F> {
+    fn new(op: UnOp, a: Num<F>) -> Self {
+        #[cfg(feature = "profiling")]
+        profile::start(Op::UnOp(op));
+
+        match op {
+            UnOp::Cos => Self::Value(a.cos_steps()),
+            UnOp::Tan => Self::Tan {
+                sin: a.sin_steps(),
+                cos: a.cos_steps(),
+            },
+            _ => Self::Value(a.sin_steps()),
+        }
+    }
+
+    fn poll(&mut self) -> Poll<Result<Num<F>, CalcError>> {
+        match self {
+            Self::Value(steps) => steps.poll().map(Ok),
+            Self::Tan { sin, cos } => match (sin.poll(), cos.poll()) {
+                (Poll::Ready(_), Poll::Ready(cos)) if cos == Num::ZERO => {
+                    Poll::Ready(Err(CalcError::DivByZero))
+                }
+                (Poll::Ready(sin), Poll::Ready(cos)) => {
+                    Poll::Ready(sin.checked_div(cos).ok_or(CalcError::Overflow))
+                }
+                _ => Poll::Pending,
+            },
+        }
+    }
+}
+
+/// Nothing is computed step by step without the trigonometric operators, so
+/// [`Calculator::poll`] and its series are left out of the basic edition
+#[cfg(feature = "basic-layout")]
+#[derive(Clone, Copy)]
+enum Eval<const F: u8> {}
 
-        Ok(self.a)
+#[cfg(feature = "basic-layout")]
+impl<const F: u8> Eval<F> {
+    const fn poll(self) -> Poll<Result<Num<F>, CalcError>> {
+        match self {}
     }
 }
 
+/// Snapshot of [`Calculator`] saved on power-off
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct State<const F: u8> {
+    pub a: Num<F>,
+    pub op: Option<BinOp>,
+    pub b: Num<F>,
+}
+
 #[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Key {
-    None,
     Num(u8),
     Dot,
     BinOp(BinOp),
@@ -189,11 +878,94 @@ pub enum Key {
     Delete,
     Clear,
     Reset,
+    Swap,
+    Live(Source),
+    /// Use the current number as alarm threshold
+    Alarm(AlarmMode),
+    /// Save state and sleep until the button is pressed
+    Power,
+    /// Pick the operand with the potentiometer
+    Slider,
+    /// Describe keys under the cursor until the button is pressed
+    Help,
+    /// Use the current whole number as the count of fractional digits
+    Precision,
+    /// Enter an aggregate of the last results
+    Stat(Stat),
+    /// Start recording the joystick, stop and dump the recording over serial
+    Record,
+    /// Feed the recorded joystick back instead of the real one
+    Replay,
+    /// Switch to the next vibro strength
+    Intensity,
+    /// Turn digit entry by chords on or off
+    Chords,
+    /// Switch between decimal hours and hours:minutes:seconds, the dot types a colon
+    Time,
+    /// Switch between results with every digit and results kept as shown
+    Rounding,
+    /// Convert the number to the second unit, or back on the result of this key
+    Convert(Conversion),
+    /// Apply the last conversion again to the number, the same way round
+    ConvertAgain,
+    /// Entry of a board crate, numbered through the [`plugin::registered`] groups
+    Plugin(u8),
+    /// Change tempo, precision and alarm threshold with the stick, see [`adjust`]
+    Adjust,
+    /// Move the cursor through the number being typed with left and right until the button
+    /// is pressed, digits and delete then work there, see [`entry`]
+    Edit,
 
     Photomath,
     GPT5,
 }
 
+impl uDisplay for Key {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        let name: Word = match *self {
+            Self::Num(n) => return uDisplay::fmt(&n, f),
+            Self::Dot => Symbol::Dot.into(),
+            Self::BinOp(op) => op.name().into(),
+            Self::UnOp(op) => op.name(),
+            Self::Const(c) => c.name().into(),
+            Self::Result => Symbol::Result.into(),
+            Self::Delete => Text::Delete.into(),
+            Self::Clear => Text::Clear.into(),
+            Self::Reset => Text::Reset.into(),
+            Self::Swap => Text::Swap.into(),
+            Self::Live(source) => source.name(),
+            Self::Alarm(mode) => {
+                uDisplay::fmt(&Text::Alarm, f)?;
+                f.write_char(' ')?;
+                mode.name()
+            }
+            Self::Power => Text::PowerOff.into(),
+            Self::Slider => Text::Slider.into(),
+            Self::Help => Text::Help.into(),
+            Self::Precision => Text::Precision.into(),
+            Self::Stat(stat) => stat.name().into(),
+            Self::Record => Text::Record.into(),
+            Self::Replay => Text::Replay.into(),
+            Self::Intensity => Text::Intensity.into(),
+            Self::Chords => Text::Chords.into(),
+            Self::Time => Text::Time.into(),
+            Self::Rounding => Text::Rounding.into(),
+            Self::Convert(conversion) => conversion.name().into(),
+            Self::ConvertAgain => Text::ConvertAgain.into(),
+            Self::Plugin(id) => match plugin::registered().get(id) {
+                Some(plugin) => return f.write_str(plugin.name()),
+                None => Symbol::Unknown.into(),
+            },
+            Self::Adjust => Text::Adjust.into(),
+            Self::Edit => Text::Edit.into(),
+            Self::Photomath => Symbol::Photomath.into(),
+            Self::GPT5 => Symbol::Gpt5.into(),
+        };
+
+        uDisplay::fmt(&name, f)
+    }
+}
+
 impl From<BinOp> for Key {
     #[inline]
     fn from(v: BinOp) -> Self {
@@ -208,6 +980,27 @@ impl From<UnOp> for Key {
     }
 }
 
+impl From<Source> for Key {
+    #[inline]
+    fn from(v: Source) -> Self {
+        Self::Live(v)
+    }
+}
+
+impl From<AlarmMode> for Key {
+    #[inline]
+    fn from(v: AlarmMode) -> Self {
+        Self::Alarm(v)
+    }
+}
+
+impl From<Stat> for Key {
+    #[inline]
+    fn from(v: Stat) -> Self {
+        Self::Stat(v)
+    }
+}
+
 impl From<Const> for Key {
     #[inline]
     fn from(v: Const) -> Self {
@@ -215,33 +1008,130 @@ impl From<Const> for Key {
     }
 }
 
+impl From<Conversion> for Key {
+    #[inline]
+    fn from(v: Conversion) -> Self {
+        Self::Convert(v)
+    }
+}
+
 #[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Op {
     BinOp(BinOp),
     UnOp(UnOp),
+    /// [`Key::Convert`], from the second unit to the first with `back`
+    Convert {
+        conversion: Conversion,
+        back: bool,
+    },
+    /// Operator of a board crate, see [`Key::Plugin`]
+    Plugin(u8),
 }
 
 #[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum BinOp {
     Add,
     Sub,
     Mul,
     Div,
+    /// 1 if the numbers are equal, else 0
+    CmpEq,
+    /// 1 if `a < b`, else 0
+    CmpLt,
+    /// 1 if `a > b`, else 0
+    CmpGt,
+    /// `b` if `a` isn't 0, else 0; pressed again before the next number that one is
+    /// the result for 0 instead, so `a ? x ? y =` is x or y
+    If,
 }
 
+impl BinOp {
+    #[must_use]
+    pub const fn name(self) -> Symbol {
+        match self {
+            Self::Add => Symbol::Add,
+            Self::Sub => Symbol::Sub,
+            Self::Mul => Symbol::Mul,
+            Self::Div => Symbol::Div,
+            Self::CmpEq => Symbol::Eq,
+            Self::CmpLt => Symbol::Lt,
+            Self::CmpGt => Symbol::Gt,
+            Self::If => Symbol::If,
+        }
+    }
+
+    /// Whether the result is 1 (true) or 0 (false)
+    #[inline]
+    #[must_use]
+    pub const fn is_comparison(self) -> bool {
+        matches!(self, Self::CmpEq | Self::CmpLt | Self::CmpGt)
+    }
+}
+
+/// Operator applied to `a` alone
+///
+/// The basic edition only has [`UnOp::Neg`], the others are compiled out with their math.
 #[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum UnOp {
     Neg,
+    #[cfg(not(feature = "basic-layout"))]
     Sqrt,
+    #[cfg(not(feature = "basic-layout"))]
     Pow2,
+    #[cfg(not(feature = "basic-layout"))]
     Pow3,
+    #[cfg(not(feature = "basic-layout"))]
     Factorial,
+    /// 1/x, not at 0
+    #[cfg(not(feature = "basic-layout"))]
+    Recip,
+    #[cfg(not(feature = "basic-layout"))]
+    Abs,
+    #[cfg(not(feature = "basic-layout"))]
     Sin,
+    #[cfg(not(feature = "basic-layout"))]
     Cos,
+    #[cfg(not(feature = "basic-layout"))]
     Tan,
+    /// Degrees to D.MMSS, or back if the operand was packed by it
+    #[cfg(not(feature = "basic-layout"))]
+    Dms,
+}
+
+impl UnOp {
+    #[must_use]
+    pub const fn name(self) -> Word {
+        match self {
+            Self::Neg => Word::Text(Text::Neg),
+            #[cfg(not(feature = "basic-layout"))]
+            Self::Sqrt => Word::Symbol(Symbol::Sqrt),
+            #[cfg(not(feature = "basic-layout"))]
+            Self::Pow2 => Word::Symbol(Symbol::Pow2),
+            #[cfg(not(feature = "basic-layout"))]
+            Self::Pow3 => Word::Symbol(Symbol::Pow3),
+            #[cfg(not(feature = "basic-layout"))]
+            Self::Factorial => Word::Symbol(Symbol::Factorial),
+            #[cfg(not(feature = "basic-layout"))]
+            Self::Recip => Word::Symbol(Symbol::Recip),
+            #[cfg(not(feature = "basic-layout"))]
+            Self::Abs => Word::Symbol(Symbol::Abs),
+            #[cfg(not(feature = "basic-layout"))]
+            Self::Sin => Word::Symbol(Symbol::Sin),
+            #[cfg(not(feature = "basic-layout"))]
+            Self::Cos => Word::Symbol(Symbol::Cos),
+            #[cfg(not(feature = "basic-layout"))]
+            Self::Tan => Word::Symbol(Symbol::Tan),
+            #[cfg(not(feature = "basic-layout"))]
+            Self::Dms => Word::Symbol(Symbol::Dms),
+        }
+    }
 }
 
 #[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Const {
     Pi,
     Tau,
@@ -251,7 +1141,662 @@ pub enum Const {
     E,
 }
 
+impl Const {
+    #[must_use]
+    pub const fn name(self) -> Symbol {
+        match self {
+            Self::Pi => Symbol::Pi,
+            Self::Tau => Symbol::Tau,
+            Self::Phi => Symbol::Phi,
+            Self::EGamma => Symbol::EGamma,
+            Self::Sqrt2 => Symbol::Sqrt2,
+            Self::E => Symbol::E,
+        }
+    }
+}
+
+/// Units [`Key::Convert`] goes between, from the first to the second
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Conversion {
+    /// Kilometers to miles
+    KmMi,
+    /// Degrees Celsius to Fahrenheit
+    CelsiusFahrenheit,
+    /// Kilograms to pounds
+    KgLb,
+}
+
+impl Conversion {
+    #[must_use]
+    pub const fn name(self) -> Symbol {
+        match self {
+            Self::KmMi => Symbol::KmMi,
+            Self::CelsiusFahrenheit => Symbol::CelsiusFahrenheit,
+            Self::KgLb => Symbol::KgLb,
+        }
+    }
+
+    /// Factors of the conversion, exact ones: 1 mi is 1.609344 km and 1 lb 0.45359237 kg
+    const fn factors<const F: u8>(self) -> units::Conversion<F, TAYLOR_FRACTION_COUNT> {
+        let (unit, num, den, offset) = match self {
+            Self::KmMi => (Unit::METER, 15_625, 25_146, Num::ZERO),
+            Self::CelsiusFahrenheit => (Unit::KELVIN, 9, 5, Num::from_int(32)),
+            Self::KgLb => (Unit::KILOGRAM, 100_000_000, 45_359_237, Num::ZERO),
+        };
+        units::Conversion {
+            unit,
+            num,
+            den,
+            offset,
+        }
+    }
+}
+
+/// Live value that operand `b` can be bound to
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Source {
+    /// Voltage on the A2 pin
+    A2,
+    /// Supply voltage
+    Battery,
+    /// Internal temperature sensor, in °C
+    Temperature,
+}
+
+impl Source {
+    #[must_use]
+    pub const fn name(self) -> Word {
+        match self {
+            Self::A2 => Word::Symbol(Symbol::A2),
+            Self::Battery => Word::Text(Text::Battery),
+            Self::Temperature => Word::Text(Text::Temperature),
+        }
+    }
+}
+
 #[derive(Debug, uDebug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum CalcError {
     Calc,
+    Overflow,
+    DivByZero,
+    /// Operand outside the operator's domain, e.g. `sqrt` of a negative number
+    Domain,
+    /// Key pressed while a result was computed
+    Cancelled,
+    /// [`Key::Stat`] before any result was computed
+    Empty,
+}
+
+impl uDisplay for CalcError {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        let text = match self {
+            Self::Calc => Text::NoOperator,
+            Self::Overflow => Text::Overflow,
+            Self::DivByZero => Text::DivByZero,
+            Self::Domain => Text::Domain,
+            Self::Cancelled => Text::Cancelled,
+            Self::Empty => Text::Empty,
+        };
+
+        uDisplay::fmt(&text, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BinOp, CalcError, Calculator, Const, Conversion, Key, Source, State, UnOp,
+        alarm::{Alarm, AlarmMode},
+        num::Num,
+    };
+
+    type TestCalc = Calculator<2>;
+
+    fn state(a: i64, op: Option<BinOp>, b: i64) -> State<2> {
+        State {
+            a: Num::from_int(a),
+            op,
+            b: Num::from_int(b),
+        }
+    }
+
+    /// Press every key, each but the last has to give nothing
+    fn press<const F: u8>(
+        calc: &mut Calculator<F>,
+        keys: &[Key],
+    ) -> Result<Option<Num<F>>, CalcError> {
+        let (last, keys) = keys.split_last().expect("a key");
+        for &key in keys {
+            assert_eq!(calc.handle_input(key), Ok(None));
+        }
+        calc.handle_input(*last)
+    }
+
+    #[test]
+    fn test_replace_operator() {
+        let mut calc = TestCalc::new();
+        let keys = [
+            Key::Num(6),
+            Key::BinOp(BinOp::Add),
+            Key::BinOp(BinOp::Sub),
+            Key::BinOp(BinOp::Mul),
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(None));
+        assert!(calc.state() == state(6, Some(BinOp::Mul), 0));
+
+        let keys = [Key::Num(3), Key::Result];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(18))));
+    }
+
+    #[test]
+    fn test_delete_operator() {
+        let mut calc = TestCalc::new();
+        let keys = [Key::Num(6), Key::BinOp(BinOp::Add), Key::Delete];
+        assert_eq!(press(&mut calc, &keys), Ok(None));
+        assert!(calc.state() == state(6, None, 0));
+
+        // Typing goes on in `a`
+        let keys = [
+            Key::Num(1),
+            Key::BinOp(BinOp::Add),
+            Key::Num(1),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(62))));
+    }
+
+    #[test]
+    fn test_delete_digits_first() {
+        let mut calc = TestCalc::new();
+        let keys = [
+            Key::Num(6),
+            Key::BinOp(BinOp::Sub),
+            Key::Num(1),
+            Key::Num(2),
+            Key::Delete,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(None));
+        assert!(calc.state() == state(6, Some(BinOp::Sub), 1));
+
+        // The operator goes once `b` is empty
+        assert_eq!(calc.handle_input(Key::Delete), Ok(None));
+        assert!(calc.state() == state(6, Some(BinOp::Sub), 0));
+        assert_eq!(calc.handle_input(Key::Delete), Ok(None));
+        assert!(calc.state() == state(6, None, 0));
+
+        // Then the digits of `a`
+        assert_eq!(calc.handle_input(Key::Delete), Ok(None));
+        assert!(calc.state() == state(0, None, 0));
+        assert_eq!(calc.handle_input(Key::Delete), Ok(None));
+        assert!(calc.state() == state(0, None, 0));
+    }
+    #[test]
+    fn test_if() {
+        let mut calc = TestCalc::new();
+        let keys = [Key::Num(2), Key::BinOp(BinOp::If), Key::Num(7), Key::Result];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(7))));
+        calc.handle_input(Key::Reset).expect("reset");
+
+        let keys = [Key::Num(0), Key::BinOp(BinOp::If), Key::Num(7), Key::Result];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::ZERO)));
+        assert!(!calc.is_comparison());
+    }
+
+    #[test]
+    fn test_if_else() {
+        let mut calc = TestCalc::new();
+        let keys = [
+            Key::Num(3),
+            Key::BinOp(BinOp::CmpGt),
+            Key::Num(5),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::ZERO)));
+
+        // The larger of the two
+        let keys = [
+            Key::BinOp(BinOp::If),
+            Key::Num(3),
+            Key::BinOp(BinOp::If),
+            Key::Num(5),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(5))));
+        calc.handle_input(Key::Reset).expect("reset");
+
+        let keys = [
+            Key::Num(1),
+            Key::BinOp(BinOp::If),
+            Key::Num(3),
+            Key::BinOp(BinOp::If),
+            Key::BinOp(BinOp::If),
+            Key::Num(5),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(3))));
+        calc.handle_input(Key::Reset).expect("reset");
+
+        // With nothing typed the value for a true condition is 0
+        let keys = [
+            Key::Num(1),
+            Key::BinOp(BinOp::If),
+            Key::BinOp(BinOp::If),
+            Key::Num(5),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::ZERO)));
+    }
+
+    #[test]
+    fn test_if_delete() {
+        let mut calc = TestCalc::new();
+        let keys = [
+            Key::Num(1),
+            Key::BinOp(BinOp::If),
+            Key::Num(3),
+            Key::BinOp(BinOp::If),
+            Key::Num(5),
+            Key::Delete,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(None));
+        assert!(calc.state() == state(3, None, 0));
+
+        // The value for a condition that isn't 0 is edited again
+        assert_eq!(calc.handle_input(Key::Delete), Ok(None));
+        assert!(calc.state() == state(1, Some(BinOp::If), 3));
+        let keys = [Key::Num(4), Key::Result];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(34))));
+    }
+
+    #[test]
+    fn test_if_replaced() {
+        let mut calc = TestCalc::new();
+        let keys = [
+            Key::Num(0),
+            Key::BinOp(BinOp::If),
+            Key::Num(3),
+            Key::BinOp(BinOp::If),
+            Key::BinOp(BinOp::Add),
+            Key::Num(5),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(5))));
+        calc.handle_input(Key::Reset).expect("reset");
+
+        // Another operator drops the value kept for a condition that isn't 0
+        let keys = [Key::Num(2), Key::BinOp(BinOp::If), Key::Num(4), Key::Result];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(4))));
+    }
+
+    #[test]
+    fn test_if_state() {
+        let mut calc = TestCalc::new();
+        let keys = [
+            Key::Num(0),
+            Key::BinOp(BinOp::If),
+            Key::Num(3),
+            Key::BinOp(BinOp::If),
+            Key::Num(5),
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(None));
+        assert!(calc.state() == state(5, None, 0));
+
+        // Saved as the value chosen
+        calc.restore(calc.state());
+        let keys = [Key::BinOp(BinOp::Add), Key::Num(1), Key::Result];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(6))));
+    }
+
+    #[test]
+    fn test_convert() {
+        let mut calc = TestCalc::new();
+        let keys = [Key::Num(5), Key::Num(0), Key::Convert(Conversion::KmMi)];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_raw(3107))));
+        // Pressed again on the result it converts back
+        let back = calc.handle_input(Key::Convert(Conversion::KmMi));
+        assert_eq!(back, Ok(Some(Num::from_int(50))));
+
+        calc.handle_input(Key::Reset).expect("reset");
+        let keys = [Key::Num(4), Key::Num(0), Key::UnOp(UnOp::Neg)];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(-40))));
+        let f = calc.handle_input(Key::Convert(Conversion::CelsiusFahrenheit));
+        assert_eq!(f, Ok(Some(Num::from_int(-40))));
+
+        // A digit typed onto the result makes another number, it is converted forward
+        let keys = [Key::Num(1), Key::Convert(Conversion::CelsiusFahrenheit)];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_raw(-68_980))));
+    }
+
+    #[test]
+    fn test_convert_again() {
+        let mut calc = TestCalc::new();
+        assert_eq!(calc.handle_input(Key::ConvertAgain), Err(CalcError::Calc));
+
+        let keys = [Key::Num(1), Key::Num(0), Key::Convert(Conversion::KgLb)];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_raw(2205))));
+        // Repeated on the result it converts it once more, not back
+        let again = calc.handle_input(Key::ConvertAgain);
+        assert_eq!(again, Ok(Some(Num::from_raw(4861))));
+
+        // The other way round once the last conversion went back
+        calc.handle_input(Key::Reset).expect("reset");
+        let keys = [Key::Num(1), Key::Num(0), Key::Convert(Conversion::KgLb)];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_raw(2205))));
+        let back = calc.handle_input(Key::Convert(Conversion::KgLb));
+        assert_eq!(back, Ok(Some(Num::from_int(10))));
+        calc.handle_input(Key::Reset).expect("reset");
+        let keys = [Key::Num(1), Key::Num(0), Key::ConvertAgain];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_raw(454))));
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut calc = TestCalc::new();
+        let keys = [Key::Num(8), Key::BinOp(BinOp::Sub), Key::Num(3), Key::Swap];
+        assert_eq!(press(&mut calc, &keys), Ok(None));
+        assert!(calc.state() == state(3, Some(BinOp::Sub), 8));
+        assert_eq!(calc.handle_input(Key::Result), Ok(Some(Num::from_int(-5))));
+
+        // Twice is back where it was
+        calc.handle_input(Key::Reset).expect("reset");
+        let keys = [
+            Key::Num(8),
+            Key::BinOp(BinOp::Div),
+            Key::Num(2),
+            Key::Swap,
+            Key::Swap,
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(4))));
+    }
+
+    #[test]
+    fn test_swap_typing() {
+        // Digits go on onto the number swapped into `b`
+        let mut calc = TestCalc::new();
+        let keys = [
+            Key::Num(8),
+            Key::BinOp(BinOp::Sub),
+            Key::Num(3),
+            Key::Swap,
+            Key::Num(1),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(-78))));
+
+        // And delete drops its digits
+        calc.handle_input(Key::Reset).expect("reset");
+        let keys = [
+            Key::Num(2),
+            Key::Num(5),
+            Key::BinOp(BinOp::Add),
+            Key::Num(1),
+            Key::Swap,
+            Key::Delete,
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(3))));
+    }
+
+    #[test]
+    fn test_swap_rejected() {
+        let mut calc = TestCalc::new();
+        assert_eq!(calc.handle_input(Key::Swap), Err(CalcError::Calc));
+        // A unary operator isn't pending, its result is shown
+        let keys = [Key::Num(4), Key::UnOp(UnOp::Neg)];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(-4))));
+        assert_eq!(calc.handle_input(Key::Swap), Err(CalcError::Calc));
+        assert!(calc.state() == state(-4, None, 0));
+    }
+
+    #[test]
+    fn test_swap_live() {
+        // The reading swapped into `a` stays put, `b` isn't bound to the source anymore
+        let mut calc = TestCalc::new();
+        let keys = [Key::Num(9), Key::BinOp(BinOp::Sub), Key::Live(Source::A2)];
+        assert_eq!(press(&mut calc, &keys), Ok(None));
+        calc.refresh(|_| Num::from_int(5));
+        assert_eq!(calc.handle_input(Key::Swap), Ok(None));
+        calc.refresh(|_| Num::from_int(100));
+        assert!(calc.state() == state(5, Some(BinOp::Sub), 9));
+        assert_eq!(calc.handle_input(Key::Result), Ok(Some(Num::from_int(-4))));
+    }
+
+    /// Calculator with 4 digits acting like one with `precision`, set with its key
+    fn with_precision(precision: u8) -> Calculator<4> {
+        let mut calc = Calculator::new();
+        let keys = [Key::Num(precision), Key::Precision, Key::Reset];
+        assert_eq!(press(&mut calc, &keys), Ok(None));
+        assert_eq!(calc.precision(), precision);
+        calc
+    }
+
+    #[test]
+    fn test_precision_entry() {
+        // Fractional digits beyond the precision are ignored
+        let mut calc = with_precision(2);
+        let keys = [
+            Key::Num(1),
+            Key::Dot,
+            Key::Num(2),
+            Key::Num(3),
+            Key::Num(4),
+            Key::Num(5),
+            Key::BinOp(BinOp::Add),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_raw(12_300))));
+
+        // With none the point starts nothing
+        let mut calc = with_precision(0);
+        let keys = [
+            Key::Num(7),
+            Key::Dot,
+            Key::Num(5),
+            Key::BinOp(BinOp::Add),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(7))));
+
+        // Constants are entered as shown
+        let mut calc = with_precision(2);
+        let keys = [Key::Const(Const::Pi), Key::BinOp(BinOp::Add), Key::Result];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_raw(31_400))));
+    }
+
+    #[test]
+    fn test_precision_results() {
+        // Shown rounded, the next operator goes on with every digit
+        let mut calc = with_precision(2);
+        let keys = [
+            Key::Num(2),
+            Key::BinOp(BinOp::Div),
+            Key::Num(3),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_raw(6700))));
+        assert!(calc.state().a == Num::from_raw(6667));
+        let keys = [Key::BinOp(BinOp::Mul), Key::Num(3), Key::Result];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(2))));
+
+        // Kept as shown with rounding
+        let keys = [Key::Reset, Key::Rounding];
+        assert_eq!(press(&mut calc, &keys), Ok(None));
+        let keys = [
+            Key::Num(2),
+            Key::BinOp(BinOp::Div),
+            Key::Num(3),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_raw(6700))));
+        let keys = [Key::BinOp(BinOp::Mul), Key::Num(3), Key::Result];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_raw(20_100))));
+
+        // Halves round away from zero
+        let mut calc = with_precision(0);
+        let keys = [
+            Key::Num(7),
+            Key::BinOp(BinOp::Div),
+            Key::Num(2),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(4))));
+        calc.handle_input(Key::Reset).expect("reset");
+        let keys = [Key::Num(7), Key::UnOp(UnOp::Neg)];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(-7))));
+        let keys = [Key::BinOp(BinOp::Div), Key::Num(2), Key::Result];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(-4))));
+    }
+
+    #[test]
+    fn test_precision_rejected() {
+        // Only whole numbers from 0 to `F` are a precision
+        let mut calc = with_precision(2);
+        let keys = [Key::Num(5), Key::Precision];
+        assert_eq!(press(&mut calc, &keys), Err(CalcError::Domain));
+        let keys = [
+            Key::Reset,
+            Key::Num(1),
+            Key::Dot,
+            Key::Num(5),
+            Key::Precision,
+        ];
+        assert_eq!(press(&mut calc, &keys), Err(CalcError::Domain));
+        let keys = [Key::Reset, Key::Num(1), Key::UnOp(UnOp::Neg)];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(-Num::ONE)));
+        assert_eq!(calc.handle_input(Key::Precision), Err(CalcError::Domain));
+        assert_eq!(calc.precision(), 2);
+
+        // The most digits there are
+        let keys = [Key::Reset, Key::Num(4), Key::Precision];
+        assert_eq!(press(&mut calc, &keys), Ok(None));
+        assert_eq!(calc.precision(), 4);
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let mut calc = TestCalc::new();
+        for (a, op, b, result) in [
+            (3, BinOp::CmpLt, 5, 1),
+            (5, BinOp::CmpLt, 3, 0),
+            (2, BinOp::CmpEq, 2, 1),
+            (2, BinOp::CmpEq, 3, 0),
+            (4, BinOp::CmpGt, 3, 1),
+            (3, BinOp::CmpGt, 3, 0),
+        ] {
+            let keys = [
+                Key::Reset,
+                Key::Num(a),
+                Key::BinOp(op),
+                Key::Num(b),
+                Key::Result,
+            ];
+            assert_eq!(press(&mut calc, &keys), Ok(Some(Num::from_int(result))));
+            assert!(calc.is_comparison());
+        }
+
+        // The result is a number like any other
+        let keys = [Key::BinOp(BinOp::Add), Key::Num(1), Key::Result];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::ONE)));
+        assert!(!calc.is_comparison());
+        let keys = [Key::BinOp(BinOp::CmpEq), Key::Num(1), Key::Result];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::ONE)));
+        assert!(calc.is_comparison());
+        assert_eq!(calc.handle_input(Key::UnOp(UnOp::Neg)), Ok(Some(-Num::ONE)));
+        assert!(!calc.is_comparison());
+    }
+
+    #[test]
+    fn test_comparison_alarm() {
+        // Half way between false and true, the alarm fires on one of them
+        let mut calc = TestCalc::new();
+        let keys = [
+            Key::Num(0),
+            Key::Dot,
+            Key::Num(5),
+            Key::Alarm(AlarmMode::Above),
+            Key::Reset,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(None));
+        let half = Num::from_raw(50);
+        assert!(
+            calc.alarm()
+                == Alarm {
+                    mode: AlarmMode::Above,
+                    threshold: half
+                }
+        );
+
+        let keys = [
+            Key::Num(3),
+            Key::BinOp(BinOp::CmpGt),
+            Key::Num(2),
+            Key::Result,
+        ];
+        let result = press(&mut calc, &keys)
+            .expect("a comparison")
+            .expect("a result");
+        assert!(calc.alarm().is_triggered(result));
+        let keys = [
+            Key::Reset,
+            Key::Num(2),
+            Key::BinOp(BinOp::CmpGt),
+            Key::Num(3),
+            Key::Result,
+        ];
+        let result = press(&mut calc, &keys)
+            .expect("a comparison")
+            .expect("a result");
+        assert!(!calc.alarm().is_triggered(result));
+
+        // Below it fires on false, the threshold is kept
+        let keys = [
+            Key::Num(0),
+            Key::Dot,
+            Key::Num(5),
+            Key::Alarm(AlarmMode::Below),
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(None));
+        assert!(
+            calc.alarm()
+                == Alarm {
+                    mode: AlarmMode::Below,
+                    threshold: half
+                }
+        );
+        let keys = [
+            Key::Reset,
+            Key::Num(2),
+            Key::BinOp(BinOp::CmpEq),
+            Key::Num(3),
+            Key::Result,
+        ];
+        let result = press(&mut calc, &keys)
+            .expect("a comparison")
+            .expect("a result");
+        assert!(calc.alarm().is_triggered(result));
+
+        // The threshold can be the second number of a comparison too
+        let keys = [
+            Key::Reset,
+            Key::Num(1),
+            Key::BinOp(BinOp::CmpLt),
+            Key::Num(4),
+            Key::Alarm(AlarmMode::Above),
+            Key::Result,
+        ];
+        assert_eq!(press(&mut calc, &keys), Ok(Some(Num::ONE)));
+        let four = Num::from_int(4);
+        assert!(
+            calc.alarm()
+                == Alarm {
+                    mode: AlarmMode::Above,
+                    threshold: four
+                }
+        );
+        assert!(!calc.alarm().is_triggered(Num::ONE));
+    }
 }
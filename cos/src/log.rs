@@ -1,5 +1,9 @@
+//! Logging over the serial port, the macros expand to nothing without the `avr` feature
+
+#[cfg(feature = "avr")]
 use core::{cell::SyncUnsafeCell, mem::MaybeUninit};
 
+#[cfg(feature = "avr")]
 use arduino_hal::{
     Usart,
     hal::port::{PD0, PD1},
@@ -10,13 +14,17 @@ use arduino_hal::{
     },
 };
 
+#[cfg(feature = "avr")]
 pub struct Serial(pub Usart<USART0, Pin<Input, PD0>, Pin<Output, PD1>>);
 
 // SAFETY: This impl is safe because arduino have only one thread.
+#[cfg(feature = "avr")]
 unsafe impl Send for Serial {}
 // SAFETY: ^
+#[cfg(feature = "avr")]
 unsafe impl Sync for Serial {}
 
+#[cfg(feature = "avr")]
 pub static SERIAL: SyncUnsafeCell<MaybeUninit<Serial>> = SyncUnsafeCell::new(MaybeUninit::uninit());
 
 /// Initialize the global serial logger
@@ -25,12 +33,14 @@ pub static SERIAL: SyncUnsafeCell<MaybeUninit<Serial>> = SyncUnsafeCell::new(May
 ///
 /// Must be called exactly once before any logging macros are used.
 /// Must not be called concurrently with any other access to SERIAL.
+#[cfg(feature = "avr")]
 pub unsafe fn init(serial: Usart<USART0, Pin<Input, PD0>, Pin<Output, PD1>>) {
     unsafe {
         SERIAL.get().write(MaybeUninit::new(Serial(serial)));
     }
 }
 
+#[cfg(feature = "avr")]
 #[macro_export]
 macro_rules! info {
     ($($arg:tt)*) => {
@@ -40,6 +50,7 @@ macro_rules! info {
     };
 }
 
+#[cfg(feature = "avr")]
 #[macro_export]
 macro_rules! info_infallible {
     ($($arg:tt)*) => {
@@ -50,6 +61,7 @@ macro_rules! info_infallible {
 }
 
 // #[cfg(debug_assertions)] removes debug output in release build
+#[cfg(feature = "avr")]
 #[macro_export]
 macro_rules! debug {
     ($($arg:tt)*) => {
@@ -62,6 +74,7 @@ macro_rules! debug {
     };
 }
 
+#[cfg(feature = "avr")]
 #[macro_export]
 macro_rules! debug_infallible {
     ($($arg:tt)*) => {
@@ -73,3 +86,27 @@ macro_rules! debug_infallible {
         }
     };
 }
+
+#[cfg(not(feature = "avr"))]
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(not(feature = "avr"))]
+#[macro_export]
+macro_rules! info_infallible {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(not(feature = "avr"))]
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(not(feature = "avr"))]
+#[macro_export]
+macro_rules! debug_infallible {
+    ($($arg:tt)*) => {};
+}
@@ -6,25 +6,129 @@
 use core::panic::PanicInfo;
 
 use arduino_hal::{
-    hal::port::PD3,
-    port::{Pin, mode::Output},
+    hal::port::PD2,
+    port::{
+        Pin,
+        mode::{Input, PullUp},
+    },
     prelude::*,
 };
+#[cfg(not(feature = "rtc"))]
+use cos::config::{SECOND_STICK, SLIDER_RANGE};
+#[cfg(feature = "profiling")]
+use cos::profile;
+#[cfg(feature = "speech")]
+use cos::speech::Voice;
 use cos::{
-    Calculator, Key,
-    config::{DEFAULT_POS, FRACTION_COUNT, keyboard_layout},
-    debug, info_infallible,
+    Calculator, Key, Source,
+    adjust::{Adjuster, Setting},
+    alarm::Alarm,
+    bus::{self, CalcEvent, InputEvent, OutputRequest},
+    busy::Busy,
+    checkpoint::Checkpoint,
+    clock::{AvrClock, Clock as _, Period, Timeout},
+    config::{AUTO_OFF_MS, FRACTION_COUNT, TEMPO_RANGE, TEMPO_STEP},
+    debug,
+    diagnostics::{ErrorCounts, ErrorKind},
+    dms,
+    help::Help,
+    hms::Hms,
+    hooks::{Event, Hooks},
+    info, info_infallible,
+    input::{Button, Chord, Chords, Dir, Press, Stick, StickRole},
+    lang::Text,
+    layout::{self, Cell, validate_layout},
     log::{self},
+    num::{Digit, Dms, Num},
+    pattern::{self, Intensity, Player, Pulses},
+    plugin,
+    power::Power,
+    recorder::{Recorder, Sample},
+    remote::{self, Command, Remote},
+    sensors::Sensors,
+    slider::Slider,
+    storage::Storage,
+    time,
+    tutorial::{self, Observed, Progress, Tutorial},
 };
-use cos_num::Num;
-use heapless::Vec;
+#[cfg(feature = "dfplayer")]
+use cos::{config::DFPLAYER_VOLUME, dfplayer::DfPlayer};
+#[cfg(feature = "rtc")]
+use cos::{
+    config::RTC_CHIP,
+    date::DateTime,
+    rtc::{Rtc, RtcError},
+};
+use ufmt::derive::uDebug;
+
+/// Hardware shared by the hooks
+struct Device {
+    sw: Pin<Input<PullUp>, PD2>,
+    /// Debounced `sw`, a long press turns the device off
+    button: Button,
+    storage: Storage,
+    power: Power,
+    busy: Busy,
+    player: Player,
+    clock: AvrClock,
+    /// Turns the device off after a while without input, see [`AUTO_OFF_MS`]
+    auto_off: Option<Timeout>,
+    /// Vibro output speed in percent of the default
+    tempo: u16,
+    slider: Option<Slider<FRACTION_COUNT>>,
+    /// Moving describes the key under the cursor, see [`Key::Help`]
+    help: bool,
+    recorder: Recorder,
+    /// Digits are entered by chords, see [`Key::Chords`]
+    chords: Option<Chords>,
+    checkpoint: Checkpoint<FRACTION_COUNT>,
+    /// Setting changed with the stick, see [`Key::Adjust`]
+    adjuster: Option<Adjuster<FRACTION_COUNT>>,
+    /// Left and right move the cursor of the number being typed, see [`Key::Edit`]
+    editing: bool,
+    /// Commands from a connected host, see [`remote`]
+    remote: Remote,
+    errors: ErrorCounts,
+    /// Guided first steps, see [`tutorial`]
+    tutorial: Tutorial,
+    /// Says entries, results and errors, see [`cos::speech`]
+    #[cfg(feature = "speech")]
+    voice: Voice,
+    /// Date and time for the history and recordings, `None` if the clock didn't answer at
+    /// boot, see [`cos::rtc`]
+    #[cfg(feature = "rtc")]
+    rtc: Option<Rtc<arduino_hal::I2c>>,
+    /// When the recording was started, see [`Key::Record`]
+    #[cfg(feature = "rtc")]
+    recorded: Option<DateTime>,
+}
+
+// Board crates add their groups here, e.g. `plugins!(thermocouple::PLUGINS)`
+cos::plugins!();
 
-#[expect(clippy::unwrap_used)]
+/// Run on every accepted key and computed result, in order
+const HOOKS: Hooks<Device, FRACTION_COUNT, 12> = Hooks([
+    mirror_serial,
+    display_result,
+    check_alarm,
+    save_settings,
+    power_off,
+    open_slider,
+    start_help,
+    use_recorder,
+    cycle_intensity,
+    toggle_chords,
+    open_adjuster,
+    start_editing,
+]);
+
+#[expect(clippy::unwrap_used, clippy::too_many_lines)]
 #[arduino_hal::entry]
 fn main() -> ! {
     let dp = arduino_hal::Peripherals::take().unwrap();
     let pins = arduino_hal::pins!(dp);
 
+    time::start(dp.TC0);
     let mut adc = arduino_hal::Adc::new(dp.ADC, Default::default());
 
     let serial = arduino_hal::default_serial!(dp, pins, 57600);
@@ -33,112 +137,1199 @@ fn main() -> ! {
     unsafe {
         log::init(serial);
     }
+    remote::listen();
 
-    let mut vibro = pins.d3.into_output();
-    let sw = pins.d2.into_pull_up_input();
+    let mut stick = Stick::new(
+        pins.a0.into_analog_input(&mut adc).into_channel(),
+        pins.a1.into_analog_input(&mut adc).into_channel(),
+    );
+    #[cfg(not(feature = "rtc"))]
+    let mut second_stick = SECOND_STICK.map(|role| {
+        let stick = Stick::new(
+            pins.a3.into_analog_input(&mut adc).into_channel(),
+            pins.a4.into_analog_input(&mut adc).into_channel(),
+        );
+        (role, stick, InputState::new())
+    });
+    // A4 carries the clock's I2C instead
+    #[cfg(feature = "rtc")]
+    let mut second_stick: Option<(StickRole, Stick, InputState)> = None;
+    let sensors = Sensors {
+        a2: pins.a2.into_analog_input(&mut adc),
+    };
+
+    let mut device = Device {
+        sw: pins.d2.into_pull_up_input(),
+        button: Button::new(),
+        storage: Storage::new(arduino_hal::Eeprom::new(dp.EEPROM)),
+        power: Power::new(dp.CPU, dp.EXINT),
+        busy: Busy::new(dp.TC1),
+        player: Player::new(dp.TC2, pins.d3.into_output()),
+        tempo: 100,
+        #[cfg(not(feature = "rtc"))]
+        slider: SLIDER_RANGE
+            .map(|range| Slider::new(pins.a5.into_analog_input(&mut adc).into_channel(), range)),
+        // And A5
+        #[cfg(feature = "rtc")]
+        slider: None,
+        help: false,
+        clock: AvrClock::new(),
+        auto_off: None,
+        recorder: Recorder::new(),
+        chords: None,
+        checkpoint: Checkpoint::new(),
+        adjuster: None,
+        editing: false,
+        remote: Remote::new(),
+        errors: ErrorCounts::new(),
+        tutorial: Tutorial::new(),
+        #[cfg(feature = "speech")]
+        voice: Voice {
+            #[cfg(feature = "dfplayer")]
+            dfplayer: DfPlayer::new(pins.d4.into_output(), pins.d5.into_pull_up_input()),
+        },
+        #[cfg(feature = "rtc")]
+        rtc: Some(Rtc::new(
+            arduino_hal::I2c::new(
+                dp.TWI,
+                pins.a4.into_pull_up_input(),
+                pins.a5.into_pull_up_input(),
+                50_000,
+            ),
+            RTC_CHIP,
+        )),
+        #[cfg(feature = "rtc")]
+        recorded: None,
+    };
 
-    let vrx = pins.a0.into_analog_input(&mut adc);
-    let vry = pins.a1.into_analog_input(&mut adc);
+    device.auto_off = AUTO_OFF_MS.map(|ms| Timeout::new(&device.clock, ms));
 
     let mut input = InputState::new();
     let mut calc = Calculator::<FRACTION_COUNT>::new();
+    calc.set_plugins(plugin::registered());
 
+    boot(
+        &mut device,
+        &mut calc,
+        &mut input,
+        &mut stick,
+        &sensors,
+        &mut adc,
+    );
+
+    // Stages talk over the bus: input -> calculator -> hooks -> outputs
+    let mut period = Period::new(&device.clock, 10);
     loop {
-        let pressed = !sw.is_high();
-        let dir = read_joystick_direction(vrx.analog_read(&mut adc), vry.analog_read(&mut adc));
-
-        if input.update(dir, pressed) {
-            if pressed {
-                if let Ok(v) = calc.handle_input(input.key()) {
-                    if let Some(v) = v {
-                        display_number(&mut vibro, v).unwrap();
-                        input.reset_position();
-                        continue;
-                    }
-                } else {
-                    blink_err(&mut vibro);
+        let now = device.clock.now_ms();
+        let press = device.button.update(&device.clock, device.sw.is_low());
+        let sample = device.recorder.poll(now).unwrap_or_else(|| Sample {
+            dir: stick.dir(&mut adc),
+            pressed: device.button.is_pressed(),
+        });
+        device.recorder.record(now, sample);
+        if (sample.dir != Dir::Center || sample.pressed)
+            && let Some(auto_off) = &mut device.auto_off
+        {
+            auto_off.restart(&device.clock);
+        }
+
+        // Open slider takes over the joystick until the button confirms the value
+        if device.slider.as_ref().is_some_and(Slider::is_open) {
+            let confirmed = input.update(Dir::Center, sample.pressed) && sample.pressed;
+            use_slider(&mut device, &mut calc, &mut adc, confirmed);
+        } else if device.adjuster.is_some() {
+            // So does the adjuster, until the button saves the setting
+            let changed = input.update(sample.dir, sample.pressed);
+            use_adjuster(&mut device, &mut calc, sample, changed);
+        } else if device.editing {
+            // And the cursor of the number being typed, until the button leaves it
+            let changed = input.update(sample.dir, sample.pressed);
+            move_cursor(&mut device, &mut calc, sample, changed);
+        } else {
+            match &mut device.chords {
+                Some(chords) => {
+                    let digit = stick.chord_digit(&mut adc);
+                    read_chord(&mut input, chords, sample, digit);
                 }
-                debug!("pressed {:?}", input.key());
-                input.reset_position();
-            } else {
-                input.update_position(dir);
-                debug!("pos: {:?}", input.pos);
+                None => read_input(&mut input, sample),
+            }
+
+            if let Some((role, stick, state)) = &mut second_stick {
+                let dir = stick.dir(&mut adc);
+
+                if state.update(dir, false) {
+                    second_stick_moved(&mut device, &mut input, *role, dir);
+                }
+            }
+
+            while let Some(event) = bus::take() {
+                handle_input(&mut device, &mut calc, &sensors, &mut adc, event);
             }
 
-            blink(&mut vibro, 1, 250);
+            // Long operators are computed a step per iteration, so input stays responsive
+            poll(&device, &mut calc);
         }
+        read_remote(&mut device, &mut calc);
 
-        arduino_hal::delay_ms(10);
+        while let Some(event) = bus::take() {
+            handle_calc(&mut device, &calc, event);
+        }
+        flush_output(&mut device);
+
+        if let Some(state) = device.checkpoint.poll(now, calc.state()) {
+            device.storage.save_checkpoint(state);
+        }
+        if let Some(total) = device.errors.poll(now) {
+            device.storage.save_errors(&total);
+        }
+
+        let idle = device
+            .auto_off
+            .as_ref()
+            .is_some_and(|auto_off| auto_off.is_over(&device.clock));
+        if press == Some(Press::Long) || idle {
+            sleep(&mut device, &calc);
+        }
+
+        period.wait(&mut device.clock);
     }
 }
 
-fn display_number(vibro: &mut Pin<Output, PD3>, value: Num<FRACTION_COUNT>) -> Result<(), u8> {
-    let mut n = value.0;
-    debug!("Value: {}", n);
+/// Run the mode held at power-on, then restore what was saved unless it is safe mode
+fn boot(
+    device: &mut Device,
+    calc: &mut Calculator<FRACTION_COUNT>,
+    input: &mut InputState,
+    stick: &mut Stick,
+    sensors: &Sensors,
+    adc: &mut arduino_hal::Adc,
+) {
+    let boot = BootMode::detect(!device.sw.is_high(), stick.dir(adc));
+    info!("boot: {:?}", boot);
+    match boot {
+        BootMode::Normal => {}
+        BootMode::Safe => {
+            input.already_pressed = true;
+            device.button.ignore_press();
+        }
+        BootMode::FactoryReset => device.storage.factory_reset(),
+        BootMode::SelfTest => self_test(sensors, adc),
+        BootMode::Calibrate => calibrate(device, stick, adc),
+    }
 
-    arduino_hal::delay_ms(1500);
+    #[cfg(feature = "rtc")]
+    check_clock(device);
 
-    if n == 0 {
-        blink(vibro, 2, 150);
+    // Not a setting, so counted on in safe mode too
+    let total = device.storage.load_errors();
+    device.errors.restore(total);
+    if boot != BootMode::Safe {
+        load_settings(device, calc, stick, adc);
+    }
+    if boot == BootMode::SelfTest {
+        dump_errors(&device.errors);
+    }
+    // First boot, or the first after a factory reset
+    if boot == BootMode::Normal && !device.storage.load_tutorial() {
+        let start = ask(device, stick, adc, Text::TutorialAsk);
+        run_tutorial(device, start);
+    }
+}
+
+/// Turn a joystick sample into [`InputEvent`]s
+fn read_input(input: &mut InputState, Sample { dir, pressed }: Sample) {
+    if !input.update(dir, pressed) {
+        return;
+    }
+
+    if pressed {
+        bus::publish(InputEvent::Press(input.cell()));
+        input.reset_position();
     } else {
-        if n < 0 {
-            vibro.set_high();
-            arduino_hal::delay_ms(1000);
-            vibro.set_low();
-            arduino_hal::delay_ms(1500);
-        }
-
-        let mut nums = Vec::<_, 19>::new();
-        let mut zero_allow = false;
-        let mut i = 0u8;
-
-        while n > 0 {
-            let digit = (n % 10) as u8;
-            debug!("Digit: {}", digit);
-            if digit != 0 {
-                nums.push(digit)?;
-                zero_allow = true;
-            } else if zero_allow {
-                nums.push(digit)?;
+        input.update_position(dir);
+        debug!("pos: {:?}", input.pos);
+        bus::publish(InputEvent::Move(input.cell()));
+    }
+}
+
+/// Turn a joystick sample into [`InputEvent`]s, with digits entered by chords
+fn read_chord(input: &mut InputState, chords: &mut Chords, sample: Sample, digit: u8) {
+    // Moves are up to the chords, only the press itself is needed
+    let pressed = input.update(Dir::Center, sample.pressed) && sample.pressed;
+
+    match chords.update(sample.dir, digit, pressed) {
+        Some(Chord::Digit(n)) => bus::publish(InputEvent::Press(Key::Num(n).into())),
+        Some(Chord::Press) => {
+            bus::publish(InputEvent::Press(input.cell()));
+            input.reset_position();
+        }
+        Some(Chord::Move(dir)) => {
+            input.update_position(dir);
+            debug!("pos: {:?}", input.pos);
+            bus::publish(InputEvent::Move(input.cell()));
+        }
+        None => {}
+    }
+}
+
+fn handle_input(
+    device: &mut Device,
+    calc: &mut Calculator<FRACTION_COUNT>,
+    sensors: &Sensors,
+    adc: &mut arduino_hal::Adc,
+    event: InputEvent,
+) {
+    // Any input cuts the readout short
+    bus::publish(OutputRequest::Stop);
+    follow_tutorial(device, Observed::Input(event));
+
+    match event {
+        InputEvent::Press(cell) => {
+            calc.refresh(|source| sensors.read(adc, source));
+            #[cfg(feature = "rtc")]
+            calc.set_now(read_clock(device).ok());
+            if press(device, calc, cell) {
+                return;
+            }
+        }
+        InputEvent::Move(cell) => {
+            if device.help {
+                info!("{}", Help(cell));
+            }
+        }
+    }
+
+    blink(1, 250);
+}
+
+/// Pass what the calculator did on to the hooks
+fn handle_calc(device: &mut Device, calc: &Calculator<FRACTION_COUNT>, event: CalcEvent) {
+    match event {
+        CalcEvent::Key(key) => HOOKS.run(device, calc, Event::Key(key)),
+        CalcEvent::Result(v) => HOOKS.run(device, calc, Event::Result(v)),
+        CalcEvent::Error(e) => {
+            device.errors.count(ErrorKind::Calc(e));
+            bus::publish(OutputRequest::Failed(e));
+        }
+    }
+    follow_tutorial(device, Observed::Calc(event));
+}
+
+/// Advance a long operator, finishing it like a pressed key would
+fn poll(device: &Device, calc: &mut Calculator<FRACTION_COUNT>) {
+    match calc.poll() {
+        Ok(Some(v)) => {
+            device.busy.stop();
+            bus::publish(CalcEvent::Result(v));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            device.busy.stop();
+            info!("{}", e);
+            bus::publish(CalcEvent::Error(e));
+        }
+    }
+}
+
+/// Run the key in `cell` through the calculator, `true` if it computed a result
+fn press(device: &mut Device, calc: &mut Calculator<FRACTION_COUNT>, cell: Cell) -> bool {
+    // Press only leaves help mode
+    if device.help {
+        device.help = false;
+        return false;
+    }
+
+    // Before the result and errors, so `cos-tools transcript` can pair them with the key
+    debug!("pressed {}", cell);
+
+    let Cell::Key(key) = cell else {
+        // Empty cells are meant to be pressed through, the rest have no key yet
+        if cell != Cell::Empty {
+            device.errors.count(ErrorKind::Rejected);
+            bus::publish(OutputRequest::Error);
+        }
+        return false;
+    };
+
+    match device.busy.run(|| calc.handle_input(key)) {
+        Ok(v) => {
+            bus::publish(CalcEvent::Key(key));
+
+            if let Some(v) = v {
+                bus::publish(CalcEvent::Result(v));
+                return true;
             }
+        }
+        Err(e) => {
+            device.busy.stop();
+            info!("{}: {}", key, e);
+            bus::publish(CalcEvent::Error(e));
+        }
+    }
 
-            if i == FRACTION_COUNT - 1 {
-                zero_allow = true;
-                if !nums.is_empty() {
-                    nums.push(10)?;
+    if calc.is_busy() {
+        device.busy.start();
+    }
+
+    false
+}
+
+/// Run the command of a line received over serial
+fn read_remote(device: &mut Device, calc: &mut Calculator<FRACTION_COUNT>) {
+    match device.remote.poll() {
+        Some(Ok(Command::Paste(v))) => {
+            info!("{}: {}", Text::Pasted, v);
+            // Like a key, cancels what is computed or read out
+            device.busy.stop();
+            bus::publish(OutputRequest::Stop);
+            calc.enter(v);
+            blink(1, 250);
+        }
+        Some(Ok(Command::Errors)) => dump_errors(&device.errors),
+        Some(Ok(Command::History)) => dump_history(calc),
+        #[cfg(feature = "profiling")]
+        Some(Ok(Command::Profile)) => dump_profile(),
+        #[cfg(feature = "profiling")]
+        Some(Ok(Command::ProfileClear)) => {
+            profile::clear();
+            blink(1, 250);
+        }
+        Some(Ok(Command::FactoryReset)) => {
+            device.storage.factory_reset();
+            restore_defaults(device, calc);
+            info!("{}", Text::Defaults);
+            blink(1, 1000);
+        }
+        Some(Err(e)) => {
+            info!("{}", e);
+            bus::publish(OutputRequest::Error);
+        }
+        None => {}
+    }
+}
+
+/// Settings as on the first boot and no errors counted, the entry stays and the joystick center
+/// until a reboot
+fn restore_defaults(device: &mut Device, calc: &mut Calculator<FRACTION_COUNT>) {
+    calc.set_alarm(Alarm::OFF);
+    calc.set_precision(FRACTION_COUNT);
+    calc.set_rounded(false);
+    device.tempo = 100;
+    device.player.set_intensity(Intensity::Strong);
+    device.chords = None;
+    device.errors.clear();
+    #[cfg(feature = "dfplayer")]
+    device.voice.dfplayer.set_volume(DFPLAYER_VOLUME);
+}
+
+/// Restore what was saved in EEPROM
+fn load_settings(
+    device: &mut Device,
+    calc: &mut Calculator<FRACTION_COUNT>,
+    stick: &mut Stick,
+    adc: &mut arduino_hal::Adc,
+) {
+    match device.storage.load_calibration() {
+        Some(center) => stick.set_center(center),
+        // First boot, the stick is most likely released
+        None => calibrate(device, stick, adc),
+    }
+    calc.set_alarm(device.storage.load_alarm());
+    if let Some(digits) = device.storage.load_precision() {
+        calc.set_precision(digits);
+    }
+    calc.set_rounded(device.storage.load_rounding());
+    if let Some(tempo) = device.storage.load_tempo() {
+        device.tempo = tempo;
+    }
+    if let Some(intensity) = device.storage.load_intensity() {
+        device.player.set_intensity(intensity);
+    }
+    if device.storage.load_chords() {
+        device.chords = Some(Chords::new());
+    }
+    #[cfg(feature = "dfplayer")]
+    if let Some(volume) = device.storage.load_volume() {
+        device.voice.dfplayer.set_volume(volume);
+    }
+    if let Some(state) = device.storage.take_state() {
+        calc.restore(state);
+        device.storage.clear_checkpoint();
+    } else if let Some(state) = device.storage.load_checkpoint()
+        && state != calc.state()
+    {
+        // Reset or battery blip in the middle of an entry
+        if ask(device, stick, adc, Text::Restore) {
+            calc.restore(state);
+        } else {
+            device.storage.clear_checkpoint();
+        }
+    }
+    if device.storage.take_corrupted() {
+        info!("{}", Text::Corrupted);
+        bus::publish(OutputRequest::Corrupted);
+    }
+}
+
+/// Print `question`, three short pulses, then wait for a press to answer yes or a move for no
+fn ask(device: &mut Device, stick: &Stick, adc: &mut arduino_hal::Adc, question: Text) -> bool {
+    info!("{}", question);
+    blink(3, 100);
+    flush_output(device);
+
+    let restore = loop {
+        if device.sw.is_low() {
+            break true;
+        }
+        if stick.dir(adc) != Dir::Center {
+            break false;
+        }
+        device.clock.delay_ms(10);
+    };
+
+    // Released first, so the answer doesn't reach the keyboard
+    while device.sw.is_low() || stick.dir(adc) != Dir::Center {
+        device.clock.delay_ms(10);
+    }
+    blink(1, 250);
+
+    restore
+}
+
+/// Tick on every tenth of the range, enter the value once `confirmed`
+fn use_slider(
+    device: &mut Device,
+    calc: &mut Calculator<FRACTION_COUNT>,
+    adc: &mut arduino_hal::Adc,
+    confirmed: bool,
+) {
+    let Some(slider) = &mut device.slider else {
+        return;
+    };
+
+    if confirmed {
+        let v = slider.close(adc);
+        info!("slider: {}", v);
+        calc.enter(v);
+        blink(1, 250);
+    } else if slider.poll(adc) {
+        bus::publish(OutputRequest::Tick(30));
+    }
+}
+
+/// Step the setting with up and down, pick another with left and right, save it on a press
+///
+/// `changed` is what [`InputState::update`] made of the sample.
+fn use_adjuster(
+    device: &mut Device,
+    calc: &mut Calculator<FRACTION_COUNT>,
+    Sample { dir, pressed }: Sample,
+    changed: bool,
+) {
+    let Some(mut adjuster) = device.adjuster.take() else {
+        return;
+    };
+    let setting = adjuster.setting();
+
+    if changed && pressed {
+        save_setting(device, calc, setting, adjuster.value());
+        blink(1, 250);
+        let on = adjuster.value() == Num::ONE;
+        if setting == Setting::Tutorial && on != device.tutorial.is_running() {
+            run_tutorial(device, on);
+        }
+        return;
+    }
+
+    match dir {
+        Dir::Left | Dir::Right if changed => {
+            save_setting(device, calc, setting, adjuster.value());
+            let step = |setting: Setting| {
+                if dir == Dir::Right {
+                    setting.next()
+                } else {
+                    setting.prev()
                 }
+            };
+            #[cfg_attr(not(feature = "rtc"), expect(unused_mut))]
+            let mut setting = step(setting);
+            #[cfg(feature = "rtc")]
+            while setting.is_clock() && device.rtc.is_none() {
+                setting = step(setting);
+            }
+            open_setting(device, calc, setting);
+            return;
+        }
+        _ => {
+            if let Some(v) = adjuster.update(&device.clock, dir) {
+                apply_setting(device, calc, setting, v);
+                bus::publish(OutputRequest::Tick(30));
+            }
+        }
+    }
+
+    if let Some(v) = adjuster.echo(&device.clock) {
+        info!("{}: {}", setting.name(), v);
+        bus::publish(OutputRequest::Number(v));
+    }
+    device.adjuster = Some(adjuster);
+}
+
+/// Move the cursor of the number being typed with left and right, leave on a press
+///
+/// `changed` is what [`InputState::update`] made of the sample.
+fn move_cursor(
+    device: &mut Device,
+    calc: &mut Calculator<FRACTION_COUNT>,
+    Sample { dir, pressed }: Sample,
+    changed: bool,
+) {
+    if !changed {
+        return;
+    }
+    if pressed {
+        device.editing = false;
+        blink(1, 250);
+        return;
+    }
+
+    let moved = match dir {
+        Dir::Left => calc.move_cursor(false),
+        Dir::Right => calc.move_cursor(true),
+        _ => return,
+    };
+    if let (true, Some(entry)) = (moved, calc.entry()) {
+        info!("{}: {}", Key::Edit, entry);
+        bus::publish(OutputRequest::Tick(30));
+    } else {
+        bus::publish(OutputRequest::Error);
+    }
+}
+
+/// Start adjusting `setting`, one pulse for the first setting, two for the second, ...
+fn open_setting(device: &mut Device, calc: &Calculator<FRACTION_COUNT>, setting: Setting) {
+    let value = match setting {
+        Setting::Tempo => Num::from_int(device.tempo.into()),
+        Setting::Precision => Num::from_int(calc.precision().into()),
+        Setting::Threshold => calc.alarm().threshold,
+        Setting::Tutorial => Num::from_int(device.tutorial.is_running().into()),
+        #[cfg(feature = "dfplayer")]
+        Setting::Volume => Num::from_int(device.voice.dfplayer.volume().into()),
+        #[cfg(feature = "rtc")]
+        Setting::Year => Num::from_int(clock_or_epoch(device).year.into()),
+        #[cfg(feature = "rtc")]
+        Setting::Month => Num::from_int(clock_or_epoch(device).month.into()),
+        #[cfg(feature = "rtc")]
+        Setting::Day => Num::from_int(clock_or_epoch(device).day.into()),
+        #[cfg(feature = "rtc")]
+        Setting::Hour => Num::from_int(clock_or_epoch(device).hour.into()),
+        #[cfg(feature = "rtc")]
+        Setting::Minute => Num::from_int(clock_or_epoch(device).minute.into()),
+    };
+    info!("{}: {}", setting.name(), value);
+
+    device.adjuster = Some(Adjuster::new(setting, value, &device.clock));
+    blink(setting as u8 + 1, 100);
+}
+
+/// Use the adjusted `value`, right away so the new tempo can be felt
+fn apply_setting(
+    device: &mut Device,
+    calc: &mut Calculator<FRACTION_COUNT>,
+    setting: Setting,
+    value: Num<FRACTION_COUNT>,
+) {
+    // Whole within the bounds of the setting
+    let whole = value.0 / Num::<FRACTION_COUNT>::SCALE;
+
+    match setting {
+        Setting::Tempo => device.tempo = u16::try_from(whole).unwrap_or(device.tempo),
+        Setting::Precision => calc.set_precision(u8::try_from(whole).unwrap_or(FRACTION_COUNT)),
+        Setting::Threshold => calc.set_alarm(Alarm {
+            threshold: value,
+            ..calc.alarm()
+        }),
+        // Started or left once saved, not on every step
+        Setting::Tutorial => {}
+        #[cfg(feature = "dfplayer")]
+        Setting::Volume => {
+            let volume = u8::try_from(whole).unwrap_or(DFPLAYER_VOLUME);
+            device.voice.dfplayer.set_volume(volume);
+        }
+        // The clock keeps running meanwhile, it is set once saved
+        #[cfg(feature = "rtc")]
+        Setting::Year | Setting::Month | Setting::Day | Setting::Hour | Setting::Minute => {}
+    }
+}
+
+/// Keep the setting, `value` is what it was adjusted to
+#[cfg_attr(not(feature = "rtc"), expect(unused_variables))]
+fn save_setting(
+    device: &mut Device,
+    calc: &Calculator<FRACTION_COUNT>,
+    setting: Setting,
+    value: Num<FRACTION_COUNT>,
+) {
+    match setting {
+        Setting::Tempo => device.storage.save_tempo(device.tempo),
+        Setting::Precision => device.storage.save_precision(calc.precision()),
+        Setting::Threshold => device.storage.save_alarm(calc.alarm()),
+        // Only whether it was done is kept, by `run_tutorial`
+        Setting::Tutorial => {}
+        #[cfg(feature = "dfplayer")]
+        Setting::Volume => device.storage.save_volume(device.voice.dfplayer.volume()),
+        #[cfg(feature = "rtc")]
+        Setting::Year | Setting::Month | Setting::Day | Setting::Hour | Setting::Minute => {
+            set_clock(device, setting, value);
+        }
+    }
+}
+
+/// Write the saved field of the date or time to the clock if it changed
+///
+/// A changed minute starts at second 0, and the seconds the clock was off before tell how
+/// far it drifted since it was last set that way.
+#[cfg(feature = "rtc")]
+fn set_clock(device: &mut Device, setting: Setting, value: Num<FRACTION_COUNT>) {
+    let Some(rtc) = &mut device.rtc else {
+        return;
+    };
+    let was = rtc.now();
+    let now = was.unwrap_or(DateTime::EPOCH);
+
+    // Whole within the bounds of the setting
+    let field =
+        |current: u8| u8::try_from(value.0 / Num::<FRACTION_COUNT>::SCALE).unwrap_or(current);
+    let set = match setting {
+        Setting::Year => DateTime {
+            year: u16::try_from(value.0 / Num::<FRACTION_COUNT>::SCALE).unwrap_or(now.year),
+            ..now
+        },
+        Setting::Month => DateTime {
+            month: field(now.month),
+            ..now
+        },
+        Setting::Day => DateTime {
+            day: field(now.day),
+            ..now
+        },
+        Setting::Hour => DateTime {
+            hour: field(now.hour),
+            ..now
+        },
+        Setting::Minute => DateTime {
+            minute: field(now.minute),
+            second: 0,
+            ..now
+        },
+        _ => return,
+    }
+    .clamp_day();
+    // A stopped clock is started even unchanged
+    let unchanged = DateTime {
+        second: now.second,
+        ..set
+    } == now;
+    if was.is_ok() && unchanged {
+        return;
+    }
+
+    if let Err(e) = rtc.set(set) {
+        info!("{}", e);
+        bus::publish(OutputRequest::Error);
+        return;
+    }
+    info!("{}", set);
+
+    if setting == Setting::Minute {
+        if let Ok(was) = was
+            && let Some(last) = device.storage.load_clock_set()
+            && let Some(ppm) = drift(was, set, last)
+        {
+            info!("{}: {}", Text::Drift, ppm);
+            bus::publish(OutputRequest::Number(ppm));
+        }
+        device.storage.save_clock_set(set.seconds());
+    }
+}
+
+/// Parts per million the clock ran fast, slow if negative: it read `was` when it was `set`,
+/// `last` seconds after the epoch it was set before
+#[cfg(feature = "rtc")]
+fn drift(was: DateTime, set: DateTime, last: u32) -> Option<Num<FRACTION_COUNT>> {
+    let elapsed = set.seconds().checked_sub(last).filter(|&s| s > 0)?;
+    let off = i64::from(was.seconds()) - i64::from(set.seconds());
+
+    Some(Num::from_raw(
+        off * 1_000_000 * Num::<FRACTION_COUNT>::SCALE / i64::from(elapsed),
+    ))
+}
+
+/// Date and time from the clock, [`RtcError::Bus`] without one
+#[cfg(feature = "rtc")]
+fn read_clock(device: &mut Device) -> Result<DateTime, RtcError> {
+    device.rtc.as_mut().map_or(Err(RtcError::Bus), Rtc::now)
+}
+
+/// Date and time from the clock, the epoch if it stopped, to set it from
+#[cfg(feature = "rtc")]
+fn clock_or_epoch(device: &mut Device) -> DateTime {
+    read_clock(device).unwrap_or(DateTime::EPOCH)
+}
+
+/// Drop the clock if it doesn't answer, tell if it lost the time and has to be set
+#[cfg(feature = "rtc")]
+fn check_clock(device: &mut Device) {
+    match read_clock(device) {
+        Ok(now) => {
+            info!("{}", now);
+        }
+        Err(RtcError::Bus) => {
+            info!("{}", RtcError::Bus);
+            device.rtc = None;
+        }
+        Err(e @ RtcError::Stopped) => {
+            info!("{}", e);
+            bus::publish(OutputRequest::Error);
+        }
+    }
+}
+
+/// Start the tutorial from its first step, or leave it and don't offer it at boot again
+fn run_tutorial(device: &mut Device, on: bool) {
+    if on {
+        let progress = device.tutorial.start();
+        announce(device, progress);
+    } else {
+        device.tutorial.stop();
+        device.storage.save_tutorial(true);
+        info!("{}", Text::TutorialSkipped);
+    }
+}
+
+/// Let the tutorial check `event`, announce what comes next once it passes a step
+fn follow_tutorial(device: &mut Device, event: Observed) {
+    if let Some(progress) = device.tutorial.observe(event) {
+        bus::publish(OutputRequest::Passed);
+        announce(device, progress);
+    }
+}
+
+/// Prompt for the step that begins, or tell that the tutorial is done
+fn announce(device: &mut Device, progress: Progress) {
+    match progress {
+        Progress::Step(n) => {
+            let step = &tutorial::SCRIPT[usize::from(n)];
+            info!(
+                "{} {}/{}: {}",
+                Text::Tutorial,
+                n + 1,
+                tutorial::SCRIPT.len(),
+                step.prompt
+            );
+            bus::publish(OutputRequest::Lesson(n));
+        }
+        Progress::Done => {
+            device.storage.save_tutorial(true);
+            info!("{}", Text::TutorialDone);
+            blink(1, 1000);
+        }
+    }
+}
+
+fn second_stick_moved(device: &mut Device, input: &mut InputState, role: StickRole, dir: Dir) {
+    match role {
+        StickRole::Navigate => {
+            input.update_position(dir);
+            debug!("pos: {:?}", input.pos);
+            if device.help {
+                info!("{}", Help(input.cell()));
+            }
+        }
+        StickRole::Tempo => {
+            device.tempo = match dir {
+                Dir::Left => device.tempo.saturating_add(TEMPO_STEP),
+                Dir::Right => device.tempo.saturating_sub(TEMPO_STEP),
+                _ => device.tempo,
             }
+            .clamp(TEMPO_RANGE.0, TEMPO_RANGE.1);
+            device.storage.save_tempo(device.tempo);
+            debug!("tempo: {}", device.tempo);
+        }
+    }
+
+    bus::publish(OutputRequest::Tick(50));
+}
+
+fn mirror_serial(_: &mut Device, calc: &Calculator<FRACTION_COUNT>, event: Event<FRACTION_COUNT>) {
+    let Event::Result(v) = event else {
+        return;
+    };
+
+    if calc.is_dms()
+        && let Some(degrees) = dms::unpack(v)
+    {
+        info!("= {} ({})", v, Dms(degrees));
+    } else if calc.is_time() {
+        info!("= {} ({})", v, Hms::new(v));
+    } else {
+        info!("= {}", v);
+    }
+}
+
+fn display_result(_: &mut Device, calc: &Calculator<FRACTION_COUNT>, event: Event<FRACTION_COUNT>) {
+    match event {
+        Event::Result(v) if calc.is_comparison() => {
+            bus::publish(OutputRequest::Truth(v == Num::ONE));
+        }
+        Event::Result(v) if calc.is_time() => bus::publish(OutputRequest::Time(v)),
+        Event::Result(v) => bus::publish(OutputRequest::Number(v)),
+        Event::Key(key) => bus::publish(OutputRequest::Entered(key)),
+    }
+}
+
+fn check_alarm(_: &mut Device, calc: &Calculator<FRACTION_COUNT>, event: Event<FRACTION_COUNT>) {
+    if let Event::Result(v) = event
+        && calc.alarm().is_triggered(v)
+    {
+        bus::publish(OutputRequest::Alarm);
+    }
+}
 
-            n /= 10;
-            i += 1;
+fn save_settings(
+    device: &mut Device,
+    calc: &Calculator<FRACTION_COUNT>,
+    event: Event<FRACTION_COUNT>,
+) {
+    match event {
+        Event::Key(Key::Alarm(_)) => device.storage.save_alarm(calc.alarm()),
+        Event::Key(Key::Precision) => device.storage.save_precision(calc.precision()),
+        Event::Key(Key::Rounding) => {
+            device.storage.save_rounding(calc.is_rounded());
+            info!("{}: {}", Key::Rounding, calc.is_rounded());
         }
+        _ => {}
+    }
+}
+
+fn power_off(device: &mut Device, calc: &Calculator<FRACTION_COUNT>, event: Event<FRACTION_COUNT>) {
+    if event == Event::Key(Key::Power) {
+        sleep(device, calc);
+    }
+}
+
+/// Save what a pulled battery would lose and sleep until the button is pressed
+fn sleep(device: &mut Device, calc: &Calculator<FRACTION_COUNT>) {
+    // Kept in case the battery is pulled while sleeping
+    device.storage.save_state(calc.state());
+    if let Some(total) = device.errors.take() {
+        device.storage.save_errors(&total);
+    }
+
+    blink(1, 1000);
+    flush_output(device);
+    while device.player.is_playing() {
+        device.power.idle();
+    }
+
+    // Pressed button would wake us right away
+    while device.sw.is_low() {
+        device.clock.delay_ms(10);
+    }
+    device.power.sleep_until_button();
+
+    // The press that woke us is not a key, and the time off is not idle time
+    device.button.ignore_press();
+    if let Some(auto_off) = &mut device.auto_off {
+        auto_off.restart(&device.clock);
+    }
+
+    // RAM survived, so the saved copy is not needed anymore
+    device.storage.clear_state();
+    blink(2, 100);
+}
 
-        nums.reverse();
+fn open_slider(device: &mut Device, _: &Calculator<FRACTION_COUNT>, event: Event<FRACTION_COUNT>) {
+    if event == Event::Key(Key::Slider) {
+        match &mut device.slider {
+            Some(slider) => slider.open(),
+            None => bus::publish(OutputRequest::Error),
+        }
+    }
+}
 
-        for num in nums {
-            debug!("Num: {}", num);
+fn start_help(device: &mut Device, _: &Calculator<FRACTION_COUNT>, event: Event<FRACTION_COUNT>) {
+    if event == Event::Key(Key::Help) {
+        device.help = true;
+        info!("{}", Help(Key::Help.into()));
+    }
+}
 
-            match num {
-                0 => blink(vibro, 2, 150),
-                10 => blink(vibro, 5, 100),
-                n @ 0..=9 => blink(vibro, n, 250),
-                _ => {}
+fn use_recorder(device: &mut Device, _: &Calculator<FRACTION_COUNT>, event: Event<FRACTION_COUNT>) {
+    let now = device.clock.now_ms();
+    match event {
+        Event::Key(Key::Record) if device.recorder.is_recording() => {
+            if !device.recorder.stop() {
+                info!("rec: full");
+            }
+            #[cfg(feature = "rtc")]
+            if let Some(at) = device.recorded {
+                info!("rec: {}", at);
+            }
+            for (after, sample) in device.recorder.steps() {
+                info!(
+                    "rec: {} {:?} {}",
+                    after,
+                    sample.dir,
+                    u8::from(sample.pressed)
+                );
+            }
+        }
+        Event::Key(Key::Record) => {
+            device.recorder.start(now);
+            #[cfg(feature = "rtc")]
+            {
+                device.recorded = read_clock(device).ok();
             }
+        }
+        Event::Key(Key::Replay) if !device.recorder.replay(now) => {
+            bus::publish(OutputRequest::Error);
+        }
+        _ => {}
+    }
+}
+
+/// Switch to the next vibro strength and let it be felt
+fn cycle_intensity(
+    device: &mut Device,
+    _: &Calculator<FRACTION_COUNT>,
+    event: Event<FRACTION_COUNT>,
+) {
+    if event != Event::Key(Key::Intensity) {
+        return;
+    }
+
+    let intensity = device.player.intensity().next();
+    device.player.set_intensity(intensity);
+    device.storage.save_intensity(intensity);
+    info!("{}: {}", Key::Intensity, intensity.name());
+
+    blink(1, 250);
+}
 
-            arduino_hal::delay_ms(1500);
+/// Turn digit entry by chords on or off
+fn toggle_chords(
+    device: &mut Device,
+    _: &Calculator<FRACTION_COUNT>,
+    event: Event<FRACTION_COUNT>,
+) {
+    if event != Event::Key(Key::Chords) {
+        return;
+    }
+
+    device.chords = match device.chords {
+        Some(_) => None,
+        None => Some(Chords::new()),
+    };
+    let on = device.chords.is_some();
+    device.storage.save_chords(on);
+    info!("{}: {}", Key::Chords, on);
+}
+
+/// Start adjusting the settings with the stick, beginning with the tempo
+fn open_adjuster(
+    device: &mut Device,
+    calc: &Calculator<FRACTION_COUNT>,
+    event: Event<FRACTION_COUNT>,
+) {
+    if event == Event::Key(Key::Adjust) {
+        open_setting(device, calc, Setting::Tempo);
+    }
+}
+
+/// Hand left and right to the cursor of the number being typed
+fn start_editing(
+    device: &mut Device,
+    _: &Calculator<FRACTION_COUNT>,
+    event: Event<FRACTION_COUNT>,
+) {
+    if event == Event::Key(Key::Edit) {
+        device.editing = true;
+    }
+}
+
+/// Read out `value` digit by digit straight from [`Num::digits`], no buffer to outgrow
+fn display_number(player: &Player, value: Num<FRACTION_COUNT>, tempo: u16) {
+    // Durations at 100% tempo
+    let ms = |ms: u16| (u32::from(ms) * u32::from(tempo) / 100) as u16;
+
+    debug!("Value: {}", value);
+
+    player.play(&[Pulses::pause(ms(1500))]);
+    play_digits(player, value.digits(), tempo);
+}
+
+/// Read out seconds as hours, minutes and seconds, split by three short pulses
+fn display_time(player: &Player, value: Num<FRACTION_COUNT>, tempo: u16) {
+    let ms = |ms: u16| (u32::from(ms) * u32::from(tempo) / 100) as u16;
+    let hms = Hms::new(value);
+
+    debug!("Time: {}", hms);
+
+    player.play(&[Pulses::pause(ms(1500))]);
+    let hours = Num::<FRACTION_COUNT>::from_int(hms.hours as i64);
+    play_digits(
+        player,
+        hms.negative
+            .then_some(Digit::Minus)
+            .into_iter()
+            .chain(hours.digits()),
+        tempo,
+    );
+
+    for field in [Num::from_int(hms.minutes.into()), hms.seconds] {
+        player.play(&[Pulses::new(3, ms(100)).then(ms(1500))]);
+        play_digits(player, field.digits(), tempo);
+    }
+}
+
+fn play_digits(player: &Player, digits: impl Iterator<Item = Digit>, tempo: u16) {
+    let ms = |ms: u16| (u32::from(ms) * u32::from(tempo) / 100) as u16;
+
+    for digit in digits {
+        debug!("Digit: {:?}", digit);
+
+        let pulses = match digit {
+            Digit::Minus => Pulses {
+                count: 1,
+                on: ms(1000),
+                off: 0,
+                then: 0,
+                emphasis: 0,
+            },
+            Digit::Num(0) => Pulses::new(2, ms(150)),
+            Digit::Num(n) => Pulses::new(n, ms(250)),
+            Digit::Dot => Pulses::new(5, ms(100)),
+        };
+        player.play(&[pulses.then(ms(1500))]);
+    }
+}
+
+/// Picked by the input held at power-up
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+enum BootMode {
+    Normal,
+    /// Button: ignore saved settings for this run
+    Safe,
+    /// Up: erase saved settings
+    FactoryReset,
+    /// Down: check hardware and math
+    SelfTest,
+    /// Left: measure the joystick center again once it is released
+    Calibrate,
+}
+
+impl BootMode {
+    const fn detect(pressed: bool, dir: Dir) -> Self {
+        match (pressed, dir) {
+            (true, _) => Self::Safe,
+            (false, Dir::Up) => Self::FactoryReset,
+            (false, Dir::Down) => Self::SelfTest,
+            (false, Dir::Left) => Self::Calibrate,
+            _ => Self::Normal,
         }
     }
+}
 
-    Ok(())
+/// Measure and save the center of the released joystick
+fn calibrate(device: &mut Device, stick: &mut Stick, adc: &mut arduino_hal::Adc) {
+    while stick.dir(adc) != Dir::Center {
+        device.clock.delay_ms(10);
+    }
+    // Let it settle after being let go
+    device.clock.delay_ms(500);
+
+    match stick.calibrate(adc, &mut device.clock) {
+        Some(center) => {
+            info!("calibrated: {} {}", center.0, center.1);
+            device.storage.save_calibration(center);
+            blink(1, 250);
+        }
+        None => bus::publish(OutputRequest::Error),
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Dir {
-    Up,
-    Down,
-    Left,
-    Right,
-    Center,
+/// Report each check over serial, then a long pulse if all passed
+fn self_test(sensors: &Sensors, adc: &mut arduino_hal::Adc) {
+    // Motor check is felt by the user
+    blink(3, 100);
+
+    let vcc = sensors.read::<FRACTION_COUNT>(adc, Source::Battery);
+    let supply = vcc >= Num::from_raw_scaled(2700, 3) && vcc <= Num::from_raw_scaled(5500, 3);
+    info!("self-test: supply {} mV {}", vcc.to_raw_scaled(3), supply);
+
+    #[cfg(not(feature = "basic-layout"))]
+    let math = Num::<FRACTION_COUNT>::from_int(4).sqrt() == Num::from_int(2)
+        && Num::<FRACTION_COUNT>::ZERO.cos() == Num::ONE;
+    // Square root and cosine aren't in the basic edition
+    #[cfg(feature = "basic-layout")]
+    let math = Num::<FRACTION_COUNT>::from_int(7) / Num::from_int(2) == Num::from_raw_scaled(35, 1);
+    info!("self-test: math {}", math);
+
+    let layout = match validate_layout() {
+        Ok(()) => true,
+        Err(e) => {
+            info!("self-test: {:?}", e);
+            false
+        }
+    };
+    info!("self-test: layout {}", layout);
+
+    bus::publish(OutputRequest::Pause(500));
+    if supply && math && layout {
+        blink(1, 1000);
+    } else {
+        bus::publish(OutputRequest::Error);
+    }
+}
+
+/// Print how often each error was hit, since boot and in total
+fn dump_errors(errors: &ErrorCounts) {
+    info!("{}", Text::Errors);
+    for kind in ErrorKind::ALL {
+        let (boot, total) = errors.get(kind);
+        info!("{}: {} / {}", kind, boot, total);
+    }
+}
+
+/// Print the results kept for [`Key::Stat`], oldest first, each with its date and time if
+/// the clock knew it
+fn dump_history(calc: &Calculator<FRACTION_COUNT>) {
+    for (v, at) in calc.history().entries() {
+        if let Some(at) = at {
+            info!("{} = {}", at, v);
+        } else {
+            info!("= {}", v);
+        }
+    }
+}
+
+/// Print the time of every operator computed since boot or `PROFILE CLEAR`
+#[cfg(feature = "profiling")]
+fn dump_profile() {
+    info!("{}", Text::Profile);
+    for (i, &op) in profile::OPS.iter().enumerate() {
+        if let Some(timing) = profile::get(i) {
+            info!(
+                "{}: {}, {} / {} / {}",
+                profile::name(op),
+                timing.count,
+                timing.min,
+                timing.avg(),
+                timing.max
+            );
+        }
+    }
 }
 
 struct InputState {
@@ -150,7 +1341,7 @@ struct InputState {
 impl InputState {
     fn new() -> Self {
         Self {
-            pos: DEFAULT_POS,
+            pos: layout::default_pos(),
             old_dir: Dir::Center,
             already_pressed: false,
         }
@@ -173,63 +1364,128 @@ impl InputState {
         dir_changed || pressed
     }
 
+    /// Move the cursor by `dir`, `false` if it is already at that edge of the layout
     fn update_position(&mut self, dir: Dir) -> bool {
-        match dir {
-            Dir::Up => self.pos.1 = self.pos.1.saturating_add(1),
-            Dir::Down => self.pos.1 = self.pos.1.saturating_sub(1),
-            Dir::Left => self.pos.0 = self.pos.0.saturating_sub(1),
-            Dir::Right => self.pos.0 = self.pos.0.saturating_add(1),
-            Dir::Center => (),
+        let (x, y) = self.pos;
+        let pos = match dir {
+            Dir::Up => (x, y.saturating_add(1)),
+            Dir::Down => (x, y.saturating_sub(1)),
+            Dir::Left => (x.saturating_sub(1), y),
+            Dir::Right => (x.saturating_add(1), y),
+            Dir::Center => return false,
+        };
+
+        if Cell::at(pos) == Cell::Outside {
+            return false;
         }
+        self.pos = pos;
 
-        false
+        true
     }
 
-    fn key(&self) -> Key {
-        let mut keyboard_layout = keyboard_layout();
-        keyboard_layout.reverse();
-
-        // Get first by y and when by x
-        keyboard_layout
-            .get(self.pos.1 as usize)
-            .and_then(|r| r.get(self.pos.0 as usize).copied())
-            .unwrap_or(Key::None)
+    fn cell(&self) -> Cell {
+        Cell::at(self.pos)
     }
 
     fn reset_position(&mut self) {
-        self.pos = DEFAULT_POS;
+        self.pos = layout::default_pos();
     }
 }
 
-fn read_joystick_direction(x: u16, y: u16) -> Dir {
-    const MID: u16 = 512;
-    const DEADZONE: u16 = 200;
+fn blink(count: u8, ms: u16) {
+    bus::publish(OutputRequest::Blink { count, ms });
+}
 
-    match (x, y) {
-        (x, _) if x > MID + DEADZONE => Dir::Right,
-        (x, _) if x < MID - DEADZONE => Dir::Left,
-        (_, y) if y > MID + DEADZONE => Dir::Down,
-        (_, y) if y < MID - DEADZONE => Dir::Up,
-        _ => Dir::Center,
+/// Play every queued [`OutputRequest`] on the vibro, and say it with `speech`
+fn flush_output(device: &mut Device) {
+    while let Some(request) = bus::take() {
+        #[cfg(feature = "speech")]
+        device.voice.show(request);
+        play(device, request);
     }
+
+    // Next word once the one before was said
+    #[cfg(feature = "dfplayer")]
+    device.voice.dfplayer.poll(device.clock.now_ms());
 }
 
-fn blink(vibro: &mut Pin<Output, PD3>, count: u8, duration: u16) {
-    for _ in 0..count {
-        vibro.set_high();
-        arduino_hal::delay_ms(duration.into());
-        vibro.set_low();
-        arduino_hal::delay_ms(duration.into());
+/// Play `request` on the vibro
+fn play(device: &mut Device, request: OutputRequest) {
+    let Device { player, tempo, .. } = device;
+
+    match request {
+        OutputRequest::Stop => player.stop(),
+        OutputRequest::Pause(ms) => player.play(&[Pulses::pause(ms)]),
+        OutputRequest::Blink { count, ms } => player.play(&[Pulses::new(count, ms)]),
+        OutputRequest::Tick(ms) => player.play(&[Pulses::new(1, ms).softer()]),
+        // Said only
+        OutputRequest::Entered(_) => {}
+        OutputRequest::Error | OutputRequest::Failed(_) => {
+            player.play(&[Pulses::new(5, 50).stronger()]);
+        }
+        // Two short pulses for true, one long for false
+        OutputRequest::Truth(true) => player.play(&[Pulses::pause(500), Pulses::new(2, 200)]),
+        OutputRequest::Truth(false) => {
+            player.play(&[Pulses::pause(500), Pulses::new(1, 1000)]);
+        }
+        OutputRequest::Alarm => {
+            player.play(&[Pulses::pause(500), Pulses::new(3, 600).stronger()]);
+        }
+        // Long, strong and unlike any result, so it isn't taken for one
+        OutputRequest::Corrupted => {
+            player.play(&[Pulses::pause(500), Pulses::new(4, 800).stronger()]);
+        }
+        OutputRequest::Number(v) => display_number(player, v, *tempo),
+        OutputRequest::Time(v) => display_time(player, v, *tempo),
+        // A long soft pulse, then one short per step number
+        OutputRequest::Lesson(n) => player.play(&[
+            Pulses::pause(500),
+            Pulses::new(1, 600).softer().then(300),
+            Pulses::new(n + 1, 150),
+        ]),
+        // Rising, unlike a result or a confirmation
+        OutputRequest::Passed => player.play(&[
+            Pulses::new(1, 80).softer(),
+            Pulses::new(1, 80),
+            Pulses::new(1, 80).stronger(),
+        ]),
     }
 }
 
-fn blink_err(vibro: &mut Pin<Output, PD3>) {
-    for _ in 0..5 {
-        vibro.set_high();
-        arduino_hal::delay_ms(50);
-        vibro.set_low();
-        arduino_hal::delay_ms(50);
-    }
+// Only wakes the MCU from power-down
+#[avr_device::interrupt(atmega328p)]
+fn INT0() {}
+
+// Pattern step, see `Player`
+#[avr_device::interrupt(atmega328p)]
+fn TIMER2_COMPA() {
+    // SAFETY: main() only touches TC2 inside critical sections, which this handler can't
+    // interrupt; the vibro pin is reached through the queue's `Mutex`.
+    let tc2 = unsafe { arduino_hal::Peripherals::steal() }.TC2;
+    pattern::tick(&tc2);
+}
+
+// Byte received over serial, see `remote`
+#[avr_device::interrupt(atmega328p)]
+fn USART_RX() {
+    remote::receive();
+}
+
+// Millisecond count, see `time`
+#[avr_device::interrupt(atmega328p)]
+fn TIMER0_COMPA() {
+    time::tick();
+}
+
+// Busy pulse, see `Busy`
+#[avr_device::interrupt(atmega328p)]
+fn TIMER1_COMPA() {
+    pattern::busy_pulse(true);
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn TIMER1_COMPB() {
+    pattern::busy_pulse(false);
 }
 
 #[inline(never)]
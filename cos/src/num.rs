@@ -0,0 +1,9 @@
+pub use cos_num::{Digit, Digits, Dms};
+
+use crate::config::TAYLOR_FRACTION_COUNT;
+
+/// [`cos_num::Num`] with the firmware's Taylor series precision
+pub type Num<const F: u8> = cos_num::Num<F, TAYLOR_FRACTION_COUNT>;
+
+/// [`cos_num::Steps`] with the firmware's Taylor series precision
+pub type Steps<const F: u8> = cos_num::Steps<F, TAYLOR_FRACTION_COUNT>;
@@ -0,0 +1,361 @@
+use core::cell::RefCell;
+
+use arduino_hal::{
+    hal::port::PD3,
+    pac::TC2,
+    port::{Pin, mode::Output},
+};
+use avr_device::interrupt::{self, Mutex};
+use heapless::Deque;
+use ufmt::derive::uDebug;
+
+use crate::lang::Text;
+
+/// Groups queued at most, a number takes one per digit plus the lead-in
+const QUEUE_LEN: usize = 32;
+/// TC2 top for a 1 ms PWM period and tick with the /64 prescaler at 16 MHz
+const TICK_TOP: u8 = 249;
+/// Milliseconds a pulse takes to reach full duty, the motor draws most when starting
+const RAMP_MS: u8 = 8;
+
+/// How hard the vibro runs
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum Intensity {
+    Soft,
+    Medium,
+    Strong,
+}
+
+impl Intensity {
+    #[must_use]
+    pub const fn name(self) -> Text {
+        match self {
+            Self::Soft => Text::Soft,
+            Self::Medium => Text::Medium,
+            Self::Strong => Text::Strong,
+        }
+    }
+
+    /// Next level, wrapping from strong back to soft
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Soft => Self::Medium,
+            Self::Medium => Self::Strong,
+            Self::Strong => Self::Soft,
+        }
+    }
+
+    /// Level `by` steps stronger, or weaker for negative `by`, clamped to the range
+    #[must_use]
+    pub const fn shift(self, by: i8) -> Self {
+        match (self as i8).saturating_add(by) {
+            ..=0 => Self::Soft,
+            1 => Self::Medium,
+            2.. => Self::Strong,
+        }
+    }
+
+    /// PWM compare value, out of [`TICK_TOP`]
+    const fn duty(self) -> u8 {
+        match self {
+            Self::Soft => 100,
+            Self::Medium => 170,
+            Self::Strong => TICK_TOP,
+        }
+    }
+}
+
+/// `count` vibro pulses of `on` ms with `off` ms pauses, then a `then` ms pause
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Pulses {
+    pub count: u8,
+    pub on: u16,
+    pub off: u16,
+    pub then: u16,
+    /// Steps from the [`Intensity`] picked by the user, see [`Intensity::shift`]
+    pub emphasis: i8,
+}
+
+impl Pulses {
+    /// `count` pulses with pauses as long as the pulses
+    #[must_use]
+    pub const fn new(count: u8, ms: u16) -> Self {
+        Self {
+            count,
+            on: ms,
+            off: ms,
+            then: 0,
+            emphasis: 0,
+        }
+    }
+
+    /// Silence for `ms`
+    #[must_use]
+    pub const fn pause(ms: u16) -> Self {
+        Self {
+            count: 0,
+            on: 0,
+            off: 0,
+            then: ms,
+            emphasis: 0,
+        }
+    }
+
+    /// Pause for `ms` after the pulses
+    #[must_use]
+    pub const fn then(mut self, ms: u16) -> Self {
+        self.then = ms;
+        self
+    }
+
+    /// One step stronger than usual, for things that must not be missed
+    #[must_use]
+    pub const fn stronger(mut self) -> Self {
+        self.emphasis = 1;
+        self
+    }
+
+    /// One step softer than usual, for frequent ticks
+    #[must_use]
+    pub const fn softer(mut self) -> Self {
+        self.emphasis = -1;
+        self
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    On,
+    Off,
+    Then,
+}
+
+struct Queue {
+    groups: Deque<Pulses, QUEUE_LEN>,
+    /// Pulses of the front group already played
+    pulse: u8,
+    phase: Phase,
+    /// Ticks left in the current phase
+    left: u16,
+    /// Ticks since the current pulse started, up to [`RAMP_MS`]
+    ramp: u8,
+    running: bool,
+    intensity: Intensity,
+    /// Shared with the busy pulses, see [`busy_pulse`]
+    vibro: Option<Pin<Output, PD3>>,
+}
+
+impl Queue {
+    const fn new() -> Self {
+        Self {
+            groups: Deque::new(),
+            pulse: 0,
+            phase: Phase::On,
+            left: 0,
+            ramp: 0,
+            running: false,
+            intensity: Intensity::Strong,
+            vibro: None,
+        }
+    }
+
+    /// Forget every group, keeping the intensity and the pin
+    fn clear(&mut self) {
+        *self = Self {
+            intensity: self.intensity,
+            vibro: self.vibro.take(),
+            ..Self::new()
+        };
+    }
+
+    fn set_vibro(&mut self, on: bool) {
+        if let Some(vibro) = &mut self.vibro {
+            if on {
+                vibro.set_high();
+            } else {
+                vibro.set_low();
+            }
+        }
+    }
+
+    /// Duty for the current pulse, ramping up while it starts
+    fn duty(&self) -> u8 {
+        let emphasis = self.groups.front().map_or(0, |group| group.emphasis);
+        let full = u16::from(self.intensity.shift(emphasis).duty());
+
+        // Fits, both factors are at most 255 and the ramp never exceeds `RAMP_MS`
+        (full * u16::from(self.ramp) / u16::from(RAMP_MS)) as u8
+    }
+
+    /// Vibro level and length of the next non-empty phase, `None` once all groups are played
+    fn advance(&mut self) -> Option<(bool, u16)> {
+        loop {
+            let group = *self.groups.front()?;
+
+            let (level, ms) = match self.phase {
+                Phase::On if self.pulse < group.count => {
+                    self.phase = Phase::Off;
+                    (true, group.on)
+                }
+                Phase::Off => {
+                    self.pulse += 1;
+                    self.phase = Phase::On;
+                    (false, group.off)
+                }
+                Phase::On => {
+                    self.phase = Phase::Then;
+                    (false, group.then)
+                }
+                Phase::Then => {
+                    self.groups.pop_front();
+                    self.pulse = 0;
+                    self.phase = Phase::On;
+                    continue;
+                }
+            };
+
+            if ms > 0 {
+                return Some((level, ms));
+            }
+        }
+    }
+}
+
+static QUEUE: Mutex<RefCell<Queue>> = Mutex::new(RefCell::new(Queue::new()));
+
+/// Plays [`Pulses`] on the vibro from the TC2 compare interrupt, so the main loop keeps running
+///
+/// The firmware has to provide a `TIMER2_COMPA` handler calling [`tick`].
+pub struct Player {
+    tc2: TC2,
+}
+
+impl Player {
+    /// Player driving `vibro`, which stays behind the queue's `Mutex` from now on
+    #[must_use]
+    pub fn new(tc2: TC2, vibro: Pin<Output, PD3>) -> Self {
+        interrupt::free(|cs| QUEUE.borrow(cs).borrow_mut().vibro = Some(vibro));
+        Self { tc2 }
+    }
+
+    /// Queue `pattern` after what is already playing, groups past the queue length are dropped
+    pub fn play(&self, pattern: &[Pulses]) {
+        let start = interrupt::free(|cs| {
+            let mut queue = QUEUE.borrow(cs).borrow_mut();
+            for &group in pattern {
+                if queue.groups.push_back(group).is_err() {
+                    break;
+                }
+            }
+
+            let start = !queue.running && !queue.groups.is_empty();
+            if start {
+                // Cut a busy pulse short, the pattern has the pin now
+                queue.set_vibro(false);
+                queue.running = true;
+            }
+            start
+        });
+        if !start {
+            return;
+        }
+
+        // Fast PWM with OCR2A as top, so one period is one tick; OC2B (D3) is
+        // connected only while a pulse plays, see `set_pwm`
+        self.tc2.tccr2a().write(|w| w.wgm2().bits(0b11));
+        self.tc2.ocr2a().write(|w| w.set(TICK_TOP));
+        self.tc2.tcnt2().write(|w| w.set(0));
+        self.tc2.timsk2().write(|w| w.ocie2a().set_bit());
+        self.tc2
+            .tccr2b()
+            .write(|w| w.wgm22().set_bit().cs2().prescale_64());
+
+        // SAFETY: the timer handlers only touch the vibro pin and state behind a `Mutex`.
+        unsafe { avr_device::interrupt::enable() };
+    }
+
+    /// Whether something is still playing
+    #[must_use]
+    pub fn is_playing(&self) -> bool {
+        interrupt::free(|cs| QUEUE.borrow(cs).borrow().running)
+    }
+
+    #[must_use]
+    pub fn intensity(&self) -> Intensity {
+        interrupt::free(|cs| QUEUE.borrow(cs).borrow().intensity)
+    }
+
+    /// Set the intensity of pulses without emphasis, takes effect from the next pulse
+    pub fn set_intensity(&self, intensity: Intensity) {
+        interrupt::free(|cs| QUEUE.borrow(cs).borrow_mut().intensity = intensity);
+    }
+
+    /// Drop everything queued and silence the vibro
+    pub fn stop(&self) {
+        interrupt::free(|cs| {
+            let mut queue = QUEUE.borrow(cs).borrow_mut();
+            queue.clear();
+            stop_timer(&self.tc2);
+            queue.set_vibro(false);
+        });
+    }
+}
+
+/// Advance the pattern by one millisecond, call from `TIMER2_COMPA`
+pub fn tick(tc2: &TC2) {
+    interrupt::free(|cs| {
+        let mut queue = QUEUE.borrow(cs).borrow_mut();
+        if queue.left > 0 {
+            queue.left -= 1;
+            if queue.phase == Phase::Off && queue.ramp < RAMP_MS {
+                queue.ramp += 1;
+                set_pwm(tc2, queue.duty());
+            }
+            return;
+        }
+
+        if let Some((level, ms)) = queue.advance() {
+            if level {
+                queue.ramp = 1;
+                set_pwm(tc2, queue.duty());
+            } else {
+                set_pwm(tc2, 0);
+                queue.set_vibro(false);
+            }
+            queue.left = ms - 1;
+        } else {
+            set_pwm(tc2, 0);
+            queue.set_vibro(false);
+            queue.running = false;
+            stop_timer(tc2);
+        }
+    });
+}
+
+/// Switch the vibro for a pulse of [`Busy`](crate::busy::Busy), call from the `TIMER1` handlers
+///
+/// Does nothing while a pattern plays, so the two never fight over the pin.
+pub fn busy_pulse(on: bool) {
+    interrupt::free(|cs| {
+        let mut queue = QUEUE.borrow(cs).borrow_mut();
+        if !queue.running {
+            queue.set_vibro(on);
+        }
+    });
+}
+
+/// Drive D3 with `duty` out of [`TICK_TOP`], 0 hands the pin back to the port
+fn set_pwm(tc2: &TC2, duty: u8) {
+    tc2.ocr2b().write(|w| w.set(duty));
+    // Non-inverting output on OC2B, or disconnected
+    let com2b = if duty == 0 { 0b00 } else { 0b10 };
+    tc2.tccr2a()
+        .write(|w| w.wgm2().bits(0b11).com2b().bits(com2b));
+}
+
+fn stop_timer(tc2: &TC2) {
+    tc2.tccr2a().reset();
+    tc2.tccr2b().reset();
+    tc2.timsk2().reset();
+}
@@ -0,0 +1,86 @@
+//! Constants and operators contributed by board and support crates, see [`plugins!`]
+//!
+//! A board crate exports its entries as `&[Plugin<FRACTION_COUNT>]` and the firmware lists
+//! every group once with [`plugins!`], the registry is then resolved at link time. Plugins take
+//! the [`Cell::Reserved`](crate::layout::Cell::Reserved) cells of the layout in reading order,
+//! so hardware-specific extensions, e.g. a thermocouple linearization, don't need a fork.
+//!
+//! ```ignore
+//! // In the board crate
+//! pub const PLUGINS: &[Plugin<FRACTION_COUNT>] = &[Plugin::UnOp {
+//!     name: "K°C",
+//!     op: |mv| mv.checked_mul(Num::from_f64(24.38)).ok_or(CalcError::Overflow),
+//! }];
+//!
+//! // In the firmware
+//! cos::plugins!(board::PLUGINS);
+//! ```
+
+use crate::{CalcError, config::FRACTION_COUNT, num::Num};
+
+/// Entry a board crate adds to the keyboard
+#[derive(Clone, Copy)]
+pub enum Plugin<const F: u8> {
+    /// Entered like [`Key::Const`](crate::Key::Const)
+    Const { name: &'static str, value: Num<F> },
+    /// Applied to the current number like [`Key::UnOp`](crate::Key::UnOp)
+    UnOp {
+        name: &'static str,
+        op: fn(Num<F>) -> Result<Num<F>, CalcError>,
+    },
+}
+
+impl<const F: u8> Plugin<F> {
+    /// Name on serial and in help
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Const { name, .. } | Self::UnOp { name, .. } => name,
+        }
+    }
+}
+
+/// Groups of plugins, numbered through in order by [`Key::Plugin`](crate::Key::Plugin)
+#[derive(Clone, Copy)]
+pub struct Plugins<const F: u8>(pub &'static [&'static [Plugin<F>]]);
+
+impl<const F: u8> Plugins<F> {
+    pub const NONE: Self = Self(&[]);
+
+    #[must_use]
+    pub fn get(self, id: u8) -> Option<&'static Plugin<F>> {
+        self.0.iter().copied().flatten().nth(id.into())
+    }
+
+    #[must_use]
+    pub fn len(self) -> usize {
+        self.0.iter().map(|group| group.len()).sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+}
+
+unsafe extern "Rust" {
+    /// Defined by [`plugins!`] in the firmware
+    static COS_PLUGINS: Plugins<FRACTION_COUNT>;
+}
+
+/// Plugins the firmware was linked with
+#[must_use]
+pub fn registered() -> Plugins<FRACTION_COUNT> {
+    // SAFETY: `plugins!` defines the symbol with this type, and it is never written.
+    unsafe { COS_PLUGINS }
+}
+
+/// Register groups of [`Plugin`]s, once in the firmware, with no groups if there are none
+#[macro_export]
+macro_rules! plugins {
+    ($($group:expr),* $(,)?) => {
+        #[unsafe(no_mangle)]
+        static COS_PLUGINS: $crate::plugin::Plugins<{ $crate::config::FRACTION_COUNT }> =
+            $crate::plugin::Plugins(&[$($group),*]);
+    };
+}
@@ -0,0 +1,49 @@
+use arduino_hal::pac::{CPU, EXINT};
+use avr_device::interrupt;
+
+/// Deep sleep, woken by the joystick button (D2 / INT0)
+pub struct Power {
+    cpu: CPU,
+    exint: EXINT,
+}
+
+impl Power {
+    #[must_use]
+    pub const fn new(cpu: CPU, exint: EXINT) -> Self {
+        Self { cpu, exint }
+    }
+
+    /// Enter power-down until the button is pressed
+    ///
+    /// INT0 triggers on low level, so the button must be released before calling this.
+    /// The firmware has to provide an (empty) `INT0` handler. Interrupts are enabled to
+    /// wake and left as they were on return, so the timer ticks go on if they ran.
+    pub fn sleep_until_button(&self) {
+        let irq = interrupt::disable_save();
+
+        // Low level is the `EICRA` reset value and the only trigger that works without a clock
+        self.exint.eimsk().write(|w| w.int().bits(0b01));
+        self.cpu.smcr().write(|w| w.sm().pdown().se().set_bit());
+
+        // SAFETY: the other interrupts only touch state behind `interrupt::free`, and INT0
+        // is masked again before the caller's interrupt state is restored.
+        unsafe { interrupt::enable() };
+        avr_device::asm::sleep();
+        interrupt::disable();
+
+        self.cpu.smcr().write(|w| w.se().clear_bit());
+        self.exint.eimsk().write(|w| w.int().bits(0));
+
+        // SAFETY: `irq` is the state saved above, interrupts were enabled or not by the caller.
+        unsafe { interrupt::restore(irq) };
+    }
+
+    /// Idle until the next interrupt, e.g. the next tick of a playing pattern
+    ///
+    /// Interrupts must be enabled, or this never returns.
+    pub fn idle(&self) {
+        self.cpu.smcr().write(|w| w.sm().idle().se().set_bit());
+        avr_device::asm::sleep();
+        self.cpu.smcr().write(|w| w.se().clear_bit());
+    }
+}
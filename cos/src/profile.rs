@@ -0,0 +1,187 @@
+//! Time each operator takes on the device, built with the `profiling` feature
+//!
+//! Shows the real cost of a math path on the hardware, e.g. to compare the series with
+//! `cordic` and `lut`, another `F` or a slower clock. [`Calculator`](crate::Calculator) times
+//! every result it computes with [`time::micros`], a long operator over the steps of
+//! [`Calculator::poll`](crate::Calculator::poll) only, without the main loop in between.
+//! Failed operators and cancelled ones aren't counted. `PROFILE` over serial prints them.
+
+use core::cell::RefCell;
+
+use avr_device::interrupt::{self, Mutex};
+
+use crate::{
+    BinOp, Conversion, Op, UnOp,
+    lang::{Text, Word},
+    time,
+};
+
+/// Binary operators, the first entries of [`OPS`]
+const BINOPS: usize = 8;
+
+/// Conversions, the entries before the last one, both ways share one
+const CONVERSIONS: usize = 3;
+
+/// Operators timed, the plugins share the last entry
+pub const OPS: &[Op] = &[
+    Op::BinOp(BinOp::Add),
+    Op::BinOp(BinOp::Sub),
+    Op::BinOp(BinOp::Mul),
+    Op::BinOp(BinOp::Div),
+    Op::BinOp(BinOp::CmpEq),
+    Op::BinOp(BinOp::CmpLt),
+    Op::BinOp(BinOp::CmpGt),
+    Op::BinOp(BinOp::If),
+    Op::UnOp(UnOp::Neg),
+    #[cfg(not(feature = "basic-layout"))]
+    Op::UnOp(UnOp::Sqrt),
+    #[cfg(not(feature = "basic-layout"))]
+    Op::UnOp(UnOp::Pow2),
+    #[cfg(not(feature = "basic-layout"))]
+    Op::UnOp(UnOp::Pow3),
+    #[cfg(not(feature = "basic-layout"))]
+    Op::UnOp(UnOp::Factorial),
+    #[cfg(not(feature = "basic-layout"))]
+    Op::UnOp(UnOp::Recip),
+    #[cfg(not(feature = "basic-layout"))]
+    Op::UnOp(UnOp::Abs),
+    #[cfg(not(feature = "basic-layout"))]
+    Op::UnOp(UnOp::Sin),
+    #[cfg(not(feature = "basic-layout"))]
+    Op::UnOp(UnOp::Cos),
+    #[cfg(not(feature = "basic-layout"))]
+    Op::UnOp(UnOp::Tan),
+    #[cfg(not(feature = "basic-layout"))]
+    Op::UnOp(UnOp::Dms),
+    Op::Convert {
+        conversion: Conversion::KmMi,
+        back: false,
+    },
+    Op::Convert {
+        conversion: Conversion::CelsiusFahrenheit,
+        back: false,
+    },
+    Op::Convert {
+        conversion: Conversion::KgLb,
+        back: false,
+    },
+    Op::Plugin(0),
+];
+
+/// Times of one operator in microseconds, resolution of [`time::micros`]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub count: u32,
+    pub min: u32,
+    pub max: u32,
+    /// Sum of all times, for the average
+    total: u64,
+}
+
+impl Timing {
+    const NONE: Self = Self {
+        count: 0,
+        min: u32::MAX,
+        max: 0,
+        total: 0,
+    };
+
+    /// Average time, 0 if never timed
+    #[must_use]
+    pub const fn avg(&self) -> u32 {
+        if self.count == 0 {
+            return 0;
+        }
+
+        (self.total / self.count as u64) as u32
+    }
+
+    const fn add(&mut self, us: u32) {
+        self.count = self.count.saturating_add(1);
+        self.min = if us < self.min { us } else { self.min };
+        self.max = if us > self.max { us } else { self.max };
+        self.total = self.total.saturating_add(us as u64);
+    }
+}
+
+struct Profile {
+    timings: [Timing; OPS.len()],
+    /// Long operator being computed and the time its steps took so far
+    running: Option<(Op, u32)>,
+}
+
+static PROFILE: Mutex<RefCell<Profile>> = Mutex::new(RefCell::new(Profile {
+    timings: [Timing::NONE; OPS.len()],
+    running: None,
+}));
+
+/// Name printed for `op`
+#[must_use]
+pub const fn name(op: Op) -> Word {
+    match op {
+        Op::BinOp(op) => Word::Symbol(op.name()),
+        Op::UnOp(op) => op.name(),
+        Op::Convert { conversion, .. } => Word::Symbol(conversion.name()),
+        Op::Plugin(_) => Word::Text(Text::Plugins),
+    }
+}
+
+/// Position of `op` in [`OPS`]
+const fn index(op: Op) -> usize {
+    match op {
+        Op::BinOp(op) => op as usize,
+        Op::UnOp(op) => BINOPS + op as usize,
+        Op::Convert { conversion, .. } => OPS.len() - 1 - CONVERSIONS + conversion as usize,
+        Op::Plugin(_) => OPS.len() - 1,
+    }
+}
+
+/// Times of `OPS[i]`, `None` if it was never timed
+#[must_use]
+pub fn get(i: usize) -> Option<Timing> {
+    interrupt::free(|cs| PROFILE.borrow(cs).borrow().timings.get(i).copied())
+        .filter(|timing| timing.count > 0)
+}
+
+/// Forget every time measured
+pub fn clear() {
+    interrupt::free(|cs| {
+        *PROFILE.borrow(cs).borrow_mut() = Profile {
+            timings: [Timing::NONE; OPS.len()],
+            running: None,
+        };
+    });
+}
+
+/// Count `us` spent computing `op` in one go
+pub fn record(op: Op, us: u32) {
+    interrupt::free(|cs| PROFILE.borrow(cs).borrow_mut().timings[index(op)].add(us));
+}
+
+/// Start timing the long operator `op`, one still running is dropped like a cancelled one
+pub fn start(op: Op) {
+    interrupt::free(|cs| PROFILE.borrow(cs).borrow_mut().running = Some((op, 0)));
+}
+
+/// Add `us` spent on a step of the long operator, `done` counts its total
+pub fn step(us: u32, done: bool) {
+    interrupt::free(|cs| {
+        let mut profile = PROFILE.borrow(cs).borrow_mut();
+        let Some((op, spent)) = &mut profile.running else {
+            return;
+        };
+
+        *spent = spent.saturating_add(us);
+        if done {
+            let (op, spent) = (*op, *spent);
+            profile.running = None;
+            profile.timings[index(op)].add(spent);
+        }
+    });
+}
+
+/// Microseconds since `start`, a previous [`time::micros`]
+#[must_use]
+pub fn elapsed_since(start: u32) -> u32 {
+    time::micros().wrapping_sub(start)
+}
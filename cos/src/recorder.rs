@@ -0,0 +1,146 @@
+use heapless::Vec;
+use ufmt::derive::uDebug;
+
+use crate::{config::RECORD_LEN, input::Dir};
+
+/// Raw joystick reading, before it is turned into moves and presses
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    pub dir: Dir,
+    pub pressed: bool,
+}
+
+impl Sample {
+    /// Stick centered, button up
+    pub const IDLE: Self = Self {
+        dir: Dir::Center,
+        pressed: false,
+    };
+}
+
+/// Change of the [`Sample`] `after` milliseconds since the previous one
+#[derive(Clone, Copy)]
+struct Step {
+    after: u16,
+    sample: Sample,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Idle,
+    Recording,
+    Replaying { next: usize },
+}
+
+/// Records joystick changes to RAM and feeds them back instead of the joystick
+///
+/// Only changes are kept, a move or a press takes two steps (there and back).
+pub struct Recorder {
+    steps: Vec<Step, RECORD_LEN>,
+    mode: Mode,
+    /// Steps did not fit since the recording started
+    full: bool,
+    last: Sample,
+    /// [`Clock::now_ms`](crate::clock::Clock::now_ms) of the last step
+    since: u32,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recorder {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            mode: Mode::Idle,
+            full: false,
+            last: Sample::IDLE,
+            since: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn is_recording(&self) -> bool {
+        self.mode == Mode::Recording
+    }
+
+    /// Drop the previous recording and start a new one
+    pub fn start(&mut self, now: u32) {
+        self.steps.clear();
+        self.mode = Mode::Recording;
+        self.full = false;
+        self.last = Sample::IDLE;
+        self.since = now;
+    }
+
+    /// Stop recording, `false` if some steps did not fit
+    pub fn stop(&mut self) -> bool {
+        // Last step is the press that stopped the recording
+        if !self.full {
+            self.steps.pop();
+        }
+        self.mode = Mode::Idle;
+
+        !self.full
+    }
+
+    /// Recorded steps as `(ms since the previous one, sample)`
+    pub fn steps(&self) -> impl Iterator<Item = (u16, Sample)> {
+        self.steps.iter().map(|step| (step.after, step.sample))
+    }
+
+    /// Remember `sample` if it changed, changes past [`RECORD_LEN`] steps are dropped
+    pub fn record(&mut self, now: u32, sample: Sample) {
+        if self.mode != Mode::Recording || self.full || sample == self.last {
+            return;
+        }
+
+        let step = Step {
+            after: u16::try_from(now.wrapping_sub(self.since)).unwrap_or(u16::MAX),
+            sample,
+        };
+        if self.steps.push(step).is_err() {
+            self.full = true;
+            return;
+        }
+
+        self.last = sample;
+        self.since = now;
+    }
+
+    /// Start feeding the recording back, `false` if there is nothing to replay
+    pub fn replay(&mut self, now: u32) -> bool {
+        if self.mode != Mode::Idle || self.steps.is_empty() {
+            return false;
+        }
+
+        self.mode = Mode::Replaying { next: 0 };
+        self.last = Sample::IDLE;
+        self.since = now;
+        true
+    }
+
+    /// Recorded sample for `now` while replaying, `None` once the recording is over
+    pub fn poll(&mut self, now: u32) -> Option<Sample> {
+        let Mode::Replaying { next } = self.mode else {
+            return None;
+        };
+
+        let Some(step) = self.steps.get(next) else {
+            self.mode = Mode::Idle;
+            return None;
+        };
+
+        if now.wrapping_sub(self.since) >= step.after.into() {
+            self.last = step.sample;
+            self.since = now;
+            self.mode = Mode::Replaying { next: next + 1 };
+        }
+
+        Some(self.last)
+    }
+}
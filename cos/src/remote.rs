@@ -0,0 +1,338 @@
+//! Commands typed on a connected host, one per line over serial
+//!
+//! - `P:123.45` pastes the number into the operand being edited, for values too long to
+//!   enter with the stick. The rest of the calculation stays on the device.
+//! - `FACTORY RESET` erases the saved settings, like holding up at power-on.
+//! - `ERRORS` prints the error counts, see [`diagnostics`](crate::diagnostics).
+//! - `HISTORY` prints the results kept for the statistics keys, oldest first, with the date
+//!   and time they were computed at if a clock is wired, see `rtc`.
+//! - `PROFILE` prints the time each operator took and `PROFILE CLEAR` forgets it, with the
+//!   `profiling` feature, see `profile`.
+//!
+//! Lines end with `\n`, `\r` or both, empty lines are ignored. The firmware has to call
+//! [`listen`] once and provide a `USART_RX` handler calling [`receive`], bytes arrive
+//! faster than the main loop reads them. Lines are split and parsed by [`Remote::feed`]
+//! everywhere, only the serial port is the board's.
+
+#[cfg(feature = "avr")]
+use core::cell::RefCell;
+
+#[cfg(feature = "avr")]
+use arduino_hal::pac::USART0;
+#[cfg(feature = "avr")]
+use avr_device::interrupt::{self, Mutex};
+#[cfg(feature = "avr")]
+use heapless::Deque;
+use heapless::Vec;
+use ufmt::{Formatter, derive::uDebug, uDisplay, uWrite};
+
+use crate::{lang::Text, num::Num};
+
+/// Longest line accepted, enough for any `Num` with its sign and point
+const LINE_LEN: usize = 32;
+/// Bytes received between two [`Remote::poll`]s at most, two lines
+#[cfg(feature = "avr")]
+const RECEIVED_LEN: usize = 2 * LINE_LEN;
+
+const PASTE: &[u8] = b"P:";
+const FACTORY_RESET: &[u8] = b"FACTORY RESET";
+const ERRORS: &[u8] = b"ERRORS";
+const HISTORY: &[u8] = b"HISTORY";
+#[cfg(feature = "profiling")]
+const PROFILE: &[u8] = b"PROFILE";
+#[cfg(feature = "profiling")]
+const PROFILE_CLEAR: &[u8] = b"PROFILE CLEAR";
+
+/// Command of a complete line, see [`Remote::feed`]
+#[derive(Debug, uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum Command<const F: u8> {
+    Paste(Num<F>),
+    FactoryReset,
+    Errors,
+    History,
+    #[cfg(feature = "profiling")]
+    Profile,
+    #[cfg(feature = "profiling")]
+    ProfileClear,
+}
+
+#[derive(Debug, uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteError {
+    /// Line is none of the commands
+    Unknown,
+    /// `P:` without a number that fits
+    Number,
+    /// Line longer than [`LINE_LEN`], it is dropped whole
+    TooLong,
+    /// Bytes of the line were received faster than they were read, it is dropped whole
+    Lost,
+}
+
+impl uDisplay for RemoteError {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        let text = match self {
+            Self::Unknown => Text::UnknownCommand,
+            Self::Number => Text::InvalidNumber,
+            Self::TooLong => Text::LineTooLong,
+            Self::Lost => Text::BytesLost,
+        };
+
+        uDisplay::fmt(&text, f)
+    }
+}
+
+/// Bytes from the receive interrupt, `lost` once one didn't fit
+#[cfg(feature = "avr")]
+struct Received {
+    bytes: Deque<u8, RECEIVED_LEN>,
+    lost: bool,
+}
+
+#[cfg(feature = "avr")]
+static RECEIVED: Mutex<RefCell<Received>> = Mutex::new(RefCell::new(Received {
+    bytes: Deque::new(),
+    lost: false,
+}));
+
+/// Enable the receive interrupt, the serial port itself is set up by the logger
+#[cfg(feature = "avr")]
+pub fn listen() {
+    // SAFETY: only the interrupt enable bit is set, the logger leaves it alone
+    let usart = unsafe { &*USART0::ptr() };
+    usart.ucsr0b().modify(|_, w| w.rxcie0().set_bit());
+
+    // SAFETY: the receive handler only touches the queue behind a `Mutex`.
+    unsafe { avr_device::interrupt::enable() };
+}
+
+/// Queue the received byte, call from `USART_RX`
+#[cfg(feature = "avr")]
+pub fn receive() {
+    // SAFETY: reading the data register is what clears the interrupt, nothing else reads it
+    let usart = unsafe { &*USART0::ptr() };
+    let byte = usart.udr0().read().bits();
+
+    interrupt::free(|cs| {
+        let mut received = RECEIVED.borrow(cs).borrow_mut();
+        if received.bytes.push_back(byte).is_err() {
+            received.lost = true;
+        }
+    });
+}
+
+/// Collects received bytes into lines and parses them
+pub struct Remote {
+    line: Vec<u8, LINE_LEN>,
+    /// Bytes of the current line did not fit
+    overflow: bool,
+    /// Bytes of the current line were dropped by [`receive`]
+    lost: bool,
+}
+
+impl Default for Remote {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Remote {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            line: Vec::new(),
+            overflow: false,
+            lost: false,
+        }
+    }
+
+    /// Read what was received, returns the command of the first complete line
+    ///
+    /// Should be called on every main loop iteration, the rest waits for the next call.
+    #[cfg(feature = "avr")]
+    pub fn poll<const F: u8>(&mut self) -> Option<Result<Command<F>, RemoteError>> {
+        loop {
+            let byte = interrupt::free(|cs| {
+                let mut received = RECEIVED.borrow(cs).borrow_mut();
+                let byte = received.bytes.pop_front();
+                // Bytes are dropped after the queued ones, so from the line still being collected
+                if byte.is_none() {
+                    self.lost |= core::mem::replace(&mut received.lost, false);
+                }
+                byte
+            });
+
+            if let Some(command) = self.feed(byte?) {
+                return Some(command);
+            }
+        }
+    }
+
+    /// Feed a received byte, returns the command once its line is complete
+    pub fn feed<const F: u8>(&mut self, byte: u8) -> Option<Result<Command<F>, RemoteError>> {
+        if byte != b'\n' && byte != b'\r' {
+            if self.line.push(byte).is_err() {
+                self.overflow = true;
+            }
+            return None;
+        }
+
+        let overflow = core::mem::replace(&mut self.overflow, false);
+        let lost = core::mem::replace(&mut self.lost, false);
+        let line = core::mem::take(&mut self.line);
+        if lost {
+            return Some(Err(RemoteError::Lost));
+        }
+        if overflow {
+            return Some(Err(RemoteError::TooLong));
+        }
+        if line.is_empty() {
+            return None;
+        }
+
+        Some(parse(&line))
+    }
+}
+
+/// Command of a line without its end
+fn parse<const F: u8>(line: &[u8]) -> Result<Command<F>, RemoteError> {
+    match line.strip_prefix(PASTE) {
+        Some(number) => Num::from_ascii(number)
+            .map(Command::Paste)
+            .ok_or(RemoteError::Number),
+        None if line == FACTORY_RESET => Ok(Command::FactoryReset),
+        None if line == ERRORS => Ok(Command::Errors),
+        None if line == HISTORY => Ok(Command::History),
+        #[cfg(feature = "profiling")]
+        None if line == PROFILE => Ok(Command::Profile),
+        #[cfg(feature = "profiling")]
+        None if line == PROFILE_CLEAR => Ok(Command::ProfileClear),
+        None => Err(RemoteError::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::{Command, LINE_LEN, Remote, RemoteError};
+    use crate::num::Num;
+
+    type Fed = Option<Result<Command<2>, RemoteError>>;
+
+    /// Feed every byte, returns what each complete line gave
+    fn feed(remote: &mut Remote, bytes: &[u8]) -> Vec<Fed, 8> {
+        bytes
+            .iter()
+            .filter_map(|&byte| {
+                let fed = remote.feed(byte);
+                matches!(byte, b'\n' | b'\r').then_some(fed)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let mut remote = Remote::new();
+        let paste = Some(Ok(Command::Paste(Num::from_raw(12_345))));
+        assert_eq!(feed(&mut remote, b"P:123.45\n"), [paste]);
+        assert_eq!(feed(&mut remote, b"P:123.45\r"), [paste]);
+        // The second end of CRLF is an empty line
+        assert_eq!(feed(&mut remote, b"P:123.45\r\n"), [paste, None]);
+        assert_eq!(
+            feed(&mut remote, b"ERRORS\r\nHISTORY\n"),
+            [Some(Ok(Command::Errors)), None, Some(Ok(Command::History))]
+        );
+
+        // Bytes wait for their line
+        assert_eq!(remote.feed::<2>(b'P'), None);
+        assert_eq!(
+            feed(&mut remote, b":-1\n"),
+            [Some(Ok(Command::Paste(-Num::ONE)))]
+        );
+    }
+
+    #[test]
+    fn test_empty_lines() {
+        let mut remote = Remote::new();
+        assert_eq!(feed(&mut remote, b"\n\r\r\n\n"), [None; 5]);
+        assert_eq!(
+            feed(&mut remote, b"\nERRORS\n"),
+            [None, Some(Ok(Command::Errors))]
+        );
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut remote = Remote::new();
+        assert_eq!(
+            feed(&mut remote, b"FACTORY RESET\n"),
+            [Some(Ok(Command::FactoryReset))]
+        );
+        // Commands are matched whole and as typed
+        for line in [
+            &b"FACTORY RESET \n"[..],
+            b"factory reset\n",
+            b"FACTORY\n",
+            b"P\n",
+        ] {
+            assert_eq!(feed(&mut remote, line), [Some(Err(RemoteError::Unknown))]);
+        }
+    }
+
+    #[test]
+    fn test_bad_number() {
+        let mut remote = Remote::new();
+        for line in [
+            &b"P:\n"[..],
+            b"P:1.2.3\n",
+            b"P:12a\n",
+            b"P: 1\n",
+            b"P:99999999999999999999\n",
+        ] {
+            assert_eq!(feed(&mut remote, line), [Some(Err(RemoteError::Number))]);
+        }
+        // The next line is read as usual
+        assert_eq!(
+            feed(&mut remote, b"P:7\n"),
+            [Some(Ok(Command::Paste(Num::from_int(7))))]
+        );
+    }
+
+    #[test]
+    fn test_too_long() {
+        let mut remote = Remote::new();
+        let mut line = [b'1'; LINE_LEN + 3];
+        line[..2].copy_from_slice(b"P:");
+        assert_eq!(feed(&mut remote, &line), []);
+        // Dropped whole, not cut to what fit
+        assert_eq!(feed(&mut remote, b"\n"), [Some(Err(RemoteError::TooLong))]);
+        assert_eq!(feed(&mut remote, b"ERRORS\n"), [Some(Ok(Command::Errors))]);
+
+        // A line of exactly the length fits
+        let mut line = [b'0'; LINE_LEN];
+        line[..2].copy_from_slice(b"P:");
+        assert_eq!(feed(&mut remote, &line), []);
+        assert_eq!(
+            feed(&mut remote, b"\n"),
+            [Some(Ok(Command::Paste(Num::ZERO)))]
+        );
+    }
+
+    #[test]
+    fn test_lost() {
+        let mut remote = Remote::new();
+        assert_eq!(feed(&mut remote, b"P:12"), []);
+        // As `Remote::poll` marks it when `receive` dropped bytes
+        remote.lost = true;
+        assert_eq!(feed(&mut remote, b"3\n"), [Some(Err(RemoteError::Lost))]);
+        assert_eq!(
+            feed(&mut remote, b"P:123\n"),
+            [Some(Ok(Command::Paste(Num::from_int(123))))]
+        );
+
+        // Lost bytes win over a line that is too long too
+        remote.lost = true;
+        assert_eq!(feed(&mut remote, &[b'1'; LINE_LEN + 1]), []);
+        assert_eq!(feed(&mut remote, b"\n"), [Some(Err(RemoteError::Lost))]);
+    }
+}
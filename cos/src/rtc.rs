@@ -0,0 +1,168 @@
+//! Date and time from a DS1307 or DS3231 real-time clock on I2C, with the `rtc` feature
+//!
+//! Both chips answer at the same address and keep the time in the same BCD registers,
+//! running on from their coin cell while the board is off. Once the oscillator stopped,
+//! on a flat cell or before the clock was ever set, [`Rtc::now`] fails with
+//! [`RtcError::Stopped`] until [`Rtc::set`] starts it again. Which chip is wired is
+//! [`RTC_CHIP`](crate::config::RTC_CHIP), they flag the stop differently.
+
+use embedded_hal::i2c::{self, I2c};
+use ufmt::{Formatter, derive::uDebug, uDisplay, uWrite};
+
+use crate::{
+    date::{DateTime, FIRST_YEAR},
+    lang::Text,
+};
+
+/// I2C address of both chips
+const ADDRESS: u8 = 0x68;
+/// First of seconds, minutes, hours, weekday, day, month and year
+const TIME_REG: u8 = 0x00;
+const TIME_LEN: usize = 7;
+/// DS3231 status, bit 7 is set once the oscillator stopped
+const STATUS_REG: u8 = 0x0F;
+const OSCILLATOR_STOPPED: u8 = 0x80;
+/// DS1307 seconds bit 7, the oscillator is halted while it is set
+const CLOCK_HALT: u8 = 0x80;
+/// Hours bit 6, set in 12-hour mode where bit 5 is PM
+const HOURS_12: u8 = 0x40;
+const PM: u8 = 0x20;
+
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum Chip {
+    /// Plain crystal, drifts about a second a day
+    Ds1307,
+    /// Temperature compensated, a minute a year at most
+    Ds3231,
+}
+
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+pub enum RtcError {
+    /// Nothing answered on the bus, the clock is missing
+    Bus,
+    /// Clock stopped and lost the time, it has to be set
+    Stopped,
+}
+
+impl uDisplay for RtcError {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        let text = match self {
+            Self::Bus => Text::NoClock,
+            Self::Stopped => Text::ClockStopped,
+        };
+
+        uDisplay::fmt(&text, f)
+    }
+}
+
+/// Real-time clock chip behind `I`
+pub struct Rtc<I> {
+    i2c: I,
+    chip: Chip,
+}
+
+impl<I: I2c> Rtc<I> {
+    #[must_use]
+    pub const fn new(i2c: I, chip: Chip) -> Self {
+        Self { i2c, chip }
+    }
+
+    /// Current date and time
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the clock doesn't answer, or if it stopped or holds no valid
+    /// date since.
+    pub fn now(&mut self) -> Result<DateTime, RtcError> {
+        let mut regs = [0u8; TIME_LEN];
+        self.i2c
+            .write_read(ADDRESS, &[TIME_REG], &mut regs)
+            .map_err(bus_error)?;
+
+        let stopped = match self.chip {
+            Chip::Ds1307 => regs[0] & CLOCK_HALT != 0,
+            Chip::Ds3231 => self.read(STATUS_REG)? & OSCILLATOR_STOPPED != 0,
+        };
+        if stopped {
+            return Err(RtcError::Stopped);
+        }
+
+        let hours = regs[2];
+        let hour = if hours & HOURS_12 == 0 {
+            from_bcd(hours & 0x3F)
+        } else {
+            // 12 AM is midnight
+            from_bcd(hours & 0x1F) % 12 + if hours & PM == 0 { 0 } else { 12 }
+        };
+        let now = DateTime {
+            year: FIRST_YEAR + u16::from(from_bcd(regs[6])),
+            // DS3231 keeps the century in bit 7
+            month: from_bcd(regs[5] & 0x1F),
+            day: from_bcd(regs[4] & 0x3F),
+            hour,
+            minute: from_bcd(regs[1] & 0x7F),
+            second: from_bcd(regs[0] & 0x7F),
+        };
+
+        // Registers never written hold garbage
+        if now.is_valid() {
+            Ok(now)
+        } else {
+            Err(RtcError::Stopped)
+        }
+    }
+
+    /// Set the clock to `time`, 24-hour mode, and start it if it stopped
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the clock doesn't answer.
+    pub fn set(&mut self, time: DateTime) -> Result<(), RtcError> {
+        // Clearing the DS1307 clock halt bit with the seconds starts it
+        let regs = [
+            TIME_REG,
+            to_bcd(time.second),
+            to_bcd(time.minute),
+            to_bcd(time.hour),
+            time.weekday(),
+            to_bcd(time.day),
+            to_bcd(time.month),
+            to_bcd((time.year - FIRST_YEAR) as u8),
+        ];
+        self.i2c.write(ADDRESS, &regs).map_err(bus_error)?;
+
+        if self.chip == Chip::Ds3231 {
+            let status = self.read(STATUS_REG)?;
+            self.i2c
+                .write(ADDRESS, &[STATUS_REG, status & !OSCILLATOR_STOPPED])
+                .map_err(bus_error)?;
+        }
+
+        Ok(())
+    }
+
+    /// Value of the register `reg`
+    fn read(&mut self, reg: u8) -> Result<u8, RtcError> {
+        let mut value = [0u8];
+        self.i2c
+            .write_read(ADDRESS, &[reg], &mut value)
+            .map_err(bus_error)?;
+
+        Ok(value[0])
+    }
+}
+
+/// Error of any I2C transfer, all the chips can do wrong is not answer
+fn bus_error(_: impl i2c::Error) -> RtcError {
+    RtcError::Bus
+}
+
+/// Two BCD digits as a number
+const fn from_bcd(bcd: u8) -> u8 {
+    (bcd >> 4) * 10 + (bcd & 0x0F)
+}
+
+/// Number below 100 as two BCD digits
+const fn to_bcd(n: u8) -> u8 {
+    ((n / 10) << 4) | (n % 10)
+}
@@ -0,0 +1,60 @@
+use arduino_hal::{
+    Adc,
+    adc::channel,
+    hal::port::PC2,
+    pac::ADC,
+    port::{Pin, mode::Analog},
+};
+
+use crate::{Source, num::Num};
+
+/// Internal bandgap reference voltage, in millivolts, also the reference for the
+/// temperature sensor
+const VBG_MV: i64 = 1100;
+
+/// Hardware behind the live sources operands can be bound to
+pub struct Sensors {
+    pub a2: Pin<Analog, PC2>,
+}
+
+impl Sensors {
+    /// Take a reading of `source`
+    pub fn read<const F: u8>(&self, adc: &mut Adc, source: Source) -> Num<F> {
+        match source {
+            Source::A2 => {
+                let raw = i64::from(self.a2.analog_read(adc));
+                Num::from_raw_scaled(raw * Self::vcc_mv(adc) / 1023, 3)
+            }
+            Source::Battery => Num::from_raw_scaled(Self::vcc_mv(adc), 3),
+            Source::Temperature => {
+                // Typical datasheet calibration: 314 mV at 25°C, 1 mV per °C
+                let raw = Self::temperature_raw(adc);
+                Num::from_int(raw * VBG_MV / 1023 - 314 + 25)
+            }
+        }
+    }
+
+    /// Measure supply voltage against the bandgap reference
+    fn vcc_mv(adc: &mut Adc) -> i64 {
+        let vbg = i64::from(adc.read_blocking(&channel::Vbg)).max(1);
+        VBG_MV * 1023 / vbg
+    }
+
+    /// Read the temperature sensor against the internal 1.1 V reference, the only one it is
+    /// specified for, and switch back to AVCC for the other channels
+    fn temperature_raw(adc: &mut Adc) -> i64 {
+        // SAFETY: only the reference bits are changed, `Adc` keeps them when it selects a
+        // channel and nothing else uses the ADC
+        let admux = unsafe { &*ADC::ptr() }.admux();
+
+        // The first conversion after switching the reference is off while it settles
+        admux.modify(|_, w| w.refs().internal());
+        adc.read_blocking(&channel::Temperature);
+        let raw = adc.read_blocking(&channel::Temperature);
+
+        admux.modify(|_, w| w.refs().avcc());
+        adc.read_blocking(&channel::Vbg);
+
+        i64::from(raw)
+    }
+}
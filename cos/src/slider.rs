@@ -0,0 +1,55 @@
+use arduino_hal::{Adc, adc::Channel};
+
+use crate::num::Num;
+
+/// Coarse value entry with a potentiometer, its position maps linearly onto `min..=max`
+pub struct Slider<const F: u8> {
+    channel: Channel,
+    min: Num<F>,
+    max: Num<F>,
+    /// Tenth of the range at the last poll, `None` while closed
+    tenth: Option<u8>,
+}
+
+impl<const F: u8> Slider<F> {
+    #[must_use]
+    pub const fn new(channel: Channel, (min, max): (Num<F>, Num<F>)) -> Self {
+        Self {
+            channel,
+            min,
+            max,
+            tenth: None,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_open(&self) -> bool {
+        self.tenth.is_some()
+    }
+
+    /// Start picking a value
+    pub const fn open(&mut self) {
+        // Out of range, so the first poll reports a change
+        self.tenth = Some(u8::MAX);
+    }
+
+    /// Stop picking and return the value
+    pub fn close(&mut self, adc: &mut Adc) -> Num<F> {
+        self.tenth = None;
+        self.value(adc.read_blocking(&self.channel))
+    }
+
+    /// Whether the value moved into another tenth of the range since the last poll
+    pub fn poll(&mut self, adc: &mut Adc) -> bool {
+        let tenth = (adc.read_blocking(&self.channel) / 103) as u8;
+        let changed = self.tenth != Some(tenth);
+        self.tenth = Some(tenth);
+
+        changed
+    }
+
+    fn value(&self, raw: u16) -> Num<F> {
+        self.min + (self.max - self.min) * Num::from_int(raw.into()) / Num::from_int(1023)
+    }
+}
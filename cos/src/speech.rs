@@ -0,0 +1,198 @@
+//! Entries, results and errors as words to be spoken, for a text-to-speech bridge on the
+//! host or an audio module playing a recorded clip per word
+//!
+//! With the `speech` feature [`Voice`] logs every accepted key, error and number read out
+//! as a `say:` line of [`Word`]s, e.g. `say: MINUS ONE TWO POINT FIVE` for -12.5, which a
+//! host reads with `cos-tools log --tag say` and hands to its speech synthesizer. The words
+//! are tokens in every language, the host picks how to say them. With `dfplayer` the same
+//! words are played on a `DFPlayer` Mini, see `dfplayer`.
+
+use ufmt::{Formatter, derive::uDebug, uDisplay, uWrite};
+
+#[cfg(feature = "speech")]
+use crate::bus::OutputRequest;
+#[cfg(feature = "dfplayer")]
+use crate::dfplayer::DfPlayer;
+use crate::{
+    BinOp, CalcError, Key, UnOp,
+    num::{Digit, Num},
+};
+
+/// Word said for a key, an error or a [`Digit`] of a number
+#[derive(uDebug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Word {
+    Minus,
+    /// 0 to 9
+    Digit(u8),
+    Point,
+    /// [`Key::Result`]
+    Equals,
+    BinOp(BinOp),
+    UnOp(UnOp),
+    Error(CalcError),
+}
+
+impl Word {
+    /// Word said when `key` is accepted, `None` for keys that only change a setting or
+    /// a mode, they have their own feedback
+    #[must_use]
+    pub const fn of_key(key: Key) -> Option<Self> {
+        Some(match key {
+            Key::Num(n) => Self::Digit(n),
+            Key::Dot => Self::Point,
+            Key::Result => Self::Equals,
+            Key::BinOp(op) => Self::BinOp(op),
+            Key::UnOp(op) => Self::UnOp(op),
+            _ => return None,
+        })
+    }
+
+    /// Number of the clip saying the word on an audio module
+    ///
+    /// `0001.mp3` to `0010.mp3` say the digits 0 to 9, `0011.mp3` minus, `0012.mp3` the
+    /// point and `0013.mp3` equals, binary operators start at `0021.mp3`, unary ones at
+    /// `0031.mp3` and errors at `0051.mp3`, each in the order of their enum.
+    #[must_use]
+    pub const fn clip(self) -> u16 {
+        match self {
+            Self::Digit(n) => n as u16 + 1,
+            Self::Minus => 11,
+            Self::Point => 12,
+            Self::Equals => 13,
+            Self::BinOp(op) => op as u16 + 21,
+            Self::UnOp(op) => op as u16 + 31,
+            Self::Error(e) => e as u16 + 51,
+        }
+    }
+
+    const fn token(self) -> &'static str {
+        const DIGITS: [&str; 10] = [
+            "ZERO", "ONE", "TWO", "THREE", "FOUR", "FIVE", "SIX", "SEVEN", "EIGHT", "NINE",
+        ];
+
+        match self {
+            Self::Minus => "MINUS",
+            Self::Digit(n) => DIGITS[(n % 10) as usize],
+            Self::Point => "POINT",
+            Self::Equals => "EQUALS",
+            Self::BinOp(op) => match op {
+                BinOp::Add => "PLUS",
+                BinOp::Sub => "MINUS",
+                BinOp::Mul => "TIMES",
+                BinOp::Div => "DIVIDED_BY",
+                BinOp::CmpEq => "IS_EQUAL_TO",
+                BinOp::CmpLt => "IS_LESS_THAN",
+                BinOp::CmpGt => "IS_GREATER_THAN",
+                BinOp::If => "IF",
+            },
+            Self::UnOp(op) => match op {
+                UnOp::Neg => "NEGATE",
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Sqrt => "SQUARE_ROOT",
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Pow2 => "SQUARED",
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Pow3 => "CUBED",
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Factorial => "FACTORIAL",
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Recip => "RECIPROCAL",
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Abs => "ABSOLUTE",
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Sin => "SINE",
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Cos => "COSINE",
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Tan => "TANGENT",
+                #[cfg(not(feature = "basic-layout"))]
+                UnOp::Dms => "DEGREES_MINUTES_SECONDS",
+            },
+            Self::Error(e) => match e {
+                CalcError::Calc => "NO_OPERATOR",
+                CalcError::Overflow => "OVERFLOW",
+                CalcError::DivByZero => "DIVISION_BY_ZERO",
+                CalcError::Domain => "OUT_OF_DOMAIN",
+                CalcError::Cancelled => "CANCELLED",
+                CalcError::Empty => "NO_RESULTS",
+            },
+        }
+    }
+}
+
+impl From<Digit> for Word {
+    fn from(digit: Digit) -> Self {
+        match digit {
+            Digit::Minus => Self::Minus,
+            Digit::Num(n) => Self::Digit(n),
+            Digit::Dot => Self::Point,
+        }
+    }
+}
+
+impl uDisplay for Word {
+    /// Token like `FIVE` or `DIVIDED_BY`, words of several are joined by `_`
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_str(self.token())
+    }
+}
+
+/// Words of `value` in reading order, like its [`Num::digits`]
+pub fn words<const F: u8>(value: Num<F>) -> impl Iterator<Item = Word> + Clone {
+    value.digits().map(Word::from)
+}
+
+/// Words separated by spaces, as logged on the `say:` line
+#[derive(Clone, Copy)]
+pub struct Spoken<I>(pub I);
+
+impl<I: Iterator<Item = Word> + Clone> uDisplay for Spoken<I> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        for (i, word) in self.0.clone().enumerate() {
+            if i > 0 {
+                f.write_char(' ')?;
+            }
+            word.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Output saying what the vibro plays, every request it can say is logged as a `say:`
+/// line and played on the `DFPlayer` with `dfplayer`
+#[cfg(feature = "speech")]
+pub struct Voice {
+    #[cfg(feature = "dfplayer")]
+    pub dfplayer: DfPlayer,
+}
+
+#[cfg(feature = "speech")]
+impl Voice {
+    /// Say `request` if it has words, numbers read out as time are left to the vibro
+    pub fn show(&mut self, request: OutputRequest) {
+        match request {
+            #[cfg(feature = "dfplayer")]
+            OutputRequest::Stop => self.dfplayer.stop(),
+            OutputRequest::Entered(key) => {
+                if let Some(word) = Word::of_key(key) {
+                    self.say(core::iter::once(word));
+                }
+            }
+            OutputRequest::Failed(e) => self.say(core::iter::once(Word::Error(e))),
+            OutputRequest::Number(v) => self.say(words(v)),
+            _ => {}
+        }
+    }
+
+    // Only logged without an audio module
+    #[cfg_attr(
+        not(feature = "dfplayer"),
+        expect(clippy::unused_self, clippy::needless_pass_by_ref_mut)
+    )]
+    fn say(&mut self, words: impl Iterator<Item = Word> + Clone) {
+        #[cfg(feature = "dfplayer")]
+        self.dfplayer.say(words.clone());
+        crate::info!("say: {}", Spoken(words));
+    }
+}
@@ -0,0 +1,404 @@
+use arduino_hal::Eeprom;
+
+use crate::{
+    BinOp, State,
+    alarm::{Alarm, AlarmMode},
+    config::TEMPO_RANGE,
+    diagnostics::KINDS,
+    num::Num,
+    pattern::Intensity,
+};
+
+// EEPROM layout
+//
+// Every record is its payload followed by a CRC-8 of `VERSION` and the payload, so a record
+// damaged by a brownout mid-write or written by a firmware with another layout reads as
+// corrupted instead of as garbage. Erased EEPROM reads as 0xFF, a record erased throughout
+// is "nothing saved".
+const VERSION: u8 = 1;
+const ALARM_OFFSET: u16 = 0;
+const ALARM_LEN: usize = 9;
+const STATE_OFFSET: u16 = after(ALARM_OFFSET, ALARM_LEN);
+const STATE_LEN: usize = 17;
+const PRECISION_OFFSET: u16 = after(STATE_OFFSET, STATE_LEN);
+const CALIBRATION_OFFSET: u16 = after(PRECISION_OFFSET, 1);
+const CALIBRATION_LEN: usize = 4;
+const INTENSITY_OFFSET: u16 = after(CALIBRATION_OFFSET, CALIBRATION_LEN);
+const CHORDS_OFFSET: u16 = after(INTENSITY_OFFSET, 1);
+const ROUNDING_OFFSET: u16 = after(CHORDS_OFFSET, 1);
+const CHECKPOINT_OFFSET: u16 = after(ROUNDING_OFFSET, 1);
+const TEMPO_OFFSET: u16 = after(CHECKPOINT_OFFSET, STATE_LEN);
+const TEMPO_LEN: usize = 2;
+const ERRORS_OFFSET: u16 = after(TEMPO_OFFSET, TEMPO_LEN);
+const ERRORS_LEN: usize = 4 * KINDS;
+const TUTORIAL_OFFSET: u16 = after(ERRORS_OFFSET, ERRORS_LEN);
+const VOLUME_OFFSET: u16 = after(TUTORIAL_OFFSET, 1);
+const CLOCK_SET_OFFSET: u16 = after(VOLUME_OFFSET, 1);
+const CLOCK_SET_LEN: usize = 4;
+/// End of the last record, everything below is erased by [`Storage::factory_reset`]
+const END: u16 = after(CLOCK_SET_OFFSET, CLOCK_SET_LEN);
+
+/// Offset of the record after the one at `offset` with a payload of `len` bytes
+const fn after(offset: u16, len: usize) -> u16 {
+    offset + len as u16 + 1
+}
+
+/// Settings persisted across power cycles
+pub struct Storage {
+    eeprom: Eeprom,
+    /// A record failed its CRC since the last [`Storage::take_corrupted`]
+    corrupted: bool,
+}
+
+impl Storage {
+    #[must_use]
+    pub const fn new(eeprom: Eeprom) -> Self {
+        Self {
+            eeprom,
+            corrupted: false,
+        }
+    }
+
+    /// Whether a record was found corrupted and erased since the last call
+    ///
+    /// Its load fell back to the default, the user should be told.
+    pub const fn take_corrupted(&mut self) -> bool {
+        core::mem::replace(&mut self.corrupted, false)
+    }
+
+    /// Load the alarm threshold, `Alarm::OFF` if none was saved
+    #[must_use]
+    pub fn load_alarm<const F: u8>(&mut self) -> Alarm<F> {
+        let Some(buf) = self.read::<ALARM_LEN>(ALARM_OFFSET) else {
+            return Alarm::OFF;
+        };
+
+        let mode = match buf[0] {
+            1 => AlarmMode::Above,
+            2 => AlarmMode::Below,
+            _ => return Alarm::OFF,
+        };
+
+        Alarm {
+            mode,
+            threshold: read_num(&buf[1..]),
+        }
+    }
+
+    /// Save the alarm threshold
+    pub fn save_alarm<const F: u8>(&mut self, alarm: Alarm<F>) {
+        let mut buf = [0u8; ALARM_LEN];
+        buf[0] = match alarm.mode {
+            AlarmMode::Off => 0,
+            AlarmMode::Above => 1,
+            AlarmMode::Below => 2,
+        };
+        buf[1..].copy_from_slice(&alarm.threshold.raw().to_le_bytes());
+
+        self.write(ALARM_OFFSET, &buf);
+    }
+
+    /// Load the count of fractional digits, `None` if none was saved
+    #[must_use]
+    pub fn load_precision(&mut self) -> Option<u8> {
+        self.read::<1>(PRECISION_OFFSET).map(|[digits]| digits)
+    }
+
+    /// Save the count of fractional digits
+    pub fn save_precision(&mut self, digits: u8) {
+        self.write(PRECISION_OFFSET, &[digits]);
+    }
+
+    /// Load the joystick center as (x, y), `None` if it was never calibrated
+    #[must_use]
+    pub fn load_calibration(&mut self) -> Option<(u16, u16)> {
+        let buf = self.read::<CALIBRATION_LEN>(CALIBRATION_OFFSET)?;
+
+        let x = u16::from_le_bytes([buf[0], buf[1]]);
+        let y = u16::from_le_bytes([buf[2], buf[3]]);
+        // ADC readings are 10 bit
+        (x < 1024 && y < 1024).then_some((x, y))
+    }
+
+    /// Save the joystick center
+    pub fn save_calibration(&mut self, (x, y): (u16, u16)) {
+        let mut buf = [0u8; CALIBRATION_LEN];
+        buf[..2].copy_from_slice(&x.to_le_bytes());
+        buf[2..].copy_from_slice(&y.to_le_bytes());
+
+        self.write(CALIBRATION_OFFSET, &buf);
+    }
+
+    /// Load the vibro strength, `None` if none was saved
+    #[must_use]
+    pub fn load_intensity(&mut self) -> Option<Intensity> {
+        match self.read(INTENSITY_OFFSET)? {
+            [0] => Some(Intensity::Soft),
+            [1] => Some(Intensity::Medium),
+            [2] => Some(Intensity::Strong),
+            _ => None,
+        }
+    }
+
+    /// Save the vibro strength
+    pub fn save_intensity(&mut self, intensity: Intensity) {
+        let byte = match intensity {
+            Intensity::Soft => 0,
+            Intensity::Medium => 1,
+            Intensity::Strong => 2,
+        };
+        self.write(INTENSITY_OFFSET, &[byte]);
+    }
+
+    /// Load whether digits are entered by chords, off if nothing was saved
+    #[must_use]
+    pub fn load_chords(&mut self) -> bool {
+        self.read(CHORDS_OFFSET) == Some([1])
+    }
+
+    /// Save whether digits are entered by chords
+    pub fn save_chords(&mut self, on: bool) {
+        self.write(CHORDS_OFFSET, &[u8::from(on)]);
+    }
+
+    /// Load whether results are kept as shown, off if nothing was saved
+    #[must_use]
+    pub fn load_rounding(&mut self) -> bool {
+        self.read(ROUNDING_OFFSET) == Some([1])
+    }
+
+    /// Save whether results are kept as shown
+    pub fn save_rounding(&mut self, on: bool) {
+        self.write(ROUNDING_OFFSET, &[u8::from(on)]);
+    }
+
+    /// Load the vibro speed in percent, `None` if none was saved
+    #[must_use]
+    pub fn load_tempo(&mut self) -> Option<u16> {
+        let tempo = u16::from_le_bytes(self.read::<TEMPO_LEN>(TEMPO_OFFSET)?);
+        (TEMPO_RANGE.0..=TEMPO_RANGE.1)
+            .contains(&tempo)
+            .then_some(tempo)
+    }
+
+    /// Save the vibro speed in percent
+    pub fn save_tempo(&mut self, tempo: u16) {
+        self.write(TEMPO_OFFSET, &tempo.to_le_bytes());
+    }
+
+    /// Load the error totals, all 0 if none were saved
+    #[must_use]
+    pub fn load_errors(&mut self) -> [u32; KINDS] {
+        let mut total = [0; KINDS];
+        if let Some(buf) = self.read::<ERRORS_LEN>(ERRORS_OFFSET) {
+            for (total, bytes) in total.iter_mut().zip(buf.chunks_exact(4)) {
+                *total = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            }
+        }
+
+        total
+    }
+
+    /// Save the error totals, see [`diagnostics`](crate::diagnostics)
+    pub fn save_errors(&mut self, total: &[u32; KINDS]) {
+        let mut buf = [0u8; ERRORS_LEN];
+        for (bytes, total) in buf.chunks_exact_mut(4).zip(total) {
+            bytes.copy_from_slice(&total.to_le_bytes());
+        }
+
+        self.write(ERRORS_OFFSET, &buf);
+    }
+
+    /// Load whether the tutorial was finished or skipped, not if nothing was saved
+    #[must_use]
+    pub fn load_tutorial(&mut self) -> bool {
+        self.read(TUTORIAL_OFFSET) == Some([1])
+    }
+
+    /// Save whether the tutorial was finished or skipped, see [`tutorial`](crate::tutorial)
+    pub fn save_tutorial(&mut self, done: bool) {
+        self.write(TUTORIAL_OFFSET, &[u8::from(done)]);
+    }
+
+    /// Load the `DFPlayer` volume, `None` if none was saved
+    #[must_use]
+    pub fn load_volume(&mut self) -> Option<u8> {
+        self.read::<1>(VOLUME_OFFSET).map(|[volume]| volume)
+    }
+
+    /// Save the `DFPlayer` volume, see `dfplayer`
+    pub fn save_volume(&mut self, volume: u8) {
+        self.write(VOLUME_OFFSET, &[volume]);
+    }
+
+    /// Load when the clock was last set to the minute, in seconds since
+    /// [`DateTime::EPOCH`](crate::date::DateTime::EPOCH), `None` if it never was
+    #[must_use]
+    pub fn load_clock_set(&mut self) -> Option<u32> {
+        self.read(CLOCK_SET_OFFSET).map(u32::from_le_bytes)
+    }
+
+    /// Save when the clock was set to the minute, for its drift at the next time, see `rtc`
+    pub fn save_clock_set(&mut self, seconds: u32) {
+        self.write(CLOCK_SET_OFFSET, &seconds.to_le_bytes());
+    }
+
+    /// Save calculator state before power-off
+    pub fn save_state<const F: u8>(&mut self, state: State<F>) {
+        self.write_state(STATE_OFFSET, state);
+    }
+
+    /// Load the state saved on power-off, `None` if there is none
+    ///
+    /// The record is erased, so a later reset starts from scratch.
+    pub fn take_state<const F: u8>(&mut self) -> Option<State<F>> {
+        let state = self.read_state(STATE_OFFSET)?;
+        self.clear_state();
+
+        Some(state)
+    }
+
+    /// Save the entry in progress, see [`checkpoint`](crate::checkpoint)
+    pub fn save_checkpoint<const F: u8>(&mut self, state: State<F>) {
+        self.write_state(CHECKPOINT_OFFSET, state);
+    }
+
+    /// Load the last checkpoint of the entry, `None` if there is none
+    #[must_use]
+    pub fn load_checkpoint<const F: u8>(&mut self) -> Option<State<F>> {
+        self.read_state(CHECKPOINT_OFFSET)
+    }
+
+    /// Forget the checkpoint of the entry
+    pub fn clear_checkpoint(&mut self) {
+        self.erase(CHECKPOINT_OFFSET, after(CHECKPOINT_OFFSET, STATE_LEN));
+    }
+
+    /// Erase every record, back to factory defaults
+    pub fn factory_reset(&mut self) {
+        self.erase(0, END);
+        self.corrupted = false;
+    }
+
+    /// Forget the state saved on power-off
+    pub fn clear_state(&mut self) {
+        self.erase(STATE_OFFSET, after(STATE_OFFSET, STATE_LEN));
+    }
+
+    /// Write a [`State`] record at `offset`
+    fn write_state<const F: u8>(&mut self, offset: u16, state: State<F>) {
+        let mut buf = [0u8; STATE_LEN];
+        buf[0] = match state.op {
+            None => 0,
+            Some(BinOp::Add) => 1,
+            Some(BinOp::Sub) => 2,
+            Some(BinOp::Mul) => 3,
+            Some(BinOp::Div) => 4,
+            Some(BinOp::CmpEq) => 5,
+            Some(BinOp::CmpLt) => 6,
+            Some(BinOp::CmpGt) => 7,
+            Some(BinOp::If) => 8,
+        };
+        buf[1..9].copy_from_slice(&state.a.raw().to_le_bytes());
+        buf[9..].copy_from_slice(&state.b.raw().to_le_bytes());
+
+        self.write(offset, &buf);
+    }
+
+    /// Read a [`State`] record at `offset`, `None` if it was erased
+    fn read_state<const F: u8>(&mut self, offset: u16) -> Option<State<F>> {
+        let buf = self.read::<STATE_LEN>(offset)?;
+
+        let op = match buf[0] {
+            0 => None,
+            1 => Some(BinOp::Add),
+            2 => Some(BinOp::Sub),
+            3 => Some(BinOp::Mul),
+            4 => Some(BinOp::Div),
+            5 => Some(BinOp::CmpEq),
+            6 => Some(BinOp::CmpLt),
+            7 => Some(BinOp::CmpGt),
+            8 => Some(BinOp::If),
+            _ => return None,
+        };
+
+        Some(State {
+            a: read_num(&buf[1..9]),
+            op,
+            b: read_num(&buf[9..]),
+        })
+    }
+
+    /// Payload of the record at `offset`, `None` if it is erased or corrupted
+    ///
+    /// A corrupted record is erased, so it is reported once.
+    fn read<const N: usize>(&mut self, offset: u16) -> Option<[u8; N]> {
+        let mut buf = [0u8; N];
+        // Layout is fixed and fits into every AVR EEPROM
+        self.eeprom.read(offset, &mut buf).ok()?;
+        let end = offset + N as u16;
+        let crc = self.eeprom.read_byte(end);
+
+        if crc == 0xFF && buf.iter().all(|&b| b == 0xFF) {
+            return None;
+        }
+        if crc != crc8(&buf) {
+            self.corrupted = true;
+            self.erase(offset, end + 1);
+            return None;
+        }
+
+        Some(buf)
+    }
+
+    /// Write `payload` and its CRC as the record at `offset`
+    fn write(&mut self, offset: u16, payload: &[u8]) {
+        self.update(offset, payload);
+        self.update(offset + payload.len() as u16, &[crc8(payload)]);
+    }
+
+    /// Write `buf` at `offset`, skipping bytes that already hold their value
+    ///
+    /// Every EEPROM cell lasts about 100 000 writes, and a checkpoint mostly changes a byte or two.
+    fn update(&mut self, offset: u16, buf: &[u8]) {
+        for (offset, &byte) in (offset..).zip(buf) {
+            if self.eeprom.read_byte(offset) != byte {
+                self.eeprom.write_byte(offset, byte);
+            }
+        }
+    }
+
+    /// Erase `start..end`, skipping bytes that are erased already
+    fn erase(&mut self, start: u16, end: u16) {
+        for offset in start..end {
+            if self.eeprom.read_byte(offset) != 0xFF {
+                self.eeprom.erase_byte(offset);
+            }
+        }
+    }
+}
+
+/// CRC-8 with polynomial 0x07 over [`VERSION`] and `payload`
+fn crc8(payload: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for &byte in core::iter::once(&VERSION).chain(payload) {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ 0x07
+            };
+        }
+    }
+
+    crc
+}
+
+/// Little endian raw value of a [`Num`] from an 8 byte slice
+fn read_num<const F: u8>(bytes: &[u8]) -> Num<F> {
+    let mut raw = [0u8; 8];
+    raw.copy_from_slice(bytes);
+
+    Num::from_raw(i64::from_le_bytes(raw))
+}
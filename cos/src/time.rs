@@ -0,0 +1,75 @@
+//! Free-running time kept by the TC0 compare interrupt, see [`millis`] and [`micros`]
+//!
+//! The firmware has to call [`start`] once and provide a `TIMER0_COMPA` handler calling
+//! [`tick`]. Time stands still in power-down sleep, where TC0 has no clock.
+
+use core::cell::Cell;
+
+use arduino_hal::pac::TC0;
+use avr_device::interrupt::{self, Mutex};
+
+/// TC0 top for a 1 ms period with the /64 prescaler at 16 MHz
+const TICK_TOP: u8 = 249;
+/// Microseconds per TC0 count with the /64 prescaler at 16 MHz
+const US_PER_COUNT: u32 = 4;
+
+static MILLIS: Mutex<Cell<u32>> = Mutex::new(Cell::new(0));
+
+/// Start counting from 0, TC0 is only read afterwards
+pub fn start(tc0: TC0) {
+    // CTC mode, counter restarts on compare A once a millisecond
+    tc0.tccr0a().write(|w| w.wgm0().bits(0b10));
+    tc0.ocr0a().write(|w| w.set(TICK_TOP));
+    tc0.tcnt0().write(|w| w.set(0));
+    tc0.timsk0().write(|w| w.ocie0a().set_bit());
+    tc0.tccr0b().write(|w| w.cs0().prescale_64());
+
+    interrupt::free(|cs| MILLIS.borrow(cs).set(0));
+
+    // SAFETY: the timer handler only touches the counter behind a `Mutex`.
+    unsafe { avr_device::interrupt::enable() };
+}
+
+/// Count one millisecond, call from `TIMER0_COMPA`
+pub fn tick() {
+    interrupt::free(|cs| {
+        let millis = MILLIS.borrow(cs);
+        millis.set(millis.get().wrapping_add(1));
+    });
+}
+
+/// Milliseconds since [`start`], wraps after ~49 days
+///
+/// Safe to call from interrupt handlers.
+#[must_use]
+pub fn millis() -> u32 {
+    interrupt::free(|cs| MILLIS.borrow(cs).get())
+}
+
+/// Microseconds since [`start`] with a resolution of 4 µs, wraps after ~71 minutes
+///
+/// Safe to call from interrupt handlers.
+#[must_use]
+pub fn micros() -> u32 {
+    // SAFETY: only the counter and the interrupt flag are read, `start` is the one writer
+    let tc0 = unsafe { &*TC0::ptr() };
+
+    interrupt::free(|cs| {
+        let mut millis = MILLIS.borrow(cs).get();
+        let count = tc0.tcnt0().read().bits();
+        // The counter restarted, but the interrupt waits for the critical section to end
+        if tc0.tifr0().read().ocf0a().bit_is_set() && count < TICK_TOP {
+            millis = millis.wrapping_add(1);
+        }
+
+        millis
+            .wrapping_mul(1000)
+            .wrapping_add(u32::from(count) * US_PER_COUNT)
+    })
+}
+
+/// Milliseconds passed since `start`, a previous [`millis`]
+#[must_use]
+pub fn elapsed_since(start: u32) -> u32 {
+    millis().wrapping_sub(start)
+}
@@ -0,0 +1,132 @@
+//! Guided first steps, offered on the first boot and run again from the settings, see
+//! [`Setting::Tutorial`](crate::adjust::Setting::Tutorial)
+//!
+//! The tutorial is a script of [`Step`]s: each is announced with its number in pulses and a
+//! prompt over serial, then waits for the event on the [`bus`](crate::bus) that shows the
+//! user got it. The calculator works as usual in between, the tutorial only watches the
+//! events the firmware passes to [`Tutorial::observe`].
+
+use crate::{
+    bus::{CalcEvent, InputEvent},
+    lang::Text,
+};
+
+/// Event a [`Step`] waits for
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Expect {
+    /// Cursor moved
+    Move,
+    /// Key was accepted by the calculator
+    Key,
+    /// Result was computed
+    Result,
+    /// Key or long operator failed
+    Error,
+}
+
+impl Expect {
+    const fn matches(self, event: Observed) -> bool {
+        matches!(
+            (self, event),
+            (Self::Move, Observed::Input(InputEvent::Move(_)))
+                | (Self::Key, Observed::Calc(CalcEvent::Key(_)))
+                | (Self::Result, Observed::Calc(CalcEvent::Result(_)))
+                | (Self::Error, Observed::Calc(CalcEvent::Error(_)))
+        )
+    }
+}
+
+/// Event of any topic the firmware took from the bus
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Observed {
+    Input(InputEvent),
+    Calc(CalcEvent),
+}
+
+/// What to tell the user and what to wait for
+pub struct Step {
+    pub prompt: Text,
+    pub until: Expect,
+}
+
+/// Steps of the tutorial, in order
+pub const SCRIPT: [Step; 4] = [
+    Step {
+        prompt: Text::TutorialMove,
+        until: Expect::Move,
+    },
+    Step {
+        prompt: Text::TutorialPress,
+        until: Expect::Key,
+    },
+    Step {
+        prompt: Text::TutorialResult,
+        until: Expect::Result,
+    },
+    Step {
+        prompt: Text::TutorialError,
+        until: Expect::Error,
+    },
+];
+
+/// Where the user is in the tutorial after an event, see [`Tutorial::observe`]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// `SCRIPT[n]` begins
+    Step(u8),
+    /// Last step was passed
+    Done,
+}
+
+/// Progress through [`SCRIPT`]
+pub struct Tutorial {
+    /// Step waited for, `None` when the tutorial isn't running
+    step: Option<u8>,
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tutorial {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { step: None }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_running(&self) -> bool {
+        self.step.is_some()
+    }
+
+    /// Begin with the first step, again if already running
+    pub const fn start(&mut self) -> Progress {
+        self.step = Some(0);
+        Progress::Step(0)
+    }
+
+    /// Leave the tutorial wherever it is
+    pub const fn stop(&mut self) {
+        self.step = None;
+    }
+
+    /// Check `event` against the step waited for, returns the progress once it is passed
+    pub fn observe(&mut self, event: Observed) -> Option<Progress> {
+        let step = self.step?;
+        if !SCRIPT[usize::from(step)].until.matches(event) {
+            return None;
+        }
+
+        let next = step + 1;
+        if usize::from(next) == SCRIPT.len() {
+            self.step = None;
+            Some(Progress::Done)
+        } else {
+            self.step = Some(next);
+            Some(Progress::Step(next))
+        }
+    }
+}
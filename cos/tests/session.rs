@@ -0,0 +1,74 @@
+// Generated by `cos-tools transcript`
+#![cfg(not(feature = "basic-layout"))]
+
+use cos::{
+    BinOp, CalcError, Calculator, Const, Key, UnOp, config::FRACTION_COUNT, history::Stat, num::Num,
+};
+
+/// Key, `None` to finish the long operator, and what it gave
+type Step = (Option<Key>, Result<Option<Num<FRACTION_COUNT>>, CalcError>);
+
+const SESSION: [Step; 26] = [
+    (Some(Key::Num(1)), Ok(None)),
+    (Some(Key::Num(2)), Ok(None)),
+    (Some(Key::Dot), Ok(None)),
+    (Some(Key::Num(5)), Ok(None)),
+    (Some(Key::BinOp(BinOp::Add)), Ok(None)),
+    (Some(Key::Num(3)), Ok(None)),
+    (Some(Key::Result), Ok(Some(num("15.5")))),
+    (Some(Key::BinOp(BinOp::Div)), Ok(None)),
+    (Some(Key::Num(0)), Ok(None)),
+    (Some(Key::Result), Err(CalcError::DivByZero)),
+    (Some(Key::Clear), Ok(None)),
+    (Some(Key::Num(2)), Ok(None)),
+    (Some(Key::UnOp(UnOp::Sqrt)), Ok(Some(num("3.94")))),
+    (Some(Key::BinOp(BinOp::Mul)), Ok(None)),
+    (Some(Key::Const(Const::Pi)), Ok(None)),
+    (Some(Key::Result), Ok(Some(num("12.37")))),
+    (Some(Key::Num(1)), Ok(None)),
+    (Some(Key::UnOp(UnOp::Sin)), Ok(None)),
+    (None, Ok(Some(num("-0.2")))),
+    (Some(Key::Stat(Stat::Max)), Ok(None)),
+    (Some(Key::Reset), Ok(None)),
+    (Some(Key::Num(9)), Ok(None)),
+    (Some(Key::Delete), Ok(None)),
+    (Some(Key::Num(4)), Ok(None)),
+    (Some(Key::UnOp(UnOp::Neg)), Ok(Some(num("-4")))),
+    (Some(Key::UnOp(UnOp::Sqrt)), Err(CalcError::Domain)),
+];
+
+#[test]
+fn session() {
+    replay(&SESSION);
+}
+
+/// Poll the long operator until it is done
+fn finish(calc: &mut Calculator<FRACTION_COUNT>) -> Result<Option<Num<FRACTION_COUNT>>, CalcError> {
+    loop {
+        let result = calc.poll();
+        if result != Ok(None) || !calc.is_busy() {
+            return result;
+        }
+    }
+}
+
+/// Number as the firmware logged it, checked at compile time
+const fn num(text: &str) -> Num<FRACTION_COUNT> {
+    match Num::parse_bytes(text.as_bytes()) {
+        Ok(num) => num,
+        Err(_) => panic!("not a number"),
+    }
+}
+
+/// Pass every key to the calculator, `None` finishes the long operator
+fn replay(steps: &[Step]) {
+    let mut calc = Calculator::<FRACTION_COUNT>::new();
+
+    for (i, &(key, expected)) in steps.iter().enumerate() {
+        let result = match key {
+            Some(key) => calc.handle_input(key),
+            None => finish(&mut calc),
+        };
+        assert_eq!(result, expected, "step {i}");
+    }
+}
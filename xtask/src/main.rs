@@ -0,0 +1,125 @@
+//! Development tasks, run with `cargo xtask <task>` in the repository root
+//!
+//! - `avr-test` - build `avr-tests` and run it on simavr, fails unless every check passed
+//! - `panic-free` - build the `panic-free` binary of `avr-tests`, which only links if the
+//!   math and the calculator have no panic branch left with the `panic-free` feature
+
+use std::{
+    env,
+    ffi::OsString,
+    io::Read as _,
+    path::Path,
+    process::{Command, ExitCode, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A run takes well under a second, anything longer is a hang
+const SIMAVR_TIMEOUT: Duration = Duration::from_secs(30);
+
+fn main() -> ExitCode {
+    let result = match env::args().nth(1).as_deref() {
+        Some("avr-test") => avr_test(),
+        Some("panic-free") => panic_free(),
+        _ => Err("usage: cargo xtask avr-test | panic-free".to_owned()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            #[expect(clippy::print_stderr, reason = "xtask is a CLI")]
+            {
+                eprintln!("error: {e}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn avr_test() -> Result<(), String> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../avr-tests");
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+
+    // Run in the crate so its .cargo/config.toml picks the AVR target
+    let status = Command::new(cargo)
+        .args(["build", "--release"])
+        .current_dir(&dir)
+        .status()
+        .map_err(|e| format!("cargo: {e}"))?;
+    if !status.success() {
+        return Err("avr-tests failed to build".to_owned());
+    }
+
+    let elf = dir.join("target/avr-none/release/avr-tests.elf");
+    let output = run_simavr(&elf)?;
+
+    #[expect(clippy::print_stdout, reason = "xtask is a CLI")]
+    {
+        print!("{output}");
+    }
+
+    if output.contains("FAIL") || !output.contains("done, 0 failed") {
+        return Err("avr-tests failed".to_owned());
+    }
+
+    Ok(())
+}
+
+fn panic_free() -> Result<(), String> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../avr-tests");
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+
+    // Linking fails on the undefined symbol the panic handler calls, cargo shows which
+    let status = Command::new(cargo)
+        .args([
+            "build",
+            "--release",
+            "--features",
+            "panic-free",
+            "--bin",
+            "panic-free",
+        ])
+        .current_dir(&dir)
+        .status()
+        .map_err(|e| format!("cargo: {e}"))?;
+    if !status.success() {
+        return Err("panic-free failed to build, a panic branch may be left".to_owned());
+    }
+
+    Ok(())
+}
+
+/// Run the firmware on simavr and return what it printed, including the serial port
+fn run_simavr(elf: &Path) -> Result<String, String> {
+    let mut child = Command::new("simavr")
+        .args(["-m", "atmega328p", "-f", "16000000"])
+        .arg(elf)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("simavr: {e}, is it installed?"))?;
+
+    let start = Instant::now();
+    while child.try_wait().map_err(|e| e.to_string())?.is_none() {
+        if start.elapsed() > SIMAVR_TIMEOUT {
+            child.kill().map_err(|e| e.to_string())?;
+            return Err("simavr timed out".to_owned());
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    // simavr logs the serial port on stderr
+    let mut output = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        stdout
+            .read_to_string(&mut output)
+            .map_err(|e| e.to_string())?;
+    }
+    if let Some(mut stderr) = child.stderr.take() {
+        stderr
+            .read_to_string(&mut output)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(output)
+}